@@ -0,0 +1,398 @@
+"""
+Coleta de telemetria como biblioteca
+=====================================
+Monta o mesmo payload que o Sender envia, sem socket, bandeja, elevação
+para Admin nem config.json. O sender_pc.py é só a casca (envio UDP, bandeja,
+pedidos de controle); outra interface usa o coletor direto:
+
+    from collector import TelemetryCollector
+    
+    collector = TelemetryCollector(top_processes=5)
+    collector.start()            # LibreHardwareMonitor, NVML e ping
+    payload = collector.collect()
+    collector.close()
+
+Sem start() nenhum sensor de hardware é aberto: o payload sai só com o
+psutil e as fontes de extra_sources (ex: MockSource de core/sensors.py).
+"""
+from __future__ import annotations
+
+import socket
+import sys
+import time
+from typing import Any, Dict, Iterable, Optional, Sequence
+
+from core.rates import compute_disk_rates
+from core.network import compute_interface_rates, parse_ping_host, PingMethod, PingWorker, DEFAULT_PING_HOST
+from core.payload import battery_from_psutil, fans_from_psutil, top_processes, mark_absent, sensor_status, system_from_psutil
+from core.payload import disk_capacity
+from core.protocol import PROTOCOL_VERSION
+from core.scheduling import SlowRefresh, PeriodicCache
+from core.control import DEFAULT_CONTROL_PROCESSES
+from core.throttle import cpu_throttling
+from core.sensors import LhmSource, NvmlSource, FanFallbackSource, CpuClockFallbackSource, SensorSource
+from core.sensors import collect_sources, enabled_sources, DEFAULT_CLOCK_SOURCE
+from core.nvml import NvmlDevice
+
+try:
+    import hardware_monitor
+    HAS_HWMON = True
+except ImportError:
+    HAS_HWMON = False
+
+# NVML (pip install nvidia-ml-py): motivos de throttling da GPU NVIDIA
+try:
+    import pynvml
+    HAS_NVML = True
+except ImportError:
+    HAS_NVML = False
+
+
+class TelemetryCollector:
+    """
+    Lê os sensores e monta o payload de telemetria
+    
+    Guarda o estado entre coletas: contadores de rede/disco (taxas), cache
+    dos discos (SMART só a cada slow_refresh_secs), pico de clock da CPU.
+    """
+    
+    def __init__(
+        self,
+        top_processes: int = 0,
+        gpu_index: int = 0,
+        network_interface: str = "",
+        ping_host: str = DEFAULT_PING_HOST,
+        ping_icmp: bool = False,
+        slow_refresh_secs: float = 10.0,
+        link_check_interval_secs: float = 60.0,
+        disabled_sources: Iterable[str] = (),
+        extra_sources: Sequence[SensorSource] = (),
+        admin: bool = False,
+        psutil_module: Any = None,
+        platform: str = sys.platform
+    ):
+        """
+        Args:
+            top_processes: Processos mais pesados no payload (0 = desativado)
+            gpu_index: GPU NVIDIA lida pelo NVML (ordem do nvidia-smi)
+            network_interface: Interface monitorada (vazio = soma das físicas)
+            ping_host: Alvo do ping ("host" ou "host:porta"); ValueError se inválido
+            ping_icmp: Ping ICMP real (precisa de Admin); cai para TCP se negado
+            slow_refresh_secs: Segundos entre leituras dos discos (SMART)
+            link_check_interval_secs: Segundos entre consultas da velocidade do link
+            disabled_sources: Fontes desligadas (ver SOURCE_NAMES em core/sensors.py)
+            extra_sources: Fontes consultadas depois das padrão
+            admin: Processo elevado (sensor_status avisa o que falta sem Admin)
+            psutil_module: Módulo psutil (ou um substituto nos testes)
+            platform: sys.platform
+        """
+        if psutil_module is None:
+            import psutil as psutil_module
+        self.psutil = psutil_module
+        self.platform = platform
+        self.top_processes = top_processes
+        self.gpu_index = gpu_index
+        self.network_interface = network_interface
+        self.disabled = set(disabled_sources)
+        self.admin = admin
+        self.hostname = socket.gethostname()
+        self.boot_time = self.psutil.boot_time()
+        self.monitor = None
+        self.nvml_gpu = None
+        
+        # Seções opcionais: o Sender muda a pedido do receiver (control_requests)
+        self.sections = {"processes": top_processes > 0}
+        
+        # Throttling: pico de clock da CPU (heurística)
+        self.cpu_peak_clock = 0
+        
+        # Fontes de sensores em ordem de prioridade (ver core/sensors.py)
+        self.sensor_sources = enabled_sources([
+            LhmSource(), NvmlSource(self._ler_nvml), FanFallbackSource(self._ler_fans),
+            CpuClockFallbackSource(self._ler_freq_cpu, platform)
+        ], self.disabled) + list(extra_sources)
+        
+        # Link de rede: o PowerShell é lento e a velocidade só muda ao reconectar o cabo
+        self.link_info = PeriodicCache(
+            lambda: self.monitor.get_network_link_info(),
+            link_check_interval_secs,
+            {"link_speed_mbps": 0, "adapter_name": ""}
+        )
+        
+        # Discos (SMART) mudam pouco: atualizados a cada slow_refresh_secs,
+        # contadores de CPU/RAM/rede/disco continuam a cada ciclo
+        self.slow_refresh = SlowRefresh(slow_refresh_secs)
+        
+        # Contadores de rede e disco: as taxas saem da diferença entre coletas
+        self.last_net = self.psutil.net_io_counters(pernic=True)
+        self.last_t = time.time()
+        self.last_disk_io = self._ler_contadores_disco()
+        self.last_disk_t = time.time()
+        
+        # Ping em thread própria (iniciada em start): a coleta só lê o último resultado
+        host, port = parse_ping_host(ping_host)
+        self.pinger = PingWorker(host, port, measure=PingMethod(ping_icmp))
+    
+    def init_sources(self) -> None:
+        """Abre o LibreHardwareMonitor e a GPU no NVML (os desligados em disabled_sources ficam de fora)."""
+        self._init_hardware_monitor()
+        self.nvml_gpu = self._init_nvml()
+    
+    def start(self) -> None:
+        """init_sources() e a thread do ping."""
+        self.init_sources()
+        self.pinger.start()
+    
+    def close(self) -> None:
+        """Para o ping e fecha o LibreHardwareMonitor."""
+        self.pinger.stop()
+        if self.monitor:
+            self.monitor.close()
+            self.monitor = None
+    
+    def restart_monitor(self) -> None:
+        """Reabre o LibreHardwareMonitor (ex: sensores sumiram após suspender)."""
+        if self.monitor:
+            try:
+                self.monitor.close()
+            except Exception:
+                pass
+        self._init_hardware_monitor()
+        self.slow_refresh.force()  # Monitor novo começa sem cache de discos
+        self.link_info.force()
+    
+    def collect(self) -> Dict[str, Any]:
+        """Lê os sensores e monta o payload (discos/SMART só quando vence o intervalo lento)."""
+        refresh_slow = self.slow_refresh.due()
+        hw_data = None
+        if self.monitor and self.monitor.enabled:
+            hw_data = self.monitor.fetch_data(refresh_storage=refresh_slow)
+        return self._build_payload(hw_data)
+    
+    def _init_hardware_monitor(self):
+        """Inicializa LibreHardwareMonitor."""
+        self.monitor = None
+        if "lhm" in self.disabled:
+            print("[HW] LibreHardwareMonitor desligado em disabled_sources")
+        elif HAS_HWMON:
+            print("[HW] Inicializando LibreHardwareMonitor...")
+            self.monitor = hardware_monitor.HardwareMonitor(self.disabled)
+            if not self.monitor.enabled:
+                print("[HW] AVISO: DLL não carregou. Dados limitados.")
+                self.monitor = None
+        else:
+            print("[HW] hardware_monitor.py não encontrado.")
+    
+    def _init_nvml(self):
+        """GPU NVIDIA de gpu_index no NVML (None sem pynvml, sem driver ou desligado no config)."""
+        if not HAS_NVML or "nvml" in self.disabled:
+            return None
+        return NvmlDevice.open(pynvml, self.gpu_index)
+    
+    def _calcular_rede(self):
+        """Calcula velocidade de rede (ignora loopback, VPN e adaptadores virtuais)."""
+        now = time.time()
+        net_io = self.psutil.net_io_counters(pernic=True)
+        delta = now - self.last_t
+        
+        interfaces = compute_interface_rates(self.last_net, net_io, delta, self.network_interface)
+        
+        self.last_net = net_io
+        self.last_t = now
+        
+        up = sum(iface[2] for iface in interfaces)
+        down = sum(iface[1] for iface in interfaces)
+        return up, down, interfaces
+    
+    def _ler_contadores_disco(self):
+        """Lê contadores de I/O por disco (None se indisponível)."""
+        try:
+            return self.psutil.disk_io_counters(perdisk=True)
+        except Exception:
+            return None
+    
+    def _calcular_discos(self, storage):
+        """Preenche taxas de leitura/escrita por disco a partir dos contadores do psutil."""
+        now = time.time()
+        disk_io = self._ler_contadores_disco()
+        if disk_io is None:
+            return
+        
+        rates = compute_disk_rates(self.last_disk_io, disk_io, now - self.last_disk_t)
+        self.last_disk_io = disk_io
+        self.last_disk_t = now
+        
+        for disk in storage:
+            index = disk.get("drive_index", -1)
+            disk_rates = rates.get(f"PhysicalDrive{index}")
+            if index < 0 or not disk_rates:
+                continue
+            
+            # Só completa o que o LibreHardwareMonitor não reportou
+            if not disk.get("read_rate"):
+                disk["read_rate"] = round(disk_rates["read_rate"], 0)
+            if not disk.get("write_rate"):
+                disk["write_rate"] = round(disk_rates["write_rate"], 0)
+            if not disk.get("total_activity"):
+                disk["total_activity"] = round(disk_rates["total_activity"], 1)
+    
+    def _calcular_capacidade(self, storage):
+        """Espaço total/livre/usado de cada disco, somando as partições com letra."""
+        if not self.monitor:
+            return
+        for disk in storage:
+            usages = []
+            for letter in self.monitor.get_drive_letters(disk.get("drive_index", -1)):
+                try:
+                    usages.append(self.psutil.disk_usage(f"{letter}:\\"))
+                except OSError:
+                    continue  # Leitor de cartão vazio, volume desmontado
+            if not usages:
+                continue
+            disk.update(disk_capacity(usages))
+            # LHM sem o sensor "Used Space": o percentual (alertas) sai da capacidade
+            if not disk.get("used_space") and disk["total_gb"]:
+                disk["used_space"] = round(disk["used_gb"] / disk["total_gb"] * 100, 1)
+    
+    def _ler_nvml(self):
+        """Throttling e uso de NVENC/NVDEC da GPU (None sem NVML ou com a GPU perdida)."""
+        if self.nvml_gpu is None:
+            return None
+        return self.nvml_gpu.query()
+    
+    def _ler_fans(self):
+        """Lê os fans pelo psutil (hwmon; a função não existe no Windows)."""
+        if not hasattr(self.psutil, "sensors_fans"):
+            return []
+        try:
+            return fans_from_psutil(self.psutil.sensors_fans())
+        except Exception:
+            return []
+    
+    def _ler_freq_cpu(self):
+        """Frequência da CPU pelo psutil (None se o SO não informar)."""
+        try:
+            return self.psutil.cpu_freq()
+        except Exception:
+            return None
+    
+    def _ler_bateria(self, hw_data):
+        """Lê estado da bateria (seção vazia em desktops)."""
+        try:
+            battery = self.psutil.sensors_battery()
+        except Exception:
+            battery = None
+        power_w = hw_data["battery"]["power"] if hw_data else 0
+        return battery_from_psutil(battery, power_w)
+    
+    def _listar_processos(self):
+        """Lista os processos com maior uso de CPU (vazio se desativado)."""
+        if not self.sections.get("processes"):
+            return []
+        
+        processes = []
+        # process_iter reaproveita os objetos entre chamadas, então
+        # cpu_percent já vem calculado desde o ciclo anterior
+        for proc in self.psutil.process_iter(['name', 'pid', 'cpu_percent', 'memory_info']):
+            try:
+                info = proc.info
+                mem = info.get('memory_info')
+                processes.append({
+                    "name": info.get('name'),
+                    "pid": info.get('pid'),
+                    "cpu_percent": info.get('cpu_percent'),
+                    "mem_mb": mem.rss / (1024**2) if mem else 0
+                })
+            except (self.psutil.NoSuchProcess, self.psutil.AccessDenied):
+                continue
+        
+        return top_processes(processes, self.top_processes or DEFAULT_CONTROL_PROCESSES)
+    
+    def _build_payload(self, hw_data: Optional[Dict[str, Any]]) -> Dict[str, Any]:
+        """Monta payload de telemetria (unificado)."""
+        cpu_percent = self.psutil.cpu_percent(interval=None)
+        mem = self.psutil.virtual_memory()
+        up, down, interfaces = self._calcular_rede()
+        ping = self.pinger.last_ms  # PING_UNREACHABLE (-1) se o host não responde
+        
+        # Valores padrão
+        payload = {
+            "protocol_version": PROTOCOL_VERSION,  # Receiver avisa se não entende o formato
+            "timestamp": round(time.time(), 3),  # Receiver detecta pacotes perdidos
+            "hostname": self.hostname,
+            "uptime_secs": int(max(0, time.time() - self.boot_time)),
+            "sensor_status": sensor_status(hw_data, self.admin),
+            "cpu": {
+                "usage": cpu_percent,
+                "temp": 0,
+                "voltage": 0,
+                "power": 0,
+                "clock": 0,
+                "clock_source": DEFAULT_CLOCK_SOURCE,  # lhm, os, os_base (nominal do Windows) ou unknown
+                "throttling": False
+            },
+            "gpu": {
+                "load": 0,
+                "temp": 0,
+                "voltage": 0,
+                "clock_core": 0,
+                "clock_mem": 0,
+                "power_w": 0,  # 0 = sem sensor de consumo
+                "throttling": False,
+                "enc_util": 0,  # NVENC/NVDEC (só NVML); 0 = ocioso ou sem suporte
+                "dec_util": 0,
+                "mem_util": 0,  # Barramento de memória (só NVML)
+                "pcie_tx_kbps": 0,  # Tráfego PCIe em KB/s (só NVML); 0 = sem suporte
+                "pcie_rx_kbps": 0,
+                "fan": 0,
+                "fan_unit": "RPM",
+                "source": "",  # nvidia/amd/intel; "intel" = GPU integrada
+                "mem_used_mb": 0,
+                "mem_total_mb": 0  # 0 = VRAM total desconhecida
+            },
+            "mobo": {
+                "temp": 0
+            },
+            "ram": {
+                "percent": mem.percent,
+                "used_gb": round(mem.used / (1024**3), 2),
+                "total_gb": round(mem.total / (1024**3), 2)
+            },
+            "storage": [],
+            "fans": [],
+            "network": {
+                "down_kbps": round(down, 1),
+                "up_kbps": round(up, 1),
+                "ping_ms": ping,
+                "ping_method": self.pinger.method,
+                "link_speed_mbps": 0,
+                "adapter_name": "",
+                "interfaces": interfaces  # [nome, down_kbps, up_kbps] por adaptador
+            },
+            "battery": self._ler_bateria(hw_data),
+            "system": system_from_psutil(self.psutil, self.platform),  # Load average (zerado no Windows) e processos
+            "processes": self._listar_processos()
+        }
+        
+        # LHM, NVML e fans do hwmon, cada fonte completando as anteriores
+        collect_sources(payload, self.sensor_sources, hw_data)
+        
+        # Throttling (padrão False quando não dá para saber)
+        cpu = payload["cpu"]
+        self.cpu_peak_clock = max(self.cpu_peak_clock, cpu["clock"] or 0)
+        cpu["throttling"] = cpu_throttling(
+            cpu["temp"], cpu["clock"], self.cpu_peak_clock,
+            hw_data["cpu"].get("tjmax_distance") if hw_data else None
+        )
+        # Taxas de leitura/escrita por disco (delta dos contadores do psutil)
+        self._calcular_discos(payload["storage"])
+        self._calcular_capacidade(payload["storage"])
+        
+        # Velocidade do link/nome do adaptador: consultados a cada link_check_interval_secs
+        if self.monitor and self.monitor.enabled:
+            link = self.link_info.get()
+            payload["network"]["link_speed_mbps"] = link.get("link_speed_mbps", 0)
+            payload["network"]["adapter_name"] = link.get("adapter_name", "")
+        
+        # Sensores não lidos vão como null (o receiver mostra "—", não "0.0")
+        return mark_absent(payload)
@@ -2,7 +2,11 @@
 Core - Módulos centrais do Sistema de Telemetria
 """
 from .config import TelemetryConfig, load_config, save_config, get_global_config
-from .protocol import MagicByte, encode_payload, decode_payload
+from .protocol import (
+    MagicByte, encode_payload, decode_payload, decode_any,
+    ProtocolError, AuthFailedError, FrameTooLargeError, TruncatedFrameError, DeserializeError, DecodeLimitError,
+    VersionMismatchError, PROTOCOL_VERSION
+)
 from .validators import validate_ip, validate_port, validate_interval
 from .logging_config import setup_logger, get_logger, LogLevel
 from .alerts import AlertConfig, AlertManager, AlertLevel, init_alerts, get_alert_manager
@@ -19,6 +23,15 @@ __all__ = [
     "MagicByte",
     "encode_payload",
     "decode_payload",
+    "decode_any",
+    "ProtocolError",
+    "AuthFailedError",
+    "FrameTooLargeError",
+    "TruncatedFrameError",
+    "DeserializeError",
+    "DecodeLimitError",
+    "VersionMismatchError",
+    "PROTOCOL_VERSION",
     # Validators
     "validate_ip",
     "validate_port",
@@ -5,8 +5,9 @@ Suporta Telegram, Discord e webhooks genéricos
 import json
 import time
 import threading
+from collections import deque
 from dataclasses import dataclass, field
-from typing import Dict, Optional, Callable, Any
+from typing import Deque, Dict, List, Optional, Callable, Any
 from enum import Enum
 from urllib.request import Request, urlopen
 from urllib.error import URLError
@@ -59,6 +60,289 @@ class AlertConfig:
         return self.enabled and (self.telegram_enabled or self.discord_enabled or self.ntfy_enabled)
 
 
+def alert_level(
+    value: Any,
+    warn_threshold: Optional[float],
+    crit_threshold: Optional[float]
+) -> Optional[AlertLevel]:
+    """
+    Classifica um valor pelos thresholds
+    
+    Returns:
+        AlertLevel.CRITICAL, AlertLevel.WARNING ou None (normal / não numérico)
+    """
+    if not isinstance(value, (int, float)) or isinstance(value, bool):
+        return None
+    if crit_threshold and value >= crit_threshold:
+        return AlertLevel.CRITICAL
+    if warn_threshold and value >= warn_threshold:
+        return AlertLevel.WARNING
+    return None
+
+
+# Link abaixo desta fração da velocidade esperada é crítico (ex: 10 Mbps num cabo de 1 Gbps)
+LINK_CRITICAL_RATIO = 0.1
+
+
+def link_speed_level(speed: Any, expected: Any) -> Optional[AlertLevel]:
+    """
+    Classifica a velocidade do link pela esperada (expected_link_speed_mbps)
+    
+    Ao contrário de alert_level, aqui menor é pior: abaixo da esperada é
+    WARNING (cabo ruim negociou 100 Mbps), abaixo de LINK_CRITICAL_RATIO
+    dela é CRITICAL.
+    
+    Returns:
+        AlertLevel ou None (normal, link desconhecido/0 ou sem velocidade esperada)
+    """
+    if not isinstance(speed, (int, float)) or isinstance(speed, bool) or speed <= 0:
+        return None
+    if not isinstance(expected, (int, float)) or expected <= 0:
+        return None
+    if speed < expected * LINK_CRITICAL_RATIO:
+        return AlertLevel.CRITICAL
+    if speed < expected:
+        return AlertLevel.WARNING
+    return None
+
+
+# Faixas de aviso/crítico de valores que não são temperatura nem uso
+# ("alertas": <métrica>_warning / <métrica>_critical; 0 desliga a cor)
+METRIC_THRESHOLD_DEFAULTS = {
+    "cpu_power": (200, 250),  # W
+    "cpu_voltage": (1.45, 1.55),  # V (Vcore)
+    "gpu_power": (350, 450),  # W
+    "vram": (90, 98),  # % da VRAM
+}
+
+
+def metric_thresholds(alertas: Dict[str, Any], metric: str) -> tuple:
+    """
+    (warning, critical) de uma métrica de METRIC_THRESHOLD_DEFAULTS
+    
+    Valores ausentes na config usam o padrão; 0 desativa a faixa.
+    """
+    warn_default, crit_default = METRIC_THRESHOLD_DEFAULTS[metric]
+    return (
+        alertas.get(f"{metric}_warning", warn_default),
+        alertas.get(f"{metric}_critical", crit_default),
+    )
+
+
+# Thresholds globais de disco (chave em "alertas" -> chave curta do override)
+STORAGE_THRESHOLD_DEFAULTS = {
+    "temp_warning": ("storage_temp_warning", 45),
+    "temp_critical": ("storage_temp_critical", 55),
+    "uso_warning": ("storage_uso_warning", 80),
+    "uso_critical": ("storage_uso_critical", 95),
+}
+
+
+def normalize_storage_name(name: Any) -> str:
+    """Nome do disco só com letras/dígitos minúsculos ("Samsung SSD 970 EVO" -> "samsungssd970evo")."""
+    return "".join(ch for ch in str(name or "").lower() if ch.isalnum())
+
+
+def storage_names_match(pattern: Any, disk_name: Any) -> bool:
+    """
+    Compara nomes de disco ignorando caixa, espaços e pontuação
+    
+    Um contém o outro: "Samsung SSD 970" casa com "Samsung SSD 970 EVO Plus 1TB".
+    """
+    a = normalize_storage_name(pattern)
+    b = normalize_storage_name(disk_name)
+    return bool(a and b) and (a in b or b in a)
+
+
+def find_disk_override(
+    disk_name: Any,
+    overrides: Optional[Dict[str, Dict[str, Any]]],
+    drive_id: str = ""
+) -> Optional[Dict[str, Any]]:
+    """
+    Escolhe o override de um disco
+    
+    A chave estável (drive_id: número de série ou "PhysicalDriveN") vence,
+    comparada por inteiro; só então o nome do modelo, por casamento parcial
+    (o padrão mais específico vence). Dois discos do mesmo modelo só se
+    distinguem pelo drive_id.
+    """
+    candidates = {k: v for k, v in (overrides or {}).items() if isinstance(v, dict)}
+    
+    wanted = normalize_storage_name(drive_id)
+    if wanted:
+        for key, values in candidates.items():
+            if normalize_storage_name(key) == wanted:
+                return values
+    
+    matches = [
+        (len(normalize_storage_name(pattern)), values)
+        for pattern, values in candidates.items()
+        if storage_names_match(pattern, disk_name)
+    ]
+    if matches:
+        return max(matches, key=lambda m: m[0])[1]
+    return None
+
+
+def disk_thresholds(
+    disk_name: Any,
+    alertas: Dict[str, Any],
+    overrides: Optional[Dict[str, Dict[str, Any]]] = None,
+    drive_id: str = ""
+) -> Dict[str, Any]:
+    """
+    Thresholds de temperatura/uso de um disco
+    
+    Consulta primeiro os overrides (pelo drive_id, depois pelo nome) e
+    completa o que faltar com os thresholds globais de storage.
+    
+    Args:
+        disk_name: Nome do disco no payload
+        alertas: Seção "alertas" da config (storage_temp_warning, ...)
+        overrides: Serial/nome do disco -> {"temp_warning", "temp_critical", "uso_warning", "uso_critical"}
+        drive_id: Chave estável enviada pelo sender (vazio em senders antigos)
+    
+    Returns:
+        Dict com as quatro chaves curtas
+    """
+    thresholds = {
+        key: alertas.get(global_key, default)
+        for key, (global_key, default) in STORAGE_THRESHOLD_DEFAULTS.items()
+    }
+    
+    override = find_disk_override(disk_name, overrides, drive_id)
+    if override:
+        thresholds.update({k: v for k, v in override.items() if k in thresholds})
+    
+    return thresholds
+
+
+class AlertTracker:
+    """
+    Nível atual de cada métrica, para agir só nas transições
+    
+    Exemplo:
+        tracker = AlertTracker()
+        if tracker.update("cpu.temp", AlertLevel.CRITICAL) == AlertLevel.CRITICAL:
+            tocar_som_critico()
+    """
+    
+    _ORDER = {None: 0, AlertLevel.INFO: 1, AlertLevel.WARNING: 2, AlertLevel.CRITICAL: 3}
+    
+    def __init__(self):
+        self.levels: Dict[str, Optional[AlertLevel]] = {}
+    
+    def update(self, metric: str, level: Optional[AlertLevel]) -> Optional[AlertLevel]:
+        """
+        Registra o nível atual de uma métrica
+        
+        Returns:
+            O novo nível se a métrica acabou de entrar em WARNING/CRITICAL
+            (ou subiu de WARNING para CRITICAL); None caso contrário
+        """
+        previous = self.levels.get(metric)
+        self.levels[metric] = level
+        if level in (AlertLevel.WARNING, AlertLevel.CRITICAL) and self._ORDER[level] > self._ORDER[previous]:
+            return level
+        return None
+
+
+@dataclass
+class AlertLogEntry:
+    """Uma transição de alerta registrada no log do dashboard"""
+    timestamp: float
+    metric: str
+    value: Any
+    level: AlertLevel
+
+
+class AlertLog:
+    """
+    Últimas transições de alerta (as mais antigas são descartadas)
+    
+    Alimentado pelo retorno de AlertTracker.update, sem depender da GUI.
+    
+    Exemplo:
+        log = AlertLog(capacity=50)
+        transition = tracker.update("CPU.temp", level)
+        if transition:
+            log.record("CPU.temp", 91.0, transition)
+    """
+    
+    def __init__(self, capacity: int = 50):
+        self._entries: Deque[AlertLogEntry] = deque(maxlen=max(1, int(capacity)))
+    
+    @property
+    def capacity(self) -> int:
+        return self._entries.maxlen
+    
+    def record(
+        self,
+        metric: str,
+        value: Any,
+        level: AlertLevel,
+        now: Optional[float] = None
+    ) -> AlertLogEntry:
+        """Registra uma transição; com o log cheio, descarta a mais antiga"""
+        entry = AlertLogEntry(time.time() if now is None else now, metric, value, level)
+        self._entries.append(entry)
+        return entry
+    
+    def entries(self) -> List[AlertLogEntry]:
+        """Transições da mais recente para a mais antiga"""
+        return list(reversed(self._entries))
+    
+    def clear(self) -> None:
+        self._entries.clear()
+    
+    def __len__(self) -> int:
+        return len(self._entries)
+
+
+class MuteRegistry:
+    """
+    Métricas silenciadas pelo usuário (sem som/notificação, mas ainda coloridas)
+    
+    O mute expira após duration_seconds ou quando a métrica volta ao normal.
+    
+    Exemplo:
+        mutes = MuteRegistry(duration_seconds=900)
+        mutes.mute("CPU.temp")
+        if not mutes.is_muted("CPU.temp"):
+            notificar()
+    """
+    
+    def __init__(self, duration_seconds: float = 900):
+        self.duration_seconds = duration_seconds
+        self._until: Dict[str, float] = {}
+    
+    def mute(self, metric: str, now: Optional[float] = None) -> None:
+        """Silencia a métrica por duration_seconds"""
+        now = time.time() if now is None else now
+        self._until[metric] = now + self.duration_seconds
+    
+    def unmute(self, metric: str) -> None:
+        """Reativa as notificações da métrica"""
+        self._until.pop(metric, None)
+    
+    def is_muted(self, metric: str, now: Optional[float] = None) -> bool:
+        """Verifica (e expira) o mute da métrica"""
+        until = self._until.get(metric)
+        if until is None:
+            return False
+        now = time.time() if now is None else now
+        if now >= until:
+            del self._until[metric]
+            return False
+        return True
+    
+    def update(self, metric: str, level: Optional[AlertLevel]) -> None:
+        """Desfaz o mute quando a métrica volta ao normal (level None)"""
+        if level is None:
+            self.unmute(metric)
+
+
 class AlertManager:
     """
     Gerenciador de alertas
@@ -0,0 +1,138 @@
+"""
+Encoding compacto do payload (MagicByte.COMPACT) para links muito estreitos
+(ponte LoRa, rádio serial...): campos numéricos em inteiros de tamanho fixo
+com escala conhecida, em vez de JSON
+
+É com perda, por projeto:
+    - temperaturas, uso (%), potência, ping e rede: 0.1 (erro máximo 0.05)
+    - voltagens: 0.001 V; RAM em GB: 0.01; clocks, fan, VRAM e uptime: inteiros
+    - valores fora da faixa do tipo são saturados (ex: temperatura > 6553.5)
+    - só vão os campos de COMPACT_FIELDS, o hostname e a unidade do fan da GPU;
+      discos, fans, processos, interfaces e bateria ficam de fora
+"""
+import struct
+from typing import Any, Dict, Tuple
+
+# (seção.campo, formato struct, escala): valor = inteiro / escala.
+# Mudar a ordem ou os tipos quebra Receivers já instalados: acrescente no fim
+COMPACT_FIELDS: Tuple[Tuple[str, str, int], ...] = (
+    ("protocol_version", "B", 1),
+    ("timestamp", "d", 1),  # float64: o Receiver calcula perdas pela diferença
+    ("uptime_secs", "I", 1),
+    ("cpu.usage", "H", 10),
+    ("cpu.temp", "H", 10),
+    ("cpu.voltage", "H", 1000),
+    ("cpu.power", "H", 10),
+    ("cpu.clock", "H", 1),
+    ("cpu.throttling", "?", 1),
+    ("gpu.load", "H", 10),
+    ("gpu.temp", "H", 10),
+    ("gpu.voltage", "H", 1000),
+    ("gpu.clock_core", "H", 1),
+    ("gpu.clock_mem", "H", 1),
+    ("gpu.power_w", "H", 10),
+    ("gpu.throttling", "?", 1),
+    ("gpu.fan", "H", 1),
+    ("gpu.mem_used_mb", "I", 1),
+    ("gpu.mem_total_mb", "I", 1),
+    ("mobo.temp", "H", 10),
+    ("ram.percent", "H", 10),
+    ("ram.used_gb", "H", 100),
+    ("ram.total_gb", "H", 100),
+    ("network.down_kbps", "I", 10),
+    ("network.up_kbps", "I", 10),
+    ("network.ping_ms", "i", 10),  # Com sinal: -1 = inalcançável
+)
+
+# Textos que só têm poucos valores possíveis: vão como índice (0 = primeiro/padrão)
+COMPACT_ENUMS: Tuple[Tuple[str, Tuple[str, ...]], ...] = (
+    ("gpu.fan_unit", ("RPM", "%")),
+)
+
+_STRUCT = struct.Struct("<" + "".join(code for _, code, _ in COMPACT_FIELDS) + "B" * len(COMPACT_ENUMS))
+
+# Maior hostname levado (prefixo de 1 byte com o tamanho)
+MAX_HOSTNAME_BYTES = 255
+
+# Faixa de cada inteiro do struct
+_INT_RANGES = {
+    "B": (0, 0xFF),
+    "H": (0, 0xFFFF),
+    "I": (0, 0xFFFFFFFF),
+    "i": (-0x80000000, 0x7FFFFFFF),
+}
+
+
+def max_error(path: str) -> float:
+    """Maior diferença entre o valor original e o decodificado (dentro da faixa do tipo)"""
+    for field, code, scale in COMPACT_FIELDS:
+        if field == path:
+            return 0.0 if code in ("d", "?") else 0.5 / scale
+    raise KeyError(path)
+
+
+def _get(payload: Dict[str, Any], path: str) -> Any:
+    section, _, field = path.rpartition(".")
+    source = payload.get(section) if section else payload
+    return source.get(field) if isinstance(source, dict) else None
+
+
+def _quantize(value: Any, code: str, scale: int) -> Any:
+    if code == "?":
+        return bool(value)
+    if isinstance(value, bool) or not isinstance(value, (int, float)) or value != value:
+        value = 0  # Ausente, texto ou NaN
+    if code == "d":
+        return float(value)
+    low, high = _INT_RANGES[code]
+    return max(low, min(high, round(value * scale)))
+
+
+def encode_compact(payload: Dict[str, Any]) -> bytes:
+    """
+    Corpo compacto do payload (sem o magic byte)
+    
+    Returns:
+        Campos de COMPACT_FIELDS + índices de COMPACT_ENUMS + hostname (1 byte de tamanho + UTF-8)
+    """
+    values = [_quantize(_get(payload, path), code, scale) for path, code, scale in COMPACT_FIELDS]
+    for path, options in COMPACT_ENUMS:
+        value = _get(payload, path)
+        values.append(options.index(value) if value in options else 0)
+    hostname = str(payload.get("hostname") or "").encode("utf-8")[:MAX_HOSTNAME_BYTES]
+    return _STRUCT.pack(*values) + bytes([len(hostname)]) + hostname
+
+
+def decode_compact(body: bytes) -> Dict[str, Any]:
+    """
+    Payload reconstruído de um corpo compacto (só os campos levados)
+    
+    Raises:
+        ValueError: Corpo truncado ou com índice/hostname inválido
+    """
+    if len(body) < _STRUCT.size + 1:
+        raise ValueError(f"Corpo compacto com {len(body)} bytes (mínimo {_STRUCT.size + 1})")
+    values = _STRUCT.unpack_from(body)
+    size = body[_STRUCT.size]
+    hostname = body[_STRUCT.size + 1:]
+    if len(hostname) != size:
+        raise ValueError("Hostname do corpo compacto com tamanho errado")
+    
+    payload: Dict[str, Any] = {}
+    
+    def put(path: str, value: Any) -> None:
+        section, _, field = path.rpartition(".")
+        target = payload.setdefault(section, {}) if section else payload
+        target[field] = value
+    
+    for (path, code, scale), value in zip(COMPACT_FIELDS, values):
+        if code in ("d", "?") or scale == 1:
+            put(path, value)
+        else:
+            put(path, round(value / scale, len(str(scale)) - 1))
+    for (path, options), index in zip(COMPACT_ENUMS, values[len(COMPACT_FIELDS):]):
+        if index >= len(options):
+            raise ValueError(f"Índice {index} inválido para {path}")
+        put(path, options[index])
+    payload["hostname"] = hostname.decode("utf-8", errors="replace")
+    return payload
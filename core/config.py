@@ -4,9 +4,10 @@ Usa dataclass para type safety e validação
 """
 from dataclasses import dataclass, field, asdict
 from pathlib import Path
-from typing import Optional, Any
+from typing import Optional, Any, Callable, Dict, Tuple
 import json
 import os
+import re
 
 
 @dataclass
@@ -137,6 +138,159 @@ def save_config(config: TelemetryConfig, config_path: Optional[Path] = None, con
         return False
 
 
+# Versão do formato do config.json / receiver_config.json
+# (ausente = v0, arquivos anteriores ao campo)
+CONFIG_VERSION = 1
+
+
+def fill_defaults(data: dict[str, Any], defaults: dict[str, Any]) -> dict[str, Any]:
+    """
+    Completa o config com as chaves que faltam, inclusive dentro das seções
+    ("alertas", "sons"...); valores do usuário nunca são trocados
+    """
+    result = dict(data)
+    for key, default in defaults.items():
+        if key not in result:
+            result[key] = json.loads(json.dumps(default))  # Cópia: o padrão não é compartilhado
+        elif isinstance(default, dict) and isinstance(result[key], dict):
+            result[key] = fill_defaults(result[key], default)
+    return result
+
+
+def _migrate_v0(data: dict[str, Any]) -> dict[str, Any]:
+    """v0 -> v1: só ganha o config_version; as seções novas vêm de fill_defaults"""
+    return data
+
+
+# Passo de cada versão para a seguinte
+MIGRATIONS: Dict[int, Callable[[dict[str, Any]], dict[str, Any]]] = {
+    0: _migrate_v0,
+}
+
+
+def migrate_config(data: dict[str, Any], defaults: dict[str, Any]) -> Tuple[dict[str, Any], bool]:
+    """
+    Atualiza um config lido do disco para CONFIG_VERSION
+    
+    Args:
+        data: Conteúdo do arquivo (sem config_version = v0)
+        defaults: config_padrao do Sender ou do Receiver
+    
+    Returns:
+        (config migrado e completo, True se difere do arquivo e deve ser regravado).
+        Versão mais nova que a deste programa só é completada, nunca rebaixada.
+    """
+    version = data.get("config_version", 0)
+    if not isinstance(version, int) or isinstance(version, bool) or version < 0:
+        print(f"[Config] config_version inválido ({version!r}); tratado como 0")
+        version = 0
+    
+    migrated = dict(data)
+    while version < CONFIG_VERSION:
+        migrated = MIGRATIONS[version](migrated)
+        version += 1
+        migrated["config_version"] = version
+    
+    migrated = fill_defaults(migrated, defaults)
+    return migrated, migrated != data
+
+
+def load_versioned_config(path: str, defaults: dict[str, Any]) -> Tuple[dict[str, Any], bool]:
+    """
+    Lê um config JSON, migra (migrate_config) e regrava o arquivo se algo mudou
+    
+    Raises:
+        OSError, ValueError: Arquivo ilegível ou JSON inválido (o chamador usa os padrões)
+    
+    Returns:
+        (config, True se o arquivo foi atualizado)
+    """
+    with open(path, 'r', encoding='utf-8') as f:
+        data = json.load(f)
+    if not isinstance(data, dict):
+        raise ValueError(f"{path} não contém um objeto JSON")
+    
+    config, changed = migrate_config(data, defaults)
+    if changed:
+        try:
+            with open(path, 'w', encoding='utf-8') as f:
+                json.dump(config, f, indent=4, ensure_ascii=False)
+        except OSError as e:
+            print(f"[Config] Não foi possível atualizar {path}: {e}")
+            return config, False
+    return config, changed
+
+
+@dataclass
+class UiState:
+    """Estado da janela do Receiver lembrado entre execuções (ui_state.json)"""
+    
+    geometry: str = ""  # Geometria do Tk: "LxA+X+Y" (vazio = padrão)
+    theme: str = ""  # Último tema usado (vazio = "tema" da config)
+    
+    @classmethod
+    def from_dict(cls, data: dict[str, Any]) -> "UiState":
+        """Cria instância a partir de dicionário (ignora geometria inválida)"""
+        state = cls(
+            geometry=str(data.get("geometry", "") or ""),
+            theme=str(data.get("theme", "") or "")
+        )
+        if not is_valid_geometry(state.geometry):
+            state.geometry = ""
+        return state
+
+
+def is_valid_geometry(geometry: str) -> bool:
+    """Verifica se a string é uma geometria do Tk ("1366x700+100+50", offsets opcionais)"""
+    return re.fullmatch(r"\d+x\d+([+-]-?\d+[+-]-?\d+)?", geometry or "") is not None
+
+
+def load_ui_state(state_path: Optional[Path] = None) -> UiState:
+    """
+    Carrega o estado da janela
+    
+    Args:
+        state_path: Caminho opcional (padrão: ui_state.json ao lado da config)
+    
+    Returns:
+        UiState salvo ou vazio (arquivo ausente/corrompido)
+    """
+    if state_path is None:
+        state_path = get_config_path("ui_state.json")
+    
+    if state_path.exists():
+        try:
+            with open(state_path, 'r', encoding='utf-8') as f:
+                return UiState.from_dict(json.load(f))
+        except (json.JSONDecodeError, IOError, AttributeError) as e:
+            print(f"[Config] Erro ao carregar {state_path}: {e}")
+    
+    return UiState()
+
+
+def save_ui_state(state: UiState, state_path: Optional[Path] = None) -> bool:
+    """
+    Salva o estado da janela
+    
+    Args:
+        state: Estado a salvar
+        state_path: Caminho opcional (padrão: ui_state.json ao lado da config)
+    
+    Returns:
+        True se salvou com sucesso
+    """
+    if state_path is None:
+        state_path = get_config_path("ui_state.json")
+    
+    try:
+        with open(state_path, 'w', encoding='utf-8') as f:
+            json.dump(asdict(state), f, indent=2, ensure_ascii=False)
+        return True
+    except IOError as e:
+        print(f"[Config] Erro ao salvar {state_path}: {e}")
+        return False
+
+
 # Configuração global (singleton)
 _global_config: Optional[TelemetryConfig] = None
 
@@ -0,0 +1,132 @@
+"""
+Canal de controle Receiver -> Sender
+O Receiver pede, pelo mesmo socket UDP, outro intervalo de envio ou liga/desliga
+seções opcionais do payload; o Sender aplica dentro dos limites do validador
+"""
+from typing import Any, Dict, List, Optional, Tuple
+
+from .protocol import (
+    MagicByte, DeserializeError, serialize_payload, parse_body, wrap_frame, unwrap_frame
+)
+from .validators import validate_interval
+
+# Tipo do único pedido por enquanto
+CONFIG_REQUEST = "config_request"
+
+# Seções opcionais que o Receiver pode ligar/desligar
+CONTROL_SECTIONS = ("processes",)
+
+# Processos enviados quando o Receiver liga a seção e o config tem top_processes = 0
+DEFAULT_CONTROL_PROCESSES = 5
+
+# Um pedido de controle é pequeno; qualquer coisa maior é descartada
+MAX_CONTROL_SIZE = 1024
+
+
+def config_request(
+    interval: Optional[float] = None,
+    sections: Optional[Dict[str, bool]] = None
+) -> Dict[str, Any]:
+    """
+    Monta um pedido de mudança de configuração
+    
+    Args:
+        interval: Novo intervalo de envio em segundos (None = mantém)
+        sections: Seção -> ligada/desligada (ver CONTROL_SECTIONS)
+    """
+    request: Dict[str, Any] = {"type": CONFIG_REQUEST}
+    if interval is not None:
+        request["interval"] = interval
+    if sections:
+        request["sections"] = dict(sections)
+    return request
+
+
+def encode_control(
+    request: Dict[str, Any],
+    key: Optional[str] = None,
+    auth_secret: Optional[str] = None
+) -> bytes:
+    """Frame de controle: MagicByte.CONTROL + JSON, com a mesma cifra/tag dos payloads"""
+    frame = bytes([MagicByte.CONTROL]) + serialize_payload(request)
+    return wrap_frame(frame, key, auth_secret)
+
+
+def decode_control(
+    frame: bytes,
+    key: Optional[str] = None,
+    auth_secret: Optional[str] = None
+) -> Optional[Dict[str, Any]]:
+    """
+    Decodifica um frame de controle
+    
+    Returns:
+        O pedido, ou None se o frame não é de controle (ex: payload de outro Sender)
+    
+    Raises:
+        ProtocolError: Frame de controle inválido ou recusado pela autenticação
+    """
+    if not frame:
+        return None
+    frame = unwrap_frame(frame, key, auth_secret)
+    if frame[0] != MagicByte.CONTROL:
+        return None
+    if len(frame) > MAX_CONTROL_SIZE:
+        raise DeserializeError(f"Frame de controle passa de {MAX_CONTROL_SIZE} bytes")
+    request = parse_body(frame[1:])
+    if request.get("type") != CONFIG_REQUEST:
+        raise DeserializeError(f"Pedido de controle desconhecido: {request.get('type')!r}")
+    return request
+
+
+def apply_config_request(
+    request: Dict[str, Any],
+    interval: float,
+    sections: Dict[str, bool]
+) -> Tuple[float, Dict[str, bool], List[str], List[str]]:
+    """
+    Aplica um pedido sobre o estado atual do Sender
+    
+    O intervalo passa pelo validate_interval; valores fora dos limites
+    e seções desconhecidas são recusados sem afetar o resto do pedido.
+    
+    Args:
+        request: Pedido decodificado (ver decode_control)
+        interval: Intervalo de envio atual
+        sections: Seção -> ligada, estado atual
+    
+    Returns:
+        (intervalo, seções, mudanças aplicadas, mensagens dos itens recusados)
+    """
+    sections = dict(sections)
+    changes: List[str] = []
+    errors: List[str] = []
+    
+    if "interval" in request:
+        value = request["interval"]
+        if isinstance(value, bool):
+            valid, error = False, f"Intervalo deve ser um número: {value}"
+        else:
+            valid, error = validate_interval(value)
+        if valid:
+            new_interval = float(value)
+            if new_interval != interval:
+                changes.append(f"intervalo {interval}s -> {new_interval}s")
+                interval = new_interval
+        else:
+            errors.append(error)
+    
+    requested = request.get("sections", {})
+    if not isinstance(requested, dict):
+        errors.append(f"Seções devem ser um objeto: {requested!r}")
+        requested = {}
+    for name, enabled in requested.items():
+        if name not in CONTROL_SECTIONS:
+            errors.append(f"Seção desconhecida: {name}")
+        elif not isinstance(enabled, bool):
+            errors.append(f"Seção {name}: esperado true/false, recebido {enabled!r}")
+        elif sections.get(name) != enabled:
+            sections[name] = enabled
+            changes.append(f"{name} {'ligado' if enabled else 'desligado'}")
+    
+    return interval, sections, changes, errors
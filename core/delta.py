@@ -0,0 +1,227 @@
+"""
+Frames delta (MagicByte.DELTA): um keyframe completo a cada N frames e, entre
+eles, só os campos que mudaram em relação ao último keyframe
+
+Corpo depois do magic byte:
+    tipo (1 byte: DELTA_KEY ou DELTA_CHANGES) + id do keyframe (1 byte, 0-255)
+    keyframe: um frame normal (magic GZIP/RAW + corpo) com o payload inteiro
+    delta:    nº de campos (uint16) + bitmask dos que mudaram + JSON com a
+              lista dos valores novos, na ordem dos bits
+
+A ordem dos campos é a das folhas do payload ordenadas pelo caminho
+(flatten_payload): o Receiver chega nela sozinho a partir do keyframe.
+Listas (discos, fans, processos) são uma folha só: mudou um item, vai a
+lista inteira. Campo novo ou removido força um keyframe
+
+Cada delta é relativo ao keyframe, não ao frame anterior: perder um delta
+não afeta os seguintes; perder o keyframe descarta os deltas até o próximo
+"""
+import json
+import struct
+from typing import Any, Dict, List, Optional, Sequence, Tuple
+
+from .protocol import (
+    MagicByte, DeserializeError, DecodeLimitError, MAX_DECODED_SIZE,
+    serialize_payload, compress_body, extract_body
+)
+
+DELTA_KEY = 0x00
+DELTA_CHANGES = 0x01
+
+_HEADER = struct.Struct("<BB")
+_COUNT = struct.Struct("<H")
+
+Path = Tuple[str, ...]
+
+
+class MissingKeyframeError(DeserializeError):
+    """Delta de um keyframe que este Receiver não tem (perdido ou ligou no meio)"""
+    pass
+
+
+def flatten_payload(payload: Dict[str, Any]) -> List[Tuple[Path, Any]]:
+    """
+    Folhas do payload como (caminho, valor), ordenadas pelo caminho
+    
+    Dicionários vazios e listas são folhas (não são percorridos).
+    """
+    leaves: List[Tuple[Path, Any]] = []
+    
+    def walk(node: Dict[str, Any], prefix: Path) -> None:
+        for key, value in node.items():
+            path = prefix + (key,)
+            if isinstance(value, dict) and value:
+                walk(value, path)
+            else:
+                leaves.append((path, value))
+    
+    walk(payload, ())
+    leaves.sort(key=lambda leaf: leaf[0])
+    return leaves
+
+
+def unflatten_payload(leaves: Sequence[Tuple[Path, Any]]) -> Dict[str, Any]:
+    """Inverso de flatten_payload"""
+    payload: Dict[str, Any] = {}
+    for path, value in leaves:
+        node = payload
+        for key in path[:-1]:
+            node = node.setdefault(key, {})
+        node[path[-1]] = value
+    return payload
+
+
+def pack_bitmask(flags: Sequence[bool]) -> bytes:
+    """Bit i = flags[i] (byte i // 8, do bit menos significativo para o mais)"""
+    mask = bytearray((len(flags) + 7) // 8)
+    for i, flag in enumerate(flags):
+        if flag:
+            mask[i // 8] |= 1 << (i % 8)
+    return bytes(mask)
+
+
+def unpack_bitmask(mask: bytes, count: int) -> List[bool]:
+    """Inverso de pack_bitmask; ValueError se mask não tem count bits"""
+    if len(mask) != (count + 7) // 8:
+        raise ValueError(f"bitmask com {len(mask)} bytes para {count} campos")
+    return [bool(mask[i // 8] & (1 << (i % 8))) for i in range(count)]
+
+
+def _full_frame(payload: Dict[str, Any]) -> bytes:
+    """Frame normal do payload: gzip se compensar, senão JSON puro"""
+    data = serialize_payload(payload)
+    compressed = compress_body(data)
+    if len(compressed) < len(data):
+        return bytes([MagicByte.GZIP]) + compressed
+    return bytes([MagicByte.RAW]) + data
+
+
+class DeltaEncoder:
+    """
+    Lado do Sender: decide entre keyframe e delta e monta o frame
+    
+    encode() não muda o estado (fit_payload pode chamá-lo várias vezes com
+    seções a menos); commit() confirma o último frame codificado depois
+    que ele foi enviado.
+    """
+    
+    def __init__(self, keyframe_every: int):
+        """
+        Args:
+            keyframe_every: Um keyframe a cada N frames (1 = só keyframes)
+        """
+        self.keyframe_every = max(1, int(keyframe_every))
+        self.key_id = -1
+        self.key_leaves: Optional[List[Tuple[Path, Any]]] = None
+        self.since_key = 0
+        self._pending: Optional[Tuple[bool, List[Tuple[Path, Any]]]] = None
+    
+    def _needs_key(self, leaves: List[Tuple[Path, Any]]) -> bool:
+        if self.key_leaves is None or self.since_key >= self.keyframe_every:
+            return True
+        return [path for path, _ in leaves] != [path for path, _ in self.key_leaves]
+    
+    def encode(self, payload: Dict[str, Any]) -> bytes:
+        """Magic byte DELTA + corpo (sem criptografia/tag: ver wrap_frame)"""
+        leaves = flatten_payload(payload)
+        is_key = self._needs_key(leaves)
+        self._pending = (is_key, leaves)
+        if is_key:
+            key_id = (self.key_id + 1) % 256
+            return bytes([MagicByte.DELTA]) + _HEADER.pack(DELTA_KEY, key_id) + _full_frame(payload)
+        
+        changed = [value != key_value for (_, value), (_, key_value) in zip(leaves, self.key_leaves)]
+        values = [value for (_, value), flag in zip(leaves, changed) if flag]
+        return (
+            bytes([MagicByte.DELTA]) + _HEADER.pack(DELTA_CHANGES, self.key_id)
+            + _COUNT.pack(len(leaves)) + pack_bitmask(changed) + serialize_payload(values)
+        )
+    
+    def commit(self) -> None:
+        """O último frame de encode() foi enviado"""
+        if self._pending is None:
+            return
+        is_key, leaves = self._pending
+        self._pending = None
+        if is_key:
+            self.key_id = (self.key_id + 1) % 256
+            self.key_leaves = leaves
+            self.since_key = 1
+        else:
+            self.since_key += 1
+
+
+class DeltaDecoder:
+    """Lado do Receiver: guarda o último keyframe de cada Sender e reconstrói os deltas"""
+    
+    def __init__(self):
+        self.keys: Dict[str, Tuple[int, List[Tuple[Path, Any]]]] = {}
+    
+    def forget(self, sender: str) -> None:
+        """Descarta o keyframe do Sender (ex: ele encerrou)"""
+        self.keys.pop(sender, None)
+    
+    def decode(self, frame: bytes, sender: str = "", limit: int = MAX_DECODED_SIZE) -> bytes:
+        """
+        Corpo JSON do payload completo, como extract_body
+        
+        Args:
+            frame: Frame DELTA já sem criptografia/tag (ver unwrap_frame)
+            sender: Quem mandou (keyframes são guardados por Sender)
+            limit: Tamanho máximo do JSON
+        
+        Raises:
+            MissingKeyframeError: Delta sem o keyframe correspondente
+            DeserializeError: Corpo inválido
+            DecodeLimitError: Corpo maior que limit
+        """
+        if len(frame) < 1 + _HEADER.size:
+            raise DeserializeError("Frame delta sem cabeçalho")
+        kind, key_id = _HEADER.unpack_from(frame, 1)
+        rest = frame[1 + _HEADER.size:]
+        
+        if kind == DELTA_KEY:
+            body = extract_body(rest, limit)
+            try:
+                payload = json.loads(body.decode('utf-8'))
+            except (json.JSONDecodeError, UnicodeDecodeError, RecursionError) as e:
+                raise DeserializeError(f"Keyframe com JSON inválido: {e}") from e
+            if not isinstance(payload, dict):
+                raise DeserializeError("Keyframe não é um objeto JSON")
+            self.keys[sender] = (key_id, flatten_payload(payload))
+            return body
+        if kind != DELTA_CHANGES:
+            raise DeserializeError(f"Tipo de frame delta desconhecido: {kind}")
+        
+        current = self.keys.get(sender)
+        if current is None or current[0] != key_id:
+            raise MissingKeyframeError(f"Delta do keyframe {key_id} sem o keyframe (aguardando o próximo)")
+        leaves = current[1]
+        if len(rest) < _COUNT.size:
+            raise DeserializeError("Frame delta sem o número de campos")
+        (count,) = _COUNT.unpack_from(rest)
+        if count != len(leaves):
+            raise DeserializeError(f"Delta com {count} campos, keyframe com {len(leaves)}")
+        mask_end = _COUNT.size + (count + 7) // 8
+        try:
+            changed = unpack_bitmask(rest[_COUNT.size:mask_end], count)
+        except ValueError as e:
+            raise DeserializeError(f"Frame delta com bitmask incompleto: {e}") from e
+        try:
+            values = json.loads(rest[mask_end:].decode('utf-8'))
+        except (json.JSONDecodeError, UnicodeDecodeError, RecursionError) as e:
+            raise DeserializeError(f"Delta com JSON inválido: {e}") from e
+        if not isinstance(values, list) or len(values) != sum(changed):
+            raise DeserializeError("Delta com número de valores diferente do bitmask")
+        
+        new_values = iter(values)
+        rebuilt = [(path, next(new_values) if flag else value) for (path, value), flag in zip(leaves, changed)]
+        body = serialize_payload(unflatten_payload(rebuilt))
+        if len(body) > limit:
+            raise DecodeLimitError(f"Corpo passa de {limit} bytes")
+        return body
+
+
+def is_delta_frame(frame: bytes) -> bool:
+    """Frame (já sem criptografia/tag) no formato delta"""
+    return bool(frame) and frame[0] == MagicByte.DELTA
@@ -0,0 +1,267 @@
+"""
+Exportação da telemetria recebida para ferramentas externas
+NDJSON (Telegraf, Vector), métricas Prometheus e o histórico dos gráficos em CSV
+"""
+import json
+import math
+import threading
+import time
+from http.server import BaseHTTPRequestHandler, ThreadingHTTPServer
+from typing import Any, Dict, List, Optional, Tuple
+
+from .sources import SourceTable
+
+
+def stream_record(
+    payload: Dict[str, Any],
+    source: str,
+    timestamp: Optional[float] = None
+) -> Dict[str, Any]:
+    """
+    Monta o registro exportado para um payload recebido
+    
+    Args:
+        payload: Payload decodificado
+        source: Endereço do Sender
+        timestamp: Instante de recebimento (epoch em segundos)
+    
+    Returns:
+        Dict com timestamp, source e payload
+    """
+    return {
+        "timestamp": round(time.time() if timestamp is None else timestamp, 3),
+        "source": source,
+        "payload": payload
+    }
+
+
+def to_ndjson(record: Dict[str, Any]) -> str:
+    """Serializa um registro como uma linha de JSON (sem quebra de linha)"""
+    return json.dumps(record, separators=(',', ':'), ensure_ascii=False)
+
+
+def export_filename(prefix: str, extension: str, now: Optional[float] = None) -> str:
+    """Nome com data/hora, ex: graphs_20250101_153000.csv"""
+    stamp = time.strftime("%Y%m%d_%H%M%S", time.localtime(time.time() if now is None else now))
+    return f"{prefix}_{stamp}.{extension}"
+
+
+def history_csv(history: Dict[str, Any], interval: Optional[float] = None) -> str:
+    """
+    Converte as séries dos gráficos em CSV, uma linha por amostra
+    
+    As séries são alinhadas pela amostra mais recente (a mais curta fica
+    vazia no início). Lacunas (NaN) e amostras ausentes viram campo vazio.
+    
+    Args:
+        history: Nome da série -> valores, do mais antigo ao mais recente
+        interval: Segundos entre amostras; com ele a primeira coluna é
+            "seconds_ago" (0 = amostra mais recente), senão "sample"
+    
+    Returns:
+        Texto CSV com cabeçalho, linhas terminadas em "\n"
+    """
+    keys = list(history)
+    series = [list(history[key]) for key in keys]
+    count = max((len(values) for values in series), default=0)
+    first = "seconds_ago" if interval else "sample"
+    lines = [",".join([first] + keys)]
+    for i in range(count):
+        if interval:
+            index = f"{(count - 1 - i) * interval:g}"
+        else:
+            index = str(i)
+        row = [index]
+        for values in series:
+            offset = i - (count - len(values))
+            value = values[offset] if offset >= 0 else None
+            if value is None or (isinstance(value, float) and math.isnan(value)):
+                row.append("")
+            else:
+                row.append(f"{value:g}" if isinstance(value, float) else str(value))
+        lines.append(",".join(row))
+    return "\n".join(lines) + "\n"
+
+
+# (seção do payload, campo, descrição) exportados como telemetry_<seção>_<campo>
+PROMETHEUS_METRICS: List[Tuple[str, str, str]] = [
+    ("cpu", "usage", "CPU usage (%)"),
+    ("cpu", "temp", "CPU temperature (°C)"),
+    ("cpu", "voltage", "CPU core voltage (V)"),
+    ("cpu", "power", "CPU package power (W)"),
+    ("cpu", "clock", "CPU clock (MHz)"),
+    ("gpu", "load", "GPU load (%)"),
+    ("gpu", "temp", "GPU temperature (°C)"),
+    ("gpu", "voltage", "GPU voltage (V)"),
+    ("gpu", "clock_core", "GPU core clock (MHz)"),
+    ("gpu", "clock_mem", "GPU memory clock (MHz)"),
+    ("gpu", "power_w", "GPU power draw (W, 0 = unknown)"),
+    ("gpu", "enc_util", "GPU video encoder (NVENC) utilization (%)"),
+    ("gpu", "dec_util", "GPU video decoder (NVDEC) utilization (%)"),
+    ("gpu", "mem_util", "GPU memory bus utilization (%)"),
+    ("gpu", "pcie_tx_kbps", "GPU PCIe transmit throughput (KB/s)"),
+    ("gpu", "pcie_rx_kbps", "GPU PCIe receive throughput (KB/s)"),
+    ("gpu", "fan", "GPU fan (RPM or %, see fan_unit)"),
+    ("gpu", "mem_used_mb", "GPU memory used (MB)"),
+    ("gpu", "mem_total_mb", "GPU memory total (MB, 0 = unknown)"),
+    ("mobo", "temp", "Motherboard temperature (°C)"),
+    ("ram", "percent", "RAM usage (%)"),
+    ("ram", "used_gb", "RAM used (GB)"),
+    ("ram", "total_gb", "RAM total (GB)"),
+    ("network", "down_kbps", "Network download (KB/s)"),
+    ("network", "up_kbps", "Network upload (KB/s)"),
+    ("network", "ping_ms", "Ping latency (ms, -1 = unreachable)"),
+    ("battery", "percent", "Battery charge (%)"),
+]
+
+# Campos por disco, com o rótulo disk="<nome>" (e drive_id="<serial>" se o sender enviar)
+PROMETHEUS_DISK_METRICS: List[Tuple[str, str]] = [
+    ("temp", "Storage temperature (°C, NVMe composite)"),
+    ("temp_max", "Hottest storage temperature sensor (°C)"),
+    ("health", "Storage health (%)"),
+    ("used_space", "Storage used space (%)"),
+    ("total_gb", "Storage capacity of mounted partitions (GB)"),
+    ("free_gb", "Storage free space (GB)"),
+]
+
+
+def _escape_label(value: str) -> str:
+    """Escapa um valor de rótulo no formato de texto do Prometheus"""
+    return value.replace("\\", "\\\\").replace("\"", "\\\"").replace("\n", "\\n")
+
+
+def _is_number(value: Any) -> bool:
+    return isinstance(value, (int, float)) and not isinstance(value, bool)
+
+
+def prometheus_text(
+    entries: List[Tuple[str, Tuple[Dict[str, Any], float]]],
+    now: float,
+    timeout: float
+) -> str:
+    """
+    Formata o último payload de cada Sender no formato de texto do Prometheus
+    
+    Args:
+        entries: Lista de (endereço, (payload, instante de recebimento))
+        now: Instante atual (epoch)
+        timeout: Segundos sem dados até o Sender ser considerado parado
+    
+    Returns:
+        Texto para o endpoint /metrics
+    """
+    lines: List[str] = [
+        "# HELP telemetry_up 1 if the sender reported within the connection timeout",
+        "# TYPE telemetry_up gauge",
+    ]
+    for source, (_, received_at) in entries:
+        lines.append(f'telemetry_up{{source="{_escape_label(source)}"}} {1 if now - received_at < timeout else 0}')
+    
+    lines += [
+        "# HELP telemetry_last_seen_seconds Seconds since the last packet from the sender",
+        "# TYPE telemetry_last_seen_seconds gauge",
+    ]
+    for source, (_, received_at) in entries:
+        lines.append(f'telemetry_last_seen_seconds{{source="{_escape_label(source)}"}} {round(now - received_at, 3)}')
+    
+    for section, field, help_text in PROMETHEUS_METRICS:
+        name = f"telemetry_{section}_{field}"
+        samples = []
+        for source, (payload, _) in entries:
+            value = (payload.get(section) or {}).get(field)
+            if _is_number(value):
+                samples.append(f'{name}{{source="{_escape_label(source)}"}} {value}')
+        if samples:
+            lines += [f"# HELP {name} {help_text}", f"# TYPE {name} gauge"] + samples
+    
+    for field, help_text in PROMETHEUS_DISK_METRICS:
+        name = f"telemetry_storage_{field}"
+        samples = []
+        for source, (payload, _) in entries:
+            for disk in payload.get("storage") or []:
+                value = disk.get(field)
+                if _is_number(value):
+                    labels = f'source="{_escape_label(source)}",disk="{_escape_label(str(disk.get("name", "?")))}"'
+                    # Discos do mesmo modelo têm o mesmo nome: o drive_id separa as séries
+                    if disk.get("drive_id"):
+                        labels += f',drive_id="{_escape_label(str(disk["drive_id"]))}"'
+                    samples.append(f"{name}{{{labels}}} {value}")
+        if samples:
+            lines += [f"# HELP {name} {help_text}", f"# TYPE {name} gauge"] + samples
+    
+    return "\n".join(lines) + "\n"
+
+
+# Sender parado continua no /metrics (telemetry_up 0) por este tempo; depois sai
+PROMETHEUS_RETENTION_SECS = 300.0
+
+
+class PrometheusExporter:
+    """
+    Endpoint HTTP /metrics com o último payload de cada Sender
+    
+    Senders sem pacote há mais de retention segundos saem da tabela (um IP
+    por DHCP não fica para sempre como série morta).
+    
+    Exemplo:
+        exporter = PrometheusExporter(port=9877, timeout=5)
+        exporter.start()
+        exporter.update("192.168.1.10", payload)
+    """
+    
+    def __init__(
+        self,
+        port: int,
+        timeout: float = 5.0,
+        host: str = "0.0.0.0",
+        retention: float = PROMETHEUS_RETENTION_SECS
+    ):
+        self.port = port
+        self.host = host
+        self.timeout = timeout
+        self.retention = max(timeout, retention)
+        self._sources = SourceTable(timeout)
+        self._lock = threading.Lock()
+        self._server: Optional[ThreadingHTTPServer] = None
+    
+    def update(self, source: str, payload: Dict[str, Any], now: Optional[float] = None) -> None:
+        """Registra o payload mais recente de um Sender"""
+        with self._lock:
+            self._sources.update(source, payload, now)
+    
+    def render(self, now: Optional[float] = None) -> str:
+        """Texto atual do /metrics (descarta os Senders além da retenção)"""
+        now = time.time() if now is None else now
+        with self._lock:
+            self._sources.evict(now, older_than=self.retention)
+            entries = self._sources.items()
+        return prometheus_text(entries, now, self.timeout)
+    
+    def start(self) -> None:
+        """Sobe o servidor HTTP em uma thread daemon"""
+        exporter = self
+        
+        class Handler(BaseHTTPRequestHandler):
+            def do_GET(self):
+                if self.path.split("?")[0] != "/metrics":
+                    self.send_error(404)
+                    return
+                body = exporter.render().encode("utf-8")
+                self.send_response(200)
+                self.send_header("Content-Type", "text/plain; version=0.0.4; charset=utf-8")
+                self.send_header("Content-Length", str(len(body)))
+                self.end_headers()
+                self.wfile.write(body)
+            
+            def log_message(self, format, *args):
+                pass  # Sem log por scrape
+        
+        self._server = ThreadingHTTPServer((self.host, self.port), Handler)
+        threading.Thread(target=self._server.serve_forever, daemon=True).start()
+        print(f"[Prometheus] Métricas em http://{self.host}:{self.port}/metrics")
+    
+    def stop(self) -> None:
+        """Encerra o servidor HTTP"""
+        if self._server:
+            self._server.shutdown()
+            self._server = None
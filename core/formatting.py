@@ -0,0 +1,151 @@
+"""
+Formatação dos valores exibidos nos painéis
+Trata sensores ausentes (None/NaN) separadamente de leituras reais iguais a zero
+"""
+import math
+from typing import Any, Dict, Optional, Tuple
+
+# Exibido no lugar de um sensor ausente (um "0.0V" pareceria medição real)
+ABSENT = "—"
+
+# Casas decimais por categoria de unidade (display_precision do Receiver)
+DEFAULT_PRECISION: Dict[str, int] = {
+    "temp": 1,
+    "voltage": 3,
+    "percent": 1,
+    "power": 1,
+    "rate": 1,
+    "other": 1,
+}
+
+# Taxas chegam em KB/s; a partir de 1024 KB/s são exibidas em MB/s
+RATE_UNITS = ("KB/s", "MB/s")
+
+# Separadores (decimal, milhar) por number_locale; "" = ponto, sem separador de milhar
+NUMBER_LOCALES: Dict[str, Tuple[str, str]] = {
+    "": (".", ""),
+    "en": (".", ","),       # 1,234.5
+    "pt": (",", "."),       # 1.234,5 (também de, es, it)
+    "de": (",", "."),
+    "fr": (",", "\u202f"),  # 1 234,5 (espaço fino)
+}
+DEFAULT_NUMBER_LOCALE = ""
+
+
+def is_absent(value: Any) -> bool:
+    """Verifica se o valor representa um sensor ausente (None ou NaN)"""
+    return value is None or (isinstance(value, float) and math.isnan(value))
+
+
+def celsius_to_fahrenheit(value: float) -> float:
+    """Converte °C para °F"""
+    return value * 9 / 5 + 32
+
+
+def unit_category(unit: str) -> str:
+    """Categoria da unidade usada na tabela de precisão ("°C" -> "temp")"""
+    unit = unit.strip()
+    if unit in ("°C", "°F"):
+        return "temp"
+    if unit == "V":
+        return "voltage"
+    if unit == "%":
+        return "percent"
+    if unit == "W":
+        return "power"
+    if unit in RATE_UNITS:
+        return "rate"
+    return "other"
+
+
+def number_separators(number_locale: str) -> Tuple[str, str]:
+    """(decimal, milhar) de number_locale; desconhecido = padrão (ponto, sem milhar)"""
+    return NUMBER_LOCALES.get(str(number_locale or "").strip().lower(), NUMBER_LOCALES[DEFAULT_NUMBER_LOCALE])
+
+
+def _format_number(value: Any, decimals: Optional[int], separators: Tuple[str, str]) -> str:
+    """Número com as casas pedidas (None = inteiro como veio) e os separadores do locale"""
+    decimal, group = separators
+    text = f"{value:,}" if decimals is None else f"{value:,.{decimals}f}"
+    return text.translate({ord(","): group, ord("."): decimal})
+
+
+def _format_rate(value: float, decimals: int, compact: bool, separators: Tuple[str, str]) -> str:
+    """Taxa em KB/s, passando para MB/s a partir de 1024 KB/s"""
+    prefix = "K"
+    if abs(value) >= 1024:
+        value /= 1024
+        prefix = "M"
+    number = _format_number(value, decimals, separators)
+    if compact:
+        return f"{number}{prefix}"
+    return f"{number} {prefix}B/s"
+
+
+def format_value(
+    value: Any,
+    unit: str = "",
+    temp_unit: str = "C",
+    precision: Optional[Dict[str, int]] = None,
+    compact: bool = False,
+    number_locale: str = DEFAULT_NUMBER_LOCALE
+) -> str:
+    """
+    Formata um valor com a unidade
+    
+    Só a exibição muda com temp_unit: o payload e os thresholds de alerta
+    continuam em Celsius.
+    
+    Args:
+        value: Valor do payload (None/NaN = sensor ausente)
+        unit: Unidade exibida após o valor (ex: "°C", " MHz")
+        temp_unit: "C" ou "F"; valores em "°C" são convertidos para "F"
+        precision: Casas decimais por categoria (ver DEFAULT_PRECISION);
+            categorias ausentes usam o padrão
+        compact: Abrevia taxas ("5.4M" em vez de "5.4 MB/s")
+        number_locale: Separadores decimal e de milhar (ver NUMBER_LOCALES)
+    
+    Returns:
+        Texto do painel; ABSENT para sensores ausentes
+    """
+    if is_absent(value):
+        return ABSENT
+    
+    if unit == "°C" and str(temp_unit).upper() == "F" and isinstance(value, (int, float)):
+        value = float(celsius_to_fahrenheit(value))
+        unit = "°F"
+    
+    category = unit_category(unit)
+    decimals = (precision or {}).get(category, DEFAULT_PRECISION[category])
+    separators = number_separators(number_locale)
+    
+    if category == "rate" and unit.strip() == "KB/s" and isinstance(value, (int, float)):
+        return _format_rate(float(value), decimals, compact, separators)
+    
+    if isinstance(value, float):
+        return f"{_format_number(value, decimals, separators)}{unit}"
+    
+    if isinstance(value, int) and not isinstance(value, bool):
+        return f"{_format_number(value, None, separators)}{unit}"
+    
+    return f"{value}{unit}"
+
+
+def format_uptime(seconds: Any) -> str:
+    """
+    Formata o uptime do sender para o banner de conexão
+    
+    Returns:
+        "3d 04h", "5h 12m" ou "7m"; vazio para senders antigos (sem o campo)
+    """
+    if not isinstance(seconds, (int, float)) or isinstance(seconds, bool) or seconds <= 0:
+        return ""
+    
+    minutes = int(seconds) // 60
+    hours, minutes = divmod(minutes, 60)
+    days, hours = divmod(hours, 24)
+    if days:
+        return f"{days}d {hours:02d}h"
+    if hours:
+        return f"{hours}h {minutes:02d}m"
+    return f"{minutes}m"
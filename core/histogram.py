@@ -0,0 +1,52 @@
+"""
+Histograma de amostras em faixas de largura fixa
+Usado na visão de distribuição de temperaturas do dashboard
+"""
+from typing import Iterable, List
+
+
+def bin_index(value: float, min_value: float, bin_width: float, bin_count: int) -> int:
+    """
+    Retorna a faixa de um valor
+    
+    Valores na fronteira entram na faixa de cima (faixas [início, fim));
+    valores fora do range são presos à primeira/última faixa.
+    
+    Args:
+        value: Amostra
+        min_value: Início da primeira faixa
+        bin_width: Largura de cada faixa
+        bin_count: Número de faixas
+    
+    Returns:
+        Índice entre 0 e bin_count - 1
+    """
+    index = int((value - min_value) // bin_width)
+    return max(0, min(bin_count - 1, index))
+
+
+def histogram(
+    values: Iterable[float],
+    min_value: float,
+    max_value: float,
+    bin_width: float
+) -> List[int]:
+    """
+    Conta amostras por faixa
+    
+    Args:
+        values: Amostras
+        min_value: Início da primeira faixa
+        max_value: Fim da última faixa
+        bin_width: Largura de cada faixa
+    
+    Returns:
+        Lista com a contagem de cada faixa (todas zero se não houver amostras)
+    """
+    bin_count = max(1, int(-(-(max_value - min_value) // bin_width)))  # ceil
+    counts = [0] * bin_count
+    
+    for value in values:
+        counts[bin_index(value, min_value, bin_width, bin_count)] += 1
+    
+    return counts
@@ -2,11 +2,17 @@
 Configuração de logging estruturado para o Sistema de Telemetria
 Substitui prints por logging com níveis e formatação
 """
+import atexit
+import json
 import logging
+import re
 import sys
-from datetime import datetime
+import threading
+import time
+from datetime import datetime, timezone
+from logging.handlers import TimedRotatingFileHandler
 from pathlib import Path
-from typing import Optional
+from typing import Any, Callable, Mapping, Optional, Sequence
 from enum import IntEnum
 
 
@@ -70,6 +76,221 @@ class FileFormatter(logging.Formatter):
         return f"{timestamp} [{level}] ({name}) {message}"
 
 
+# Formatos de saída do log: texto (padrão) ou um objeto JSON por linha (NDJSON)
+LOG_FORMATS = ("text", "json")
+LOG_FORMAT_ENV = "TELEMETRIA_LOG_FORMAT"
+
+# Palavras que definem o nível das linhas impressas com print("[Tag] ...")
+_ERROR_WORDS = ("erro", "error", "falha", "failed")
+_WARNING_WORDS = ("aviso", "warning")
+_TAGGED_LINE = re.compile(r"\s*\[([^\]]+)\]\s*(.*)")
+
+# Nomes aceitos em log_level (no estilo do RUST_LOG/EnvFilter); "off" silencia tudo
+LOG_LEVEL_NAMES = {
+    "trace": logging.DEBUG,
+    "debug": logging.DEBUG,
+    "info": logging.INFO,
+    "warn": logging.WARNING,
+    "warning": logging.WARNING,
+    "error": logging.ERROR,
+    "off": logging.CRITICAL + 10,
+}
+
+
+def parse_log_level(value: Any) -> int:
+    """
+    Converte o log_level do config em nível do logging
+    
+    Aceita os nomes de LOG_LEVEL_NAMES (sem diferenciar maiúsculas) ou um
+    número; qualquer outra coisa vira INFO.
+    """
+    if isinstance(value, int) and not isinstance(value, bool):
+        return value
+    return LOG_LEVEL_NAMES.get(str(value).strip().lower(), logging.INFO)
+
+
+def log_record(timestamp: float, level: str, target: str, message: str) -> str:
+    """Linha JSON de log: ts (ISO 8601 UTC), level, target e message"""
+    ts = datetime.fromtimestamp(timestamp, tz=timezone.utc).isoformat(timespec="milliseconds")
+    return json.dumps(
+        {"ts": ts, "level": level, "target": target, "message": message},
+        ensure_ascii=False
+    )
+
+
+class JsonFormatter(logging.Formatter):
+    """Formatter JSON (uma linha por registro) para agregadores de log"""
+    
+    def format(self, record: logging.LogRecord) -> str:
+        message = record.getMessage()
+        if record.exc_info:
+            message = f"{message}\n{self.formatException(record.exc_info)}"
+        return log_record(record.created, record.levelname, record.name, message)
+
+
+def parse_print_line(line: str) -> tuple[str, str, str]:
+    """
+    Separa uma linha de print("[Tag] mensagem") em (nível, target, mensagem)
+    
+    O prefixo entre colchetes vira o target; o nível sai das palavras da
+    mensagem (erro/falha = ERROR, aviso = WARNING, senão INFO).
+    """
+    match = _TAGGED_LINE.match(line)
+    target, message = (match.group(1), match.group(2)) if match else ("", line.strip())
+    lowered = message.lower()
+    if any(word in lowered for word in _ERROR_WORDS):
+        level = "ERROR"
+    elif any(word in lowered for word in _WARNING_WORDS):
+        level = "WARNING"
+    else:
+        level = "INFO"
+    return level, target, message
+
+
+def format_print_line(line: str, timestamp: float) -> str:
+    """Converte uma linha de print("[Tag] mensagem") em JSON (ver parse_print_line)"""
+    return log_record(timestamp, *parse_print_line(line))
+
+
+def rotating_file_handler(log_dir: str, name: str, backup_days: int = 7) -> logging.Handler:
+    """
+    Arquivo de log com rotação diária (à meia-noite) em log_dir/<name>.log
+    
+    Os arquivos antigos ganham a data no nome (<name>.log.2024-05-01) e só os
+    últimos backup_days são mantidos.
+    """
+    path = Path(log_dir) / f"{name}.log"
+    path.parent.mkdir(parents=True, exist_ok=True)
+    return TimedRotatingFileHandler(path, when="midnight", backupCount=max(0, backup_days), encoding="utf-8")
+
+
+class LogWriter:
+    """
+    Stream que recebe os print() e aplica o nível mínimo, o formato e o arquivo
+    
+    Sender e receiver registram com print(); basta trocar o sys.stdout por
+    este writer. Cada linha completa passa pelo filtro de nível e vai para o
+    console (se houver) e para o arquivo (se configurado).
+    """
+    
+    json_output = False
+    
+    def __init__(
+        self,
+        stream: Any,
+        level: int = logging.INFO,
+        file_handler: Optional[logging.Handler] = None,
+        clock: Callable[[], float] = time.time
+    ):
+        self.stream = stream
+        self.level = level
+        self.file_handler = file_handler
+        self._clock = clock
+        self._buffer = ""
+        self._lock = threading.Lock()  # print() de várias threads
+        if file_handler is not None:
+            file_handler.setFormatter(JsonFormatter() if self.json_output else FileFormatter())
+    
+    def write(self, text: str) -> int:
+        with self._lock:
+            self._buffer += text
+            while "\n" in self._buffer:
+                line, self._buffer = self._buffer.split("\n", 1)
+                self._emit(line)
+        return len(text)
+    
+    def _emit(self, line: str) -> None:
+        # Linhas vazias ou só de separador ("=====") não são mensagens
+        if not line.strip(" =-"):
+            if self.stream is not None and not self.json_output and self.level <= logging.INFO:
+                self.stream.write(line + "\n")
+            return
+        level, target, message = parse_print_line(line)
+        levelno = logging.getLevelName(level)
+        if levelno < self.level:
+            return
+        now = self._clock()
+        if self.stream is not None:
+            self.stream.write((log_record(now, level, target, message) if self.json_output else line) + "\n")
+        if self.file_handler is not None:
+            record = logging.LogRecord(target or "telemetry", levelno, "", 0, message, None, None)
+            record.created = now
+            self.file_handler.handle(record)
+    
+    def flush(self) -> None:
+        if self.stream is not None:
+            self.stream.flush()
+        if self.file_handler is not None:
+            self.file_handler.flush()
+    
+    def close_file(self) -> None:
+        """Grava o que falta e fecha o arquivo (chamado na saída do programa)."""
+        with self._lock:
+            if self._buffer:
+                self._emit(self._buffer)
+                self._buffer = ""
+        if self.file_handler is not None:
+            self.file_handler.close()
+    
+    def __getattr__(self, name: str) -> Any:
+        # encoding, isatty, fileno... do stream original
+        return getattr(self.stream, name)
+
+
+class JsonLineWriter(LogWriter):
+    """LogWriter com uma linha JSON por mensagem (NDJSON), no console e no arquivo"""
+    
+    json_output = True
+
+
+def log_format_from(argv: Sequence[str], environ: Mapping[str, str]) -> str:
+    """
+    Formato de log pedido: --log-format json|text, senão TELEMETRIA_LOG_FORMAT
+    
+    Valores desconhecidos voltam para "text".
+    """
+    value = environ.get(LOG_FORMAT_ENV, "")
+    for i, arg in enumerate(argv):
+        if arg == "--log-format" and i + 1 < len(argv):
+            value = argv[i + 1]
+        elif arg.startswith("--log-format="):
+            value = arg.split("=", 1)[1]
+    value = value.strip().lower()
+    return value if value in LOG_FORMATS else "text"
+
+
+def configure_output(
+    log_format: str,
+    stream: Any,
+    level: Any = "info",
+    file_handler: Optional[logging.Handler] = None
+) -> Any:
+    """
+    Stream de log para o formato, nível e arquivo escolhidos (usado como sys.stdout)
+    
+    Args:
+        log_format: "text" ou "json"
+        stream: Stream original (None no pythonw, sem console)
+        level: Nível mínimo (ver parse_log_level)
+        file_handler: Cópia em arquivo (ver rotating_file_handler); fechado na saída
+    
+    Returns:
+        JsonLineWriter em "json"; LogWriter se houver filtro de nível ou
+        arquivo; senão o próprio stream
+    """
+    levelno = parse_log_level(level)
+    if log_format == "json" and (stream is not None or file_handler is not None):
+        writer = JsonLineWriter(stream, levelno, file_handler)
+    elif file_handler is not None or (stream is not None and levelno != logging.INFO):
+        writer = LogWriter(stream, levelno, file_handler)
+    else:
+        return stream
+    if file_handler is not None:
+        # O writer vive até o fim do programa (é o sys.stdout); só falta fechar o arquivo
+        atexit.register(writer.close_file)
+    return writer
+
+
 # Cache de loggers
 _loggers: dict[str, logging.Logger] = {}
 _initialized: bool = False
@@ -80,7 +301,8 @@ def setup_logger(
     level: LogLevel = LogLevel.INFO,
     log_file: Optional[Path] = None,
     console_output: bool = True,
-    use_colors: bool = True
+    use_colors: bool = True,
+    log_format: str = "text"
 ) -> logging.Logger:
     """
     Configura e retorna um logger
@@ -91,6 +313,7 @@ def setup_logger(
         log_file: Caminho opcional para arquivo de log
         console_output: Se True, imprime no console
         use_colors: Se True, usa cores no console
+        log_format: "json" troca o console por uma linha JSON por registro
     
     Returns:
         Logger configurado
@@ -112,7 +335,7 @@ def setup_logger(
     if console_output:
         console_handler = logging.StreamHandler(sys.stdout)
         console_handler.setLevel(level)
-        console_handler.setFormatter(ColoredFormatter(use_colors))
+        console_handler.setFormatter(JsonFormatter() if log_format == "json" else ColoredFormatter(use_colors))
         logger.addHandler(console_handler)
     
     # Handler de arquivo
@@ -0,0 +1,652 @@
+"""
+Utilitários de rede do Sender e do Receiver
+Filtragem de interfaces, taxas por adaptador, medição de ping e escuta em várias portas
+"""
+import errno
+import ipaddress
+import os
+import queue
+import socket
+import struct
+import threading
+import time
+from typing import Any, Callable, Dict, List, Optional, Tuple
+
+from .protocol import RECV_BUFFER_SIZE
+from .rates import compute_rate
+
+
+# Prefixos (minúsculos) de interfaces loopback, virtuais e de VPN.
+# Somar essas interfaces duplica o tráfego que já passa pelo adaptador físico.
+VIRTUAL_INTERFACE_PREFIXES = (
+    # Windows
+    "loopback", "vethernet", "vmware", "virtualbox", "hyper-v", "npcap",
+    "openvpn", "wireguard", "tailscale", "zerotier", "local area connection*",
+    # Linux ("lo" é comparado por igualdade, ver is_physical_interface)
+    "docker", "veth", "br-", "virbr", "vmnet", "tun", "tap", "wg", "zt",
+)
+
+
+def is_physical_interface(name: str) -> bool:
+    """
+    Verifica se a interface deve entrar na soma de tráfego
+    
+    Args:
+        name: Nome da interface (ex: "Ethernet", "Wi-Fi", "vEthernet (WSL)")
+    
+    Returns:
+        False para loopback, adaptadores virtuais e VPN
+    """
+    lowered = name.strip().lower()
+    # "lo" exato: como prefixo pegaria "Local Area Connection" (Ethernet física)
+    if not lowered or lowered == "lo":
+        return False
+    return not lowered.startswith(VIRTUAL_INTERFACE_PREFIXES)
+
+
+def select_interfaces(names: List[str], only: str = "") -> List[str]:
+    """
+    Escolhe quais interfaces monitorar
+    
+    Args:
+        names: Interfaces disponíveis
+        only: Nome de uma interface específica (vazio = todas as físicas)
+    
+    Returns:
+        Lista de interfaces selecionadas
+    """
+    if only:
+        return [name for name in names if name.lower() == only.lower()]
+    return [name for name in names if is_physical_interface(name)]
+
+
+def compute_interface_rates(
+    prev: Optional[Dict[str, Any]],
+    curr: Dict[str, Any],
+    delta_t: float,
+    only: str = ""
+) -> List[List[Any]]:
+    """
+    Calcula download/upload por interface entre dois snapshots
+    
+    Os snapshots seguem o formato de psutil.net_io_counters(pernic=True).
+    
+    Args:
+        prev: Snapshot anterior (None no primeiro ciclo)
+        curr: Snapshot atual
+        delta_t: Tempo decorrido em segundos
+        only: Interface específica (vazio = todas as físicas)
+    
+    Returns:
+        Lista de [nome, down_kbps, up_kbps]
+    """
+    rates = []
+    for name in select_interfaces(list(curr.keys()), only):
+        before = prev.get(name) if prev else None
+        if before is None:
+            rates.append([name, 0.0, 0.0])
+            continue
+        
+        counters = curr[name]
+        down = compute_rate(before.bytes_recv, counters.bytes_recv, delta_t) / 1024
+        up = compute_rate(before.bytes_sent, counters.bytes_sent, delta_t) / 1024
+        rates.append([name, round(down, 1), round(up, 1)])
+    
+    return rates
+
+
+# Valor de ping_ms quando o host não responde (0 ms seria uma latência válida)
+PING_UNREACHABLE = -1
+DEFAULT_PING_HOST = "8.8.8.8:53"
+
+
+def parse_ping_host(value: str, default_port: int = 53) -> Tuple[str, int]:
+    """
+    Interpreta o alvo do ping no formato "host" ou "host:porta"
+    
+    IPv6 vai sozinho ("2001:db8::1") ou entre colchetes com porta
+    ("[2001:db8::1]:443"), como em parse_ip_port.
+    
+    Args:
+        value: Valor de ping_host do config.json
+        default_port: Porta usada quando não especificada
+    
+    Returns:
+        Tupla (host, porta)
+    
+    Raises:
+        ValueError: Se o host estiver vazio ou a porta for inválida
+    """
+    value = (value or "").strip()
+    if value.startswith("["):
+        host, bracket, rest = value[1:].partition("]")
+        if not bracket or (rest and not rest.startswith(":")):
+            raise ValueError(f"ping_host inválido: '{value}'")
+        port_text = rest[1:]
+    elif value.count(":") > 1:  # IPv6 sem colchetes: sem porta
+        host, port_text = value, ""
+    else:
+        host, sep, port_text = value.rpartition(":")
+        if not sep:
+            host, port_text = value, ""
+    
+    host = host.strip()
+    if not host:
+        raise ValueError(f"ping_host inválido: '{value}'")
+    
+    if not port_text:
+        return host, default_port
+    
+    try:
+        port = int(port_text)
+    except ValueError:
+        raise ValueError(f"Porta inválida em ping_host: '{port_text}'")
+    if not 1 <= port <= 65535:
+        raise ValueError(f"Porta fora do intervalo em ping_host: {port}")
+    
+    return host, port
+
+
+def tcp_ping(host: str, port: int, timeout: float = 1.0) -> float:
+    """
+    Mede o tempo do handshake TCP até host:porta
+    
+    Returns:
+        Latência em ms
+    
+    Raises:
+        OSError: Se o host não responder dentro do timeout
+    """
+    t1 = time.perf_counter()
+    with socket.create_connection((host, port), timeout=timeout):
+        pass
+    return round((time.perf_counter() - t1) * 1000, 1)
+
+
+ICMP_ECHO_REQUEST = 8
+ICMP_ECHO_REPLY = 0
+
+
+def icmp_checksum(data: bytes) -> int:
+    """Checksum da Internet (RFC 1071) usado no cabeçalho ICMP"""
+    if len(data) % 2:
+        data += b"\x00"
+    total = sum(struct.unpack(f"!{len(data) // 2}H", data))
+    total = (total >> 16) + (total & 0xFFFF)
+    total += total >> 16
+    return ~total & 0xFFFF
+
+
+def icmp_ping(host: str, port: int = 0, timeout: float = 1.0) -> float:
+    """
+    Mede a latência com ICMP echo (ping real)
+    
+    Precisa de raw socket: no Windows exige Administrador, no Linux CAP_NET_RAW.
+    A porta é ignorada (mantém a mesma assinatura de tcp_ping).
+    
+    Returns:
+        Latência em ms
+    
+    Raises:
+        PermissionError: Sem privilégio para abrir raw socket
+        OSError: Se o host não responder dentro do timeout
+    """
+    address = socket.gethostbyname(host)
+    ident = os.getpid() & 0xFFFF
+    seq = int(time.time()) & 0xFFFF
+    body = b"telemetria"
+    
+    header = struct.pack("!BBHHH", ICMP_ECHO_REQUEST, 0, 0, ident, seq)
+    checksum = icmp_checksum(header + body)
+    packet = struct.pack("!BBHHH", ICMP_ECHO_REQUEST, 0, checksum, ident, seq) + body
+    
+    with socket.socket(socket.AF_INET, socket.SOCK_RAW, socket.IPPROTO_ICMP) as sock:
+        t1 = time.perf_counter()
+        deadline = t1 + timeout
+        sock.sendto(packet, (address, 0))
+        
+        # O raw socket recebe todo o ICMP da máquina: filtra pela nossa resposta
+        while True:
+            remaining = deadline - time.perf_counter()
+            if remaining <= 0:
+                raise socket.timeout(f"Sem resposta ICMP de {host}")
+            sock.settimeout(remaining)
+            data, source = sock.recvfrom(1024)
+            
+            ihl = (data[0] & 0x0F) * 4  # Tamanho do cabeçalho IP
+            reply_type, _, _, reply_id, reply_seq = struct.unpack("!BBHHH", data[ihl:ihl + 8])
+            if reply_type == ICMP_ECHO_REPLY and reply_id == ident and reply_seq == seq \
+                    and source[0] == address:
+                return round((time.perf_counter() - t1) * 1000, 1)
+
+
+class PingMethod:
+    """
+    Escolhe entre ICMP e TCP, caindo para TCP quando ICMP não é permitido
+    
+    O método em uso fica em `method` ("icmp" ou "tcp") para ir no payload.
+    """
+    
+    def __init__(
+        self,
+        use_icmp: bool,
+        icmp: Callable[[str, int], float] = icmp_ping,
+        tcp: Callable[[str, int], float] = tcp_ping
+    ):
+        self.method = "icmp" if use_icmp else "tcp"
+        self._icmp = icmp
+        self._tcp = tcp
+    
+    def __call__(self, host: str, port: int) -> float:
+        if self.method == "icmp":
+            try:
+                return self._icmp(host, port)
+            except PermissionError:
+                # Sem raw socket não adianta tentar de novo: fica no TCP
+                print("[Ping] ICMP sem permissão (raw socket); usando TCP")
+                self.method = "tcp"
+        return self._tcp(host, port)
+
+
+class PingWorker:
+    """
+    Mede a latência em uma thread própria
+    
+    O loop de coleta lê apenas o último resultado (last_ms), sem bloquear
+    quando a rede está fora do ar.
+    
+    Exemplo:
+        worker = PingWorker("8.8.8.8", 53)
+        worker.start()
+        print(worker.last_ms)  # PING_UNREACHABLE se o host não responde
+    """
+    
+    def __init__(
+        self,
+        host: str,
+        port: int,
+        interval: float = 1.0,
+        measure: Optional[Callable[[str, int], float]] = None
+    ):
+        self.host = host
+        self.port = port
+        self.interval = interval
+        self._measure = measure or tcp_ping
+        self._stop = threading.Event()
+        self._thread: Optional[threading.Thread] = None
+        self.last_ms: float = 0
+    
+    @property
+    def method(self) -> str:
+        """Método de medição em uso ("icmp" ou "tcp")"""
+        return getattr(self._measure, "method", "tcp")
+    
+    def measure_once(self) -> float:
+        """Executa uma medição e atualiza last_ms"""
+        try:
+            self.last_ms = self._measure(self.host, self.port)
+        except OSError:
+            self.last_ms = PING_UNREACHABLE
+        return self.last_ms
+    
+    def start(self) -> None:
+        """Inicia a thread de medição"""
+        self._thread = threading.Thread(target=self._run, daemon=True)
+        self._thread.start()
+    
+    def stop(self) -> None:
+        """Sinaliza o encerramento da thread"""
+        self._stop.set()
+    
+    def _run(self) -> None:
+        while not self._stop.is_set():
+            self.measure_once()
+            self._stop.wait(self.interval)
+
+
+# === Endereços IPv4/IPv6 ===
+
+def is_ipv6_address(ip: str) -> bool:
+    """Verifica se o texto é um endereço IPv6 literal (aceita "%zona" e colchetes)"""
+    ip = (ip or "").strip().strip("[]").split("%", 1)[0]
+    if ":" not in ip:
+        return False
+    try:
+        socket.inet_pton(socket.AF_INET6, ip)
+        return True
+    except (OSError, ValueError):
+        return False
+
+
+def socket_family(*addresses: str, ipv6: bool = False) -> int:
+    """
+    Família do socket para os endereços configurados
+    
+    Args:
+        addresses: IPs configurados (bind_ip, dest_ip, sender_ip...); vazios são ignorados
+        ipv6: Força IPv6 mesmo sem endereço IPv6 configurado
+    
+    Returns:
+        socket.AF_INET6 se ipv6 ou algum endereço for IPv6; senão socket.AF_INET
+    """
+    if ipv6 or any(is_ipv6_address(address) for address in addresses if address):
+        return socket.AF_INET6
+    return socket.AF_INET
+
+
+def bind_address(bind_ip: str, port: int, family: int) -> Tuple[str, int]:
+    """
+    Endereço de bind para a família escolhida
+    
+    Args:
+        bind_ip: IP local configurado (vazio = todas as interfaces)
+        port: Porta local (0 = escolhida pelo sistema)
+        family: socket.AF_INET ou socket.AF_INET6
+    
+    Returns:
+        (host, porta): "0.0.0.0" ou "::" quando bind_ip está vazio
+    """
+    if bind_ip:
+        return bind_ip.strip().strip("[]"), port
+    return ("::" if family == socket.AF_INET6 else "0.0.0.0"), port
+
+
+def interface_address(interfaces: Dict[str, List[Any]], name: str, family: int = socket.AF_INET) -> str:
+    """
+    IP atual de uma interface pelo nome (para o bind sobreviver à renovação do DHCP)
+    
+    Args:
+        interfaces: psutil.net_if_addrs() ({nome: [snicaddr(family, address, ...)]})
+        name: Nome da interface ("Ethernet", "wlan0"), sem diferenciar maiúsculas
+        family: socket.AF_INET ou socket.AF_INET6
+    
+    Returns:
+        Primeiro endereço da família ("" se a interface não existe ou está sem IP).
+        No IPv6 o link-local (fe80::) só é usado se não houver outro.
+    """
+    match = next((key for key in interfaces if key.lower() == name.strip().lower()), None)
+    if match is None:
+        return ""
+    addresses = [
+        str(addr.address).split("%", 1)[0]  # Remove o escopo ("fe80::1%eth0")
+        for addr in interfaces[match]
+        if addr.family == family and addr.address
+    ]
+    if family == socket.AF_INET6:
+        addresses.sort(key=lambda ip: ipaddress.ip_address(ip).is_link_local)
+    return addresses[0] if addresses else ""
+
+
+def peer_address(ip: str, port: int, family: int) -> Tuple[str, int]:
+    """
+    Destino de sendto para a família do socket
+    
+    Um IPv4 enviado por socket IPv6 (ipv6 forçado, dual-stack) vira "::ffff:a.b.c.d".
+    """
+    ip = ip.strip().strip("[]")
+    if family == socket.AF_INET6 and not is_ipv6_address(ip):
+        return f"::ffff:{ip}", port
+    return ip, port
+
+
+def open_udp_socket(family: int) -> socket.socket:
+    """
+    Cria um socket UDP; em IPv6 tenta dual-stack (aceita também IPv4)
+    
+    O dual-stack é desligado por padrão em alguns sistemas (IPV6_V6ONLY=1);
+    onde o sistema não permitir, o socket fica só IPv6.
+    """
+    sock = socket.socket(family, socket.SOCK_DGRAM)
+    if family == socket.AF_INET6 and hasattr(socket, "IPV6_V6ONLY"):
+        try:
+            sock.setsockopt(socket.IPPROTO_IPV6, socket.IPV6_V6ONLY, 0)
+        except OSError:
+            pass
+    return sock
+
+
+def normalize_host(host: str) -> str:
+    """
+    IP de origem de um pacote como exibido/comparado pelo Receiver
+    
+    Num socket dual-stack, senders IPv4 chegam como "::ffff:192.168.1.10":
+    volta ao IPv4 para casar com sender_ip e manter as abas por host.
+    """
+    if host.lower().startswith("::ffff:") and "." in host:
+        return host[7:]
+    return host
+
+
+def format_address(host: str, port: Optional[int] = None) -> str:
+    """"host:porta", com colchetes em IPv6 ("[fe80::1]:5005")"""
+    host = normalize_host(host)
+    if port is None:
+        return host
+    if ":" in host:
+        return f"[{host}]:{port}"
+    return f"{host}:{port}"
+
+
+# === Multicast ===
+
+# Grupo padrão: escopo administrativo (239.255.0.0/16, só dentro da organização)
+DEFAULT_MULTICAST_GROUP = "239.255.0.1"
+
+
+def is_multicast_address(ip: str) -> bool:
+    """Verifica se o IP é um grupo multicast (224.0.0.0/4 ou ff00::/8)"""
+    try:
+        return ipaddress.ip_address((ip or "").strip().strip("[]").split("%", 1)[0]).is_multicast
+    except ValueError:
+        return False
+
+
+def set_multicast_ttl(sock: socket.socket, ttl: int, family: int = socket.AF_INET) -> None:
+    """
+    TTL dos pacotes multicast enviados
+    
+    1 (padrão do sistema) não passa do primeiro roteador; aumente para
+    atravessar sub-redes com roteamento multicast.
+    """
+    ttl = max(1, min(255, int(ttl)))
+    if family == socket.AF_INET6:
+        sock.setsockopt(socket.IPPROTO_IPV6, socket.IPV6_MULTICAST_HOPS, ttl)
+    else:
+        sock.setsockopt(socket.IPPROTO_IP, socket.IP_MULTICAST_TTL, ttl)
+
+
+def set_multicast_interface(sock: socket.socket, interface_ip: str) -> None:
+    """Envia o multicast IPv4 pela interface do IP informado (bind_ip)"""
+    sock.setsockopt(socket.IPPROTO_IP, socket.IP_MULTICAST_IF, socket.inet_aton(interface_ip))
+
+
+def join_multicast_group(sock: socket.socket, group: str, interface_ip: str = "") -> None:
+    """
+    Inscreve o socket (já com bind na porta) no grupo multicast
+    
+    Args:
+        sock: Socket UDP do Receiver
+        group: Grupo multicast (ex: "239.255.0.1" ou "ff15::1")
+        interface_ip: IPv4 da interface que recebe (vazio = escolhida pelo sistema)
+    """
+    group = group.strip().strip("[]")
+    if is_ipv6_address(group):
+        mreq = socket.inet_pton(socket.AF_INET6, group) + struct.pack("@I", 0)
+        sock.setsockopt(socket.IPPROTO_IPV6, socket.IPV6_JOIN_GROUP, mreq)
+    else:
+        mreq = socket.inet_aton(group) + socket.inet_aton(interface_ip or "0.0.0.0")
+        sock.setsockopt(socket.IPPROTO_IP, socket.IP_ADD_MEMBERSHIP, mreq)
+
+
+# === Opções do socket ===
+
+# Faixas aceitas no config; 0 mantém o padrão do sistema
+MIN_SEND_BUFFER = 4096
+MAX_SEND_BUFFER = 16 * 1024 * 1024
+MAX_TTL = 255
+
+
+def send_buffer_size(value: Any) -> int:
+    """
+    Tamanho do buffer de envio (SO_SNDBUF) pedido no config
+    
+    Returns:
+        Bytes entre MIN_SEND_BUFFER e MAX_SEND_BUFFER, ou 0 (padrão do
+        sistema) para 0, negativo ou valor inválido
+    """
+    try:
+        size = int(value or 0)
+    except (TypeError, ValueError):
+        return 0
+    if size <= 0:
+        return 0
+    return max(MIN_SEND_BUFFER, min(MAX_SEND_BUFFER, size))
+
+
+def socket_ttl(value: Any) -> int:
+    """
+    TTL dos pacotes unicast/broadcast pedido no config
+    
+    Returns:
+        Saltos entre 1 e MAX_TTL, ou 0 (padrão do sistema) para 0, negativo
+        ou valor inválido
+    """
+    try:
+        ttl = int(value or 0)
+    except (TypeError, ValueError):
+        return 0
+    if ttl <= 0:
+        return 0
+    return min(MAX_TTL, ttl)
+
+
+def apply_socket_options(sock: socket.socket, family: int, ttl: int = 0, send_buffer: int = 0) -> Dict[str, int]:
+    """
+    Aplica TTL e buffer de envio (já validados; 0 = não mexe)
+    
+    O TTL do multicast continua em set_multicast_ttl.
+    
+    Returns:
+        Valores em vigor lidos de volta do socket ("ttl", "send_buffer"); o
+        Linux, por exemplo, reserva o dobro do SO_SNDBUF pedido
+    """
+    if family == socket.AF_INET6:
+        ttl_option = (socket.IPPROTO_IPV6, socket.IPV6_UNICAST_HOPS)
+    else:
+        ttl_option = (socket.IPPROTO_IP, socket.IP_TTL)
+    if ttl:
+        sock.setsockopt(*ttl_option, ttl)
+    if send_buffer:
+        sock.setsockopt(socket.SOL_SOCKET, socket.SO_SNDBUF, send_buffer)
+    return {
+        "ttl": sock.getsockopt(*ttl_option),
+        "send_buffer": sock.getsockopt(socket.SOL_SOCKET, socket.SO_SNDBUF),
+    }
+
+
+def listen_ports(port: Any, ports: Any = None) -> Tuple[List[int], List[str]]:
+    """
+    Portas de escuta do Receiver: "ports" (lista) ou, sem ela, "porta"
+    
+    "porta" também pode ser uma lista (mesmo efeito de "ports"). Repetidas
+    são ignoradas; inválidas geram aviso. Sem nenhuma válida, volta a 5005.
+    
+    Returns:
+        (portas na ordem do config, avisos)
+    """
+    candidates = ports if isinstance(ports, list) and ports else port
+    if not isinstance(candidates, list):
+        candidates = [candidates]
+    result: List[int] = []
+    warnings: List[str] = []
+    for value in candidates:
+        if isinstance(value, bool) or not isinstance(value, int) or not 1 <= value <= 65535:
+            warnings.append(f"porta inválida ignorada: {value!r}")
+        elif value not in result:
+            result.append(value)
+    return result or [5005], warnings
+
+
+# Erro do Winsock para datagrama maior que o buffer do recvfrom
+WSAEMSGSIZE = 10040
+
+
+def is_datagram_too_long(error: OSError) -> bool:
+    """
+    recvfrom falhou por o datagrama não caber no buffer
+    
+    O Windows descarta e levanta WSAEMSGSIZE (10040); no Linux o recvfrom
+    devolve os dados truncados sem erro (ver protocol.check_datagram).
+    """
+    return getattr(error, "winerror", None) == WSAEMSGSIZE or error.errno == errno.EMSGSIZE
+
+
+class PortListener:
+    """
+    Escuta UDP em várias portas: uma thread por socket, todas entregando na
+    mesma fila com a porta de chegada
+    
+    Exemplo:
+        listener = PortListener({5005: sock_a, 5006: sock_b})
+        data, addr, port = listener.recvfrom(timeout=1.0)  # socket.timeout se nada chegar
+        listener.close()
+    """
+    
+    # Pacotes na fila antes de descartar (UI travada não acumula memória)
+    MAX_PENDING = 1024
+    
+    def __init__(self, sockets: Dict[int, socket.socket], bufsize: int = RECV_BUFFER_SIZE):
+        self.sockets = sockets
+        self.bufsize = bufsize
+        self.dropped = 0
+        self.oversized = 0
+        self._queue: "queue.Queue[Tuple[bytes, Any, int]]" = queue.Queue(self.MAX_PENDING)
+        self._stop = threading.Event()
+        self._threads = [
+            threading.Thread(target=self._listen, args=(port, sock), daemon=True, name=f"udp-{port}")
+            for port, sock in sockets.items()
+        ]
+        for thread in self._threads:
+            thread.start()
+    
+    def _listen(self, port: int, sock: socket.socket) -> None:
+        sock.settimeout(0.5)
+        while not self._stop.is_set():
+            try:
+                data, addr = sock.recvfrom(self.bufsize)
+            except socket.timeout:
+                continue
+            except OSError as e:
+                if self._stop.is_set():
+                    return
+                if is_datagram_too_long(e):
+                    self.oversized += 1
+                    print(f"[Network] Datagrama maior que {self.bufsize} bytes descartado na porta {port}")
+                continue  # Ex: ICMP port unreachable de um envio anterior (Windows)
+            try:
+                self._queue.put_nowait((data, addr, port))
+            except queue.Full:
+                self.dropped += 1
+    
+    def recvfrom(self, timeout: float = 1.0) -> Tuple[bytes, Any, int]:
+        """
+        Próximo pacote de qualquer porta
+        
+        Returns:
+            (dados, endereço de origem, porta local onde chegou)
+        
+        Raises:
+            socket.timeout: Nada chegou em timeout segundos
+        """
+        try:
+            return self._queue.get(timeout=timeout)
+        except queue.Empty:
+            raise socket.timeout("timed out") from None
+    
+    def close(self) -> None:
+        """Para as threads e fecha os sockets"""
+        self._stop.set()
+        for thread in self._threads:
+            thread.join(timeout=2)
+        for sock in self.sockets.values():
+            sock.close()
@@ -0,0 +1,217 @@
+"""
+Leituras da GPU NVIDIA pelo NVML (pynvml)
+O módulo do NVML é recebido como parâmetro: os testes passam um objeto falso
+"""
+import time
+from typing import Any, Callable, Dict, Optional
+
+# Códigos de nvml.h que indicam handle perdido (driver reiniciado, eGPU
+# desconectada): a GPU só volta com nvmlInit e um handle novo.
+# INVALID_ARGUMENT fica de fora: é erro de chamada (índice, contador), não da GPU
+NVML_ERROR_UNINITIALIZED = 1
+NVML_ERROR_INVALID_ARGUMENT = 2
+NVML_ERROR_NOT_SUPPORTED = 3
+NVML_ERROR_DRIVER_NOT_LOADED = 9
+NVML_ERROR_GPU_IS_LOST = 15
+NVML_ERROR_RESET_REQUIRED = 16
+HANDLE_LOST_ERRORS = (
+    NVML_ERROR_UNINITIALIZED,
+    NVML_ERROR_DRIVER_NOT_LOADED,
+    NVML_ERROR_GPU_IS_LOST,
+    NVML_ERROR_RESET_REQUIRED,
+)
+
+# Contadores de nvmlDeviceGetPcieThroughput (nvmlPcieUtilCounter_t)
+NVML_PCIE_UTIL_TX_BYTES = 0
+NVML_PCIE_UTIL_RX_BYTES = 1
+
+
+def handle_lost(error: Exception) -> bool:
+    """True se o erro do NVML (NVMLError.value) exige reinicializar"""
+    return getattr(error, "value", None) in HANDLE_LOST_ERRORS
+
+
+def _codec_utilization(query: Any, handle: Any) -> float:
+    """Uso do encoder/decoder em % (0 se a GPU não informar)"""
+    try:
+        # nvmlDeviceGetEncoderUtilization/DecoderUtilization -> [uso %, período de amostragem em µs]
+        util = query(handle)[0]
+    except Exception:
+        return 0.0
+    return float(min(100, max(0, util or 0)))
+
+
+def _pcie_throughput(nvml: Any, handle: Any, counter: int) -> float:
+    """Tráfego PCIe em KB/s (0 se a GPU ou o driver não informar)"""
+    try:
+        # Média dos últimos 20ms, já em KB/s
+        kbps = nvml.nvmlDeviceGetPcieThroughput(handle, counter)
+    except Exception:
+        return 0.0
+    return float(max(0, kbps or 0))
+
+
+def _memory_utilization(nvml: Any, handle: Any) -> float:
+    """Uso do barramento de memória em % (0 se a GPU não informar)"""
+    try:
+        util = nvml.nvmlDeviceGetUtilizationRates(handle).memory
+    except Exception:
+        return 0.0
+    return float(min(100, max(0, util or 0)))
+
+
+def query_gpu(nvml: Any, handle: Any) -> Dict[str, Any]:
+    """
+    Consulta os campos que só o NVML fornece
+    
+    Cada leitura falha sozinha: uma GPU sem NVENC ainda informa o throttling.
+    
+    Args:
+        nvml: Módulo pynvml (ou objeto com as mesmas funções)
+        handle: Handle da GPU (nvmlDeviceGetHandleByIndex)
+    
+    Returns:
+        throttle_reasons (máscara ou None), enc_util, dec_util e mem_util (% ou 0),
+        pcie_tx_kbps e pcie_rx_kbps (KB/s ou 0)
+    
+    Raises:
+        Exception: O erro do NVML quando o handle foi perdido (ver handle_lost)
+    """
+    try:
+        reasons: Optional[int] = nvml.nvmlDeviceGetCurrentClocksThrottleReasons(handle)
+    except Exception as e:
+        if handle_lost(e):
+            raise
+        reasons = None
+    return {
+        "throttle_reasons": reasons,
+        "enc_util": _codec_utilization(getattr(nvml, "nvmlDeviceGetEncoderUtilization", None), handle),
+        "dec_util": _codec_utilization(getattr(nvml, "nvmlDeviceGetDecoderUtilization", None), handle),
+        "mem_util": _memory_utilization(nvml, handle),
+        "pcie_tx_kbps": _pcie_throughput(nvml, handle, NVML_PCIE_UTIL_TX_BYTES),
+        "pcie_rx_kbps": _pcie_throughput(nvml, handle, NVML_PCIE_UTIL_RX_BYTES),
+    }
+
+
+class ReinitBackoff:
+    """
+    Quando tentar reinicializar o NVML depois de perder a GPU
+    
+    A primeira tentativa espera `initial` segundos (o driver leva alguns
+    segundos para voltar); cada falha dobra a espera até `maximum`.
+    """
+    
+    def __init__(self, initial: float = 5.0, maximum: float = 300.0, clock: Callable[[], float] = time.monotonic):
+        self.initial = initial
+        self.maximum = maximum
+        self.delay = initial
+        self._clock = clock
+        self._next: Optional[float] = None
+        self.attempts = 0
+    
+    @property
+    def waiting(self) -> bool:
+        """True entre a perda da GPU e a reinicialização bem-sucedida"""
+        return self._next is not None
+    
+    def lost(self) -> None:
+        """Registra a perda do handle: primeira tentativa daqui a `initial` segundos"""
+        self.delay = self.initial
+        self.attempts = 0
+        self._next = self._clock() + self.delay
+    
+    def due(self) -> bool:
+        """True se já é hora de tentar de novo"""
+        return self._next is not None and self._clock() >= self._next
+    
+    def failed(self) -> None:
+        """Tentativa falhou: dobra a espera (até `maximum`)"""
+        self.attempts += 1
+        self.delay = min(self.maximum, self.delay * 2)
+        self._next = self._clock() + self.delay
+    
+    def succeeded(self) -> None:
+        """GPU de volta: para de tentar"""
+        self._next = None
+        self.delay = self.initial
+
+
+class NvmlDevice:
+    """
+    GPU NVIDIA no NVML, reaberta se o handle se perder
+    
+    Exemplo:
+        gpu = NvmlDevice.open(pynvml)  # None sem driver NVIDIA
+        data = gpu.query() if gpu else None
+    """
+    
+    def __init__(self, nvml: Any, index: int = 0, backoff: Optional[ReinitBackoff] = None):
+        self.nvml = nvml
+        self.requested_index = index
+        self.index = index
+        self.handle: Any = None
+        self.backoff = backoff or ReinitBackoff()
+    
+    @classmethod
+    def open(cls, nvml: Any, index: int = 0, backoff: Optional[ReinitBackoff] = None) -> Optional["NvmlDevice"]:
+        """
+        Abre a GPU; None se o NVML não iniciar (sem driver NVIDIA) ou a GPU não existir
+        
+        Índice além do número de GPUs registra um aviso e usa a GPU 0.
+        
+        A reinicialização com espera (ReinitBackoff) só vale para uma GPU que
+        já abriu: se o driver não estiver carregado na partida, o NVML fica
+        desligado até o Sender ser reiniciado.
+        """
+        device = cls(nvml, index, backoff)
+        return device if device._init() else None
+    
+    def _init(self) -> bool:
+        try:
+            self.nvml.nvmlInit()
+            self.index = self._checked_index()
+            self.handle = self.nvml.nvmlDeviceGetHandleByIndex(self.index)
+            return True
+        except Exception:
+            self.handle = None
+            return False
+    
+    def _checked_index(self) -> int:
+        """Índice pedido, ou 0 (com aviso) se passar do número de GPUs"""
+        try:
+            count = self.nvml.nvmlDeviceGetCount()
+        except Exception:
+            return self.requested_index  # Sem contagem: o handle inválido falha sozinho
+        if 0 <= self.requested_index < count:
+            return self.requested_index
+        print(f"[NVML] Aviso: gpu_index {self.requested_index} fora do intervalo ({count} GPU(s)); usando a GPU 0")
+        return 0
+    
+    def query(self) -> Optional[Dict[str, Any]]:
+        """
+        Campos de query_gpu, ou None enquanto a GPU estiver perdida
+        
+        Perder o handle dispara a reinicialização com espera crescente
+        (ReinitBackoff); as tentativas são registradas no log.
+        """
+        if self.handle is None:
+            if not self.backoff.due():
+                return None
+            if not self._init():
+                self.backoff.failed()
+                print(f"[NVML] Reinicialização falhou; nova tentativa em {self.backoff.delay:.0f}s")
+                return None
+            self.backoff.succeeded()
+            print("[NVML] GPU recuperada")
+        
+        try:
+            return query_gpu(self.nvml, self.handle)
+        except Exception as e:
+            print(f"[NVML] GPU perdida ({e}); reinicializando em {self.backoff.initial:.0f}s")
+            self.handle = None
+            try:
+                self.nvml.nvmlShutdown()
+            except Exception:
+                pass
+            self.backoff.lost()
+            return None
@@ -0,0 +1,210 @@
+"""
+Sobrescrita de campos do config sem editar o arquivo
+Precedência: padrão < arquivo < variáveis de ambiente < linha de comando
+"""
+import copy
+import json
+from typing import Any, Dict, List, Mapping, Sequence, Tuple
+
+# Opções nomeadas da linha de comando -> campo do config (caminho com pontos)
+SENDER_CLI_OPTIONS = {
+    "--port": "porta",
+    "--dest-ip": "dest_ip",
+    "--interval": "intervalo",
+    "--mode": "modo",
+    "--bind-ip": "bind_ip",
+    "--multicast-group": "multicast_group",
+    "--ping-host": "ping_host",
+    "--log-dir": "log_dir",
+    "--log-level": "log_level",
+}
+RECEIVER_CLI_OPTIONS = {
+    "--port": "porta",
+    "--sender-ip": "sender_ip",
+    "--mode": "modo",
+    "--multicast-group": "multicast_group",
+    "--theme": "tema",
+    "--temp-unit": "temp_unit",
+}
+
+# Qualquer campo: --set caminho=valor (ex: --set alertas.cpu_temp_warning=80)
+SET_OPTION = "--set"
+
+# Variáveis de ambiente: prefixo + nome da opção (--dest-ip -> TELEMETRIA_SENDER_DEST_IP)
+SENDER_ENV_PREFIX = "TELEMETRIA_SENDER_"
+RECEIVER_ENV_PREFIX = "TELEMETRIA_RECEIVER_"
+
+_TRUE = ("1", "true", "yes", "on", "sim")
+_FALSE = ("0", "false", "no", "off", "nao", "não")
+
+
+def parse_cli_overrides(argv: Sequence[str], options: Mapping[str, str]) -> Dict[str, str]:
+    """
+    Extrai da linha de comando os campos a sobrescrever
+    
+    Aceita "--opcao valor" e "--opcao=valor", mais "--set caminho=valor"
+    para qualquer campo. Argumentos desconhecidos (ex: --no-admin) são
+    ignorados; a última ocorrência de um campo vence.
+    
+    Args:
+        argv: sys.argv (o primeiro item, o script, é ignorado)
+        options: Opção -> campo (SENDER_CLI_OPTIONS/RECEIVER_CLI_OPTIONS)
+    
+    Returns:
+        Campo (caminho com pontos) -> valor em texto, ainda sem conversão
+    """
+    overrides: Dict[str, str] = {}
+    args = list(argv[1:])
+    i = 0
+    while i < len(args):
+        arg = args[i]
+        name, has_value, value = arg.partition("=")
+        if name in options or name == SET_OPTION:
+            if not has_value:
+                if i + 1 >= len(args):
+                    break
+                i += 1
+                value = args[i]
+            if name == SET_OPTION:
+                path, _, value = value.partition("=")
+                if path:
+                    overrides[path.strip()] = value
+            else:
+                overrides[options[name]] = value
+        i += 1
+    return overrides
+
+
+def env_var_names(prefix: str, options: Mapping[str, str]) -> Dict[str, str]:
+    """Variável de ambiente -> campo, uma para cada opção nomeada"""
+    return {prefix + option.lstrip("-").replace("-", "_").upper(): path for option, path in options.items()}
+
+
+def env_overrides(environ: Mapping[str, str], prefix: str, options: Mapping[str, str]) -> Dict[str, str]:
+    """
+    Campos a sobrescrever pelas variáveis de ambiente (containers, serviços)
+    
+    Só as variáveis de env_var_names são lidas; as ausentes ou vazias
+    deixam o valor do arquivo.
+    
+    Returns:
+        Campo (caminho com pontos) -> valor em texto, ainda sem conversão
+    """
+    overrides = {}
+    for name, path in env_var_names(prefix, options).items():
+        value = environ.get(name, "")
+        if value.strip():
+            overrides[path] = value
+    return overrides
+
+
+def coerce_value(raw: str, current: Any) -> Any:
+    """
+    Converte o texto para o tipo do valor atual do campo
+    
+    Raises:
+        ValueError: Texto incompatível com o tipo (ex: "abc" num campo int)
+    """
+    text = raw.strip()
+    if isinstance(current, bool):
+        if text.lower() in _TRUE:
+            return True
+        if text.lower() in _FALSE:
+            return False
+        raise ValueError(f"esperado true/false, recebido {raw!r}")
+    if isinstance(current, int):
+        return int(text)
+    if isinstance(current, float):
+        return float(text)
+    if isinstance(current, (dict, list)):
+        value = json.loads(text)
+        if not isinstance(value, type(current)):
+            raise ValueError(f"esperado {type(current).__name__} em JSON, recebido {raw!r}")
+        return value
+    return raw
+
+
+def apply_overrides(
+    config: Dict[str, Any],
+    overrides: Mapping[str, str],
+    source: str = "linha de comando"
+) -> Tuple[Dict[str, Any], List[str]]:
+    """
+    Aplica os valores sobre uma cópia do config
+    
+    Só campos que já existem no config (padrão ou arquivo) são aceitos,
+    para um erro de digitação não passar despercebido. O tipo segue o
+    valor atual do campo.
+    
+    Args:
+        config: Config carregado (não é alterado)
+        overrides: Campo (caminho com pontos) -> valor em texto
+        source: Origem, usada nas mensagens de erro
+    
+    Returns:
+        (config com os valores aplicados, mensagens dos valores ignorados)
+    """
+    result = copy.deepcopy(config)
+    errors = []
+    for path, raw in overrides.items():
+        *parents, field = path.split(".")
+        target = result
+        for key in parents:
+            target = target.get(key) if isinstance(target, dict) else None
+        if not isinstance(target, dict) or field not in target:
+            errors.append(f"{source}: campo desconhecido '{path}'")
+            continue
+        try:
+            target[field] = coerce_value(raw, target[field])
+        except ValueError as e:
+            errors.append(f"{source}: valor inválido para '{path}': {e}")
+    return result, errors
+
+
+def _get_path(config: Dict[str, Any], path: str) -> Tuple[Dict[str, Any], str]:
+    """Dict que contém o campo e o nome do campo (caminho com pontos)"""
+    *parents, field = path.split(".")
+    for key in parents:
+        config = config[key]
+    return config, field
+
+
+def without_overrides(
+    config: Dict[str, Any],
+    base: Dict[str, Any],
+    overridden: Mapping[str, Any]
+) -> Dict[str, Any]:
+    """
+    Config para gravar no arquivo, sem os valores sobrescritos
+    
+    Campos ainda com o valor sobrescrito voltam ao valor de `base`; se o
+    usuário mudou o campo depois (ex: na janela de Settings), a mudança fica.
+    
+    Args:
+        config: Config em uso
+        base: Config antes das sobrescritas
+        overridden: Campo -> valor aplicado (ver applied_values)
+    """
+    result = copy.deepcopy(config)
+    for path, value in overridden.items():
+        try:
+            target, field = _get_path(result, path)
+            original, _ = _get_path(base, path)
+        except (KeyError, TypeError):
+            continue
+        if target.get(field) == value and field in original:
+            target[field] = copy.deepcopy(original[field])
+    return result
+
+
+def applied_values(config: Dict[str, Any], overrides: Mapping[str, str]) -> Dict[str, Any]:
+    """Valor final de cada campo sobrescrito (ignora os que não foram aplicados)"""
+    values = {}
+    for path in overrides:
+        try:
+            target, field = _get_path(config, path)
+        except (KeyError, TypeError):
+            continue
+        if field in target:
+            values[path] = target[field]
+    return values
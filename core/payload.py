@@ -0,0 +1,374 @@
+"""
+Estrutura do payload de telemetria
+Valores padrão e conversões das seções opcionais enviadas pelo Sender
+"""
+from typing import Any, Dict, Optional
+
+from .sanity import spinning_rpm
+
+
+def default_battery() -> Dict[str, Any]:
+    """
+    Retorna a seção de bateria vazia (desktop sem bateria)
+
+    Returns:
+        Dict com present=False e todos os valores zerados
+    """
+    return {
+        "present": False,
+        "percent": 0,
+        "charging": False,
+        "time_remaining_min": 0,
+        "power_w": 0
+    }
+
+
+def battery_from_psutil(battery: Optional[Any], power_w: float = 0) -> Dict[str, Any]:
+    """
+    Converte o retorno de psutil.sensors_battery() para a seção do payload
+
+    Args:
+        battery: Objeto retornado pelo psutil (None se não houver bateria)
+        power_w: Potência de carga/descarga em Watts (LibreHardwareMonitor)
+
+    Returns:
+        Dict da seção "battery" do payload
+    """
+    data = default_battery()
+    if battery is None:
+        return data
+
+    # secsleft é negativo quando desconhecido ou ligado na tomada
+    secs_left = battery.secsleft if battery.secsleft and battery.secsleft > 0 else 0
+
+    data["present"] = True
+    data["percent"] = round(float(battery.percent), 1)
+    data["charging"] = bool(battery.power_plugged)
+    data["time_remaining_min"] = round(secs_left / 60, 0)
+    data["power_w"] = round(abs(power_w), 1)
+    return data
+
+
+def default_system() -> Dict[str, Any]:
+    """Seção "system" zerada (Sender antigo ou sem leitura)"""
+    return {"load_avg": [0.0, 0.0, 0.0], "process_count": 0}
+
+
+def system_from_psutil(psutil_module: Any, platform: str) -> Dict[str, Any]:
+    """
+    Load average (1, 5 e 15 min) e total de processos para a seção "system"
+    
+    Args:
+        psutil_module: Módulo psutil (ou um substituto nos testes)
+        platform: sys.platform; no Windows o load average fica zerado (o psutil
+            só o emula com uma thread extra que começa em zero)
+    
+    Returns:
+        Dict da seção "system"; o que falhar na leitura fica zerado
+    """
+    data = default_system()
+    if not platform.startswith("win"):
+        try:
+            data["load_avg"] = [round(float(value), 2) for value in psutil_module.getloadavg()]
+        except (AttributeError, OSError):
+            pass
+    try:
+        data["process_count"] = len(psutil_module.pids())
+    except (AttributeError, OSError):
+        pass
+    return data
+
+
+def fans_from_sensors(readings: list[tuple[str, str, float]]) -> list[Dict[str, Any]]:
+    """
+    Extrai os fans da seção "fans" a partir de sensores rotulados
+    
+    Só sensores do tipo "Fan" (RPM) contam: temperatura, "Control" (% do
+    duty cycle) e outros tipos nunca viram fan, mesmo com valor acima de 100.
+    
+    Args:
+        readings: (tipo, nome, valor) de cada sensor, com valor já saneado
+    
+    Returns:
+        Lista de {"name", "rpm"} na ordem dos sensores
+    """
+    return [
+        {"name": name, "rpm": round(float(value), 0)}
+        for s_type, name, value in readings
+        if s_type == "Fan" and spinning_rpm(value)
+    ]
+
+
+def fans_from_psutil(sensors_fans: Optional[Dict[str, list]]) -> list[Dict[str, Any]]:
+    """
+    Converte o retorno de psutil.sensors_fans() (hwmon no Linux) para a seção "fans"
+    
+    Args:
+        sensors_fans: {chip: [sfan(label, current)]} (None/vazio se não houver)
+    
+    Returns:
+        Lista de {"name", "rpm"}; sem rótulo vira "<chip> <n>"
+    """
+    readings = []
+    for chip, entries in (sensors_fans or {}).items():
+        for i, entry in enumerate(entries, 1):
+            readings.append(("Fan", entry.label or f"{chip} {i}", float(entry.current)))
+    return fans_from_sensors(readings)
+
+
+# Fontes de sensores reportadas em "sensor_status" (o que está faltando e por quê)
+SENSOR_STATUS_FIELDS = ("lhm_available", "gpu_available", "smart_available", "admin")
+
+
+def default_sensor_status() -> Dict[str, bool]:
+    """
+    Retorna a seção "sensor_status" sem nenhuma fonte ativa
+    
+    Returns:
+        Dict com todos os campos de SENSOR_STATUS_FIELDS em False
+    """
+    return {field: False for field in SENSOR_STATUS_FIELDS}
+
+
+def sensor_status(hw_data: Optional[Dict[str, Any]], admin: bool) -> Dict[str, bool]:
+    """
+    Resume quais fontes de sensores o Sender conseguiu ler
+    
+    Args:
+        hw_data: Retorno do HardwareMonitor.fetch_data (None sem LibreHardwareMonitor)
+        admin: Sender rodando como Administrador
+    
+    Returns:
+        Dict da seção "sensor_status" do payload. A GPU também vem do LHM:
+        gpu_available indica que uma GPU com sensores foi encontrada.
+    """
+    status = default_sensor_status()
+    status["admin"] = bool(admin)
+    if not hw_data:
+        return status
+    
+    status["lhm_available"] = True
+    status["gpu_available"] = bool((hw_data.get("gpu") or {}).get("source"))
+    # Sem Admin os discos aparecem, mas a temperatura SMART vem zerada
+    status["smart_available"] = any((disk.get("temp") or 0) > 0 for disk in hw_data.get("storage") or [])
+    return status
+
+
+def sensor_status_hints(status: Optional[Dict[str, Any]]) -> list[str]:
+    """
+    Explica as fontes ausentes para a faixa de diagnóstico do Receiver
+    
+    Returns:
+        Mensagens curtas, da causa mais provável para a menos; vazio se tudo
+        estiver ativo ou o sender for antigo (sem "sensor_status")
+    """
+    if not status:
+        return []
+    hints = []
+    if not status.get("lhm_available"):
+        hints.append("LHM not detected: no temps/voltages/clocks")
+    if not status.get("admin"):
+        hints.append("Sender not admin: sensors limited")
+    if status.get("lhm_available") and not status.get("smart_available"):
+        hints.append("No SMART data")
+    if status.get("lhm_available") and not status.get("gpu_available"):
+        hints.append("No GPU sensors")
+    return hints
+
+
+# Limite de processos por pacote (mantém o frame UDP pequeno)
+MAX_TOP_PROCESSES = 20
+MAX_PROCESS_NAME = 32
+
+
+def top_processes(processes: list[Dict[str, Any]], limit: int) -> list[Dict[str, Any]]:
+    """
+    Seleciona os processos que mais consomem CPU
+
+    Args:
+        processes: Lista de dicts com name, pid, cpu_percent e mem_mb
+        limit: Quantidade desejada (0 = desativado)
+
+    Returns:
+        Lista ordenada por CPU, truncada em min(limit, MAX_TOP_PROCESSES)
+    """
+    limit = min(max(limit, 0), MAX_TOP_PROCESSES)
+    if limit == 0:
+        return []
+
+    ranked = sorted(
+        processes,
+        key=lambda p: (p.get("cpu_percent") or 0, p.get("mem_mb") or 0),
+        reverse=True
+    )
+
+    return [
+        {
+            "name": str(p.get("name") or "?")[:MAX_PROCESS_NAME],
+            "pid": int(p.get("pid") or 0),
+            "cpu_percent": round(p.get("cpu_percent") or 0, 1),
+            "mem_mb": round(p.get("mem_mb") or 0, 0)
+        }
+        for p in ranked[:limit]
+    ]
+
+
+def vram_percent(used_mb: Optional[float], total_mb: Optional[float]) -> Optional[float]:
+    """
+    Percentual de VRAM em uso
+    
+    Args:
+        used_mb: Memória dedicada usada (MB)
+        total_mb: VRAM total (MB); 0/None quando o sensor não expõe
+    
+    Returns:
+        Percentual (0-100, 1 casa) ou None se o total é desconhecido
+    """
+    if not total_mb or total_mb <= 0:
+        return None
+    return round(min(100.0, max(0.0, (used_mb or 0) / total_mb * 100)), 1)
+
+
+def disk_capacity(usages: list[Any]) -> Dict[str, float]:
+    """
+    Capacidade de um disco somando as partições montadas
+    
+    Args:
+        usages: Resultados de psutil.disk_usage (total/free em bytes), um por partição
+    
+    Returns:
+        total_gb, free_gb e used_gb (= total - livre), 1 casa; zeros sem partição
+    """
+    total = sum(usage.total for usage in usages)
+    free = sum(usage.free for usage in usages)
+    total_gb = round(total / 1024**3, 1)
+    free_gb = round(free / 1024**3, 1)
+    return {"total_gb": total_gb, "free_gb": free_gb, "used_gb": round(total_gb - free_gb, 1)}
+
+
+# Campos em que 0 significa "sensor ausente": nenhuma leitura real é zero
+# (ao contrário de uso/carga/fan, onde 0 é válido em idle ou zero-RPM)
+ABSENT_WHEN_ZERO = {
+    "cpu": ("temp", "voltage", "power", "clock"),
+    "gpu": ("temp", "voltage", "clock_core", "clock_mem"),
+    "mobo": ("temp",),
+}
+
+
+def mark_absent(payload: Dict[str, Any]) -> Dict[str, Any]:
+    """
+    Troca por None (null no JSON) os sensores que não foram lidos
+    
+    Mantém o frame compacto (null ocupa o mesmo que um número curto) e permite
+    ao receiver mostrar "—" em vez de um zero enganoso.
+    
+    Args:
+        payload: Payload montado pelo Sender (alterado no lugar)
+    
+    Returns:
+        O mesmo payload
+    """
+    for section, fields in ABSENT_WHEN_ZERO.items():
+        values = payload.get(section)
+        if not values:
+            continue
+        for field in fields:
+            if values.get(field) == 0:
+                values[field] = None
+    
+    for disk in payload.get("storage") or []:
+        for field in ("temp", "temp_max"):
+            if disk.get(field) == 0:
+                disk[field] = None
+    
+    return payload
+
+
+UNAVAILABLE = "indisponível"
+
+
+def _format_field(value: Any) -> str:
+    """Valor de um campo para format_payload (0/None/vazio = UNAVAILABLE)"""
+    if isinstance(value, bool):
+        return "sim" if value else "não"
+    if value is None or value == 0 or value == "" or value == []:
+        return UNAVAILABLE
+    if isinstance(value, float):
+        return f"{value:g}"
+    if isinstance(value, list):
+        return ", ".join(_format_field(item) for item in value)
+    return str(value)
+
+
+def format_payload(payload: Dict[str, Any], indent: str = "  ") -> str:
+    """
+    Texto legível do payload completo, para o --selftest do Sender
+    
+    Cada seção vira um bloco com um campo por linha; listas de objetos
+    (discos, fans, processos) ganham o índice no nome. Campos zerados,
+    vazios ou None aparecem como "indisponível" para destacar o que o
+    Sender não conseguiu ler (uso 0% em idle também aparece assim).
+    
+    Args:
+        payload: Payload montado pelo Sender
+        indent: Recuo de cada nível
+    
+    Returns:
+        Texto com uma linha por campo, sem quebra de linha no final
+    """
+    lines = []
+    
+    def add(name: str, value: Any, depth: int) -> None:
+        prefix = indent * depth
+        if isinstance(value, dict):
+            lines.append(f"{prefix}{name}:" if value else f"{prefix}{name}: {UNAVAILABLE}")
+            for key, item in value.items():
+                add(key, item, depth + 1)
+        elif isinstance(value, list) and value and all(isinstance(item, dict) for item in value):
+            for i, item in enumerate(value):
+                add(f"{name}[{i}]", item, depth)
+        else:
+            lines.append(f"{prefix}{name}: {_format_field(value)}")
+    
+    for key, value in payload.items():
+        add(key, value, 0)
+    return "\n".join(lines)
+
+
+# Campos que mudam a cada pacote sem refletir o hardware
+DIFF_IGNORED_FIELDS = ("timestamp", "uptime_secs")
+
+
+def significant_diff(current: Any, previous: Any, epsilon: float = 0.5) -> bool:
+    """
+    Verifica se o payload mudou de forma relevante desde o anterior
+    
+    Números são comparados com tolerância absoluta (oscilação de sensor
+    abaixo de epsilon não conta); textos, booleanos e None, exatamente.
+    Listas (discos, fans, processos) mudam se o tamanho mudar ou algum
+    item mudar. Os campos de DIFF_IGNORED_FIELDS são ignorados.
+    
+    Args:
+        current: Payload atual (ou uma seção dele)
+        previous: Payload anterior (None = não há anterior)
+        epsilon: Maior variação numérica ainda considerada ruído
+    
+    Returns:
+        True se algum campo mudou além da tolerância
+    """
+    if isinstance(current, dict) and isinstance(previous, dict):
+        keys = (set(current) | set(previous)) - set(DIFF_IGNORED_FIELDS)
+        return any(
+            key not in current or key not in previous
+            or significant_diff(current[key], previous[key], epsilon)
+            for key in keys
+        )
+    if isinstance(current, list) and isinstance(previous, list):
+        return len(current) != len(previous) or any(
+            significant_diff(a, b, epsilon) for a, b in zip(current, previous)
+        )
+    numeric = (int, float)
+    if (isinstance(current, numeric) and isinstance(previous, numeric)
+            and not isinstance(current, bool) and not isinstance(previous, bool)):
+        return abs(current - previous) > epsilon
+    return current != previous or type(current) is not type(previous)
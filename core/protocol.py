@@ -3,25 +3,320 @@ Protocolo de comunicação do Sistema de Telemetria
 Define o formato de mensagens e compressão
 """
 import gzip
+import hashlib
+import hmac
 import json
-from enum import IntEnum
-from typing import Any, Optional
+import os
+import zlib
+from enum import IntEnum, IntFlag
+from typing import Any, Callable, Optional
+
+from .compact import encode_compact, decode_compact
+
+# Criptografia é opcional (pip install cryptography)
+try:
+    from cryptography.exceptions import InvalidTag
+    from cryptography.hazmat.primitives.ciphers.aead import ChaCha20Poly1305
+    HAS_CRYPTO = True
+except ImportError:
+    HAS_CRYPTO = False
 
 
 class MagicByte(IntEnum):
     """Magic bytes para identificar tipo de payload"""
     RAW = 0x00      # JSON sem compressão
     GZIP = 0x01     # JSON comprimido com gzip
+    CONTROL = 0x04  # Pedido de controle Receiver -> Sender (ver core/control.py)
+    GOODBYE = 0x05  # Último frame do Sender ao encerrar (ver encode_goodbye)
+    COMPACT = 0x06  # Campos quantizados em binário, com perda (ver core/compact.py)
+    DELTA = 0x07    # Keyframe ou só os campos que mudaram (ver core/delta.py)
     
     # Reservados para futuras expansões
     MSGPACK = 0x02  # MessagePack (futuro)
     PROTOBUF = 0x03 # Protocol Buffers (futuro)
 
 
+class FrameFlag(IntFlag):
+    """Flags nos bits altos do magic byte (os bits baixos indicam o encoding)"""
+    ENCRYPTED = 0x80  # Corpo cifrado com ChaCha20-Poly1305: nonce (12) + ciphertext + tag (16)
+    AUTH_TAG = 0x40   # HMAC-SHA256 truncado (8 bytes) no fim do frame
+
+
+FLAGS_MASK = 0xC0
+NONCE_SIZE = 12
+CIPHER_TAG_SIZE = 16  # Tag do Poly1305 no fim do ciphertext
+AUTH_TAG_SIZE = 8
+
+# Maior frame que o Sender monta (fit_payload)
+MAX_UDP_PAYLOAD = 16384
+
+# Maior acréscimo de wrap_frame: magic + nonce + tag do Poly1305 + tag HMAC
+HEADER_SIZE = 1 + NONCE_SIZE + CIPHER_TAG_SIZE + AUTH_TAG_SIZE
+
+# Buffer do recvfrom nos receivers: o maior frame com folga para o cabeçalho e
+# 1 byte a mais, que só é preenchido se o datagrama não coube (o SO trunca
+# sem avisar no Linux; ver check_datagram)
+RECV_BUFFER_SIZE = MAX_UDP_PAYLOAD + HEADER_SIZE + 1
+
+# Limites na desserialização: um frame forjado não pode fazer o receiver
+# alocar muito além do que um payload real ocupa
+MAX_DECODED_SIZE = MAX_UDP_PAYLOAD * 16  # JSON descomprimido (gzip bomb)
+MAX_LIST_ITEMS = {"storage": 32, "fans": 64}  # Itens por lista do payload
+
+# Formato do corpo JSON, fixo para que senders e receivers de versões
+# diferentes continuem se entendendo (não depende dos padrões do json):
+#   - separators: sem espaços (o frame tem que caber num datagrama)
+#   - ensure_ascii: acentos viram \uXXXX, o corpo é sempre ASCII puro
+#   - allow_nan: NaN/Infinity passam (o json do Python lê de volta)
+#   - sort_keys: não, as chaves seguem a ordem de montagem do payload
+JSON_OPTIONS = {"separators": (",", ":"), "ensure_ascii": True, "allow_nan": True, "sort_keys": False}
+
+# mtime fixo no cabeçalho gzip: o mesmo payload gera sempre os mesmos bytes
+GZIP_MTIME = 0
+GZIP_LEVEL = 6
+
+# Início dos frames legados, de senders anteriores ao magic byte:
+# gzip puro (0x1f 0x8b) ou JSON em claro ('{')
+LEGACY_PREFIXES = (b"\x1f\x8b", b"{")
+
+# Versão do formato do payload, enviada pelo Sender em "protocol_version".
+# Payloads sem o campo são de senders anteriores a ele (versão 1); o
+# Receiver aceita de MIN_PROTOCOL_VERSION até a sua própria versão
+PROTOCOL_VERSION = 1
+MIN_PROTOCOL_VERSION = 1
+
+# Seções opcionais descartadas (nessa ordem) quando o frame não cabe
+BULKY_SECTIONS = (("processes",), ("network", "interfaces"))
+
+
+def frame_flags(magic: int) -> int:
+    """
+    Extrai as flags do magic byte
+    
+    Frames legados sem magic byte começam com 0x1f (gzip) ou '{' (0x7b);
+    como os bits baixos não formam um MagicByte válido, retornam 0.
+    """
+    if (magic & ~FLAGS_MASK & 0xFF) not in set(MagicByte):
+        return 0
+    return magic & FLAGS_MASK
+
+
+class ProtocolError(Exception):
+    """Erro ao interpretar um frame recebido"""
+
+
+class AuthFailedError(ProtocolError):
+    """Frame não autenticado: chave errada, adulterado ou sem a proteção exigida"""
+
+
+class FrameTooLargeError(ProtocolError):
+    """Frame maior que MAX_UDP_PAYLOAD mesmo sem as seções opcionais"""
+
+
+class TruncatedFrameError(ProtocolError):
+    """Datagrama maior que o buffer de recepção: chegou cortado"""
+
+
+class DeserializeError(ProtocolError):
+    """Corpo do frame inválido (gzip/JSON corrompido)"""
+
+
+class VersionMismatchError(ProtocolError):
+    """Payload de um Sender com versão de protocolo que este Receiver não entende"""
+    
+    def __init__(self, sender_version: Any, receiver_version: int = PROTOCOL_VERSION):
+        super().__init__(f"Protocolo do sender v{sender_version} incompatível com o receiver v{receiver_version}")
+        self.sender_version = sender_version
+        self.receiver_version = receiver_version
+
+
+class DecodeLimitError(DeserializeError):
+    """Frame acima dos limites de desserialização (MAX_DECODED_SIZE, MAX_LIST_ITEMS)"""
+
+
+class DecodeStats:
+    """
+    Frames aceitos e recusados pelo Receiver, com o último erro
+    
+    Separa "nenhum pacote chegando" de "pacotes chegando mas recusados"
+    (chave/segredo diferente, Sender de outra versão...) no overlay de debug.
+    """
+    
+    def __init__(self):
+        self.decoded = 0
+        self.rejected = 0
+        self.last_error: Optional[str] = None  # Classe do erro (AuthFailedError, DeserializeError...)
+        self.last_error_message = ""
+        self.last_error_at = 0.0
+    
+    def record_ok(self) -> None:
+        """Frame decodificado com sucesso"""
+        self.decoded += 1
+    
+    def record_error(self, error: ProtocolError, now: float) -> None:
+        """Frame recusado; guarda o tipo e a mensagem do erro"""
+        self.rejected += 1
+        self.last_error = type(error).__name__
+        self.last_error_message = str(error)
+        self.last_error_at = now
+    
+    def summary(self) -> str:
+        """Texto curto para o overlay, ex: OK: 120 | Recusados: 3 (último: AuthFailedError)"""
+        text = f"OK: {self.decoded} | Recusados: {self.rejected}"
+        if self.last_error:
+            text += f" (último: {self.last_error})"
+        return text
+
+
+def derive_key(key: str) -> bytes:
+    """Deriva a chave de 32 bytes a partir da senha compartilhada do config"""
+    return hashlib.sha256(key.encode('utf-8')).digest()
+
+
+def seal_frame(frame: bytes, key: str) -> bytes:
+    """
+    Cifra o corpo de um frame já codificado (magic + corpo)
+    
+    O magic byte segue em claro (com a flag ENCRYPTED) e entra como dado
+    associado, então também é autenticado.
+    
+    Args:
+        frame: Frame produzido por encode_payload ou pelo Sender
+        key: Senha compartilhada
+    
+    Returns:
+        Frame cifrado
+    
+    Raises:
+        ProtocolError: Se o pacote 'cryptography' não estiver instalado
+    """
+    if not HAS_CRYPTO:
+        raise ProtocolError("Criptografia requer o pacote 'cryptography' (pip install cryptography)")
+    
+    header = bytes([frame[0] | FrameFlag.ENCRYPTED])
+    nonce = os.urandom(NONCE_SIZE)
+    ciphertext = ChaCha20Poly1305(derive_key(key)).encrypt(nonce, frame[1:], header)
+    return header + nonce + ciphertext
+
+
+def open_frame(frame: bytes, key: Optional[str]) -> bytes:
+    """
+    Decifra um frame, devolvendo magic (sem a flag) + corpo em claro
+    
+    Com chave configurada, frames sem a flag ENCRYPTED são recusados
+    (senão qualquer um na rede poderia injetar pacotes em claro).
+    Sem chave, frames em claro passam inalterados.
+    
+    Raises:
+        AuthFailedError: Chave errada, frame adulterado ou proteção ausente
+    """
+    encrypted = bool(frame_flags(frame[0]) & FrameFlag.ENCRYPTED)
+    
+    if not key:
+        if encrypted:
+            raise AuthFailedError("Frame cifrado, mas nenhuma chave configurada")
+        return frame
+    if not encrypted:
+        raise AuthFailedError("Frame em claro recusado (chave configurada)")
+    if not HAS_CRYPTO:
+        raise ProtocolError("Criptografia requer o pacote 'cryptography' (pip install cryptography)")
+    if len(frame) < 1 + NONCE_SIZE + CIPHER_TAG_SIZE:
+        raise AuthFailedError(f"Frame cifrado curto demais ({len(frame)} bytes)")
+    
+    header = frame[:1]
+    nonce = frame[1:1 + NONCE_SIZE]
+    try:
+        body = ChaCha20Poly1305(derive_key(key)).decrypt(nonce, frame[1 + NONCE_SIZE:], header)
+    except (InvalidTag, ValueError):
+        raise AuthFailedError("Falha na autenticação do frame (chave errada ou adulterado)")
+    
+    return bytes([frame[0] & ~FrameFlag.ENCRYPTED & 0xFF]) + body
+
+
+def compute_auth_tag(data: bytes, secret: str) -> bytes:
+    """HMAC-SHA256 truncado em AUTH_TAG_SIZE bytes"""
+    return hmac.new(secret.encode('utf-8'), data, hashlib.sha256).digest()[:AUTH_TAG_SIZE]
+
+
+def sign_frame(frame: bytes, secret: str) -> bytes:
+    """
+    Acrescenta a tag HMAC ao frame (sem cifrar o conteúdo)
+    
+    A tag cobre o magic byte (já com a flag AUTH_TAG) e todo o corpo.
+    """
+    signed = bytes([frame[0] | FrameFlag.AUTH_TAG]) + frame[1:]
+    return signed + compute_auth_tag(signed, secret)
+
+
+def verify_frame(frame: bytes, secret: Optional[str]) -> bytes:
+    """
+    Confere e remove a tag HMAC, devolvendo o frame sem a flag AUTH_TAG
+    
+    Com secret configurado, frames sem tag ou com tag inválida são recusados.
+    Sem secret, a tag (se houver) é apenas removida.
+    
+    Raises:
+        AuthFailedError: Tag ausente ou inválida
+    """
+    signed = bool(frame_flags(frame[0]) & FrameFlag.AUTH_TAG)
+    
+    if not signed:
+        if secret:
+            raise AuthFailedError("Frame sem tag HMAC recusado (auth_secret configurado)")
+        return frame
+    if len(frame) <= AUTH_TAG_SIZE + 1:
+        raise AuthFailedError("Frame curto demais para conter a tag HMAC")
+    
+    body, tag = frame[:-AUTH_TAG_SIZE], frame[-AUTH_TAG_SIZE:]
+    if secret and not hmac.compare_digest(tag, compute_auth_tag(body, secret)):
+        raise AuthFailedError("Tag HMAC inválida (secret diferente ou frame forjado)")
+    
+    return bytes([body[0] & ~FrameFlag.AUTH_TAG & 0xFF]) + body[1:]
+
+
+def wrap_frame(frame: bytes, key: Optional[str] = None, auth_secret: Optional[str] = None) -> bytes:
+    """Aplica a criptografia e a tag HMAC configuradas (nessa ordem)"""
+    if key:
+        frame = seal_frame(frame, key)
+    if auth_secret:
+        frame = sign_frame(frame, auth_secret)
+    return frame
+
+
+def unwrap_frame(frame: bytes, key: Optional[str] = None, auth_secret: Optional[str] = None) -> bytes:
+    """
+    Desfaz wrap_frame: confere a tag HMAC e decifra, antes de desserializar
+    
+    Frames sem flags e sem chaves configuradas passam inalterados.
+    
+    Raises:
+        AuthFailedError: Frame recusado pela autenticação
+    """
+    if not (key or auth_secret or frame_flags(frame[0])):
+        return frame
+    frame = verify_frame(frame, auth_secret)
+    if key or frame_flags(frame[0]) & FrameFlag.ENCRYPTED:
+        frame = open_frame(frame, key)
+    return frame
+
+
+def serialize_payload(data: dict[str, Any]) -> bytes:
+    """Serializa o payload no formato fixo do protocolo (ver JSON_OPTIONS)"""
+    return json.dumps(data, **JSON_OPTIONS).encode('utf-8')
+
+
+def compress_body(body: bytes, compression_level: int = GZIP_LEVEL) -> bytes:
+    """Comprime o corpo com gzip, sem horário no cabeçalho (ver GZIP_MTIME)"""
+    return gzip.compress(body, compresslevel=compression_level, mtime=GZIP_MTIME)
+
+
 def encode_payload(
     data: dict[str, Any], 
     compress: bool = True,
-    compression_level: int = 6
+    compression_level: int = GZIP_LEVEL,
+    key: Optional[str] = None,
+    auth_secret: Optional[str] = None,
+    compact: bool = False
 ) -> bytes:
     """
     Codifica payload para transmissão
@@ -30,51 +325,269 @@ def encode_payload(
         data: Dicionário com dados de telemetria
         compress: Se True, comprime com gzip
         compression_level: Nível de compressão (1-9)
+        key: Senha compartilhada para cifrar o frame (None = em claro)
+        auth_secret: Segredo para a tag HMAC (None = sem tag)
+        compact: Encoding compacto com perda (ignora compress; ver core/compact.py)
     
     Returns:
         Bytes prontos para envio via socket
     """
-    json_data = json.dumps(data, separators=(',', ':')).encode('utf-8')
+    if compact:
+        return wrap_frame(bytes([MagicByte.COMPACT]) + encode_compact(data), key, auth_secret)
+    
+    json_data = serialize_payload(data)
     
     if compress:
-        compressed = gzip.compress(json_data, compresslevel=compression_level)
-        return bytes([MagicByte.GZIP]) + compressed
+        compressed = compress_body(json_data, compression_level)
+        frame = bytes([MagicByte.GZIP]) + compressed
+    else:
+        frame = bytes([MagicByte.RAW]) + json_data
+    
+    return wrap_frame(frame, key, auth_secret)
+
+
+def fit_payload(
+    data: dict[str, Any],
+    encode: Callable[[dict[str, Any]], bytes],
+    limit: int = MAX_UDP_PAYLOAD
+) -> tuple[bytes, list[str]]:
+    """
+    Codifica o payload garantindo que o frame cabe em um datagrama
+    
+    Se o frame passar do limite, descarta as seções de BULKY_SECTIONS uma a
+    uma (sem alterar o payload original) e codifica de novo.
+    
+    Args:
+        data: Payload de telemetria
+        encode: Função que gera o frame final (ex: encode_payload com as chaves)
+        limit: Tamanho máximo do frame em bytes
+    
+    Returns:
+        (frame, seções descartadas como "network.interfaces")
+    
+    Raises:
+        FrameTooLargeError: Nem sem as seções opcionais o frame coube
+    """
+    frame = encode(data)
+    dropped: list[str] = []
+    
+    for path in BULKY_SECTIONS:
+        if len(frame) <= limit:
+            break
+        parent = data
+        for part in path[:-1]:
+            parent = parent.get(part) if isinstance(parent, dict) else None
+        if not isinstance(parent, dict) or not parent.get(path[-1]):
+            continue
+        
+        # Cópia rasa só do caminho alterado
+        data = dict(data)
+        target = data
+        for part in path[:-1]:
+            target[part] = dict(target[part])
+            target = target[part]
+        del target[path[-1]]
+        dropped.append(".".join(path))
+        frame = encode(data)
+    
+    if len(frame) > limit:
+        raise FrameTooLargeError(f"Frame de {len(frame)} bytes excede {limit} bytes")
+    
+    return frame, dropped
+
+
+def encode_goodbye(
+    hostname: str,
+    key: Optional[str] = None,
+    auth_secret: Optional[str] = None
+) -> bytes:
+    """
+    Frame de despedida do Sender: o Receiver marca o host offline na hora,
+    em vez de esperar o timeout da conexão
+    """
+    frame = bytes([MagicByte.GOODBYE]) + serialize_payload({"hostname": hostname})
+    return wrap_frame(frame, key, auth_secret)
+
+
+def decode_goodbye(frame: bytes) -> Optional[dict[str, Any]]:
+    """
+    Conteúdo de um frame de despedida já sem criptografia/tag (ver unwrap_frame)
+    
+    Returns:
+        {"hostname": ...}, ou None se o frame não é de despedida
+    
+    Raises:
+        DeserializeError: Frame de despedida com corpo inválido
+    """
+    if not frame or frame[0] != MagicByte.GOODBYE:
+        return None
+    return parse_body(frame[1:])
+
+
+def _bounded_gunzip(data: bytes, limit: int) -> bytes:
+    """Descomprime gzip sem passar de limit bytes (não aloca o conteúdo todo)"""
+    try:
+        decompressor = zlib.decompressobj(wbits=31)
+        body = decompressor.decompress(data, limit + 1)
+    except zlib.error as e:
+        raise DeserializeError(f"gzip inválido: {e}") from e
+    if len(body) > limit or decompressor.unconsumed_tail:
+        raise DecodeLimitError(f"Corpo descomprimido passa de {limit} bytes")
+    if not decompressor.eof:
+        raise DeserializeError("gzip truncado (sem o fim do stream)")
+    return body
+
+
+def check_datagram(data: bytes, bufsize: int = RECV_BUFFER_SIZE) -> bytes:
+    """
+    Confere se o datagrama lido com recvfrom(bufsize) veio inteiro
+    
+    Um datagrama que enche o buffer foi truncado pelo SO; decodificá-lo daria
+    um erro de gzip/JSON sem relação com a causa.
     
-    return bytes([MagicByte.RAW]) + json_data
+    Raises:
+        TruncatedFrameError: len(data) >= bufsize
+    """
+    if len(data) >= bufsize:
+        raise TruncatedFrameError(f"Datagrama truncado: não coube no buffer de {bufsize} bytes")
+    return data
+
+
+def is_legacy_frame(frame: bytes) -> bool:
+    """Frame de sender antigo, sem magic byte (gzip ou JSON direto)"""
+    return frame.startswith(LEGACY_PREFIXES)
 
 
-def decode_payload(data: bytes) -> Optional[dict[str, Any]]:
+def extract_body(frame: bytes, limit: int = MAX_DECODED_SIZE, legacy: bool = False) -> bytes:
     """
-    Decodifica payload recebido
+    Corpo JSON de um frame já sem criptografia/tag (ver unwrap_frame)
+    
+    Args:
+        frame: Magic byte + corpo
+        limit: Tamanho máximo do JSON descomprimido
+        legacy: Aceita também frames legados sem magic byte (ver LEGACY_PREFIXES)
+    
+    Raises:
+        DeserializeError: gzip inválido ou magic byte desconhecido
+        DecodeLimitError: Corpo maior que limit
+    """
+    if not frame:
+        raise DeserializeError("Frame vazio")
+    magic = frame[0]
+    if magic == MagicByte.GZIP:
+        return _bounded_gunzip(frame[1:], limit)
+    if magic == MagicByte.RAW:
+        body = frame[1:]
+    elif magic == MagicByte.COMPACT:
+        # Volta a JSON para seguir o mesmo caminho (limites e versão em parse_body)
+        try:
+            return serialize_payload(decode_compact(frame[1:]))
+        except ValueError as e:
+            raise DeserializeError(f"Corpo compacto inválido: {e}") from e
+    elif magic == MagicByte.DELTA:
+        # Depende do keyframe anterior: quem recebe passa por DeltaDecoder
+        raise DeserializeError("Frame delta precisa de DeltaDecoder (core/delta.py)")
+    elif legacy and frame.startswith(b"\x1f\x8b"):
+        return _bounded_gunzip(frame, limit)
+    elif legacy and is_legacy_frame(frame):
+        body = frame
+    else:
+        raise DeserializeError(f"Magic byte desconhecido: 0x{magic:02x}")
+    if len(body) > limit:
+        raise DecodeLimitError(f"Corpo passa de {limit} bytes")
+    return body
+
+
+def parse_body(body: bytes) -> dict[str, Any]:
+    """
+    Desserializa o corpo JSON e confere os limites das listas
+    
+    Raises:
+        DeserializeError: JSON inválido ou payload que não é objeto
+        DecodeLimitError: Lista acima de MAX_LIST_ITEMS
+        VersionMismatchError: Payload de uma versão incompatível do protocolo
+    """
+    try:
+        payload = json.loads(body.decode('utf-8'))
+    except (json.JSONDecodeError, UnicodeDecodeError, RecursionError) as e:
+        raise DeserializeError(f"JSON inválido: {e}") from e
+    if not isinstance(payload, dict):
+        raise DeserializeError("Payload não é um objeto JSON")
+    for key, limit in MAX_LIST_ITEMS.items():
+        items = payload.get(key)
+        if isinstance(items, list) and len(items) > limit:
+            raise DecodeLimitError(f"Lista '{key}' com {len(items)} itens (máximo {limit})")
+    check_version(payload)
+    return payload
+
+
+def check_version(payload: dict[str, Any]) -> None:
+    """
+    Confere o "protocol_version" do payload (ausente = versão 1)
+    
+    Raises:
+        VersionMismatchError: Versão fora de MIN_PROTOCOL_VERSION..PROTOCOL_VERSION
+    """
+    version = payload.get("protocol_version", 1)
+    if isinstance(version, bool) or not isinstance(version, int) or not MIN_PROTOCOL_VERSION <= version <= PROTOCOL_VERSION:
+        raise VersionMismatchError(version)
+
+
+def decode_payload(
+    data: bytes,
+    key: Optional[str] = None,
+    auth_secret: Optional[str] = None
+) -> Optional[dict[str, Any]]:
+    """
+    Decodifica payload recebido (só frames com magic byte)
     
     Args:
         data: Bytes recebidos via socket
+        key: Senha compartilhada (obrigatória se o Sender cifra os frames)
+        auth_secret: Segredo da tag HMAC (exige frames assinados)
     
     Returns:
-        Dicionário com dados ou None se inválido
+        Dicionário com dados ou None se inválido (inclui frames acima dos
+        limites; extract_body/parse_body levantam DecodeLimitError)
+    
+    Raises:
+        AuthFailedError: Frame que não passou na autenticação (antes de desserializar)
+        VersionMismatchError: Sender com versão incompatível do protocolo
+    """
+    return _decode(data, key, auth_secret, legacy=False)
+
+
+def decode_any(
+    data: bytes,
+    key: Optional[str] = None,
+    auth_secret: Optional[str] = None
+) -> Optional[dict[str, Any]]:
     """
+    Como decode_payload, mas aceita também frames legados sem magic byte
+    
+    Decide pelo primeiro byte: MagicByte segue o caminho normal, 0x1f/'{'
+    é tratado como gzip/JSON de um sender antigo.
+    """
+    return _decode(data, key, auth_secret, legacy=True)
+
+
+def _decode(
+    data: bytes,
+    key: Optional[str],
+    auth_secret: Optional[str],
+    legacy: bool
+) -> Optional[dict[str, Any]]:
     if not data or len(data) < 2:
         return None
     
-    try:
-        magic = data[0]
-        payload_data = data[1:]
-        
-        if magic == MagicByte.GZIP:
-            json_data = gzip.decompress(payload_data)
-        elif magic == MagicByte.RAW:
-            json_data = payload_data
-        else:
-            # Retrocompatibilidade: sem magic byte
-            # Tenta gzip primeiro, depois raw
-            try:
-                json_data = gzip.decompress(data)
-            except (gzip.BadGzipFile, OSError):
-                json_data = data
-        
-        return json.loads(json_data.decode('utf-8'))
+    # Frames legados não têm flags: passam direto, ou são recusados se há chave/segredo
+    data = unwrap_frame(data, key, auth_secret)
     
-    except (json.JSONDecodeError, gzip.BadGzipFile, OSError, UnicodeDecodeError) as e:
+    try:
+        return parse_body(extract_body(data, legacy=legacy))
+    except VersionMismatchError:
+        raise
+    except DeserializeError as e:
         print(f"[Protocol] Erro ao decodificar payload: {e}")
         return None
 
@@ -0,0 +1,124 @@
+"""
+Cálculo de taxas a partir de contadores acumulados
+Usado pelo Sender para derivar bytes/s entre dois ciclos de coleta
+"""
+import time
+from collections import deque
+from typing import Any, Dict, Optional
+
+
+def compute_rate(prev: float, curr: float, delta_t: float) -> float:
+    """
+    Calcula a taxa por segundo entre duas leituras de um contador
+
+    Args:
+        prev: Valor anterior do contador
+        curr: Valor atual do contador
+        delta_t: Tempo decorrido em segundos
+
+    Returns:
+        Taxa por segundo (0 se o contador voltou ou o tempo é inválido)
+    """
+    if delta_t <= 0:
+        return 0.0
+
+    delta = curr - prev
+    # Contador reiniciado (reboot do driver, overflow): descarta o ciclo
+    if delta < 0:
+        return 0.0
+
+    return delta / delta_t
+
+
+def compute_disk_rates(
+    prev: Optional[Dict[str, Any]],
+    curr: Dict[str, Any],
+    delta_t: float
+) -> Dict[str, Dict[str, float]]:
+    """
+    Calcula taxas de leitura/escrita por disco entre dois snapshots
+
+    Os snapshots seguem o formato de psutil.disk_io_counters(perdisk=True):
+    nome do disco -> objeto com read_bytes, write_bytes, read_time, write_time.
+
+    Args:
+        prev: Snapshot anterior (None no primeiro ciclo)
+        curr: Snapshot atual
+        delta_t: Tempo decorrido em segundos
+
+    Returns:
+        Dict nome -> {"read_rate", "write_rate" (bytes/s), "total_activity" (%)}
+    """
+    rates: Dict[str, Dict[str, float]] = {}
+
+    for name, counters in curr.items():
+        before = prev.get(name) if prev else None
+
+        # Primeiro ciclo (ou disco recém-conectado): reporta zero
+        if before is None:
+            rates[name] = {"read_rate": 0.0, "write_rate": 0.0, "total_activity": 0.0}
+            continue
+
+        read_rate = compute_rate(before.read_bytes, counters.read_bytes, delta_t)
+        write_rate = compute_rate(before.write_bytes, counters.write_bytes, delta_t)
+
+        # Tempo ocupado (ms) sobre o intervalo -> % de atividade
+        busy_ms = compute_rate(before.read_time, counters.read_time, delta_t) + \
+            compute_rate(before.write_time, counters.write_time, delta_t)
+        activity = min(100.0, busy_ms / 10)  # ms/s -> %
+
+        rates[name] = {
+            "read_rate": read_rate,
+            "write_rate": write_rate,
+            "total_activity": activity
+        }
+
+    return rates
+
+
+class TrafficMeter:
+    """
+    Estatísticas de tráfego recebido numa janela deslizante
+    
+    Exemplo:
+        meter = TrafficMeter(window_seconds=5)
+        meter.record(len(data))
+        print(meter.packets_per_sec, meter.kbit_per_sec)
+    """
+    
+    def __init__(self, window_seconds: float = 5.0):
+        self.window_seconds = window_seconds
+        self._samples: deque = deque()  # (timestamp, bytes)
+        self.last_size = 0
+        self.total_packets = 0
+    
+    def record(self, size: int, now: Optional[float] = None) -> None:
+        """Registra um pacote recebido"""
+        now = time.time() if now is None else now
+        self._samples.append((now, size))
+        self.last_size = size
+        self.total_packets += 1
+        self._expire(now)
+    
+    def _expire(self, now: float) -> None:
+        """Descarta amostras fora da janela"""
+        while self._samples and now - self._samples[0][0] > self.window_seconds:
+            self._samples.popleft()
+    
+    @property
+    def avg_size(self) -> float:
+        """Tamanho médio dos pacotes na janela (bytes)"""
+        if not self._samples:
+            return 0.0
+        return sum(size for _, size in self._samples) / len(self._samples)
+    
+    @property
+    def packets_per_sec(self) -> float:
+        """Pacotes por segundo na janela"""
+        return len(self._samples) / self.window_seconds
+    
+    @property
+    def kbit_per_sec(self) -> float:
+        """Banda estimada em kbit/s na janela"""
+        total = sum(size for _, size in self._samples)
+        return total * 8 / 1000 / self.window_seconds
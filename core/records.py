@@ -0,0 +1,176 @@
+"""
+Recordes de todos os tempos por Sender (records.json)
+Máxima de cada métrica (e mínima, onde faz sentido) com o instante em que ocorreu;
+o Receiver grava com intervalo mínimo, não a cada pacote
+"""
+import json
+import os
+import threading
+import time
+from typing import Any, Callable, Dict, List, Tuple
+
+# Métricas acompanhadas: (seção.campo, rótulo, unidade, guarda a mínima)
+RECORD_METRICS: Tuple[Tuple[str, str, str, bool], ...] = (
+    ("cpu.temp", "CPU Temp", "°C", True),
+    ("cpu.power", "CPU Power", " W", False),
+    ("cpu.clock", "CPU Clock", " MHz", False),
+    ("gpu.temp", "GPU Temp", "°C", True),
+    ("gpu.power_w", "GPU Power", " W", False),
+    ("gpu.clock_core", "GPU Clock", " MHz", False),
+    ("mobo.temp", "MOBO Temp", "°C", True),
+    ("ram.percent", "RAM", "%", False),
+    ("network.down_kbps", "Download", " KB/s", False),
+    ("network.up_kbps", "Upload", " KB/s", False),
+    ("network.ping_ms", "Ping", " ms", True),
+)
+
+# Intervalo mínimo entre gravações do arquivo
+DEFAULT_SAVE_SECS = 60.0
+
+
+def record_values(payload: Dict[str, Any]) -> Dict[str, float]:
+    """
+    Valores do payload que podem virar recorde
+    
+    Zero, negativo (ping -1 = inalcançável) e ausente (None) ficam de fora:
+    são sensor sem leitura, não um recorde de mínima.
+    """
+    values: Dict[str, float] = {}
+    for key, _, _, _ in RECORD_METRICS:
+        section, field = key.split(".", 1)
+        value = (payload.get(section) or {}).get(field)
+        if isinstance(value, (int, float)) and not isinstance(value, bool) and value > 0:
+            values[key] = float(value)
+    return values
+
+
+def update_records(
+    records: Dict[str, Dict[str, float]],
+    values: Dict[str, float],
+    now: float
+) -> List[str]:
+    """
+    Aplica uma leitura sobre os recordes (alterados no lugar)
+    
+    Args:
+        records: metrica -> {"max", "max_at", "min", "min_at"}
+        values: metrica -> valor (ver record_values)
+        now: Instante da leitura (epoch)
+    
+    Returns:
+        Métricas que bateram recorde nesta leitura
+    """
+    tracks_min = {key: keep_min for key, _, _, keep_min in RECORD_METRICS}
+    changed: List[str] = []
+    for key, value in values.items():
+        record = records.setdefault(key, {})
+        new = False
+        if "max" not in record or value > record["max"]:
+            record["max"], record["max_at"] = value, now
+            new = True
+        if tracks_min.get(key) and ("min" not in record or value < record["min"]):
+            record["min"], record["min_at"] = value, now
+            new = True
+        if new:
+            changed.append(key)
+    return changed
+
+
+def _valid_record(record: Any) -> bool:
+    """Recorde com "max" e, se houver, "max_at"/"min"/"min_at" numéricos"""
+    if not isinstance(record, dict) or "max" not in record:
+        return False
+    return all(
+        isinstance(record[field], (int, float)) and not isinstance(record[field], bool)
+        for field in ("max", "max_at", "min", "min_at") if field in record
+    )
+
+
+def load_records(path: str) -> Dict[str, Dict[str, Dict[str, float]]]:
+    """
+    Recordes salvos (host -> métrica -> recorde)
+    
+    Vazio se o arquivo não existir ou estiver corrompido; entradas com formato
+    inválido (editadas à mão, versão antiga) são descartadas com um aviso.
+    """
+    if not os.path.exists(path):
+        return {}
+    try:
+        with open(path, "r", encoding="utf-8") as f:
+            data = json.load(f)
+    except (OSError, json.JSONDecodeError) as e:
+        print(f"[Records] Erro ao carregar {path}: {e}")
+        return {}
+    if not isinstance(data, dict):
+        print(f"[Records] Aviso: {path} não é um objeto JSON; recomeçando os recordes")
+        return {}
+    
+    records: Dict[str, Dict[str, Dict[str, float]]] = {}
+    for host, metrics in data.items():
+        if not isinstance(metrics, dict):
+            print(f"[Records] Aviso: recordes de '{host}' inválidos; descartados")
+            continue
+        valid = {key: record for key, record in metrics.items() if _valid_record(record)}
+        for key in metrics.keys() - valid.keys():
+            print(f"[Records] Aviso: recorde '{key}' de '{host}' inválido; descartado")
+        records[host] = valid
+    return records
+
+
+def save_records(path: str, records: Dict[str, Dict[str, Dict[str, float]]]) -> bool:
+    """Grava os recordes (arquivo temporário + rename: nunca fica pela metade)"""
+    tmp_path = f"{path}.tmp"
+    try:
+        with open(tmp_path, "w", encoding="utf-8") as f:
+            json.dump(records, f, indent=2, ensure_ascii=False)
+        os.replace(tmp_path, path)
+        return True
+    except OSError as e:
+        print(f"[Records] Erro ao salvar {path}: {e}")
+        return False
+
+
+class RecordsStore:
+    """
+    Recordes de todos os Senders, gravados no máximo a cada save_secs
+    
+    Exemplo:
+        store = RecordsStore("records.json")
+        store.update("gamer-pc", payload)  # A cada pacote
+        store.flush()                      # A cada ciclo da UI (grava se for a hora)
+        store.flush(force=True)            # Ao sair
+    """
+    
+    def __init__(self, path: str, save_secs: float = DEFAULT_SAVE_SECS, clock: Callable[[], float] = time.time):
+        self.path = path
+        self.save_secs = save_secs
+        self._clock = clock
+        self._lock = threading.Lock()
+        self.records = load_records(path)
+        self.dirty = False
+        self._last_save = clock()
+    
+    def update(self, host: str, payload: Dict[str, Any]) -> List[str]:
+        """Aplica um pacote do Sender; retorna as métricas que bateram recorde"""
+        with self._lock:
+            host_records = self.records.setdefault(host, {})
+            changed = update_records(host_records, record_values(payload), self._clock())
+            if changed:
+                self.dirty = True
+            return changed
+    
+    def host_records(self, host: str) -> Dict[str, Dict[str, float]]:
+        """Cópia dos recordes de um Sender"""
+        with self._lock:
+            return {key: dict(record) for key, record in self.records.get(host, {}).items()}
+    
+    def flush(self, force: bool = False) -> bool:
+        """Grava se houver recorde novo e já passou save_secs (ou force); True se gravou"""
+        with self._lock:
+            now = self._clock()
+            if not self.dirty or (not force and now - self._last_save < self.save_secs):
+                return False
+            snapshot = json.loads(json.dumps(self.records))
+            self.dirty = False
+            self._last_save = now
+        return save_records(self.path, snapshot)
@@ -0,0 +1,46 @@
+"""
+Faixas plausíveis das leituras de sensores
+Fora delas é sensor ausente ou lixo (ex: 255°C de canal desconectado, 65535 RPM);
+os coletores tratam como "sem leitura" (0 no payload)
+"""
+
+# Temperaturas em °C (CPU, GPU, placa-mãe, discos): acima disso o hardware já desligou
+MIN_TEMP = 0.0
+MAX_TEMP = 150.0
+
+# RPM: 0 é fan parado (ex: modo zero-RPM da GPU), acima do máximo é lixo
+# (ex: 65535 de canal desconectado). Na lista de fans da placa-mãe, abaixo
+# de MIN_FAN_RPM é fan parado/leitura espúria do SuperIO
+MIN_FAN_RPM = 100
+MAX_FAN_RPM = 20000
+
+# Voltagem de núcleo (CPU/GPU): valores acima de 2V são de outros trilhos (12V, 5V)
+MAX_CORE_VOLTAGE = 2.0
+
+# Uso, duty cycle e níveis em %
+MAX_PERCENT = 100.0
+
+
+def plausible_temp(value: float) -> bool:
+    """True se a temperatura é uma leitura real (0 exclusivo até MAX_TEMP exclusivo)"""
+    return MIN_TEMP < value < MAX_TEMP
+
+
+def plausible_rpm(value: float) -> bool:
+    """True se o RPM é uma leitura real (0 = parado, até MAX_FAN_RPM)"""
+    return 0 <= value <= MAX_FAN_RPM
+
+
+def spinning_rpm(value: float) -> bool:
+    """True se o RPM é de um fan girando (MIN_FAN_RPM a MAX_FAN_RPM)"""
+    return MIN_FAN_RPM <= value <= MAX_FAN_RPM
+
+
+def plausible_voltage(value: float) -> bool:
+    """True se a voltagem é de núcleo (acima de 0 e abaixo de MAX_CORE_VOLTAGE)"""
+    return 0 < value < MAX_CORE_VOLTAGE
+
+
+def plausible_percent(value: float) -> bool:
+    """True se o percentual é uma leitura com valor (acima de 0 até MAX_PERCENT)"""
+    return 0 < value <= MAX_PERCENT
@@ -0,0 +1,120 @@
+"""
+Agendamento de tarefas periódicas do Sender
+Separa o que é lido a cada ciclo (contadores) do que é caro e muda pouco
+(topologia de discos, SMART, enumeração de adaptadores), e marca o ritmo
+dos envios em prazos fixos, sem acumular atraso
+"""
+import math
+import time
+from typing import Any, Callable, Optional, Tuple
+
+
+def next_deadline(deadline: float, interval: float, now: float) -> Tuple[float, int]:
+    """
+    Próximo prazo de um relógio de ticks fixos (deadline + N * interval)
+    
+    Se o ciclo estourou o intervalo, os ticks que já passaram são pulados
+    em vez de enviados em rajada para "alcançar" o ritmo.
+    
+    Args:
+        deadline: Prazo do tick que acabou de rodar
+        interval: Segundos entre ticks
+        now: Instante atual (fim do ciclo)
+    
+    Returns:
+        (próximo prazo, ticks pulados)
+    """
+    ticks = max(1, math.ceil((now - deadline) / interval)) if interval > 0 else 1
+    return deadline + ticks * interval, ticks - 1
+
+
+class FixedTicker:
+    """
+    Ritmo de envio em prazos absolutos (t0, t0 + i, t0 + 2i, ...)
+    
+    Dormir "intervalo - duração do ciclo" acumula o atraso de cada ciclo
+    lento; aqui o prazo seguinte não depende de quando o ciclo terminou.
+    
+    Exemplo:
+        ticker = FixedTicker(0.5)
+        while running:
+            enviar()
+            wait, skipped = ticker.advance()
+            stop_event.wait(wait)
+    """
+    
+    def __init__(self, interval: float, clock: Callable[[], float] = time.monotonic):
+        self.interval = max(0.0, float(interval))
+        self._clock = clock
+        self.deadline = clock()
+        self.skipped = 0  # Total de ticks pulados por estouro
+    
+    def advance(self) -> Tuple[float, int]:
+        """Avança para o próximo prazo; devolve (segundos até ele, ticks pulados agora)."""
+        now = self._clock()
+        self.deadline, skipped = next_deadline(self.deadline, self.interval, now)
+        self.skipped += skipped
+        return max(0.0, self.deadline - now), skipped
+
+
+class SlowRefresh:
+    """
+    Portão de atualização lenta: libera uma vez a cada `interval` segundos
+    
+    O relógio é injetável para os testes; em produção usa time.monotonic,
+    que não volta quando o relógio do Windows é ajustado.
+    """
+    
+    def __init__(self, interval: float, clock: Callable[[], float] = time.monotonic):
+        self.interval = max(0.0, float(interval))
+        self._clock = clock
+        self._last: Optional[float] = None
+    
+    def due(self) -> bool:
+        """True na primeira chamada e depois sempre que o intervalo vence (marca a atualização)."""
+        now = self._clock()
+        if self._last is not None and now - self._last < self.interval:
+            return False
+        self._last = now
+        return True
+    
+    def force(self):
+        """Faz a próxima chamada de due() liberar (ex: após reiniciar o monitor)."""
+        self._last = None
+
+
+class PeriodicCache:
+    """
+    Resultado de uma consulta cara, refeita só a cada `interval` segundos
+    
+    Entre as consultas devolve o último resultado. Se a consulta falhar,
+    mantém o resultado anterior até o próximo intervalo.
+    
+    Exemplo:
+        link = PeriodicCache(monitor.get_network_link_info, 60.0, {})
+        info = link.get()  # Consulta na 1ª chamada, depois só a cada 60s
+    """
+    
+    def __init__(
+        self,
+        fetch: Callable[[], Any],
+        interval: float,
+        default: Any = None,
+        clock: Callable[[], float] = time.monotonic
+    ):
+        self._fetch = fetch
+        self._gate = SlowRefresh(interval, clock)
+        self.value = default
+    
+    def get(self) -> Any:
+        """Último resultado (consulta de novo se o intervalo venceu)."""
+        if self._gate.due():
+            try:
+                self.value = self._fetch()
+            except Exception as e:
+                print(f"[Scheduling] Consulta falhou, mantendo o valor anterior: {e}")
+        return self.value
+    
+    def force(self):
+        """Faz a próxima chamada de get() consultar de novo."""
+        self._gate.force()
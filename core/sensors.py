@@ -0,0 +1,246 @@
+"""
+Fontes de sensores do Sender: cada uma preenche a sua parte do payload
+As fontes rodam em ordem de prioridade; uma fonte vê o que as anteriores
+já preencheram e decide se completa ou se deixa como está
+
+Ordem padrão do Sender:
+    1. LhmSource: LibreHardwareMonitor (CPU, GPU, placa-mãe, discos/SMART, fans)
+    2. NvmlSource: campos que só o NVML tem, se a GPU escolhida for NVIDIA
+    3. FanFallbackSource: fans do hwmon (psutil) quando o LHM não trouxe nenhum
+    4. CpuClockFallbackSource: clock da CPU pelo SO quando o LHM não leu
+
+disabled_sources no config.json desliga fontes mesmo quando disponíveis
+(ver SOURCE_NAMES): elas nem são inicializadas nem consultadas
+
+cpu.clock_source diz de onde veio o clock (CLOCK_SOURCES): o do LHM é o
+maior entre os núcleos (boost); o do Windows é o nominal (base), que não
+acompanha o boost
+
+Exemplo (testes, sem hardware):
+    sources = [MockSource("lhm", {"cpu": {"temp": 61.0}}), NvmlSource(lambda: None)]
+    collect_sources(payload, sources)
+"""
+import copy
+from typing import Any, Callable, Dict, Iterable, List, Optional, Sequence, Set, Tuple
+
+from .throttle import gpu_throttling
+
+# Origens de cpu.clock: "os_base" = clock nominal do Windows (não é o atual)
+CLOCK_SOURCES = ("lhm", "os", "os_base", "unknown")
+DEFAULT_CLOCK_SOURCE = "unknown"
+
+# Nomes aceitos em disabled_sources: as fontes abaixo mais as partes do
+# HardwareMonitor ("storage" = discos/SMART pelo LHM, "wmi" = consultas ao
+# PowerShell/WMI: seriais, letras, barramento e velocidade do link)
+SOURCE_NAMES = ("lhm", "nvml", "hwmon", "cpu_freq", "storage", "wmi")
+
+# Outros nomes aceitos para as mesmas fontes
+SOURCE_ALIASES = {"smart": "storage"}
+
+
+class SensorSource:
+    """
+    Uma fonte de leituras do Sender
+    
+    Subclasses implementam contribute(), que altera o payload no lugar.
+    """
+    
+    name = ""
+    
+    def contribute(self, payload: Dict[str, Any], hw_data: Optional[Dict[str, Any]] = None) -> None:
+        """
+        Preenche a parte do payload que esta fonte conhece
+        
+        Args:
+            payload: Payload em montagem (já com os valores padrão e o que as fontes anteriores puseram)
+            hw_data: Leitura do HardwareMonitor deste ciclo (None sem LHM)
+        """
+        raise NotImplementedError
+
+
+class LhmSource(SensorSource):
+    """Copia a leitura do HardwareMonitor (fetch_data) para o payload"""
+    
+    name = "lhm"
+    
+    def contribute(self, payload: Dict[str, Any], hw_data: Optional[Dict[str, Any]] = None) -> None:
+        if not hw_data:
+            return
+        cpu, gpu = payload["cpu"], payload["gpu"]
+        cpu["temp"] = round(hw_data["cpu"]["temp"], 1)
+        cpu["voltage"] = round(hw_data["cpu"]["voltage"], 3)
+        cpu["power"] = round(hw_data["cpu"]["power"], 1)
+        cpu["clock"] = round(hw_data["cpu"]["clock"], 0)
+        if cpu["clock"] > 0:
+            cpu["clock_source"] = "lhm"
+        
+        gpu["load"] = round(hw_data["gpu"]["load"], 1)
+        gpu["temp"] = round(hw_data["gpu"]["temp"], 1)
+        gpu["voltage"] = round(hw_data["gpu"]["voltage"], 3)
+        gpu["clock_core"] = round(hw_data["gpu"]["clock_core"], 0)
+        gpu["clock_mem"] = round(hw_data["gpu"]["clock_mem"], 0)
+        gpu["power_w"] = round(hw_data["gpu"].get("power", 0), 1)
+        gpu["fan"] = round(hw_data["gpu"]["fan"], 0)
+        gpu["fan_unit"] = hw_data["gpu"]["fan_unit"]
+        gpu["source"] = hw_data["gpu"]["source"]
+        gpu["mem_used_mb"] = round(hw_data["gpu"]["mem_used"], 0)
+        gpu["mem_total_mb"] = round(hw_data["gpu"]["mem_total"], 0)
+        
+        payload["mobo"]["temp"] = round(hw_data["mobo"]["temp"], 1)
+        payload["storage"] = hw_data["storage"]
+        payload["fans"] = hw_data["fans"]
+
+
+class NvmlSource(SensorSource):
+    """
+    Throttling, NVENC/NVDEC, barramento de memória e PCIe pelo NVML
+    
+    Só vale para GPU NVIDIA (ou nenhuma escolhida pelo LHM): o NVML não
+    enxerga a iGPU/AMD, e os campos dela ficariam com os de outra placa.
+    """
+    
+    name = "nvml"
+    
+    def __init__(self, read: Callable[[], Optional[Dict[str, Any]]]):
+        """
+        Args:
+            read: Retorna NvmlDevice.query() ou None (sem NVML ou GPU perdida)
+        """
+        self.read = read
+    
+    def contribute(self, payload: Dict[str, Any], hw_data: Optional[Dict[str, Any]] = None) -> None:
+        gpu = payload["gpu"]
+        if gpu["source"] not in ("nvidia", ""):
+            return
+        nvml = self.read()
+        if not nvml:
+            return
+        gpu["throttling"] = gpu_throttling(nvml["throttle_reasons"])
+        gpu["enc_util"] = round(nvml["enc_util"], 0)
+        gpu["dec_util"] = round(nvml["dec_util"], 0)
+        gpu["mem_util"] = round(nvml["mem_util"], 0)
+        gpu["pcie_tx_kbps"] = round(nvml["pcie_tx_kbps"], 0)
+        gpu["pcie_rx_kbps"] = round(nvml["pcie_rx_kbps"], 0)
+
+
+class FanFallbackSource(SensorSource):
+    """Fans de outra leitura (hwmon no Linux) quando nenhuma fonte anterior trouxe fans"""
+    
+    name = "hwmon"
+    
+    def __init__(self, read: Callable[[], List[Dict[str, Any]]]):
+        self.read = read
+    
+    def contribute(self, payload: Dict[str, Any], hw_data: Optional[Dict[str, Any]] = None) -> None:
+        if not payload["fans"]:
+            payload["fans"] = self.read()
+
+
+class CpuClockFallbackSource(SensorSource):
+    """
+    Clock da CPU pelo psutil.cpu_freq() quando nenhuma fonte anterior leu
+    
+    No Linux é o clock atual (sysfs); no Windows o psutil só tem o nominal,
+    marcado como "os_base" para o dashboard não confundir com o boost.
+    """
+    
+    name = "cpu_freq"
+    
+    def __init__(self, read: Callable[[], Any], platform: str):
+        """
+        Args:
+            read: Retorna psutil.cpu_freq() (objeto com .current em MHz) ou None
+            platform: sys.platform
+        """
+        self.read = read
+        self.platform = platform
+    
+    def contribute(self, payload: Dict[str, Any], hw_data: Optional[Dict[str, Any]] = None) -> None:
+        cpu = payload["cpu"]
+        if cpu["clock"]:
+            return
+        freq = self.read()
+        current = getattr(freq, "current", 0) or 0
+        if current <= 0:
+            return
+        cpu["clock"] = round(current, 0)
+        cpu["clock_source"] = "os_base" if self.platform.startswith("win") else "os"
+
+
+class MockSource(SensorSource):
+    """
+    Valores fixos mesclados no payload (testes e demonstração sem hardware)
+    
+    Com fill_only=True só preenche campos ainda zerados/vazios, como uma
+    fonte de menor prioridade.
+    """
+    
+    def __init__(self, name: str, values: Dict[str, Any], fill_only: bool = False):
+        self.name = name
+        self.values = values
+        self.fill_only = fill_only
+        self.calls = 0
+    
+    def contribute(self, payload: Dict[str, Any], hw_data: Optional[Dict[str, Any]] = None) -> None:
+        self.calls += 1
+        _merge(payload, self.values, self.fill_only)
+
+
+def _merge(target: Dict[str, Any], values: Dict[str, Any], fill_only: bool) -> None:
+    for key, value in values.items():
+        current = target.get(key)
+        if isinstance(value, dict) and isinstance(current, dict):
+            _merge(current, value, fill_only)
+        elif not fill_only or not current:
+            target[key] = copy.deepcopy(value)
+
+
+def parse_disabled_sources(values: Any) -> Tuple[Set[str], List[str]]:
+    """
+    Lê disabled_sources do config (sem diferenciar maiúsculas)
+    
+    Returns:
+        (nomes desligados, avisos para nomes desconhecidos ou valor inválido)
+    """
+    if values is None:
+        return set(), []
+    if isinstance(values, str) or not isinstance(values, (list, tuple)):
+        return set(), [f"disabled_sources deve ser uma lista, não {values!r}"]
+    disabled, warnings = set(), []
+    for value in values:
+        name = str(value).strip().lower()
+        name = SOURCE_ALIASES.get(name, name)
+        if name in SOURCE_NAMES:
+            disabled.add(name)
+        else:
+            warnings.append(f"fonte desconhecida em disabled_sources: {value!r} (válidas: {', '.join(SOURCE_NAMES)}; 'smart' = storage)")
+    return disabled, warnings
+
+
+def enabled_sources(sources: Iterable[SensorSource], disabled: Set[str]) -> List[SensorSource]:
+    """As fontes fora de disabled, na mesma ordem"""
+    return [source for source in sources if source.name not in disabled]
+
+
+def collect_sources(
+    payload: Dict[str, Any],
+    sources: Sequence[SensorSource],
+    hw_data: Optional[Dict[str, Any]] = None
+) -> List[str]:
+    """
+    Passa o payload por cada fonte, na ordem da lista
+    
+    Uma fonte que falha não derruba o ciclo: o erro é impresso e as
+    seguintes rodam normalmente.
+    
+    Returns:
+        Nomes das fontes que falharam neste ciclo
+    """
+    failed = []
+    for source in sources:
+        try:
+            source.contribute(payload, hw_data)
+        except Exception as e:
+            print(f"[Sensors] Fonte {source.name} falhou: {e}")
+            failed.append(source.name)
+    return failed
@@ -0,0 +1,365 @@
+"""
+Séries dos gráficos do dashboard
+Capacidade calculada a partir de uma janela em segundos e do intervalo real entre pacotes
+"""
+import math
+from collections import deque
+from statistics import median
+from typing import Deque, Dict, Iterable, List, Optional, Tuple
+
+MIN_HISTORY_SIZE = 10
+MAX_HISTORY_SIZE = 3600
+
+# Amostra de lacuna: o gráfico interrompe a linha em vez de ligar os pontos
+GAP = float("nan")
+
+
+def history_capacity(history_seconds: float, interval: float) -> int:
+    """
+    Calcula quantas amostras cabem na janela de tempo
+    
+    Args:
+        history_seconds: Duração desejada da janela
+        interval: Intervalo médio entre pacotes em segundos
+    
+    Returns:
+        Capacidade entre MIN_HISTORY_SIZE e MAX_HISTORY_SIZE
+    """
+    if interval <= 0 or history_seconds <= 0:
+        return MIN_HISTORY_SIZE
+    size = math.ceil(history_seconds / interval)
+    return max(MIN_HISTORY_SIZE, min(MAX_HISTORY_SIZE, size))
+
+
+def resize_series(series: Deque[float], capacity: int) -> Deque[float]:
+    """
+    Cria uma série com nova capacidade mantendo as amostras mais recentes
+    
+    Ao crescer, completa à esquerda com lacunas (como na inicialização):
+    o gráfico continua ocupando a largura inteira sem zeros falsos puxando
+    o auto-scale e a exportação.
+    
+    Args:
+        series: Série atual
+        capacity: Nova capacidade
+    
+    Returns:
+        Nova deque com maxlen=capacity
+    """
+    recent = list(series)[-capacity:]
+    return deque([GAP] * (capacity - len(recent)) + recent, maxlen=capacity)
+
+
+class IntervalTracker:
+    """
+    Estima o intervalo real entre pacotes (mediana das últimas chegadas)
+    
+    Lacunas maiores que max_gap (desconexões) não entram na estimativa.
+    """
+    
+    def __init__(self, window: int = 20, max_gap: float = 5.0):
+        self.max_gap = max_gap
+        self._deltas: Deque[float] = deque(maxlen=window)
+        self._last: Optional[float] = None
+    
+    def record(self, now: float) -> None:
+        """Registra a chegada de um pacote"""
+        if self._last is not None:
+            delta = now - self._last
+            if 0 < delta <= self.max_gap:
+                self._deltas.append(delta)
+        self._last = now
+    
+    @property
+    def interval(self) -> Optional[float]:
+        """Intervalo estimado em segundos (None até haver amostras)"""
+        if not self._deltas:
+            return None
+        return median(self._deltas)
+
+
+def sample_interval(packet_interval: Optional[float], aggregate_secs: float) -> Optional[float]:
+    """
+    Segundos entre amostras dos gráficos
+    
+    Com agregação, cada amostra é um balde de aggregate_secs (ou o próprio
+    pacote, se os pacotes chegam mais espaçados que o balde).
+    """
+    if aggregate_secs and aggregate_secs > 0:
+        return max(packet_interval or 0.0, aggregate_secs)
+    return packet_interval
+
+
+class SampleAggregator:
+    """
+    Agrupa os pacotes em baldes de tempo fixos e entrega a média de cada um
+    
+    Com o Sender a 0.5s e baldes de 1s, o gráfico recebe uma amostra por
+    segundo e a janela continua representando o mesmo tempo de relógio.
+    
+    Exemplo:
+        agg = SampleAggregator(1.0)
+        means = agg.add(time.time(), {"cpu_usage": 12.0})  # None até o balde fechar
+    """
+    
+    def __init__(self, bucket_secs: float):
+        self.bucket_secs = float(bucket_secs)
+        self._bucket: Optional[int] = None
+        self._sums: Dict[str, float] = {}
+        self._counts: Dict[str, int] = {}
+    
+    def add(self, timestamp: float, values: Dict[str, Optional[float]]) -> Optional[Dict[str, float]]:
+        """
+        Acumula um pacote no balde do seu instante
+        
+        Returns:
+            Médias do balde anterior quando este pacote abre um balde novo, senão None
+        """
+        bucket = math.floor(timestamp / self.bucket_secs)
+        means = None
+        if self._bucket is not None and bucket != self._bucket:
+            means = self.flush()
+        self._bucket = bucket
+        for key, value in values.items():
+            self._counts.setdefault(key, 0)
+            if value is None:  # Sensor ausente: não puxa a média para 0
+                continue
+            self._sums[key] = self._sums.get(key, 0.0) + value
+            self._counts[key] += 1
+        return means
+    
+    def flush(self) -> Optional[Dict[str, float]]:
+        """
+        Médias do balde atual (None se vazio) e recomeça
+        
+        Toda chave vista no balde sai no resultado, para as séries avançarem
+        juntas; a que só teve None sai como GAP.
+        """
+        if self._bucket is None:
+            return None
+        means = {
+            key: self._sums[key] / count if count else GAP
+            for key, count in self._counts.items()
+        }
+        self._bucket = None
+        self._sums, self._counts = {}, {}
+        return means
+
+
+def aggregate_samples(
+    samples: Iterable[Tuple[float, Dict[str, Optional[float]]]],
+    bucket_secs: float
+) -> List[Dict[str, float]]:
+    """Médias por balde de uma sequência (instante, valores), incluindo o último balde"""
+    aggregator = SampleAggregator(bucket_secs)
+    buckets = []
+    for timestamp, values in samples:
+        means = aggregator.add(timestamp, values)
+        if means is not None:
+            buckets.append(means)
+    last = aggregator.flush()
+    if last is not None:
+        buckets.append(last)
+    return buckets
+
+
+def gap_samples(
+    prev_timestamp: Optional[float],
+    timestamp: Optional[float],
+    interval: Optional[float],
+    limit: int
+) -> int:
+    """
+    Quantas amostras de lacuna inserir antes de um pacote
+    
+    Usa o timestamp do payload (horário do Sender), então pacotes perdidos
+    viram lacunas mesmo que os seguintes cheguem em rajada.
+    
+    Args:
+        prev_timestamp: Timestamp do pacote anterior (None no primeiro)
+        timestamp: Timestamp do pacote atual (None em senders antigos)
+        interval: Intervalo esperado entre pacotes em segundos
+        limit: Máximo de lacunas (a capacidade da série)
+    
+    Returns:
+        Número de intervalos perdidos (0 se não dá para saber)
+    """
+    if prev_timestamp is None or timestamp is None or not interval or interval <= 0:
+        return 0
+    missed = round((timestamp - prev_timestamp) / interval) - 1
+    return max(0, min(limit, missed))
+
+
+def history_sample(value: Optional[float]) -> float:
+    """Valor a guardar no histórico: sensor ausente (None) vira lacuna, não 0"""
+    return GAP if value is None else value
+
+
+def series_values(series) -> list:
+    """Amostras reais da série (sem as lacunas), para min/média/máx e escalas"""
+    return [v for v in series if not (isinstance(v, float) and math.isnan(v))]
+
+
+def split_segments(series) -> list:
+    """
+    Divide a série em trechos contínuos separados pelas lacunas
+    
+    Returns:
+        Lista de listas de (índice, valor)
+    """
+    segments = []
+    current = []
+    for i, value in enumerate(series):
+        if isinstance(value, float) and math.isnan(value):
+            if current:
+                segments.append(current)
+            current = []
+        else:
+            current.append((i, value))
+    if current:
+        segments.append(current)
+    return segments
+
+
+def axis_bounds(
+    series,
+    fixed_max: Optional[float] = None,
+    autoscale: bool = False,
+    margin: float = 0.1,
+    min_span: float = 1.0
+) -> Tuple[float, float]:
+    """
+    Limites do eixo Y de um gráfico
+    
+    Escala fixa vai de 0 a fixed_max (percentuais). No auto-scale os limites
+    seguem a faixa dos dados com uma margem, para um ping sempre em ~8ms não
+    virar uma linha reta no fundo do gráfico.
+    
+    Args:
+        series: Amostras (lacunas NaN são ignoradas)
+        fixed_max: Topo da escala fixa (None = 120% do maior valor)
+        autoscale: Usa a faixa dos dados em vez da escala fixa
+        margin: Folga acima/abaixo dos dados, em fração da faixa
+        min_span: Altura mínima do eixo (série constante, zerada ou com uma amostra)
+    
+    Returns:
+        (mínimo, máximo) com máximo > mínimo
+    """
+    values = series_values(series)
+    if not autoscale:
+        top = fixed_max if fixed_max and fixed_max > 0 else max(values, default=0) * 1.2
+        return 0.0, max(float(top), min_span)
+    
+    if not values:
+        return 0.0, min_span
+    
+    low, high = float(min(values)), float(max(values))
+    if high - low < min_span:
+        center = (high + low) / 2
+        low, high = center - min_span / 2, center + min_span / 2
+    span = high - low
+    low, high = low - span * margin, high + span * margin
+    # Métricas sem valores negativos não ganham eixo negativo
+    if min(values) >= 0 and low < 0:
+        low = 0.0
+    return low, high
+
+
+def plot_points(
+    series,
+    x: float,
+    y: float,
+    w: float,
+    h: float,
+    low: float,
+    high: float
+) -> list:
+    """
+    Converte a série em coordenadas de linha no canvas
+    
+    A série ocupa toda a largura (primeira amostra em x, última em x + w) e
+    o valor `low` fica na base do retângulo. Lacunas quebram a linha.
+    
+    Args:
+        series: Amostras (deque/lista; pode estar vazia)
+        x, y, w, h: Retângulo do gráfico
+        low, high: Limites do eixo Y (ver axis_bounds)
+    
+    Returns:
+        Lista de trechos [x0, y0, x1, y1, ...] com pelo menos dois pontos cada
+    """
+    data = list(series)
+    if len(data) < 2:
+        return []
+    
+    step_x = w / (len(data) - 1)
+    span = high - low
+    lines = []
+    for segment in split_segments(data):
+        points = []
+        for i, value in segment:
+            ratio = (value - low) / span if span > 0 else 0
+            points.extend([x + i * step_x, y + h - ratio * h])
+        if len(points) >= 4:
+            lines.append(points)
+    return lines
+
+
+def paired_points(xs, ys) -> list:
+    """
+    Junta duas séries do histórico em pontos (x, y) para o gráfico de dispersão
+    
+    As séries são alinhadas pelo fim (amostra mais recente com a mais
+    recente); se os tamanhos diferem, sobram as amostras antigas da mais
+    longa. Pares com lacuna (NaN) ou None são descartados (inclui o
+    preenchimento inicial do histórico).
+    
+    Args:
+        xs: Série do eixo X (deque/lista; pode estar vazia)
+        ys: Série do eixo Y
+    
+    Returns:
+        Lista de (x, y), da amostra mais antiga para a mais recente
+    """
+    xs, ys = list(xs), list(ys)
+    count = min(len(xs), len(ys))
+    if count == 0:
+        return []
+    points = []
+    for x, y in zip(xs[len(xs) - count:], ys[len(ys) - count:]):
+        if x is None or y is None or math.isnan(x) or math.isnan(y):
+            continue
+        points.append((float(x), float(y)))
+    return points
+
+
+def scatter_coords(
+    points,
+    x: float,
+    y: float,
+    w: float,
+    h: float,
+    x_bounds: Tuple[float, float],
+    y_bounds: Tuple[float, float]
+) -> list:
+    """
+    Converte pontos (x, y) em coordenadas no canvas
+    
+    Valores fora dos limites ficam na borda do retângulo.
+    
+    Args:
+        points: Pares (x, y) (ver paired_points)
+        x, y, w, h: Retângulo do gráfico
+        x_bounds, y_bounds: (mínimo, máximo) de cada eixo (ver axis_bounds)
+    
+    Returns:
+        Lista de (cx, cy)
+    """
+    (x_low, x_high), (y_low, y_high) = x_bounds, y_bounds
+    x_span, y_span = x_high - x_low, y_high - y_low
+    coords = []
+    for px, py in points:
+        rx = min(1.0, max(0.0, (px - x_low) / x_span)) if x_span > 0 else 0.0
+        ry = min(1.0, max(0.0, (py - y_low) / y_span)) if y_span > 0 else 0.0
+        coords.append((x + rx * w, y + h - ry * h))
+    return coords
@@ -0,0 +1,122 @@
+"""
+Suavização de séries para exibição (média móvel exponencial) e retenção
+de valores durante perdas curtas de pacotes
+Só afetam a exibição: alertas continuam avaliando o valor bruto
+"""
+from collections import deque
+from typing import Deque, Dict, Optional, Tuple
+
+
+class Smoother:
+    """
+    Média móvel exponencial (EMA) por métrica
+    
+    Exemplo:
+        smoother = Smoother(alpha=0.3)
+        smoothed = smoother.update("ping", 42.0)
+    
+    alpha = 0 desativa a suavização (retorna o valor bruto);
+    alpha próximo de 1 segue o valor bruto quase sem atraso.
+    """
+    
+    def __init__(self, alpha: float = 0.0):
+        """
+        Inicializa o suavizador
+        
+        Args:
+            alpha: Peso da nova amostra (0 = desligado, 0 < alpha <= 1)
+        """
+        self.alpha = max(0.0, min(1.0, alpha))
+        self._state: Dict[str, float] = {}
+    
+    @property
+    def enabled(self) -> bool:
+        return 0 < self.alpha < 1
+    
+    def update(self, key: str, value: float) -> float:
+        """
+        Incorpora uma nova amostra e retorna o valor suavizado
+        
+        Args:
+            key: Nome da métrica
+            value: Valor bruto
+        
+        Returns:
+            Valor suavizado (ou o bruto se desativado)
+        """
+        if not self.enabled:
+            return value
+        
+        previous = self._state.get(key)
+        # Primeira amostra inicializa a média (evita rampa a partir de zero)
+        smoothed = value if previous is None else previous + self.alpha * (value - previous)
+        self._state[key] = smoothed
+        return smoothed
+    
+    def reset(self) -> None:
+        """Descarta o estado acumulado (ex: após reconexão)"""
+        self._state.clear()
+
+
+class HoldState:
+    """
+    Valor exibido de uma métrica durante perdas curtas de pacotes
+    
+    Sem pacote novo, o valor segue a tendência das duas últimas amostras
+    (extrapolação linear) por até max_hold_frames intervalos; depois disso
+    é declarado obsoleto. Só para exibição: alertas usam o valor real.
+    
+    Exemplo:
+        hold = HoldState(max_hold_frames=2)
+        hold.update(42.0, packet_time)
+        shown = hold.value(time.time(), interval=0.5)  # None = obsoleto
+    """
+    
+    def __init__(self, max_hold_frames: int = 2):
+        self.max_hold_frames = max(0, int(max_hold_frames))
+        self._samples: Deque[Tuple[float, float]] = deque(maxlen=2)
+    
+    def update(self, value: float, timestamp: float) -> None:
+        """
+        Registra a amostra real de um pacote
+        
+        Redesenhos do mesmo pacote (timestamp repetido) são ignorados.
+        """
+        if self._samples and timestamp <= self._samples[-1][0]:
+            return
+        self._samples.append((timestamp, float(value)))
+    
+    def missed_frames(self, now: float, interval: float) -> int:
+        """Intervalos inteiros sem pacote desde a última amostra"""
+        if not self._samples or interval <= 0:
+            return 0
+        return max(0, int((now - self._samples[-1][0]) / interval))
+    
+    def value(self, now: float, interval: float) -> Optional[float]:
+        """
+        Valor a exibir agora
+        
+        Args:
+            now: Horário atual (mesma base dos timestamps de update)
+            interval: Intervalo esperado entre pacotes em segundos
+        
+        Returns:
+            Último valor, o valor extrapolado ou None se obsoleto
+        """
+        if not self._samples:
+            return None
+        last_time, last = self._samples[-1]
+        missed = self.missed_frames(now, interval)
+        if missed == 0:
+            return last
+        if missed > self.max_hold_frames:
+            return None
+        if len(self._samples) < 2:
+            return last
+        
+        prev_time, prev = self._samples[0]
+        held = last + (last - prev) / (last_time - prev_time) * (now - last_time)
+        # Métricas sem valores negativos não extrapolam abaixo de zero
+        if prev >= 0 and last >= 0:
+            held = max(0.0, held)
+        return held
@@ -4,10 +4,12 @@ Usa winsound nativo do Windows - sem dependências externas
 """
 from __future__ import annotations
 
+import os
+import re
 import threading
 import time
 from enum import Enum
-from typing import Optional
+from typing import Any, Dict, Optional, Union
 import sys
 
 # winsound só existe no Windows
@@ -18,6 +20,10 @@ else:
     HAS_WINSOUND = False
 
 
+# WAVs empacotados: assets/sounds/<nome>.wav (gerados por scripts/generate_sounds.py)
+SOUNDS_DIR = os.path.join(os.path.dirname(os.path.dirname(os.path.abspath(__file__))), "assets", "sounds")
+
+
 class AlertSound(Enum):
     """Sons de alerta disponíveis"""
     # Sons do sistema Windows (não precisam de arquivo)
@@ -40,15 +46,92 @@ class SoundConfig:
         self,
         enabled: bool = True,
         volume_percent: int = 100,  # Não usado no winsound, mas reservado
-        cooldown_seconds: float = 10.0,  # Intervalo mínimo entre sons do mesmo tipo
-        warning_sound: AlertSound = AlertSound.WARNING,
-        critical_sound: AlertSound = AlertSound.BEEP_URGENT
+        cooldown_seconds: float = 10.0,  # Intervalo mínimo entre quaisquer dois sons
+        warning_sound: Union[AlertSound, str] = AlertSound.WARNING,
+        critical_sound: Union[AlertSound, str] = AlertSound.BEEP_URGENT,
+        connection_lost_sound: Union[AlertSound, str] = AlertSound.BEEP_LOW,
+        per_metric: Optional[Dict[str, Any]] = None  # Ver resolve_alert_sound
     ):
         self.enabled = enabled
         self.volume_percent = volume_percent
         self.cooldown_seconds = cooldown_seconds
         self.warning_sound = warning_sound
         self.critical_sound = critical_sound
+        self.connection_lost_sound = connection_lost_sound
+        self.per_metric = per_metric or {}
+
+
+def resolve_alert_sound(config: SoundConfig, metric: str, critical: bool) -> Union[AlertSound, str]:
+    """
+    Som de um alerta: o de per_metric para a métrica, senão o genérico do nível
+    
+    per_metric aceita a métrica ("GPU.temp") ou o painel inteiro ("RAM"), sem
+    diferenciar maiúsculas; a métrica vence o painel. O valor é um nome de som
+    para os dois níveis ou {"warning": ..., "critical": ...} (nível ausente
+    usa o genérico).
+    
+    Exemplo:
+        per_metric = {"GPU.temp": {"critical": "beep_high"}, "RAM": "beep_low"}
+    """
+    level = "critical" if critical else "warning"
+    fallback = config.critical_sound if critical else config.warning_sound
+    overrides = {str(key).lower(): value for key, value in (config.per_metric or {}).items()}
+    metric = (metric or "").lower()
+    for key in (metric, metric.split(".")[0]):
+        choice = overrides.get(key)
+        if isinstance(choice, dict):
+            choice = choice.get(level)
+        if isinstance(choice, str) and choice:
+            return choice
+    return fallback
+
+
+class CooldownGate:
+    """
+    Libera no máximo um evento a cada cooldown_seconds
+    
+    Exemplo:
+        gate = CooldownGate(10)
+        if gate.ready():
+            tocar_som()
+    """
+    
+    def __init__(self, cooldown_seconds: float):
+        self.cooldown_seconds = cooldown_seconds
+        self._last: Optional[float] = None
+        self._lock = threading.Lock()
+    
+    def ready(self, now: Optional[float] = None) -> bool:
+        """Retorna True (e marca o instante) se o cooldown já passou"""
+        now = time.time() if now is None else now
+        with self._lock:
+            if self._last is not None and now - self._last < self.cooldown_seconds:
+                return False
+            self._last = now
+            return True
+    
+    def reset(self) -> None:
+        """Libera o próximo evento imediatamente"""
+        with self._lock:
+            self._last = None
+
+
+def resolve_sound_asset(name: str, sounds_dir: str = SOUNDS_DIR) -> Optional[str]:
+    """
+    Procura o WAV de um som pelo nome (ex: "beep_urgent" -> assets/sounds/beep_urgent.wav)
+    
+    Args:
+        name: Nome do som no config
+        sounds_dir: Pasta dos WAVs
+    
+    Returns:
+        Caminho do arquivo, ou None se não existir (o chamador usa o beep de fallback)
+    """
+    # Só nomes simples: o config não pode apontar para fora da pasta de sons
+    if not re.fullmatch(r"[A-Za-z0-9_-]+", name or ""):
+        return None
+    path = os.path.join(sounds_dir, f"{name}.wav")
+    return path if os.path.isfile(path) else None
 
 
 class SoundManager:
@@ -84,15 +167,18 @@ class SoundManager:
             config: Configuração de sons (usa padrão se None)
         """
         self.config = config or SoundConfig()
-        self.last_played: dict[str, float] = {}
-        self._lock = threading.Lock()
+        # Cooldown global: vários alertas ao mesmo tempo tocam um único som
+        self._gate = CooldownGate(self.config.cooldown_seconds)
     
-    def play(self, sound: AlertSound, async_play: bool = True) -> bool:
+    def play(self, sound: Union[AlertSound, str], async_play: bool = True) -> bool:
         """
         Toca um som de alerta
         
+        Usa o WAV de assets/sounds se existir; senão o som embutido do winsound
+        (ou um beep simples para nomes desconhecidos).
+        
         Args:
-            sound: Som a tocar
+            sound: Som a tocar (enum ou nome do config, ex: "beep_urgent")
             async_play: Se True, toca em thread separada
         
         Returns:
@@ -101,22 +187,33 @@ class SoundManager:
         if not self.config.enabled or not HAS_WINSOUND:
             return False
         
-        # Verifica cooldown
-        with self._lock:
-            now = time.time()
-            last_time = self.last_played.get(sound.value, 0)
-            
-            if now - last_time < self.config.cooldown_seconds:
-                return False
-            
-            self.last_played[sound.value] = now
+        if not self._gate.ready():
+            return False
         
         if async_play:
-            thread = threading.Thread(target=self._play_sound, args=(sound,), daemon=True)
+            thread = threading.Thread(target=self._play_named, args=(sound,), daemon=True)
             thread.start()
             return True
         else:
-            return self._play_sound(sound)
+            return self._play_named(sound)
+    
+    def _play_named(self, sound: Union[AlertSound, str]) -> bool:
+        """Resolve o nome (WAV > som embutido > beep) e toca (interno)"""
+        name = sound.value if isinstance(sound, AlertSound) else str(sound)
+        
+        asset = resolve_sound_asset(name)
+        if asset:
+            try:
+                winsound.PlaySound(asset, winsound.SND_FILENAME)
+                return True
+            except Exception as e:
+                print(f"[Sound] Erro ao tocar {asset}: {e}")
+        
+        try:
+            builtin = AlertSound(name)
+        except ValueError:
+            builtin = AlertSound.BEEP
+        return self._play_sound(builtin)
     
     def _play_sound(self, sound: AlertSound) -> bool:
         """Toca o som (interno)"""
@@ -146,7 +243,7 @@ class SoundManager:
                 # Beep genérico
                 winsound.Beep(800, 200)
                 return True
-                
+        
         except Exception as e:
             print(f"[Sound] Erro ao tocar som: {e}")
             return False
@@ -159,6 +256,23 @@ class SoundManager:
         """Toca som crítico"""
         return self.play(self.config.critical_sound)
     
+    def play_alert(self, metric: str, critical: bool) -> bool:
+        """Toca o som do alerta da métrica (per_metric ou o genérico do nível)"""
+        return self.play(resolve_alert_sound(self.config, metric, critical))
+    
+    def play_connection_lost(self) -> bool:
+        """
+        Toca o som de conexão perdida
+        
+        Fora do cooldown dos alertas: um alerta de métrica logo antes não
+        pode engolir o aviso, e o ConnectionWatchdog já evita repetição.
+        """
+        if not self.config.enabled or not HAS_WINSOUND:
+            return False
+        thread = threading.Thread(target=self._play_named, args=(self.config.connection_lost_sound,), daemon=True)
+        thread.start()
+        return True
+    
     def play_beep(self, frequency: int = 800, duration_ms: int = 200) -> bool:
         """
         Toca beep customizado
@@ -196,11 +310,11 @@ class SoundManager:
     def update_config(self, config: SoundConfig) -> None:
         """Atualiza configuração"""
         self.config = config
+        self._gate.cooldown_seconds = config.cooldown_seconds
     
     def clear_cooldowns(self) -> None:
         """Limpa todos os cooldowns"""
-        with self._lock:
-            self.last_played.clear()
+        self._gate.reset()
 
 
 # Instância global (singleton)
@@ -0,0 +1,166 @@
+"""
+Estado de múltiplos Senders no mesmo dashboard
+Guarda o último payload de cada host e descarta os que pararam de enviar
+"""
+import time
+from typing import Any, Dict, List, Optional, Tuple
+
+# Estado da conexão pelo tempo desde o último pacote
+FRESH = "fresh"
+STALE = "stale"              # Mais da metade do timeout sem dados: painéis esmaecidos
+DISCONNECTED = "disconnected"
+
+
+def connection_state(elapsed: float, timeout: float) -> str:
+    """
+    Classifica a conexão pelo tempo desde o último pacote
+    
+    Args:
+        elapsed: Segundos desde o último pacote
+        timeout: connection_timeout_secs do Receiver
+    
+    Returns:
+        FRESH até metade do timeout, STALE depois, DISCONNECTED a partir do timeout
+    """
+    if elapsed >= timeout:
+        return DISCONNECTED
+    if elapsed > timeout / 2:
+        return STALE
+    return FRESH
+
+
+def elapsed_since(last_time: float, now: float) -> float:
+    """Segundos desde o último pacote (infinito se nunca chegou ou após a despedida)"""
+    return now - last_time if last_time else float("inf")
+
+
+def goodbye_resets_connection(addr: str, selected: Optional[str]) -> bool:
+    """
+    Decide se a despedida de um Sender derruba a conexão exibida
+    
+    Args:
+        addr: Host que se despediu
+        selected: Host exibido no multi-sender (None com um Sender só)
+    
+    Returns:
+        True com um Sender só ou se o host era o exibido
+    """
+    return selected is None or addr == selected
+
+
+class ConnectionWatchdog:
+    """
+    Detecta a queda de um Sender que estava conectado (conectado -> desconectado)
+    
+    Só avisa depois da primeira conexão; após um aviso, novas quedas dentro
+    de debounce_secs (link oscilando) não avisam de novo.
+    
+    Exemplo:
+        watchdog = ConnectionWatchdog(debounce_secs=60)
+        if watchdog.update(state != DISCONNECTED, time.time()):
+            tocar_aviso()
+    """
+    
+    def __init__(self, debounce_secs: float = 60.0):
+        self.debounce_secs = debounce_secs
+        self.connected = False
+        self._last_alert: Optional[float] = None
+    
+    def update(self, connected: bool, now: float) -> bool:
+        """
+        Registra o estado atual da conexão
+        
+        Returns:
+            True só no ciclo em que a conexão caiu, fora do debounce
+        """
+        was_connected = self.connected
+        self.connected = connected
+        if connected or not was_connected:
+            return False
+        if self._last_alert is not None and now - self._last_alert < self.debounce_secs:
+            return False
+        self._last_alert = now
+        return True
+
+
+class SourceTable:
+    """
+    Último payload recebido de cada Sender, com timeout por host
+    
+    Exemplo:
+        sources = SourceTable(timeout=5)
+        sources.update("192.168.1.10", payload)
+        for addr in sources.active():
+            payload, received_at = sources.get(addr)
+    """
+    
+    def __init__(self, timeout: float = 5.0):
+        """
+        Inicializa a tabela
+        
+        Args:
+            timeout: Segundos sem pacotes até o host ser considerado desconectado
+        """
+        self.timeout = timeout
+        self._entries: Dict[str, Tuple[Dict[str, Any], float]] = {}
+    
+    def update(self, addr: str, payload: Dict[str, Any], now: Optional[float] = None) -> bool:
+        """
+        Registra o payload mais recente de um host
+        
+        Returns:
+            True se o host é novo na tabela
+        """
+        now = time.time() if now is None else now
+        is_new = addr not in self._entries
+        self._entries[addr] = (payload, now)
+        return is_new
+    
+    def get(self, addr: str) -> Optional[Tuple[Dict[str, Any], float]]:
+        """Retorna (payload, instante de recebimento) do host, ou None"""
+        return self._entries.get(addr)
+    
+    def active(self, now: Optional[float] = None) -> List[str]:
+        """Hosts que enviaram dentro do timeout, em ordem estável (por endereço)"""
+        now = time.time() if now is None else now
+        return sorted(
+            addr for addr, (_, received_at) in self._entries.items()
+            if now - received_at < self.timeout
+        )
+    
+    def items(self) -> List[Tuple[str, Tuple[Dict[str, Any], float]]]:
+        """Cópia das entradas (endereço, (payload, instante)), incluindo as expiradas"""
+        return sorted(self._entries.items())
+    
+    def mark_offline(self, addr: str) -> bool:
+        """
+        Remove o host na hora (frame de despedida do Sender)
+        
+        Returns:
+            True se o host estava na tabela
+        """
+        return self._entries.pop(addr, None) is not None
+    
+    def evict(self, now: Optional[float] = None, older_than: Optional[float] = None) -> List[str]:
+        """
+        Remove os hosts que passaram do timeout
+        
+        Args:
+            now: Instante atual
+            older_than: Segundos sem pacote até remover (None = o timeout)
+        
+        Returns:
+            Endereços removidos
+        """
+        now = time.time() if now is None else now
+        limit = self.timeout if older_than is None else older_than
+        expired = [
+            addr for addr, (_, received_at) in self._entries.items()
+            if now - received_at >= limit
+        ]
+        for addr in expired:
+            del self._entries[addr]
+        return expired
+    
+    def __len__(self) -> int:
+        return len(self._entries)
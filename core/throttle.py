@@ -0,0 +1,85 @@
+"""
+Detecção de throttling (clock reduzido por temperatura ou energia) de CPU e GPU
+GPU: motivos reportados pelo driver NVIDIA (NVML); CPU: heurística sobre os sensores do LHM
+"""
+from enum import IntFlag
+from typing import Optional
+
+
+class NvmlThrottleReason(IntFlag):
+    """Bits de nvmlDeviceGetCurrentClocksThrottleReasons (nvml.h)"""
+    GPU_IDLE = 0x01
+    APPLICATIONS_CLOCKS_SETTING = 0x02
+    SW_POWER_CAP = 0x04
+    HW_SLOWDOWN = 0x08
+    SYNC_BOOST = 0x10
+    SW_THERMAL_SLOWDOWN = 0x20
+    HW_THERMAL_SLOWDOWN = 0x40
+    HW_POWER_BRAKE_SLOWDOWN = 0x80
+    DISPLAY_CLOCK_SETTING = 0x100
+
+
+# Motivos em que a GPU está de fato sendo segurada. Ociosidade, clocks
+# fixados pelo usuário, sync boost e display não são throttling.
+THROTTLING_REASONS = (
+    NvmlThrottleReason.SW_POWER_CAP
+    | NvmlThrottleReason.HW_SLOWDOWN
+    | NvmlThrottleReason.SW_THERMAL_SLOWDOWN
+    | NvmlThrottleReason.HW_THERMAL_SLOWDOWN
+    | NvmlThrottleReason.HW_POWER_BRAKE_SLOWDOWN
+)
+
+# CPU sem sensor de distância ao TjMax (AMD): Tctl em que os Ryzen começam a segurar
+CPU_THROTTLE_TEMP = 95.0
+# Intel: distância ao TjMax (°C) a partir da qual o PROCHOT já atua
+TJMAX_MARGIN = 2.0
+# Clock abaixo dessa fração do pico já visto conta como reduzido
+CLOCK_DROP = 0.9
+
+
+def gpu_throttle_reasons(mask: Optional[int]) -> list[str]:
+    """
+    Motivos de throttling ativos na máscara do NVML
+    
+    Args:
+        mask: Retorno de nvmlDeviceGetCurrentClocksThrottleReasons (None = sem NVML)
+    
+    Returns:
+        Nomes dos bits de THROTTLING_REASONS ativos, em ordem crescente de bit
+    """
+    if not mask:
+        return []
+    return [reason.name for reason in NvmlThrottleReason if reason & THROTTLING_REASONS and mask & reason]
+
+
+def gpu_throttling(mask: Optional[int]) -> bool:
+    """True se algum motivo de THROTTLING_REASONS estiver ativo"""
+    return bool(mask) and bool(mask & THROTTLING_REASONS)
+
+
+def cpu_throttling(
+    temp: Optional[float],
+    clock: Optional[float],
+    peak_clock: float,
+    tjmax_distance: Optional[float] = None
+) -> bool:
+    """
+    Heurística de throttling térmico da CPU
+    
+    Com o sensor "Distance to TjMax" (Intel) decide só por ele. Sem o sensor,
+    exige a CPU quente (CPU_THROTTLE_TEMP) e o clock abaixo do pico já visto.
+    
+    Args:
+        temp: Temperatura da CPU em °C (None/0 = sem sensor)
+        clock: Clock atual em MHz
+        peak_clock: Maior clock visto desde o início do Sender
+        tjmax_distance: Menor distância ao TjMax entre os núcleos (None = sem sensor)
+    
+    Returns:
+        True se a CPU parece estar em throttling
+    """
+    if tjmax_distance is not None:
+        return tjmax_distance <= TJMAX_MARGIN
+    if not temp or temp < CPU_THROTTLE_TEMP or not clock or not peak_clock:
+        return False
+    return clock < peak_clock * CLOCK_DROP
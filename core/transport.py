@@ -0,0 +1,155 @@
+"""
+Envio de frames UDP pelo Sender
+Retry com backoff para erros transitórios, detecção de falhas consecutivas
+e resumo periódico dos envios para o log
+"""
+import errno
+import time
+from typing import Callable, Optional, Tuple
+
+# Erros de envio que costumam passar sozinhos (interface caindo/voltando, buffer cheio).
+# Inclui os códigos WinSock equivalentes (WSAEWOULDBLOCK, WSAENETDOWN, ...).
+TRANSIENT_ERRNOS = {
+    errno.ENETUNREACH, errno.EHOSTUNREACH, errno.ENETDOWN, errno.ENOBUFS, errno.EAGAIN,
+    10035, 10050, 10051, 10055, 10065,
+}
+
+Sink = Callable[[bytes, Tuple[str, int]], int]
+
+
+def is_transient_error(exc: OSError) -> bool:
+    """Verifica se o erro de envio vale uma nova tentativa"""
+    return exc.errno in TRANSIENT_ERRNOS or getattr(exc, "winerror", None) in TRANSIENT_ERRNOS
+
+
+def send_with_retry(
+    send: Sink,
+    frame: bytes,
+    address: Tuple[str, int],
+    retries: int = 2,
+    backoff: float = 0.05,
+    sleep: Callable[[float], None] = time.sleep
+) -> int:
+    """
+    Envia um frame, tentando de novo em erros transitórios
+    
+    Args:
+        send: Função de envio (ex: sock.sendto)
+        frame: Bytes do frame
+        address: (ip, porta) de destino
+        retries: Tentativas extras após a primeira
+        backoff: Espera inicial em segundos (dobra a cada tentativa)
+        sleep: Função de espera (injetável nos testes)
+    
+    Returns:
+        Bytes enviados
+    
+    Raises:
+        OSError: Erro não transitório, ou transitório após esgotar as tentativas
+    """
+    for attempt in range(retries + 1):
+        try:
+            return send(frame, address)
+        except OSError as e:
+            if not is_transient_error(e) or attempt == retries:
+                raise
+            sleep(backoff * (2 ** attempt))
+    raise AssertionError("inalcançável")
+
+
+class SendHealth:
+    """
+    Acompanha falhas consecutivas de envio
+    
+    Exemplo:
+        health = SendHealth(rebuild_after=5)
+        if health.failure():
+            recriar_socket()
+    """
+    
+    def __init__(self, rebuild_after: int = 5):
+        """
+        Args:
+            rebuild_after: Falhas consecutivas até recomendar recriar o socket
+        """
+        self.rebuild_after = rebuild_after
+        self.consecutive_failures = 0
+        self.total_failures = 0
+    
+    def success(self) -> Optional[int]:
+        """
+        Registra um envio bem-sucedido
+        
+        Returns:
+            Quantas falhas seguidas houve antes da recuperação (None se não havia falhas)
+        """
+        failures = self.consecutive_failures
+        self.consecutive_failures = 0
+        return failures or None
+    
+    def failure(self) -> bool:
+        """
+        Registra um envio que falhou (já depois dos retries)
+        
+        Returns:
+            True quando atingir rebuild_after falhas seguidas (e a cada múltiplo)
+        """
+        self.consecutive_failures += 1
+        self.total_failures += 1
+        return self.consecutive_failures % self.rebuild_after == 0
+
+
+class SendSummary:
+    """
+    Junta os envios de um período numa linha de log só (log_summary_secs)
+    
+    Exemplo:
+        summary = SendSummary(period_secs=60)
+        line = summary.add(182, cpu=45.0, gpu=80.0)
+        if line:
+            print(f"[Send] {line}")
+    """
+    
+    def __init__(self, period_secs: float, clock: Callable[[], float] = time.monotonic):
+        self.period_secs = period_secs
+        self._clock = clock
+        self._reset(None)
+    
+    def _reset(self, start: Optional[float]) -> None:
+        self.start = start
+        self.frames = 0
+        self.total_bytes = 0
+        self._cpu_sum = 0.0
+        self._gpu_sum = 0.0
+    
+    def add(self, size: int, cpu: Optional[float] = None, gpu: Optional[float] = None) -> Optional[str]:
+        """
+        Registra um frame enviado
+        
+        Args:
+            size: Bytes enviados
+            cpu, gpu: Uso de CPU e carga da GPU no payload (None conta como 0)
+        
+        Returns:
+            O resumo quando o período fecha (e recomeça a contagem), senão None
+        """
+        now = self._clock()
+        if self.start is None:
+            self.start = now
+        self.frames += 1
+        self.total_bytes += size
+        self._cpu_sum += cpu or 0
+        self._gpu_sum += gpu or 0
+        if now - self.start < self.period_secs:
+            return None
+        line = self.summary(now - self.start)
+        self._reset(now)
+        return line
+    
+    def summary(self, elapsed: float) -> str:
+        """Texto do período, ex: 120 frames em 60s, média 182 bytes, CPU 45% GPU 80%"""
+        frames = max(1, self.frames)
+        return (
+            f"{self.frames} frames em {elapsed:.0f}s, média {self.total_bytes / frames:.0f} bytes, "
+            f"CPU {self._cpu_sum / frames:.0f}% GPU {self._gpu_sum / frames:.0f}%"
+        )
@@ -1,13 +1,16 @@
 """
 Validadores reutilizáveis para o Sistema de Telemetria
 """
+import ipaddress
 import re
-from typing import Tuple, Optional
+from typing import Any, Dict, List, Tuple, Optional
+
+from .network import is_multicast_address
 
 
 def validate_ip(ip: str) -> Tuple[bool, Optional[str]]:
     """
-    Valida um endereço IPv4
+    Valida um endereço IPv4 ou IPv6
     
     Args:
         ip: String com endereço IP
@@ -18,6 +21,13 @@ def validate_ip(ip: str) -> Tuple[bool, Optional[str]]:
     if not ip:
         return False, "IP não pode ser vazio"
     
+    if ":" in ip:
+        try:
+            ipaddress.IPv6Address(ip.strip("[]").split("%", 1)[0])
+            return True, None
+        except ValueError:
+            return False, f"IPv6 inválido: {ip}"
+    
     # Permite broadcast
     if ip == "255.255.255.255":
         return True, None
@@ -42,6 +52,66 @@ def validate_ip(ip: str) -> Tuple[bool, Optional[str]]:
     return True, None
 
 
+def is_broadcast_ip(ip: str) -> bool:
+    """
+    Verifica se o IP é um endereço de broadcast
+    
+    Aceita o broadcast limitado (255.255.255.255) e broadcasts
+    direcionados de sub-rede (último octeto 255, ex: 192.168.1.255).
+    
+    Args:
+        ip: String com endereço IP já validado
+    
+    Returns:
+        True se for broadcast (IPv6 não tem broadcast)
+    """
+    if ":" in ip:
+        return False
+    return ip == "255.255.255.255" or ip.endswith(".255")
+
+
+def validate_sender_network(modo: str, dest_ip: str, bind_ip: str) -> Tuple[bool, Optional[str]]:
+    """
+    Valida a configuração de rede do Sender antes de abrir o socket
+    
+    Args:
+        modo: "broadcast", "unicast" ou "multicast"
+        dest_ip: IP de destino (o grupo, em multicast)
+        bind_ip: IP local para bind (vazio = interface padrão)
+    
+    Returns:
+        Tupla (válido, mensagem) - mensagem pode ser um aviso mesmo se válido
+    """
+    if modo not in ("broadcast", "unicast", "multicast"):
+        return False, f"modo deve ser 'broadcast', 'unicast' ou 'multicast': {modo}"
+    
+    if bind_ip:
+        valid, error = validate_ip(bind_ip)
+        if not valid:
+            return False, f"bind_ip inválido ({bind_ip}): {error}"
+    
+    valid, error = validate_ip(dest_ip)
+    if not valid:
+        return False, f"dest_ip inválido ({dest_ip}): {error}"
+    
+    if bind_ip and (":" in bind_ip) != (":" in dest_ip):
+        return False, f"bind_ip ({bind_ip}) e dest_ip ({dest_ip}) são de famílias diferentes (IPv4/IPv6)"
+    
+    if modo == "multicast" and not is_multicast_address(dest_ip):
+        return False, f"multicast_group inválido ({dest_ip}): use 224.0.0.0-239.255.255.255 (ex: 239.255.0.1) ou ff00::/8"
+    
+    if modo == "broadcast" and ":" in dest_ip:
+        return False, f"IPv6 não tem broadcast: use modo 'unicast' com dest_ip {dest_ip}"
+    
+    if modo == "broadcast" and not is_broadcast_ip(dest_ip):
+        return True, (
+            f"modo 'broadcast' com dest_ip unicast ({dest_ip}): "
+            "os pacotes só chegarão a esse IP"
+        )
+    
+    return True, None
+
+
 def validate_port(port: int | str) -> Tuple[bool, Optional[str]]:
     """
     Valida uma porta de rede
@@ -91,6 +161,98 @@ def validate_interval(interval: float | str) -> Tuple[bool, Optional[str]]:
     return True, None
 
 
+# Limites físicos dos thresholds de alerta (acima disso o alerta nunca dispara
+# ou foi digitado na unidade errada)
+MAX_TEMP_THRESHOLD = 120.0  # °C; 120 já passa do TjMax de qualquer CPU/GPU
+MAX_PERCENT_THRESHOLD = 100.0
+MAX_VOLTAGE_THRESHOLD = 2.0  # V; nenhum Vcore atual chega perto
+
+
+def _threshold_unit(name: str) -> Optional[str]:
+    """Unidade do threshold pelo nome (cpu_temp -> °C, ram -> %), None se não tem limite físico"""
+    if "temp" in name:
+        return "°C"
+    if "voltage" in name:
+        return "V"
+    if name.endswith("uso") or name in ("ram", "vram"):
+        return "%"
+    return None
+
+
+def _check_threshold(name: str, value: Any) -> Optional[str]:
+    """Mensagem se o valor é fisicamente implausível para a unidade do threshold"""
+    unit = _threshold_unit(name)
+    if unit is None or not value:  # 0 desativa a faixa
+        return None
+    if value < 0:
+        return f"{name}: {value}{unit} é negativo"
+    if unit == "°C" and value > MAX_TEMP_THRESHOLD:
+        return f"{name}: {value}°C passa de {MAX_TEMP_THRESHOLD:g}°C (digitado em °F?)"
+    if unit == "%" and value > MAX_PERCENT_THRESHOLD:
+        return f"{name}: {value}% passa de 100% (o alerta nunca dispararia)"
+    if unit == "V" and value > MAX_VOLTAGE_THRESHOLD:
+        return f"{name}: {value}V passa de {MAX_VOLTAGE_THRESHOLD:g}V (digitado em mV?)"
+    return None
+
+
+def _is_number(value: Any) -> bool:
+    return isinstance(value, (int, float)) and not isinstance(value, bool)
+
+
+def _threshold_names(thresholds: Dict[str, Any]) -> List[str]:
+    """Nomes com par de thresholds (cpu_temp_warning -> cpu_temp), na ordem do dict"""
+    names = []
+    for key in thresholds:
+        for suffix in ("_warning", "_critical"):
+            if key.endswith(suffix) and key[:-len(suffix)] not in names:
+                names.append(key[:-len(suffix)])
+    return names
+
+
+def check_threshold_pair(name: str, warning: Any, critical: Any) -> List[str]:
+    """
+    Confere um par aviso/crítico
+    
+    Returns:
+        Mensagens: valor fora do limite físico da unidade ou crítico abaixo do aviso
+    """
+    errors = []
+    for value in (warning, critical):
+        message = _check_threshold(name, value) if _is_number(value) else None
+        if message:
+            errors.append(message)
+    if _is_number(warning) and _is_number(critical) and warning and critical and critical < warning:
+        errors.append(f"{name}: crítico ({critical}) abaixo do aviso ({warning}); o aviso nunca apareceria")
+    return errors
+
+
+def validate_alert_thresholds(alertas: Dict[str, Any], disk_overrides: Optional[Dict[str, Any]] = None) -> List[str]:
+    """
+    Thresholds de alerta implausíveis: crítico abaixo do aviso, temperatura
+    acima de 120°C, percentual acima de 100%, voltagem acima de 2V ou negativos
+    
+    Args:
+        alertas: Seção "alertas" do receiver_config.json (0 desativa uma faixa)
+        disk_overrides: Thresholds por disco (temp_warning, uso_critical...)
+    
+    Returns:
+        Mensagens, uma por problema (vazia se tudo certo)
+    """
+    errors = []
+    for name in _threshold_names(alertas):
+        errors += check_threshold_pair(name, alertas.get(f"{name}_warning"), alertas.get(f"{name}_critical"))
+    
+    for disk, overrides in (disk_overrides or {}).items():
+        if not isinstance(overrides, dict):
+            continue
+        # Override parcial (só temp_warning) é comparado com o crítico global de disco
+        for name in _threshold_names(overrides):
+            warning = overrides.get(f"{name}_warning", alertas.get(f"storage_{name}_warning"))
+            critical = overrides.get(f"{name}_critical", alertas.get(f"storage_{name}_critical"))
+            errors += [f"disk_overrides.{disk}.{message}" for message in check_threshold_pair(name, warning, critical)]
+    return errors
+
+
 def validate_webhook_url(url: str, webhook_type: str = "generic") -> Tuple[bool, Optional[str]]:
     """
     Valida URL de webhook
@@ -141,7 +303,7 @@ def parse_ip_port(address: str) -> Tuple[Optional[str], Optional[int]]:
     Parseia uma string no formato IP:PORTA
     
     Args:
-        address: String no formato "192.168.1.1:5005"
+        address: String no formato "192.168.1.1:5005" ou "[fe80::1]:5005"
     
     Returns:
         Tupla (ip, porta) ou (None, None) se inválido
@@ -155,6 +317,12 @@ def parse_ip_port(address: str) -> Tuple[Optional[str], Optional[int]]:
     
     ip, port_str = parts
     
+    # IPv6 precisa de colchetes: em "fe80::1:5005" a porta seria ambígua
+    if ":" in ip:
+        if not (ip.startswith("[") and ip.endswith("]")):
+            return None, None
+        ip = ip[1:-1]
+    
     ip_valid, _ = validate_ip(ip)
     if not ip_valid:
         return None, None
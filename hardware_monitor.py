@@ -9,7 +9,11 @@ from __future__ import annotations
 import os
 import sys
 import math
-from typing import Any, Optional
+from typing import Any, Iterable, Optional
+
+from core.payload import fans_from_sensors
+from core.sanity import plausible_temp, plausible_rpm, plausible_voltage, plausible_percent
+from core.scheduling import SlowRefresh
 
 # Tenta importar pythonnet (clr)
 try:
@@ -22,6 +26,363 @@ except ImportError:
 # SensorType: Voltage, Clock, Temperature, Load, Frequency, Fan, Flow, Control, Level, Factor, Power, Data, SmallData, Throughput
 # HardwareType: Motherboard, SuperIO, Cpu, Memory, GpuNvidia, GpuAmd, GpuIntel, Storage, Network, Cooler, EmbeddedController, Psu
 
+# Campos de disco que mudam a cada ciclo (não valem do cache de atualização lenta)
+STORAGE_FAST_FIELDS = ("read_activity", "write_activity", "total_activity", "read_rate", "write_rate")
+
+
+def resolve_gpu_fan(rpm: Optional[float], percent: float) -> tuple[float, str]:
+    """
+    Escolhe a leitura do fan da GPU e sua unidade.
+    
+    Sensores "Fan" reportam RPM; sensores "Control" reportam % do duty cycle.
+    Algumas placas (principalmente NVIDIA) só expõem o percentual.
+    RPM tem prioridade para manter compatibilidade com o payload antigo;
+    0 RPM é fan parado (modo zero-RPM), não falta de sensor (rpm None).
+    
+    Returns:
+        Tupla (valor, unidade) com unidade "RPM" ou "%"
+    """
+    if rpm is not None and plausible_rpm(rpm):
+        return rpm, "RPM"
+    if plausible_percent(percent):
+        return percent, "%"
+    return 0, "RPM"
+
+
+# Prefixos de identificador das GPUs no LHM (varia entre versões da DLL)
+GPU_IDENTIFIER_PREFIXES = {
+    "/gpu-nvidia/": "nvidia",
+    "/gpu-amd/": "amd",
+    "/atigpu/": "amd",
+    "/intelgpu/": "intel",
+    "/gpu-intel-integrated/": "intel",
+}
+
+
+def gpu_source(hw_type: str, identifier: str = "") -> str:
+    """
+    Fabricante da GPU ("nvidia", "amd", "intel") ou "" se não for GPU.
+    
+    Usa o HardwareType (GpuNvidia/GpuAmd/GpuIntel) e, se ele não bastar,
+    o prefixo do identificador (ex: /intelgpu/0).
+    """
+    for suffix, source in (("Nvidia", "nvidia"), ("Amd", "amd"), ("Intel", "intel")):
+        if hw_type == f"Gpu{suffix}":
+            return source
+    for prefix, source in GPU_IDENTIFIER_PREFIXES.items():
+        if str(identifier).lower().startswith(prefix):
+            return source
+    return ""
+
+
+def parse_gpu_sensors(source: str, readings: list[tuple[str, str, float]]) -> dict[str, Any]:
+    """
+    Interpreta os sensores de uma GPU.
+    
+    Args:
+        source: "nvidia", "amd" ou "intel" (ver gpu_source)
+        readings: (tipo, nome, valor) de cada sensor, com valor já saneado
+    
+    Returns:
+        Dict no formato da seção "gpu" do fetch_data, com "source"
+    """
+    gpu: dict[str, Any] = {
+        "temp": 0, "load": 0, "voltage": 0, "clock_core": 0, "clock_mem": 0,
+        "power": 0, "mem_used": 0, "mem_total": 0, "source": source
+    }
+    fan_rpm = None  # Sem sensor "Fan": tenta o duty cycle
+    fan_percent = 0
+    integrated = source == "intel"
+    
+    for s_type, name, val in readings:
+        if s_type == "Temperature":
+            # GPU Core (não Hot Spot ou Memory para principal); iGPU Intel só tem "GPU Package"
+            if ("Core" in name or (integrated and "GPU" in name)) and plausible_temp(val):
+                gpu["temp"] = val
+        elif s_type == "Load":
+            # GPU Core load (não D3D); a iGPU Intel só expõe os motores D3D
+            if "Core" in name and "D3D" not in name and plausible_percent(val):
+                gpu["load"] = val
+            elif integrated and name == "D3D 3D" and plausible_percent(val):
+                gpu["load"] = val
+        elif s_type == "Voltage":
+            if "Core" in name and plausible_voltage(val):
+                gpu["voltage"] = val
+        elif s_type == "Power":
+            # NVIDIA: GPU Package/Board Power, AMD: GPU Core/PPT, iGPU: GPU Power (o maior é o total)
+            if val > 0:
+                gpu["power"] = max(gpu["power"], val)
+        elif s_type == "Clock":
+            if "Core" in name and val > 0:
+                gpu["clock_core"] = val
+            elif "Memory" in name and val > 0:
+                gpu["clock_mem"] = val
+        elif s_type == "Fan":
+            if plausible_rpm(val):
+                fan_rpm = val
+        elif s_type == "Control":
+            # Duty cycle do fan em %
+            if "Fan" in name and plausible_percent(val):
+                fan_percent = val
+        elif s_type == "SmallData":
+            # Memória dedicada usada (em MB); a iGPU usa a RAM compartilhada
+            if "Dedicated" in name and "Used" in name and val > 0:
+                gpu["mem_used"] = val
+            elif integrated and "Shared" in name and "Used" in name and val > 0:
+                gpu["mem_used"] = max(gpu["mem_used"], val)
+            # "GPU Memory Total" (NVIDIA/AMD) ou "D3D Dedicated Memory Total"
+            elif "Total" in name and "Shared" not in name and val > 0:
+                gpu["mem_total"] = max(gpu["mem_total"], val)
+    
+    gpu["fan"], gpu["fan_unit"] = resolve_gpu_fan(fan_rpm, fan_percent)
+    return gpu
+
+
+def select_gpu(gpus: list[dict[str, Any]]) -> Optional[dict[str, Any]]:
+    """Escolhe a GPU exibida: a primeira dedicada; a integrada só se não houver outra."""
+    for gpu in gpus:
+        if gpu.get("source") != "intel":
+            return gpu
+    return gpus[0] if gpus else None
+
+
+def parse_storage_temps(readings: list[tuple[str, float]]) -> tuple[float, float, list[float]]:
+    """
+    Separa as temperaturas de um disco.
+    
+    NVMe expõe a "Composite Temperature" (a que o fabricante usa para
+    throttling) e sensores individuais ("Temperature #1", "#2"...).
+    
+    Args:
+        readings: (nome, valor) dos sensores de temperatura, na ordem do LHM
+    
+    Returns:
+        (principal, máxima, extras): principal é a composite (ou a primeira
+        válida), máxima é a maior de todas e extras são as demais leituras
+    """
+    valid = [(name, val) for name, val in readings if plausible_temp(val)]
+    if not valid:
+        return 0, 0, []
+    
+    primary_index = next((i for i, (name, _) in enumerate(valid) if "Composite" in name), 0)
+    primary = valid[primary_index][1]
+    extras = [val for i, (_, val) in enumerate(valid) if i != primary_index]
+    return primary, max(val for _, val in valid), extras
+
+
+def parse_drive_letters(rows: Any) -> dict[int, list[str]]:
+    """
+    Converte a saída JSON de Get-Partition (DiskNumber, DriveLetter) em índice -> letras.
+    
+    Partições sem letra (recuperação, EFI) são ignoradas; o PowerShell
+    devolve a letra como texto ou como código de caractere, conforme a versão.
+    """
+    if isinstance(rows, dict):
+        rows = [rows]
+    letters: dict[int, list[str]] = {}
+    for row in rows or []:
+        try:
+            index = int(row.get("DiskNumber"))
+        except (TypeError, ValueError, AttributeError):
+            continue
+        letter = row.get("DriveLetter")
+        if isinstance(letter, int):
+            letter = chr(letter) if letter else ""
+        letter = str(letter or "").strip().rstrip(":").upper()
+        if len(letter) == 1 and letter.isalpha():
+            letters.setdefault(index, []).append(letter)
+    return letters
+
+
+def parse_drive_serials(rows: Any) -> dict[int, str]:
+    """
+    Converte a saída JSON de Win32_DiskDrive (Index, SerialNumber) em índice -> serial.
+    
+    O PowerShell devolve um dict quando há um só disco; seriais vêm com
+    espaços de preenchimento que são removidos.
+    """
+    if isinstance(rows, dict):
+        rows = [rows]
+    serials: dict[int, str] = {}
+    for row in rows or []:
+        try:
+            index = int(row.get("Index"))
+        except (TypeError, ValueError, AttributeError):
+            continue
+        serial = str(row.get("SerialNumber") or "").strip()
+        if serial:
+            serials[index] = serial
+    return serials
+
+
+def make_drive_id(serial: str, drive_index: int) -> str:
+    """Chave estável do disco: número de série, senão o índice físico (PhysicalDriveN)."""
+    if serial:
+        return serial
+    if drive_index >= 0:
+        return f"PhysicalDrive{drive_index}"
+    return ""
+
+
+# STORAGE_BUS_TYPE (winioctl.h), o BusType de Get-PhysicalDisk (MSFT_PhysicalDisk)
+STORAGE_BUS_TYPES = {
+    1: "scsi", 2: "atapi", 3: "ata", 4: "1394", 5: "ssa", 6: "fibre", 7: "usb", 8: "raid",
+    9: "iscsi", 10: "sas", 11: "sata", 12: "sd", 13: "mmc", 14: "virtual",
+    15: "file_backed_virtual", 16: "spaces", 17: "nvme", 18: "scm", 19: "ufs",
+}
+UNKNOWN_BUS = "unknown"
+
+# Temperatura pelo contador de confiabilidade (discos USB sem sensor no LHM):
+# consulta ao PowerShell no ciclo de envio, então no máximo uma por minuto
+RELIABILITY_REFRESH_SECS = 60.0
+
+# Letras das partições e seriais: disco que não aparece na consulta (sem
+# partição com letra, USB/virtual sem serial) ou consulta que falhou só pede
+# o PowerShell de novo a cada intervalo
+DRIVE_QUERY_REFRESH_SECS = 60.0
+
+
+def parse_bus_type(value: Any) -> str:
+    """
+    Barramento do disco ("usb", "sata", "nvme"...) a partir do BusType.
+    
+    O PowerShell 5 devolve o número do enum; versões novas podem devolver
+    o nome ("USB", "NVMe"). Desconhecido ou 0 vira UNKNOWN_BUS.
+    """
+    if isinstance(value, bool):
+        return UNKNOWN_BUS
+    if isinstance(value, str):
+        text = value.strip().lower()
+        if text.isdigit():
+            value = int(text)
+        else:
+            return text if text in STORAGE_BUS_TYPES.values() else UNKNOWN_BUS
+    if isinstance(value, (int, float)):
+        return STORAGE_BUS_TYPES.get(int(value), UNKNOWN_BUS)
+    return UNKNOWN_BUS
+
+
+def parse_physical_disks(rows: Any) -> dict[int, dict[str, Any]]:
+    """
+    Converte a saída JSON de Get-PhysicalDisk + Get-StorageReliabilityCounter
+    (DeviceId, BusType, Temperature) em índice -> {"bus_type", "temp"}.
+    
+    temp é 0 quando o disco não informa (comum em pontes USB-SATA baratas)
+    ou a leitura não é plausível.
+    """
+    if isinstance(rows, dict):
+        rows = [rows]
+    disks: dict[int, dict[str, Any]] = {}
+    for row in rows or []:
+        try:
+            index = int(row.get("DeviceId"))
+        except (TypeError, ValueError, AttributeError):
+            continue
+        temp = row.get("Temperature")
+        if isinstance(temp, bool) or not isinstance(temp, (int, float)) or not plausible_temp(temp):
+            temp = 0
+        disks[index] = {"bus_type": parse_bus_type(row.get("BusType")), "temp": float(temp)}
+    return disks
+
+
+def apply_physical_disks(disks: list[dict[str, Any]], physical: dict[int, dict[str, Any]]) -> list[int]:
+    """
+    Completa os discos do LHM com o barramento e, sem sensor no LHM, com a
+    temperatura do contador de confiabilidade do Windows (discos USB).
+    
+    Returns:
+        Índices dos discos cuja temperatura veio do contador
+    """
+    filled = []
+    for disk in disks:
+        info = physical.get(disk.get("drive_index", -1))
+        if not info:
+            continue
+        disk["bus_type"] = info["bus_type"]
+        if not disk.get("temp") and info["temp"]:
+            disk["temp"] = disk["temp_max"] = info["temp"]
+            filled.append(disk["drive_index"])
+    return filled
+
+
+# Campos de disco combinados pelo maior valor ao juntar entradas do mesmo disco
+# (o nó que não tem o sensor reporta 0)
+STORAGE_MAX_FIELDS = (
+    "temp", "temp_max", "used_space", "read_activity", "write_activity", "total_activity",
+    "read_rate", "write_rate", "data_read_gb", "data_written_gb",
+)
+
+
+def merge_storage_entries(disks: list[dict[str, Any]]) -> list[dict[str, Any]]:
+    """
+    Junta as entradas que são o mesmo disco físico (mesmo drive_id)
+    
+    O LHM pode expor um disco em mais de um nó (ex: NVMe e o driver genérico),
+    cada um com parte dos sensores; sem juntar, o painel mostra o disco
+    duas vezes. Discos sem drive_id (índice físico desconhecido) ficam separados.
+    
+    Returns:
+        Uma entrada por disco, na ordem em que apareceram
+    """
+    merged: list[dict[str, Any]] = []
+    by_id: dict[str, dict[str, Any]] = {}
+    for disk in disks:
+        drive_id = disk.get("drive_id", "")
+        first = by_id.get(drive_id) if drive_id else None
+        if first is None:
+            entry = dict(disk)
+            merged.append(entry)
+            if drive_id:
+                by_id[drive_id] = entry
+            continue
+        for field in STORAGE_MAX_FIELDS:
+            if field in disk:
+                first[field] = max(first.get(field) or 0, disk[field] or 0)
+        # Saúde padrão é 100 sem sensor: o menor valor é o lido de verdade
+        if "health" in disk:
+            first["health"] = min(first.get("health", 100), disk["health"])
+        if len(disk.get("extra_temps") or []) > len(first.get("extra_temps") or []):
+            first["extra_temps"] = disk["extra_temps"]
+    return merged
+
+
+def format_sensor_table(sensors: list[dict[str, Any]]) -> str:
+    """
+    Monta a tabela do --dump-sensors (um sensor por linha).
+    
+    Args:
+        sensors: Dicts com identifier, sensor_type, name, parent e value
+    
+    Returns:
+        Texto com cabeçalho e colunas alinhadas; valores ausentes como "-"
+    """
+    headers = ("Identifier", "Type", "Name", "Parent", "Value")
+    rows = []
+    for sensor in sensors:
+        value = sensor.get("value")
+        if value is None or (isinstance(value, float) and math.isnan(value)):
+            value_text = "-"
+        else:
+            value_text = f"{value:.3f}"
+        rows.append((
+            str(sensor.get("identifier", "")),
+            str(sensor.get("sensor_type", "")),
+            str(sensor.get("name", "")),
+            str(sensor.get("parent", "")),
+            value_text
+        ))
+    
+    widths = [max([len(h)] + [len(row[i]) for row in rows]) for i, h in enumerate(headers)]
+    
+    def line(cells):
+        # Valor alinhado à direita, o resto à esquerda
+        text = "  ".join(cell.ljust(w) for cell, w in zip(cells[:-1], widths[:-1]))
+        return f"{text}  {cells[-1].rjust(widths[-1])}"
+    
+    out = [line(headers), line(tuple("-" * w for w in widths))]
+    out.extend(line(row) for row in rows)
+    return "\n".join(out)
+
 
 class HardwareMonitor:
     """
@@ -34,10 +395,25 @@ class HardwareMonitor:
             print(f"CPU Temp: {data['cpu']['temp']}°C")
     """
     
-    def __init__(self) -> None:
+    def __init__(self, disabled: Iterable[str] = ()) -> None:
+        """
+        Args:
+            disabled: Partes desligadas no config (disabled_sources): "storage"
+                não abre os discos no LHM; "wmi" não roda as consultas ao PowerShell
+        """
         self.computer = None
         self.enabled = False
+        self.storage_enabled = "storage" not in disabled
+        self.wmi_enabled = "wmi" not in disabled
         self.Hardware = None  # Namespace reference
+        self._storage_cache: list[dict[str, Any]] = []  # Última leitura completa dos discos
+        self._drive_serials: dict[int, str] = {}  # Índice físico -> número de série
+        self._drive_serials_refresh = SlowRefresh(DRIVE_QUERY_REFRESH_SECS)  # Última consulta dos seriais (feita ou não)
+        self._drive_letters: dict[int, list[str]] = {}  # Índice físico -> letras das partições
+        self._drive_letters_refresh = SlowRefresh(DRIVE_QUERY_REFRESH_SECS)  # Última consulta das letras (feita ou não)
+        self._physical_disks: Optional[dict[int, dict[str, Any]]] = None  # Índice físico -> barramento/temperatura
+        self._no_fallback_temp: set[int] = set()  # Discos sem temperatura nem pelo contador (não consulta de novo)
+        self._reliability_refresh = SlowRefresh(RELIABILITY_REFRESH_SECS)  # Consulta ao contador de confiabilidade
         
         # Caminho absoluto baseado na localização deste script
         base_path = os.path.dirname(os.path.abspath(__file__))
@@ -62,14 +438,19 @@ class HardwareMonitor:
             self.computer.IsGpuEnabled = True
             self.computer.IsMemoryEnabled = True
             self.computer.IsMotherboardEnabled = True
-            self.computer.IsStorageEnabled = True
+            self.computer.IsStorageEnabled = self.storage_enabled
             self.computer.IsNetworkEnabled = True
             self.computer.IsControllerEnabled = True
+            try:
+                # Disponível apenas em versões recentes da DLL
+                self.computer.IsBatteryEnabled = True
+            except Exception:
+                pass
             
             self.computer.Open()
             self.enabled = True
             print("[HardwareMonitor] Inicializado com sucesso!")
-            
+
         except Exception as e:
             print(f"[HardwareMonitor] Erro ao inicializar (Rode como Admin!): {e}")
             self.computer = None
@@ -82,6 +463,26 @@ class HardwareMonitor:
         """Retorna o nome do tipo de hardware como string."""
         return str(hardware.HardwareType).split('.')[-1]
     
+    def _collect_raw(self, hardware: Any, out: list[dict[str, Any]]) -> None:
+        """Adiciona os sensores do hardware (e sub-hardwares) sem interpretação."""
+        for hw in [hardware] + list(hardware.SubHardware):
+            for sensor in hw.Sensors:
+                value = sensor.Value
+                out.append({
+                    "identifier": str(sensor.Identifier),
+                    "sensor_type": self._get_sensor_type_name(sensor),
+                    "name": sensor.Name,
+                    "parent": hw.Name,
+                    "value": None if value is None else float(value)
+                })
+    
+    def _get_drive_index(self, hardware: Any) -> int:
+        """Retorna o índice físico do disco (ex: /nvme/1 -> 1) ou -1."""
+        try:
+            return int(str(hardware.Identifier).rstrip('/').split('/')[-1])
+        except (ValueError, AttributeError):
+            return -1
+    
     def _safe_value(self, val: Any) -> float:
         """Retorna 0 se valor for None, NaN ou inválido."""
         if val is None:
@@ -93,9 +494,20 @@ class HardwareMonitor:
         except:
             return 0
 
-    def fetch_data(self) -> dict[str, Any]:
+    def fetch_data(
+        self,
+        refresh_storage: bool = True,
+        raw_sensors: Optional[list[dict[str, Any]]] = None
+    ) -> dict[str, Any]:
         """
         Retorna dicionário completo com todos os sensores disponíveis.
+        
+        Com refresh_storage=False os discos não são atualizados (leitura SMART
+        é lenta): devolve a última leitura, sem as taxas/atividade, que o
+        sender preenche a cada ciclo pelos contadores do psutil.
+        
+        Se raw_sensors for uma lista, recebe também cada sensor bruto do LHM
+        (identifier, sensor_type, name, parent, value) para o --dump-sensors.
         """
         data = {
             "cpu": {
@@ -103,7 +515,8 @@ class HardwareMonitor:
                 "voltage": 0,
                 "load": 0,
                 "power": 0,
-                "clock": 0
+                "clock": 0,
+                "tjmax_distance": None  # Intel: menor distância ao TjMax (None = sem sensor)
             },
             "gpu": {
                 "temp": 0,
@@ -111,8 +524,12 @@ class HardwareMonitor:
                 "voltage": 0,
                 "clock_core": 0,
                 "clock_mem": 0,
+                "power": 0,  # Consumo em W
                 "fan": 0,
-                "mem_used": 0
+                "fan_unit": "RPM",  # "RPM" ou "%" (ver resolve_gpu_fan)
+                "source": "",  # "nvidia", "amd", "intel" (iGPU) ou "" sem GPU
+                "mem_used": 0,
+                "mem_total": 0  # VRAM total em MB (0 = desconhecido)
             },
             "mobo": {
                 "temp": 0
@@ -123,20 +540,29 @@ class HardwareMonitor:
                 "available_gb": 0
             },
             "storage": [],
-            "fans": []
+            "fans": [],
+            "battery": {
+                "power": 0
+            }
         }
 
         if not self.enabled or not self.computer:
             return data
 
         try:
+            gpus: list[dict[str, Any]] = []  # Notebook pode ter iGPU + dedicada
             for hardware in self.computer.Hardware:
-                hardware.Update()
                 hw_type = self._get_hardware_type_name(hardware)
+                if hw_type == "Storage" and not refresh_storage:
+                    continue
+                hardware.Update()
                 
                 # Atualiza sub-hardwares
                 for subhw in hardware.SubHardware:
                     subhw.Update()
+                
+                if raw_sensors is not None:
+                    self._collect_raw(hardware, raw_sensors)
 
                 # === CPU ===
                 if hw_type == "Cpu":
@@ -145,17 +571,22 @@ class HardwareMonitor:
                         name = sensor.Name
                         val = self._safe_value(sensor.Value)
                         
-                        if s_type == "Temperature":
+                        if s_type == "Temperature" and "Distance to TjMax" in name:
+                            # Intel: quanto falta para o TjMax, não é temperatura
+                            if sensor.Value is not None:
+                                distance = data["cpu"]["tjmax_distance"]
+                                data["cpu"]["tjmax_distance"] = val if distance is None else min(distance, val)
+                        elif s_type == "Temperature":
                             # AMD: Tctl/Tdie, Intel: Package/Core
-                            if val > 0:
+                            if plausible_temp(val):
                                 data["cpu"]["temp"] = max(data["cpu"]["temp"], val)
                         elif s_type == "Voltage":
                             # AMD: SVI2 TFN, VID | Intel: VCore
-                            # Filtra voltagens válidas (< 2V tipicamente)
-                            if val > 0 and val < 2:
+                            # Filtra voltagens de núcleo (outros trilhos passam de 2V)
+                            if plausible_voltage(val):
                                 data["cpu"]["voltage"] = max(data["cpu"]["voltage"], val)
                         elif s_type == "Load":
-                            if "Total" in name and val > 0:
+                            if "Total" in name and plausible_percent(val):
                                 data["cpu"]["load"] = val
                         elif s_type == "Power":
                             if val > 0:
@@ -164,53 +595,37 @@ class HardwareMonitor:
                             if val > 0:
                                 data["cpu"]["clock"] = max(data["cpu"]["clock"], val)
 
-                # === GPU (Nvidia, AMD, Intel) ===
-                elif "Gpu" in hw_type:
-                    for sensor in hardware.Sensors:
-                        s_type = self._get_sensor_type_name(sensor)
-                        name = sensor.Name
-                        val = self._safe_value(sensor.Value)
-                        
-                        if s_type == "Temperature":
-                            # GPU Core (não Hot Spot ou Memory para principal)
-                            if "Core" in name and val > 0:
-                                data["gpu"]["temp"] = val
-                        elif s_type == "Load":
-                            # GPU Core load (não D3D)
-                            if "Core" in name and "D3D" not in name and val > 0:
-                                data["gpu"]["load"] = val
-                        elif s_type == "Voltage":
-                            if "Core" in name and val > 0:
-                                data["gpu"]["voltage"] = val
-                        elif s_type == "Clock":
-                            if "Core" in name and val > 0:
-                                data["gpu"]["clock_core"] = val
-                            elif "Memory" in name and val > 0:
-                                data["gpu"]["clock_mem"] = val
-                        elif s_type == "Fan":
-                            if val > 0:
-                                data["gpu"]["fan"] = val
-                        elif s_type == "SmallData":
-                            # Memória dedicada usada (em MB)
-                            if "Dedicated" in name and val > 0:
-                                data["gpu"]["mem_used"] = val
+                # === GPU (Nvidia, AMD, Intel integrada) ===
+                elif gpu_source(hw_type, str(hardware.Identifier)):
+                    readings = [
+                        (self._get_sensor_type_name(sensor), sensor.Name, self._safe_value(sensor.Value))
+                        for sensor in hardware.Sensors
+                    ]
+                    gpus.append(parse_gpu_sensors(gpu_source(hw_type, str(hardware.Identifier)), readings))
 
                 # === Motherboard ===
                 elif hw_type == "Motherboard":
                     # Sensores da motherboard geralmente estão em sub-hardware (SuperIO)
+                    readings = []
                     for subhw in hardware.SubHardware:
                         subhw.Update()
                         for sensor in subhw.Sensors:
                             s_type = self._get_sensor_type_name(sensor)
-                            name = sensor.Name
                             val = self._safe_value(sensor.Value)
+                            readings.append((s_type, sensor.Name, val))
                             
                             if s_type == "Temperature":
-                                if val > 0 and val < 150:  # Temp válida
+                                if plausible_temp(val):
                                     data["mobo"]["temp"] = max(data["mobo"]["temp"], val)
-                            elif s_type == "Fan":
-                                if val > 100:  # RPM válido (ignora leituras erradas)
-                                    data["fans"].append({"name": name, "rpm": val})
+                    data["fans"].extend(fans_from_sensors(readings))
+                
+                # === Controladoras de fan / AIO (IsControllerEnabled) ===
+                elif hw_type == "Cooler":
+                    readings = [
+                        (self._get_sensor_type_name(sensor), sensor.Name, self._safe_value(sensor.Value))
+                        for sensor in hardware.Sensors
+                    ]
+                    data["fans"].extend(fans_from_sensors(readings))
 
                 # === RAM/Memory ===
                 elif hw_type == "Memory":
@@ -228,11 +643,28 @@ class HardwareMonitor:
                             elif "Available" in name and "Virtual" not in name:
                                 data["ram"]["available_gb"] = val
 
+                # === Bateria (notebooks) ===
+                elif hw_type == "Battery":
+                    for sensor in hardware.Sensors:
+                        s_type = self._get_sensor_type_name(sensor)
+                        val = self._safe_value(sensor.Value)
+                        
+                        # "Charge Rate" / "Discharge Rate"
+                        if s_type == "Power" and val != 0:
+                            data["battery"]["power"] = val
+
                 # === Storage (SSDs, HDDs) ===
                 elif hw_type == "Storage":
+                    drive_index = self._get_drive_index(hardware)
                     disk_info = {
                         "name": hardware.Name,
-                        "temp": 0,
+                        "drive_index": drive_index,
+                        # Serial distingue discos do mesmo modelo ("WD Blue" x2)
+                        "drive_id": make_drive_id(self._get_drive_serial(drive_index), drive_index),
+                        "bus_type": UNKNOWN_BUS,  # "usb", "sata", "nvme"... (Get-PhysicalDisk)
+                        "temp": 0,            # Composite (NVMe) ou o único sensor
+                        "temp_max": 0,        # Maior entre todos os sensores do disco
+                        "extra_temps": [],    # Sensores além do principal (NVMe "Temperature #N")
                         "health": 100,        # Default 100% se não tiver sensor
                         "used_space": 0,      # % de espaço usado
                         "read_activity": 0,   # % atividade de leitura
@@ -245,14 +677,15 @@ class HardwareMonitor:
                     }
                     has_health = False
                     has_any_data = False
+                    temps: list[tuple[str, float]] = []
                     
                     for sensor in hardware.Sensors:
                         s_type = self._get_sensor_type_name(sensor)
                         name = sensor.Name
                         val = self._safe_value(sensor.Value)
                         
-                        if s_type == "Temperature" and val > 0:
-                            disk_info["temp"] = max(disk_info["temp"], val)
+                        if s_type == "Temperature" and plausible_temp(val):
+                            temps.append((name, val))
                             has_any_data = True
                         elif s_type == "Level":
                             # "Available Spare" indica saúde do SSD (100% = novo)
@@ -290,15 +723,167 @@ class HardwareMonitor:
                                 disk_info["data_written_gb"] = val
                                 has_any_data = True
                     
+                    disk_info["temp"], disk_info["temp_max"], disk_info["extra_temps"] = parse_storage_temps(temps)
+                    
                     # Adiciona disco se tiver algum sensor válido
                     if has_any_data:
                         data["storage"].append(disk_info)
 
+            gpu = select_gpu(gpus)
+            if gpu:
+                data["gpu"].update(gpu)
+            
+            # Um disco em dois nós do LHM vira uma entrada só
+            data["storage"] = merge_storage_entries(data["storage"])
+            
+            if refresh_storage:
+                # Barramento e, para discos USB sem sensor no LHM, a temperatura pelo Windows
+                apply_physical_disks(data["storage"], self._get_physical_disks(data["storage"]))
+                self._storage_cache = [dict(disk) for disk in data["storage"]]
+            else:
+                data["storage"] = [
+                    {**disk, **{field: 0 for field in STORAGE_FAST_FIELDS}}
+                    for disk in self._storage_cache
+                ]
+
         except Exception as e:
             print(f"[HardwareMonitor] Erro na leitura: {e}")
-            
+
         return data
 
+    def _get_drive_serial(self, drive_index: int) -> str:
+        """
+        Número de série do disco físico
+        
+        Consulta o WMI uma vez; disco novo consulta de novo, no máximo a cada
+        DRIVE_QUERY_REFRESH_SECS. Discos sem serial ficam como "" sem apagar os demais.
+        """
+        if drive_index < 0 or not self.wmi_enabled:
+            return ""
+        if drive_index not in self._drive_serials and self._drive_serials_refresh.due():
+            serials = self._query_drive_serials()
+            if serials is not None:
+                self._drive_serials.update(serials)
+                self._drive_serials.setdefault(drive_index, "")
+        return self._drive_serials.get(drive_index, "")
+    
+    def _get_physical_disks(self, disks: list[dict[str, Any]]) -> dict[int, dict[str, Any]]:
+        """
+        Barramento e temperatura de confiabilidade dos discos físicos.
+        
+        O barramento é lido uma vez (de novo só se surgir disco novo). A
+        temperatura dos discos sem sensor no LHM é renovada no máximo a cada
+        RELIABILITY_REFRESH_SECS: a consulta (PowerShell, lenta) roda dentro
+        do ciclo de envio. Disco só é dado como sem temperatura depois de uma
+        consulta que funcionou; se ela falhar, tenta de novo no próximo intervalo.
+        """
+        if not self.wmi_enabled:
+            return {}
+        indices = [disk["drive_index"] for disk in disks if disk.get("drive_index", -1) >= 0]
+        missing_temp = [
+            disk["drive_index"] for disk in disks
+            if disk.get("drive_index", -1) >= 0 and not disk.get("temp")
+            and disk["drive_index"] not in self._no_fallback_temp
+        ]
+        known = self._physical_disks
+        if known is not None and any(i not in known for i in indices):
+            self._reliability_refresh.force()  # Disco novo: barramento desconhecido
+        elif known is not None and not missing_temp:
+            return known
+        if not self._reliability_refresh.due():
+            return known or {}
+        
+        physical = self._query_physical_disks()
+        if physical is None:
+            return known or {}
+        for index in indices:
+            physical.setdefault(index, {"bus_type": UNKNOWN_BUS, "temp": 0})
+        for index in missing_temp:
+            if not physical[index]["temp"]:
+                self._no_fallback_temp.add(index)
+        self._physical_disks = physical
+        return physical
+    
+    def get_drive_letters(self, drive_index: int) -> list[str]:
+        """
+        Letras das partições do disco físico
+        
+        Consulta uma vez; disco novo consulta de novo, no máximo a cada
+        DRIVE_QUERY_REFRESH_SECS (roda no ciclo de envio). Discos sem
+        partição com letra ficam como [] sem apagar os demais.
+        """
+        if drive_index < 0 or not self.wmi_enabled:
+            return []
+        if drive_index not in self._drive_letters and self._drive_letters_refresh.due():
+            letters = self._query_drive_letters()
+            if letters is not None:
+                self._drive_letters.update(letters)
+                self._drive_letters.setdefault(drive_index, [])
+        return self._drive_letters.get(drive_index, [])
+    
+    def _query_drive_letters(self) -> Optional[dict[int, list[str]]]:
+        """Lê disco e letra de cada partição via Get-Partition (None se falhar)."""
+        try:
+            import subprocess
+            import json as json_mod
+            
+            result = subprocess.run(
+                ['powershell', '-Command',
+                 'Get-Partition | Select-Object DiskNumber, DriveLetter | ConvertTo-Json'],
+                capture_output=True,
+                text=True,
+                timeout=5,
+                creationflags=0x08000000  # CREATE_NO_WINDOW
+            )
+            if result.returncode == 0 and result.stdout.strip():
+                return parse_drive_letters(json_mod.loads(result.stdout))
+        except Exception:
+            pass
+        return None
+    
+    def _query_drive_serials(self) -> Optional[dict[int, str]]:
+        """Lê índice e número de série dos discos via Win32_DiskDrive (None se falhar)."""
+        try:
+            import subprocess
+            import json as json_mod
+            
+            result = subprocess.run(
+                ['powershell', '-Command',
+                 'Get-CimInstance Win32_DiskDrive | Select-Object Index, SerialNumber | ConvertTo-Json'],
+                capture_output=True,
+                text=True,
+                timeout=5,
+                creationflags=0x08000000  # CREATE_NO_WINDOW
+            )
+            if result.returncode == 0 and result.stdout.strip():
+                return parse_drive_serials(json_mod.loads(result.stdout))
+        except Exception:
+            pass
+        return None
+    
+    def _query_physical_disks(self) -> Optional[dict[int, dict[str, Any]]]:
+        """Lê BusType e a temperatura do contador de confiabilidade de cada disco físico (None se falhar)."""
+        try:
+            import subprocess
+            import json as json_mod
+            
+            result = subprocess.run(
+                ['powershell', '-Command',
+                 'Get-PhysicalDisk | ForEach-Object { '
+                 '$r = $_ | Get-StorageReliabilityCounter -ErrorAction SilentlyContinue; '
+                 '[pscustomobject]@{ DeviceId = $_.DeviceId; BusType = $_.BusType; Temperature = $r.Temperature } '
+                 '} | ConvertTo-Json'],
+                capture_output=True,
+                text=True,
+                timeout=10,
+                creationflags=0x08000000  # CREATE_NO_WINDOW
+            )
+            if result.returncode == 0 and result.stdout.strip():
+                return parse_physical_disks(json_mod.loads(result.stdout))
+        except Exception:
+            pass
+        return None
+    
     def get_network_link_info(self) -> dict[str, Any]:
         """Retorna informações de link de rede (velocidade negociada, status)"""
         info: dict[str, Any] = {
@@ -306,6 +891,8 @@ class HardwareMonitor:
             "link_status": "Unknown",
             "adapter_name": ""
         }
+        if not self.wmi_enabled:
+            return info
         
         try:
             import subprocess
@@ -346,10 +933,10 @@ class HardwareMonitor:
                     info['adapter_name'] = adapter.get('Name', '')
                     info['link_status'] = adapter.get('Status', 'Unknown')
                     info['link_speed_mbps'] = self._parse_link_speed(link_speed_str)
-                    
+
         except Exception:
             pass
-            
+
         return info
     
     def _parse_link_speed(self, speed_str: str) -> int:
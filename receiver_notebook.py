@@ -5,12 +5,42 @@ Real-time hardware monitoring dashboard.
 
 Keyboard Shortcuts:
     G: Toggle graphs
+    H: Toggle temperature histogram (CPU/GPU distribution)
+    B: Toggle bar view (CPU temperature per core, GPU VRAM usage)
+    D: Toggle debug overlay (packet size, packets/s, bandwidth, accepted/rejected frames)
     F/F11: Fullscreen
     T: Toggle theme (dark/light)
+    U: Toggle temperature unit (°C/°F, display only)
+    A: Show/hide the alert log (recent Warning/Critical transitions)
+    M: Show/hide all-time records (max/min per metric, kept in records.json)
+    E: Show/hide the theme editor (live color pickers, Save as a custom theme)
     L: Enable/disable CSV logging
+    P: Export the graph history (CSV) and the graph area (PNG) next to the config
+    +/-: Ask the sender to halve/double its send interval (needs control_requests on the sender)
+    R: Ask the sender to start/stop sending the process list (same requirement)
     S: ⚙️ General Settings (Connection, Appearance, Alerts, Notifications)
     I: Configure Sender IP (shortcut to settings)
+    1-6: Focus one panel full-size (CPU, GPU, RAM, MOBO, STORAGE, NETWORK)
+    0: Back to the full grid (ESC also leaves focus)
+    /: Search metrics (dims rows that don't match the typed text; ESC closes)
     Q/ESC: Quit
+
+Options:
+    --json-stream: Also write each received payload as NDJSON to stdout
+                   (logs go to stderr), e.g. for piping into Telegraf/Vector
+    --prometheus <port>: Serve the latest metrics per sender at
+                         http://<host>:<port>/metrics (Prometheus text format)
+    --log-format json: One JSON object per log line (ts, level, target, message)
+                       for log aggregators; also TELEMETRIA_LOG_FORMAT=json
+    --port, --sender-ip, --mode, --multicast-group, --theme, --temp-unit <value>:
+                       Override receiver_config.json for this run
+    --set <field>=<value>: Override any field (dotted path for nested ones,
+                           e.g. --set alertas.cpu_temp_warning=80)
+
+Environment: TELEMETRIA_RECEIVER_<OPTION> for the same named options
+(e.g. TELEMETRIA_RECEIVER_PORT=6000, TELEMETRIA_RECEIVER_THEME=light)
+
+Config precedence: defaults < receiver_config.json < environment < command line
 """
 from __future__ import annotations
 
@@ -19,9 +49,11 @@ import json
 import sys
 import os
 import gzip
+import math
 import tkinter as tk
 from tkinter import ttk
 from tkinter import font as tkfont
+from tkinter import colorchooser
 from collections import deque
 import threading
 import time
@@ -45,17 +77,229 @@ except ImportError:
 
 # ========== MÓDULOS LOCAIS (se disponíveis) ==========
 try:
-    from ui.themes import get_legacy_colors, get_theme_names
+    from ui.themes import get_legacy_colors, get_theme_names, blend_color
+    from ui.themes import COLOR_FIELDS, rgb_to_hex, load_custom_themes, save_custom_theme
     HAS_THEME_MODULE = True
 except ImportError:
     HAS_THEME_MODULE = False
 
 try:
-    from core.sounds import get_sound_manager, AlertSound
+    from ui.lang import tr, set_language, parse_language
+    HAS_LANG_MODULE = True
+except ImportError:
+    HAS_LANG_MODULE = False
+    
+    # Sem ui/lang: textos em português em vez das chaves (cópia da tabela de
+    # ui/lang.py; tests/test_lang.py confere que as duas são iguais)
+    FALLBACK_STRINGS = {
+        "status.connected": "● Conectado",
+        "status.stale": "◐ Sem dados recentes",
+        "status.host": " a {host}",
+        "status.uptime": " (ligado há {uptime})",
+        "status.no_data_for": " | Sem dados há {secs}s",
+        "status.updated": " | Atualizado: {time}",
+        "status.senders": " | {count} senders",
+        "status.port": " | porta {port}",
+        "status.waiting": "○ Aguardando dados...",
+        "status.disconnected": "○ Desconectado - Aguardando dados...{mode}",
+        "status.version_mismatch": "⚠ Protocolo do sender v{sender} incompatível com o receiver v{receiver}",
+        "footer.hint": "[F] Tela cheia | [G] Gráficos | [H] Histograma | [B] Barras | [X] Dispersão | [D] Debug | [T] Tema | [U] °C/°F | [1-6] Foco | [A] Alertas | [M] Recordes | [E] Editor de temas | [L] Log | [P] Exportar | [/] Buscar | [+/-] Taxa | [S] ⚙️ Configurações | [Q] Sair",
+        "panel.CPU": "CPU",
+        "panel.GPU": "GPU",
+        "panel.RAM": "RAM",
+        "panel.MOBO": "PLACA-MÃE",
+        "panel.STORAGE": "ARMAZENAMENTO",
+        "panel.NETWORK": "REDE",
+        "panel.BATTERY": "BATERIA",
+        "panel.PROCESSES": "PROCESSOS",
+        "panel.ALERT_LOG": "LOG DE ALERTAS",
+        "panel.RECORDS": "RECORDES",
+        "panel.THEME_EDITOR": "EDITOR DE TEMAS",
+        "row.usage": "Uso",
+        "row.temp": "Temp",
+        "row.voltage": "Voltagem",
+        "row.power": "Consumo",
+        "row.clock": "Clock",
+        "row.clock_source": "  Fonte",
+        "row.used": "Usado",
+        "row.total": "Total",
+        "row.disk": "Disco {n}",
+        "row.health": "  Saúde",
+        "row.used_space": "  Usado",
+        "row.free": "  Livre",
+        "row.read": "  Leitura",
+        "row.write": "  Escrita",
+        "row.adapter": "Adaptador",
+        "row.charge": "Carga",
+        "row.status": "Status",
+        "row.remaining": "Restante",
+        "row.battery_power": "Potência",
+        "value.unreachable": "Inalcançável",
+        "value.charging": "Carregando",
+        "value.on_battery": "Na bateria",
+        "graph.samples": "{label} ({count} amostras)",
+        "clock_source.lhm": "LHM",
+        "clock_source.os": "SO",
+        "clock_source.os_base": "SO (base)",
+        "system.load": "Carga {load}",
+        "system.processes": "{count} processos",
+    }
+    
+    def tr(key: str, **values: Any) -> str:
+        text = FALLBACK_STRINGS.get(key, key)
+        return text.format(**values) if values else text
+
+try:
+    from ui.layout import focus_for_key, visible_panels, plot_grid, panel_order, panel_rows, bar_fill, metric_matches
+    HAS_LAYOUT_MODULE = True
+except ImportError:
+    HAS_LAYOUT_MODULE = False
+
+try:
+    from core.network import (
+        socket_family, bind_address, open_udp_socket, normalize_host, format_address, is_ipv6_address,
+        join_multicast_group, is_multicast_address, listen_ports, PortListener
+    )
+    HAS_NETWORK_MODULE = True
+except ImportError:
+    HAS_NETWORK_MODULE = False
+
+try:
+    from core.sounds import init_sounds, SoundConfig
     HAS_SOUND_MODULE = True
 except ImportError:
     HAS_SOUND_MODULE = False
 
+try:
+    from core.alerts import AlertLevel, AlertLog, AlertTracker, MuteRegistry, alert_level, disk_thresholds
+    from core.alerts import link_speed_level, metric_thresholds
+    HAS_ALERTS_MODULE = True
+except ImportError:
+    HAS_ALERTS_MODULE = False
+
+try:
+    from core.validators import validate_alert_thresholds
+    HAS_VALIDATORS_MODULE = True
+except ImportError:
+    HAS_VALIDATORS_MODULE = False
+
+try:
+    from core.config import load_ui_state, save_ui_state, load_versioned_config, CONFIG_VERSION
+    HAS_UI_STATE_MODULE = True
+except ImportError:
+    HAS_UI_STATE_MODULE = False
+
+try:
+    from core.formatting import format_value, format_uptime, DEFAULT_PRECISION
+    HAS_FORMATTING_MODULE = True
+except ImportError:
+    HAS_FORMATTING_MODULE = False
+
+try:
+    from core.payload import vram_percent, sensor_status_hints, significant_diff
+    HAS_PAYLOAD_MODULE = True
+except ImportError:
+    HAS_PAYLOAD_MODULE = False
+
+try:
+    from core.smoothing import Smoother, HoldState
+    HAS_SMOOTHING_MODULE = True
+except ImportError:
+    HAS_SMOOTHING_MODULE = False
+
+try:
+    from core.overrides import parse_cli_overrides, apply_overrides, applied_values, without_overrides
+    from core.overrides import env_overrides, RECEIVER_CLI_OPTIONS, RECEIVER_ENV_PREFIX
+    HAS_OVERRIDES_MODULE = True
+except ImportError:
+    HAS_OVERRIDES_MODULE = False
+
+try:
+    from core.histogram import histogram
+    HAS_HISTOGRAM_MODULE = True
+except ImportError:
+    HAS_HISTOGRAM_MODULE = False
+
+try:
+    from core.series import (
+        history_capacity, resize_series, IntervalTracker,
+        GAP, gap_samples, series_values, axis_bounds, plot_points, history_sample,
+        paired_points, scatter_coords, SampleAggregator, sample_interval
+    )
+    HAS_SERIES_MODULE = True
+except ImportError:
+    HAS_SERIES_MODULE = False
+    GAP = float("nan")
+    
+    def history_sample(value):
+        return GAP if value is None else value
+
+try:
+    from core.rates import TrafficMeter
+    HAS_TRAFFIC_MODULE = True
+except ImportError:
+    HAS_TRAFFIC_MODULE = False
+
+try:
+    from core.protocol import unwrap_frame, extract_body, parse_body, ProtocolError, VersionMismatchError
+    from core.protocol import decode_goodbye, DecodeStats, check_datagram, RECV_BUFFER_SIZE
+    from core.delta import DeltaDecoder, is_delta_frame
+    HAS_PROTOCOL_MODULE = True
+except ImportError:
+    HAS_PROTOCOL_MODULE = False
+
+try:
+    from core.control import config_request, encode_control
+    HAS_CONTROL_MODULE = True
+except ImportError:
+    HAS_CONTROL_MODULE = False
+
+try:
+    from core.export import stream_record, to_ndjson, PrometheusExporter, history_csv, export_filename
+    HAS_EXPORT_MODULE = True
+except ImportError:
+    HAS_EXPORT_MODULE = False
+
+try:
+    from core.records import RecordsStore, RECORD_METRICS
+    HAS_RECORDS_MODULE = True
+except ImportError:
+    HAS_RECORDS_MODULE = False
+
+try:
+    from ui.redraw import render_interval
+    HAS_REDRAW_MODULE = True
+except ImportError:
+    HAS_REDRAW_MODULE = False
+
+try:
+    from core.sources import SourceTable, connection_state, ConnectionWatchdog
+    from core.sources import elapsed_since, goodbye_resets_connection
+    HAS_SOURCES_MODULE = True
+except ImportError:
+    HAS_SOURCES_MODULE = False
+
+try:
+    from core.logging_config import log_format_from, configure_output
+    HAS_LOGGING_MODULE = True
+except ImportError:
+    HAS_LOGGING_MODULE = False
+
+
+# ========== JSON STREAM ==========
+# Com --json-stream o stdout fica reservado para o NDJSON; os logs vão para
+# stderr (o redirecionamento é feito em main(), não no import)
+JSON_STREAM = "--json-stream" in sys.argv and HAS_EXPORT_MODULE
+STREAM_OUT = sys.stdout
+
+# --prometheus <porta>: endpoint /metrics (desligado por padrão)
+PROMETHEUS_PORT = 0
+if "--prometheus" in sys.argv and HAS_EXPORT_MODULE:
+    try:
+        PROMETHEUS_PORT = int(sys.argv[sys.argv.index("--prometheus") + 1])
+    except (IndexError, ValueError):
+        print("[Prometheus] Uso: --prometheus <porta>")
+
 
 # ========== CONFIGURAÇÕES ==========
 CONFIG_PATH = os.path.join(os.path.dirname(os.path.abspath(__file__)), "receiver_config.json")
@@ -64,14 +308,42 @@ CONFIG_PATH = os.path.join(os.path.dirname(os.path.abspath(__file__)), "receiver
 def carregar_config() -> dict[str, Any]:
     """Carrega configurações do receiver_config.json ou usa padrões."""
     config_padrao = {
+        "config_version": CONFIG_VERSION if HAS_UI_STATE_MODULE else 1,  # Formato do arquivo (migrado automaticamente)
+        
         # === CONEXÃO ===
         "porta": 5005,
+        "ports": [],  # Escuta em várias portas, ex: [5005, 5006] (vazio = só "porta")
         "sender_ip": "",  # Vazio = broadcast/auto
-        "modo": "auto",    # "auto" ou "manual"
+        "ipv6": False,  # Escuta em [::] (dual-stack); automático se sender_ip for IPv6
+        "modo": "auto",    # "auto", "manual" ou "multicast"
+        "connection_timeout_secs": 5.0,  # Sem dados por esse tempo = desconectado (metade = painéis esmaecidos)
+        "connection_lost_debounce_secs": 60,  # Aviso de conexão perdida no máximo uma vez nesse intervalo (link oscilando)
+        "multicast_group": "239.255.0.1",  # Grupo do modo "multicast" (mesmo do Sender)
+        "multi_sender": False,  # Aceita vários Senders (uma aba por host); ignora sender_ip
+        "encryption_key": "",  # Mesma chave do Sender (vazio = frames em claro)
+        "auth_secret": "",  # Mesmo segredo HMAC do Sender (vazio = não exige tag)
+        "accept_legacy_frames": True,  # Aceita frames sem magic byte (Senders antigos); false = só o protocolo atual
         "expected_link_speed_mbps": 1000,  # Velocidade esperada: CAT5=100, CAT5e/6=1000, CAT6a/7=10000
         
         # === APARÊNCIA ===
-        "tema": "dark",  # dark, light, high_contrast, cyberpunk
+        "language": "pt",  # Idioma do dashboard: "pt" ou "en" (desconhecido = pt)
+        "tema": "dark",  # dark, light, high_contrast, cyberpunk ou um tema salvo no editor (tecla E)
+        "themes_file": "themes.json",  # Temas customizados do editor (relativo à pasta do receiver)
+        "temp_unit": "C",  # "C" ou "F" (só exibição; alertas sempre em °C)
+        "display_precision": {  # Casas decimais por tipo de valor
+            "temp": 1,
+            "voltage": 3,
+            "percent": 1,
+            "power": 1,
+            "rate": 1,
+            "other": 1,
+        },
+        "compact_values": False,  # Taxas abreviadas: "5.4M" em vez de "5.4 MB/s"
+        "number_locale": "",  # Separadores: "" = 1234.5, "en" = 1,234.5, "pt"/"de" = 1.234,5, "fr" = 1 234,5
+        "panels": ["CPU", "GPU", "RAM", "MOBO", "STORAGE", "NETWORK"],  # Painéis exibidos e ordem, 3 por linha
+        "max_fps": 2,  # Redesenhos por segundo com dados chegando
+        "idle_fps": 0.5,  # Redesenhos por segundo sem dados nem entrada (0 = sem modo ocioso)
+        "idle_after_secs": 10,  # Segundos sem pacotes nem teclado/mouse até o modo ocioso
         "cores_customizadas": {
             "cpu": "",      # Vazio = usa cor do tema
             "gpu": "",
@@ -81,6 +353,17 @@ def carregar_config() -> dict[str, Any]:
             "mobo": ""
         },
         
+        # === GRÁFICOS ===
+        "smoothing_alpha": 0.0,  # Suavização EMA dos gráficos (0 = valores brutos)
+        "history_seconds": 30,  # Janela dos gráficos em segundos (ajustada ao intervalo real)
+        "history_gaps": True,  # Pacotes perdidos interrompem a linha dos gráficos
+        "aggregate_secs": 0,  # Média dos pacotes por balde de N segundos nos gráficos (0 = cada pacote é uma amostra)
+        "max_hold_frames": 0,  # Pacotes perdidos em que os valores seguem a tendência antes de esmaecer (0 = congela no último)
+        "graph_autoscale": False,  # Eixo Y segue os dados (temperatura, ping); percentuais ficam 0-100
+        "alert_log_size": 50,  # Transições de alerta mantidas no log (tecla A)
+        "records_file": "records.json",  # Recordes de todos os tempos (tecla M; relativo à pasta do receiver)
+        "records_save_secs": 60,  # Intervalo mínimo entre gravações dos recordes
+        
         # === ALERTAS (Thresholds) ===
         "alertas": {
             "cpu_temp_warning": 70,
@@ -91,6 +374,15 @@ def carregar_config() -> dict[str, Any]:
             "gpu_temp_critical": 90,
             "gpu_uso_warning": 80,
             "gpu_uso_critical": 95,
+            # Potência/voltagem/VRAM (0 = sem cor); ver METRIC_THRESHOLD_DEFAULTS
+            "cpu_power_warning": 200,
+            "cpu_power_critical": 250,
+            "cpu_voltage_warning": 1.45,
+            "cpu_voltage_critical": 1.55,
+            "gpu_power_warning": 350,
+            "gpu_power_critical": 450,
+            "vram_warning": 90,
+            "vram_critical": 98,
             "ram_warning": 70,
             "ram_critical": 90,
             "storage_temp_warning": 45,
@@ -98,15 +390,27 @@ def carregar_config() -> dict[str, Any]:
             "storage_uso_warning": 80,
             "storage_uso_critical": 95,
             "ping_warning": 50,
-            "ping_critical": 100
+            "ping_critical": 100,
+            "mobo_temp_warning": 50,
+            "mobo_temp_critical": 70,
+            "link_speed_alert": True  # Alerta com o link abaixo de expected_link_speed_mbps
         },
         
+        # Thresholds por disco, pelo número de série / "PhysicalDriveN" (exato)
+        # ou pelo nome (casamento parcial, sem caixa/espaços):
+        # {"Samsung SSD 970": {"temp_warning": 60, "temp_critical": 70}}
+        "disk_overrides": {},
+        
         # === SONS ===
         "sons": {
             "enabled": True,
             "cooldown_seconds": 10,
             "warning_sound": "warning",
-            "critical_sound": "beep_urgent"
+            "critical_sound": "beep_urgent",
+            "connection_lost_sound": "beep_low",  # Sender conectado parou de enviar
+            "mute_minutes": 15,  # Duração do mute ao clicar num valor em alerta
+            # Som por métrica ou painel: {"GPU.temp": "beep_high", "RAM": {"critical": "beep_low"}}
+            "per_metric": {}
         },
         
         # === NOTIFICAÇÕES WEBHOOK ===
@@ -124,12 +428,19 @@ def carregar_config() -> dict[str, Any]:
         "historico": {
             "csv_enabled": False,
             "auto_start_log": False,
-            "retention_days": 7
+            "retention_days": 7,
+            "csv_change_epsilon": 0.0  # Só grava no CSV o que mudou além disso (0 = todo pacote)
         }
     }
     
     if os.path.exists(CONFIG_PATH):
         try:
+            if HAS_UI_STATE_MODULE:
+                config, migrado = load_versioned_config(CONFIG_PATH, config_padrao)
+                print(f"[Config] Carregado de {CONFIG_PATH}")
+                if migrado:
+                    print(f"[Config] receiver_config.json atualizado para a versão {CONFIG_VERSION}")
+                return config
             with open(CONFIG_PATH, 'r', encoding='utf-8') as f:
                 config = json.load(f)
                 print(f"[Config] Carregado de {CONFIG_PATH}")
@@ -153,6 +464,8 @@ def carregar_config() -> dict[str, Any]:
 
 def salvar_config(config: dict[str, Any]) -> bool:
     """Salva configurações do receiver."""
+    if OVERRIDDEN:
+        config = without_overrides(config, FILE_CONFIG, OVERRIDDEN)
     try:
         with open(CONFIG_PATH, 'w', encoding='utf-8') as f:
             json.dump(config, f, indent=4, ensure_ascii=False)
@@ -164,10 +477,45 @@ def salvar_config(config: dict[str, Any]) -> bool:
 
 
 CONFIG = carregar_config()
+# --port 6000, --set campo=valor...: só nesta execução (o Settings não grava esses valores)
+FILE_CONFIG = CONFIG
+OVERRIDDEN: dict[str, Any] = {}
+if HAS_OVERRIDES_MODULE:
+    # Ambiente (valor inválido só gera aviso), depois a linha de comando
+    _env = env_overrides(os.environ, RECEIVER_ENV_PREFIX, RECEIVER_CLI_OPTIONS)
+    CONFIG, _config_warnings = apply_overrides(CONFIG, _env, "variável de ambiente")
+    for _warning in _config_warnings:
+        print(f"[Config] Aviso: {_warning} (ignorado)")
+    _overrides = parse_cli_overrides(sys.argv, RECEIVER_CLI_OPTIONS)
+    CONFIG, _config_errors = apply_overrides(CONFIG, _overrides)
+    for _error in _config_errors:
+        print(f"[Config] ERRO: {_error}")
+    if _config_errors:
+        sys.exit(1)
+    OVERRIDDEN = applied_values(CONFIG, {**_env, **_overrides})
+# Thresholds que nunca disparariam (ou sempre): só avisa, o arquivo pode ser corrigido no Settings
+if HAS_VALIDATORS_MODULE:
+    for _warning in validate_alert_thresholds(CONFIG.get("alertas", {}), CONFIG.get("disk_overrides", {})):
+        print(f"[Config] Aviso: threshold implausível - {_warning}")
+# Idioma dos textos do dashboard (os logs continuam em português)
+if HAS_LANG_MODULE:
+    if parse_language(CONFIG.get("language", "pt")) is None:
+        print(f"[Config] Aviso: idioma desconhecido {CONFIG.get('language')!r}; usando português")
+    set_language(CONFIG.get("language", "pt"))
 HOST = "0.0.0.0"
 PORTA = CONFIG["porta"]
-HISTORY_SIZE = 60
-CONNECTION_TIMEOUT = 5  # segundos sem dados = desconectado
+HISTORY_SIZE = 60  # Capacidade inicial; depois segue history_seconds / intervalo real
+CONNECTION_TIMEOUT = max(0.5, float(CONFIG.get("connection_timeout_secs", 5.0)))  # segundos sem dados = desconectado
+WAKEUP_POLL_MS = 100  # Intervalo com que a UI confere pacotes novos no modo ocioso
+# Séries do histórico oferecidas nos eixos da dispersão: nome -> topo da escala fixa (None = segue os dados)
+SCATTER_SERIES = {
+    "CPU %": ("cpu_usage", 100), "CPU Temp °C": ("cpu_temp", None),
+    "GPU %": ("gpu_load", 100), "GPU Temp °C": ("gpu_temp", None),
+    "RAM %": ("ram", 100), "Net Down KB/s": ("net_down", None),
+    "Net Up KB/s": ("net_up", None), "Ping ms": ("ping", None),
+}
+THEMES_PATH = os.path.join(os.path.dirname(os.path.abspath(__file__)), CONFIG.get("themes_file", "themes.json"))
+RECORDS_PATH = os.path.join(os.path.dirname(os.path.abspath(__file__)), CONFIG.get("records_file", "records.json"))
 # ===================================
 
 
@@ -181,41 +529,123 @@ class TelemetryDashboard:
     def __init__(self) -> None:
         self.root = tk.Tk()
         self.root.title("Telemetry Center")
-        self.root.geometry("1366x700")
+        # Tamanho/posição e tema da última execução (ui_state.json)
+        self.ui_state = load_ui_state() if HAS_UI_STATE_MODULE else None
+        self.root.geometry(self.ui_state.geometry if self.ui_state and self.ui_state.geometry else "1366x700")
+        self.root.protocol("WM_DELETE_WINDOW", self._quit_app)
         self.root.minsize(1200, 600)
         
         # Estado
         self.is_fullscreen = False
+        self.focus_panel = None  # Painel ampliado (teclas 1-6); None = grade
         self.show_graphs = False
+        self.graph_mode = "lines"  # "lines", "histogram", "scatter" ou "bars"
+        self.show_debug = False
         self.dark_theme = True
+        self.temp_unit = "F" if str(CONFIG.get("temp_unit", "C")).upper() == "F" else "C"  # Só exibição
+        self.display_precision = dict(DEFAULT_PRECISION) if HAS_FORMATTING_MODULE else {}
+        self.display_precision.update({
+            category: int(places)
+            for category, places in (CONFIG.get("display_precision") or {}).items()
+            if isinstance(places, int) and not isinstance(places, bool) and 0 <= places <= 6
+        })
+        self.number_locale = CONFIG.get("number_locale", "")  # Desconhecido = padrão (ponto)
         self.logging_enabled = False
         self.last_data_time = 0
         self.is_connected = False
         self.notified_critical = {}  # Evita spam de notificações
+        self.alert_tracker = AlertTracker() if HAS_ALERTS_MODULE else None  # Sons nas transições
+        self.alert_log = AlertLog(CONFIG.get("alert_log_size", 50)) if HAS_ALERTS_MODULE else None
+        self.alert_log_dirty = False  # Nova transição ainda não exibida
+        # Máxima/mínima de todos os tempos por Sender (records.json, gravação espaçada)
+        self.records = RecordsStore(RECORDS_PATH, float(CONFIG.get("records_save_secs", 60))) if HAS_RECORDS_MODULE else None
+        self.records_dirty = False  # Recorde novo ainda não exibido
+        # Clique num valor em alerta silencia som/notificação daquela métrica
+        mute_minutes = CONFIG.get("sons", {}).get("mute_minutes", 15)
+        self.mutes = MuteRegistry(mute_minutes * 60) if HAS_ALERTS_MODULE else None
+        self.sound_manager = self._init_sound_manager()
+        # Aviso (som + notificação) quando um Sender conectado para de enviar
+        debounce = float(CONFIG.get("connection_lost_debounce_secs", 60) or 0)
+        self.connection_watchdog = ConnectionWatchdog(debounce) if HAS_SOURCES_MODULE else None
         
         # Configuração de conexão
         self.sender_ip = CONFIG.get("sender_ip", "")
         self.connection_mode = CONFIG.get("modo", "auto")
         self.porta = CONFIG.get("porta", 5005)
         self.restart_receiver = False  # Flag para reiniciar receiver
+        self.encryption_key = CONFIG.get("encryption_key", "")
+        self.auth_secret = CONFIG.get("auth_secret", "")
+        # Canal de controle: pedidos vão pelo socket de escuta para o último Sender exibido
+        self.control_sock = None
+        self.control_addr = None
+        # Portas de escuta ("ports" no config) e a do último pacote exibido
+        self.listen_ports = [self.porta]
+        self.last_port = None
+        self.accept_legacy = CONFIG.get("accept_legacy_frames", True)
+        
+        # Multi-sender: último payload por host e host exibido
+        self.multi_sender = CONFIG.get("multi_sender", False) and HAS_SOURCES_MODULE
+        self.sources = SourceTable(CONNECTION_TIMEOUT) if self.multi_sender else None
+        self.selected_source = ""
+        self.source_tabs: dict[str, tk.Label] = {}
         
         # Dados (encapsulados na classe)
         self.current_data = {}
         self.data_lock = threading.Lock()
+        # Posições ainda sem dados são lacunas: não entram no auto-scale nem no CSV
         self.history = {
-            "cpu_usage": deque([0]*HISTORY_SIZE, maxlen=HISTORY_SIZE),
-            "cpu_temp": deque([0]*HISTORY_SIZE, maxlen=HISTORY_SIZE),
-            "gpu_load": deque([0]*HISTORY_SIZE, maxlen=HISTORY_SIZE),
-            "gpu_temp": deque([0]*HISTORY_SIZE, maxlen=HISTORY_SIZE),
-            "ram": deque([0]*HISTORY_SIZE, maxlen=HISTORY_SIZE),
-            "net_down": deque([0]*HISTORY_SIZE, maxlen=HISTORY_SIZE),
-            "net_up": deque([0]*HISTORY_SIZE, maxlen=HISTORY_SIZE),
-            "ping": deque([0]*HISTORY_SIZE, maxlen=HISTORY_SIZE),
+            "cpu_usage": deque([GAP]*HISTORY_SIZE, maxlen=HISTORY_SIZE),
+            "cpu_temp": deque([GAP]*HISTORY_SIZE, maxlen=HISTORY_SIZE),
+            "gpu_load": deque([GAP]*HISTORY_SIZE, maxlen=HISTORY_SIZE),
+            "gpu_temp": deque([GAP]*HISTORY_SIZE, maxlen=HISTORY_SIZE),
+            "ram": deque([GAP]*HISTORY_SIZE, maxlen=HISTORY_SIZE),
+            "net_down": deque([GAP]*HISTORY_SIZE, maxlen=HISTORY_SIZE),
+            "net_up": deque([GAP]*HISTORY_SIZE, maxlen=HISTORY_SIZE),
+            "ping": deque([GAP]*HISTORY_SIZE, maxlen=HISTORY_SIZE),
         }
         
+        # Intervalo real entre pacotes (dimensiona a janela dos gráficos)
+        self.packet_interval = IntervalTracker(max_gap=CONNECTION_TIMEOUT) if HAS_SERIES_MODULE else None
+        self.last_payload_ts = None  # Timestamp do Sender no último pacote (lacunas nos gráficos)
+        # Agregação: senders rápidos (0.5s) viram uma amostra por balde nos gráficos
+        self.aggregate_secs = max(0.0, float(CONFIG.get("aggregate_secs", 0) or 0))
+        self.aggregator = SampleAggregator(self.aggregate_secs) if HAS_SERIES_MODULE and self.aggregate_secs else None
+        self.last_source = ""  # "ip:porta" (ou "[ipv6]:porta") do último pacote exibido
+        self.last_hostname = ""  # Nome mostrado no banner (para o aviso de conexão perdida)
+        
+        # Suavização só dos gráficos (alertas usam o valor bruto de current_data)
+        alpha = CONFIG.get("smoothing_alpha", 0.0)
+        self.smoother = Smoother(alpha) if HAS_SMOOTHING_MODULE else None
+        
+        # Valores extrapolados durante perdas curtas (só exibição; alertas usam o valor real)
+        self.max_hold_frames = int(CONFIG.get("max_hold_frames", 0))
+        self.holds: dict[str, Any] = {}
+        
+        # Estatísticas de pacotes (overlay de debug)
+        self.traffic = TrafficMeter(window_seconds=5) if HAS_TRAFFIC_MODULE else None
+        self.last_json_size = 0
+        # Frames aceitos/recusados (corrompidos, autenticação, versão) e o último erro
+        self.decode_stats = DecodeStats() if HAS_PROTOCOL_MODULE else None
+        self.delta_decoder = DeltaDecoder() if HAS_PROTOCOL_MODULE else None  # Último keyframe de cada Sender
+        self.mismatch_packets = 0  # Frames de um Sender com outra versão do protocolo
+        self.version_mismatch = None  # (versão do sender, versão do receiver) do último recusado
+        self.version_mismatch_time = 0
+        
+        # Exportador Prometheus (só com --prometheus <porta>)
+        self.exporter = None
+        if PROMETHEUS_PORT:
+            try:
+                self.exporter = PrometheusExporter(PROMETHEUS_PORT, CONNECTION_TIMEOUT)
+                self.exporter.start()
+            except OSError as e:
+                print(f"[Prometheus] Não foi possível abrir a porta {PROMETHEUS_PORT}: {e}")
+                self.exporter = None
+        
         # Log CSV
         self.log_file = None
         self.log_dir = os.path.join(os.path.dirname(os.path.abspath(__file__)), "logs")
+        self.csv_epsilon = float(CONFIG.get("historico", {}).get("csv_change_epsilon", 0.0))
+        self.last_csv_data = None  # Último payload gravado (linhas repetidas são puladas)
         
         # Toast notifier
         self.toaster = ToastNotifier() if HAS_TOAST else None
@@ -262,8 +692,15 @@ class TelemetryDashboard:
                 }
             }
         
-        # Carrega tema salvo do CONFIG
+        # Temas criados no editor (tecla E)
+        if HAS_THEME_MODULE:
+            load_custom_themes(THEMES_PATH)
+        
+        # Carrega tema salvo (último usado, senão o do CONFIG)
         saved_theme = CONFIG.get("tema", "dark")
+        if self.ui_state and self.ui_state.theme:
+            saved_theme = self.ui_state.theme
+        self.theme_name = saved_theme
         self.dark_theme = saved_theme in ["dark", "cyberpunk", "high_contrast"]
         
         # Aplica tema salvo
@@ -289,6 +726,9 @@ class TelemetryDashboard:
         self.font_value = tkfont.Font(family="Consolas", size=12)
         self.font_small = tkfont.Font(family="Consolas", size=9)
         self.font_help = tkfont.Font(family="Consolas", size=8)
+        # Painel em foco (teclas 1-6) usa fontes ampliadas
+        self.font_focus_name = tkfont.Font(family="Consolas", size=14)
+        self.font_focus_value = tkfont.Font(family="Consolas", size=26, weight="bold")
         
         # Cria interface
         self._create_ui()
@@ -296,12 +736,23 @@ class TelemetryDashboard:
         # Binds de teclado
         self._bind_keys()
         
+        # Ritmo do loop de atualização: max_fps; ocioso sem dados nem entrada
+        self.max_fps = float(CONFIG.get("max_fps", 2) or 0)
+        self.idle_fps = float(CONFIG.get("idle_fps", 0.5) or 0)
+        self.idle_after = float(CONFIG.get("idle_after_secs", 10) or 0)
+        self.last_input_time = time.time()
+        self.ui_after_id = None
+        self.ui_idle = False
+        # Pacote novo no modo ocioso: a thread de recepção só sinaliza (Tk não é thread-safe)
+        self.data_wakeup = threading.Event()
+        
         # Inicia thread de recebimento
         self.recv_thread = threading.Thread(target=self._receiver_loop, daemon=True)
         self.recv_thread.start()
         
         # Inicia loop de atualização
         self._update_ui()
+        self._poll_data_wakeup()
     
     def _create_ui(self):
         """Cria todos os elementos da interface."""
@@ -322,32 +773,164 @@ class TelemetryDashboard:
         # Connection status
         self.status_label = tk.Label(
             self.main_frame,
-            text="○ Waiting for data...",
+            text=tr("status.waiting"),
             font=self.font_small,
             fg=self.colors["dim"],
             bg=self.colors["bg"]
         )
         self.status_label.pack()
         
+        # Fontes de sensores do Sender (só aparece se o payload trouxer sensor_status)
+        self.diag_label = tk.Label(
+            self.main_frame,
+            text="",
+            font=self.font_help,
+            fg=self.colors["dim"],
+            bg=self.colors["bg"]
+        )
+        self.diag_visible = False
+        
+        # Load average e processos do Sender (oculta enquanto vier zerada)
+        self.system_label = tk.Label(
+            self.main_frame,
+            text="",
+            font=self.font_help,
+            fg=self.colors["dim"],
+            bg=self.colors["bg"]
+        )
+        self.system_visible = False
+        
+        # Abas de Senders (modo multi_sender)
+        self.sources_bar = tk.Frame(self.main_frame, bg=self.colors["bg"])
+        if self.multi_sender:
+            self.sources_bar.pack()
+        
+        # Overlay de debug (oculto por padrão, tecla D)
+        self.debug_label = tk.Label(
+            self.main_frame,
+            text="",
+            font=self.font_help,
+            fg=self.colors["dim"],
+            bg=self.colors["bg"]
+        )
+        
+        # Busca de métricas (tecla /): esmaece as linhas que não casam
+        self.search_query = ""
+        self.search_frame = tk.Frame(self.main_frame, bg=self.colors["bg"])
+        tk.Label(
+            self.search_frame, text="🔍", font=self.font_small, fg=self.colors["dim"], bg=self.colors["bg"]
+        ).pack(side=tk.LEFT)
+        self.search_entry = tk.Entry(
+            self.search_frame, font=self.font_small, width=30,
+            bg=self.colors["panel"], fg=self.colors["text"], insertbackground=self.colors["text"]
+        )
+        self.search_entry.pack(side=tk.LEFT, padx=5)
+        # Sem a tag da janela: as letras digitadas não disparam os atalhos
+        self.search_entry.bindtags((str(self.search_entry), "Entry", "all"))
+        self.search_entry.bind("<KeyRelease>", self._on_search_changed)
+        self.search_entry.bind("<Escape>", self._close_search)
+        self.search_entry.bind("<Return>", lambda e: self.root.focus_set())
+        self.search_visible = False
+        
         # Container para painéis
         self.panels_frame = tk.Frame(self.main_frame, bg=self.colors["bg"])
         self.panels_frame.pack(fill=tk.BOTH, expand=True, pady=5)
         
-        # Row 1: CPU | GPU | RAM
-        row1 = tk.Frame(self.panels_frame, bg=self.colors["bg"])
-        row1.pack(fill=tk.X, pady=3)
-        
-        self.cpu_panel = self._create_panel(row1, "CPU", self.colors["cpu"])
-        self.gpu_panel = self._create_panel(row1, "GPU", self.colors["gpu"])
-        self.ram_panel = self._create_panel(row1, "RAM", self.colors["ram"])
-        
-        # Row 2: MOBO | STORAGE | NETWORK
-        row2 = tk.Frame(self.panels_frame, bg=self.colors["bg"])
-        row2.pack(fill=tk.X, pady=3)
+        # Linhas de painéis na ordem do config ("panels"), 3 por linha
+        if HAS_LAYOUT_MODULE:
+            self.panel_order, unknown = panel_order(CONFIG.get("panels"))
+            for name in unknown:
+                print(f"[Config] Aviso: painel desconhecido ignorado: {name}")
+            rows = panel_rows(self.panel_order)
+        else:
+            self.panel_order = ("CPU", "GPU", "RAM", "MOBO", "STORAGE", "NETWORK")
+            rows = [self.panel_order[:3], self.panel_order[3:]]
+        
+        panels = {}
+        self.panel_rows = []
+        for names in rows:
+            row = tk.Frame(self.panels_frame, bg=self.colors["bg"])
+            row.pack(fill=tk.X, pady=3)
+            self.panel_rows.append((row, names))
+            for name in names:
+                panels[name] = self._create_panel(row, tr(f"panel.{name}"), self.colors[name.lower()], name=name)
+        
+        # Painéis fora do layout existem (os updates escrevem neles), mas nunca aparecem
+        self.hidden_panels_row = tk.Frame(self.panels_frame, bg=self.colors["bg"])
+        for name in ("CPU", "GPU", "RAM", "MOBO", "STORAGE", "NETWORK"):
+            if name not in panels:
+                panels[name] = self._create_panel(self.hidden_panels_row, tr(f"panel.{name}"), self.colors[name.lower()], name=name)
+        
+        self.cpu_panel, self.gpu_panel, self.ram_panel = panels["CPU"], panels["GPU"], panels["RAM"]
+        self.mobo_panel, self.storage_panel, self.network_panel = panels["MOBO"], panels["STORAGE"], panels["NETWORK"]
+        
+        # Bateria: só aparece quando o sender é um notebook (no fim da última linha)
+        self.battery_panel = self._create_panel(
+            self.panel_rows[-1][0], tr("panel.BATTERY"), self.colors["warning"], name="BATTERY"
+        )
+        self.battery_panel["frame"].pack_forget()
+        self.battery_visible = False
         
-        self.mobo_panel = self._create_panel(row2, "MOBO", self.colors["mobo"])
-        self.storage_panel = self._create_panel(row2, "STORAGE", self.colors["storage"])
-        self.network_panel = self._create_panel(row2, "NETWORK", self.colors["network"])
+        # Row 3: TOP PROCESSES (só aparece se o sender enviar a lista)
+        self.processes_row = tk.Frame(self.panels_frame, bg=self.colors["bg"])
+        self.processes_panel = self._create_panel(
+            self.processes_row, f"{tr('panel.PROCESSES')} ▾", self.colors["cpu"], name="TOP PROCESSES ▾"
+        )
+        self.processes_panel["title"].configure(cursor="hand2")
+        self.processes_panel["title"].bind("<Button-1>", self._toggle_processes)
+        self.processes_visible = False
+        self.processes_collapsed = False
+        
+        # Row 4: ALERT LOG (tecla A)
+        self.alert_log_row = tk.Frame(self.panels_frame, bg=self.colors["bg"])
+        self.alert_log_panel = self._create_panel(
+            self.alert_log_row, tr("panel.ALERT_LOG"), self.colors["critical"], name="ALERT LOG"
+        )
+        self.alert_log_list = tk.Listbox(
+            self.alert_log_panel["values"],
+            font=self.font_small,
+            bg=self.colors["panel"],
+            fg=self.colors["text"],
+            height=6,
+            borderwidth=0,
+            highlightthickness=0,
+            activestyle="none"
+        )
+        alert_log_scroll = tk.Scrollbar(self.alert_log_panel["values"], command=self.alert_log_list.yview)
+        self.alert_log_list.configure(yscrollcommand=alert_log_scroll.set)
+        alert_log_scroll.pack(side=tk.RIGHT, fill=tk.Y)
+        self.alert_log_list.pack(side=tk.LEFT, fill=tk.BOTH, expand=True)
+        self.alert_log_visible = False
+        
+        # Row 5: RECORDS (tecla M)
+        self.records_row = tk.Frame(self.panels_frame, bg=self.colors["bg"])
+        self.records_panel = self._create_panel(
+            self.records_row, tr("panel.RECORDS"), self.colors["title"], name="RECORDS"
+        )
+        self.records_list = tk.Listbox(
+            self.records_panel["values"],
+            font=self.font_small,
+            bg=self.colors["panel"],
+            fg=self.colors["text"],
+            height=6,
+            borderwidth=0,
+            highlightthickness=0,
+            activestyle="none"
+        )
+        records_scroll = tk.Scrollbar(self.records_panel["values"], command=self.records_list.yview)
+        self.records_list.configure(yscrollcommand=records_scroll.set)
+        records_scroll.pack(side=tk.RIGHT, fill=tk.Y)
+        self.records_list.pack(side=tk.LEFT, fill=tk.BOTH, expand=True)
+        self.records_visible = False
+        
+        # Row 6: THEME EDITOR (tecla E; widgets criados ao abrir)
+        self.theme_editor_row = tk.Frame(self.panels_frame, bg=self.colors["bg"])
+        self.theme_editor_panel = self._create_panel(
+            self.theme_editor_row, tr("panel.THEME_EDITOR"), self.colors["title"], name="THEME EDITOR"
+        )
+        self.theme_editor_swatches = {}
+        self.theme_editor_widgets = []
+        self.theme_editor_visible = False
         
         # Pré-cria labels de storage para evitar recriação
         self._precreate_storage_labels()
@@ -361,18 +944,31 @@ class TelemetryDashboard:
             height=150
         )
         
+        # Eixos da dispersão (tecla X; só aparece nesse modo)
+        self.scatter_bar = tk.Frame(self.main_frame, bg=self.colors["bg"])
+        self.scatter_x = tk.StringVar(value="GPU %")
+        self.scatter_y = tk.StringVar(value="CPU Temp °C")
+        self.scatter_menus = []
+        for axis, var in (("X", self.scatter_x), ("Y", self.scatter_y)):
+            tk.Label(self.scatter_bar, text=f"{axis}:", font=self.font_help, fg=self.colors["dim"], bg=self.colors["bg"]).pack(side=tk.LEFT, padx=(8, 2))
+            menu = tk.OptionMenu(self.scatter_bar, var, *SCATTER_SERIES, command=lambda _: self._draw_graphs())
+            menu.configure(font=self.font_help, bg=self.colors["panel"], fg=self.colors["text"],
+                           activebackground=self.colors["border"], highlightthickness=0, relief=tk.FLAT)
+            menu.pack(side=tk.LEFT)
+            self.scatter_menus.append(menu)
+        
         # Help bar
         self.help_label = tk.Label(
             self.main_frame,
-            text="[F] Fullscreen | [G] Graphs | [T] Theme | [L] Log | [S] ⚙️ Settings | [Q] Quit",
+            text=tr("footer.hint"),
             font=self.font_help,
             fg=self.colors["dim"],
             bg=self.colors["bg"]
         )
         self.help_label.pack(side=tk.BOTTOM, pady=2)
     
-    def _create_panel(self, parent, title, color):
-        """Cria um painel individual."""
+    def _create_panel(self, parent, title, color, name=None):
+        """Cria um painel individual (name identifica o painel no foco/mute; padrão = título)."""
         frame = tk.Frame(
             parent,
             bg=self.colors["panel"],
@@ -393,15 +989,147 @@ class TelemetryDashboard:
         values_frame = tk.Frame(frame, bg=self.colors["panel"])
         values_frame.pack(fill=tk.BOTH, expand=True, padx=8, pady=5)
         
-        return {"frame": frame, "title": title_lbl, "values": values_frame, "labels": {}, "color": color}
+        return {"frame": frame, "title": title_lbl, "values": values_frame, "labels": {}, "color": color, "name": name or title}
+    
+    def _set_badge(self, panel, text: str) -> None:
+        """Mostra um selo abaixo do título do painel (texto vazio esconde)."""
+        badge = panel.get("badge")
+        if not text:
+            if badge is not None:
+                badge.pack_forget()
+            return
+        if badge is None:
+            badge = panel["badge"] = tk.Label(panel["frame"], font=self.font_small)
+        badge.config(text=f" {text} ", bg=self.colors["critical"], fg=self.colors["panel"])
+        if not badge.winfo_manager():
+            badge.pack(after=panel["title"], pady=(0, 2))
+    
+    def _dim_panels(self) -> None:
+        """Esmaece os valores exibidos: os dados estão ficando velhos."""
+        if not HAS_THEME_MODULE:
+            return
+        for panel in self._grid_panels().values():
+            for label_dict in panel["labels"].values():
+                value = label_dict["value"]
+                value.config(fg=blend_color(value.cget("fg"), self.colors["panel"], 0.6))
+    
+    def _update_diagnostics(self, status) -> None:
+        """Faixa com as fontes de sensores ativas no Sender (e o motivo das ausentes)."""
+        if not status or not HAS_PAYLOAD_MODULE:
+            if self.diag_visible:
+                self.diag_label.pack_forget()
+                self.diag_visible = False
+            return
+        
+        sources = "  ".join(
+            f"{name} {'✓' if status.get(field) else '✗'}"
+            for name, field in (("LHM", "lhm_available"), ("GPU", "gpu_available"),
+                                ("SMART", "smart_available"), ("Admin", "admin"))
+        )
+        hints = sensor_status_hints(status)
+        self.diag_label.config(
+            text=sources + (f"  —  {' | '.join(hints)}" if hints else ""),
+            fg=self.colors["warning"] if hints else self.colors["dim"]
+        )
+        if not self.diag_visible:
+            self.diag_label.pack(after=self.status_label)
+            self.diag_visible = True
+    
+    def _update_system_strip(self, system) -> None:
+        """Faixa com load average e total de processos (Sender antigo não manda a seção)."""
+        if not isinstance(system, dict) or not system.get("process_count"):
+            if self.system_visible:
+                self.system_label.pack_forget()
+                self.system_visible = False
+            return
+        
+        text = tr("system.processes", count=system["process_count"])
+        load = system.get("load_avg") or []
+        if any(load):  # Zerado no Windows
+            text = tr("system.load", load=" ".join(f"{value:.2f}" for value in load)) + "  " + text
+        self.system_label.config(text=text)
+        if not self.system_visible:
+            self.system_label.pack(after=self.diag_label if self.diag_visible else self.status_label)
+            self.system_visible = True
+    
+    def _set_battery_visible(self, visible: bool) -> None:
+        """Mostra/oculta o painel de bateria."""
+        if visible == self.battery_visible:
+            return
+        self.battery_visible = visible
+        if self.focus_panel:
+            return  # Reaparece (ou não) ao voltar para a grade
+        if visible:
+            self.battery_panel["frame"].pack(side=tk.LEFT, fill=tk.BOTH, expand=True, padx=3)
+        else:
+            self.battery_panel["frame"].pack_forget()
+    
+    def _toggle_processes(self, event=None):
+        """Recolhe/expande a lista de processos."""
+        self.processes_collapsed = not self.processes_collapsed
+        panel = self.processes_panel
+        if self.processes_collapsed:
+            panel["values"].pack_forget()
+            panel["title"].configure(text=f"── {tr('panel.PROCESSES')} ▸ ──")
+        else:
+            panel["values"].pack(fill=tk.BOTH, expand=True, padx=8, pady=5)
+            panel["title"].configure(text=f"── {tr('panel.PROCESSES')} ▾ ──")
+    
+    def _update_processes(self, processes: list) -> None:
+        """Atualiza a lista de processos (oculta se o sender não enviar)."""
+        if bool(processes) != self.processes_visible:
+            self.processes_visible = bool(processes)
+            if self.processes_visible and not self.focus_panel:
+                self.processes_row.pack(fill=tk.X, pady=3)
+            else:
+                self.processes_row.pack_forget()
+        
+        if not processes or self.processes_collapsed:
+            return
+        
+        # Linhas já criadas e que sobraram são limpas
+        count = max(len(processes), len(self.processes_panel["labels"]))
+        for i in range(count):
+            if i < len(processes):
+                proc = processes[i]
+                name = proc.get("name", "?")[:10]
+                text = f"{proc.get('cpu_percent', 0):.1f}% {proc.get('mem_mb', 0):.0f}MB"
+            else:
+                name, text = "-", "-"
+            self._update_value(self.processes_panel, f"proc{i}", name, text, "")
+            # O ranking muda a cada pacote: o rótulo também precisa ser atualizado
+            self.processes_panel["labels"][f"proc{i}"]["name"].config(text=f"{name}:")
+    
+    def _update_interfaces(self, interfaces: list) -> None:
+        """Detalha o tráfego por adaptador (só quando há mais de um)."""
+        if len(interfaces) < 2 and not any(k.startswith("if") for k in self.network_panel["labels"]):
+            return
+        
+        count = max(len(interfaces), sum(1 for k in self.network_panel["labels"] if k.startswith("if")))
+        for i in range(count):
+            if i < len(interfaces) and len(interfaces) > 1:
+                name, down, up = interfaces[i]
+                name = f"  {name[:8]}"
+                text = f"↓{down:.0f} ↑{up:.0f}"
+            else:
+                name, text = "  -", "-"
+            self._update_value(self.network_panel, f"if{i}", name, text, "")
+            self.network_panel["labels"][f"if{i}"]["name"].config(text=f"{name}:")
     
     def _precreate_storage_labels(self):
         """Pre-creates storage labels to avoid recreation on each update."""
         for i in range(2):  # Max 2 disks
-            self._update_value(self.storage_panel, f"disk{i}_name", f"Disk {i+1}", "-", "")
-            self._update_value(self.storage_panel, f"disk{i}_temp", "  Temp", 0, "°C")
-            self._update_value(self.storage_panel, f"disk{i}_health", "  Health", 0, "%")
-            self._update_value(self.storage_panel, f"disk{i}_used", "  Used", 0, "%")
+            self._clear_disk_labels(i)
+    
+    def _clear_disk_labels(self, i: int) -> None:
+        """Linhas do disco i sem dados (placeholder)."""
+        self._update_value(self.storage_panel, f"disk{i}_name", tr("row.disk", n=i + 1), "-", "")
+        self._update_value(self.storage_panel, f"disk{i}_temp", f"  {tr('row.temp')}", 0, "°C")
+        self._update_value(self.storage_panel, f"disk{i}_health", tr("row.health"), 0, "%")
+        self._update_value(self.storage_panel, f"disk{i}_used", tr("row.used_space"), 0, "%")
+        self._update_value(self.storage_panel, f"disk{i}_free", tr("row.free"), "-", "")
+        self._update_value(self.storage_panel, f"disk{i}_read", tr("row.read"), 0.0, " KB/s")
+        self._update_value(self.storage_panel, f"disk{i}_write", tr("row.write"), 0.0, " KB/s")
     
     def _bind_keys(self):
         """Configura atalhos de teclado."""
@@ -410,47 +1138,194 @@ class TelemetryDashboard:
         self.root.bind('<F11>', self._toggle_fullscreen)
         self.root.bind('<G>', self._toggle_graphs)
         self.root.bind('<g>', self._toggle_graphs)
+        self.root.bind('<H>', self._toggle_histogram)
+        self.root.bind('<h>', self._toggle_histogram)
+        self.root.bind('<B>', self._toggle_bars)
+        self.root.bind('<b>', self._toggle_bars)
+        self.root.bind('<X>', self._toggle_scatter)
+        self.root.bind('<x>', self._toggle_scatter)
+        self.root.bind('<D>', self._toggle_debug)
+        self.root.bind('<d>', self._toggle_debug)
         self.root.bind('<T>', self._toggle_theme)
         self.root.bind('<t>', self._toggle_theme)
+        self.root.bind('<U>', self._toggle_temp_unit)
+        self.root.bind('<u>', self._toggle_temp_unit)
+        self.root.bind('<A>', self._toggle_alert_log)
+        self.root.bind('<a>', self._toggle_alert_log)
+        self.root.bind('<M>', self._toggle_records)
+        self.root.bind('<m>', self._toggle_records)
+        self.root.bind('<E>', self._toggle_theme_editor)
+        self.root.bind('<e>', self._toggle_theme_editor)
         self.root.bind('<L>', self._toggle_logging)
         self.root.bind('<l>', self._toggle_logging)
+        self.root.bind('<P>', self._export_graphs)
+        self.root.bind('<p>', self._export_graphs)
+        # Pedidos ao Sender (precisa de control_requests no config.json dele)
+        self.root.bind('<plus>', lambda e: self._request_interval(0.5))
+        self.root.bind('<KP_Add>', lambda e: self._request_interval(0.5))
+        self.root.bind('<minus>', lambda e: self._request_interval(2.0))
+        self.root.bind('<KP_Subtract>', lambda e: self._request_interval(2.0))
+        self.root.bind('<R>', self._request_processes)
+        self.root.bind('<r>', self._request_processes)
         self.root.bind('<I>', self._show_ip_config)
         self.root.bind('<i>', self._show_ip_config)
         self.root.bind('<S>', self._show_settings)
         self.root.bind('<s>', self._show_settings)
         self.root.bind('<q>', self._quit_app)
         self.root.bind('<Q>', self._quit_app)
-        self.root.bind('<Escape>', self._quit_app)
+        self.root.bind('<Escape>', self._on_escape)
+        if HAS_LAYOUT_MODULE:
+            self.root.bind('<slash>', self._open_search)
+            self.root.bind('<KP_Divide>', self._open_search)
+        # 1-6 ampliam um painel, 0 volta para a grade
+        for key in "0123456":
+            self.root.bind(key, self._on_focus_key)
+        # Teclado/mouse tiram do modo ocioso; o pacote novo chega por data_wakeup
+        for sequence in ('<Key>', '<Motion>', '<Button>'):
+            self.root.bind_all(sequence, self._on_user_input, add='+')
+    
+    def _on_user_input(self, event=None):
+        """Entrada do usuário: conta como atividade e redesenha na hora se estava ocioso."""
+        self.last_input_time = time.time()
+        if self.ui_idle:
+            self._wake_ui()
+    
+    def _poll_data_wakeup(self):
+        """Confere (na thread da UI) se a recepção pediu um redesenho antecipado."""
+        if self.data_wakeup.is_set():
+            self.data_wakeup.clear()
+            self._wake_ui()
+        self.root.after(WAKEUP_POLL_MS, self._poll_data_wakeup)
+    
+    def _wake_ui(self):
+        """Antecipa o próximo redesenho (pacote novo ou entrada durante o modo ocioso)."""
+        if self.ui_after_id is not None:
+            self.root.after_cancel(self.ui_after_id)
+            self.ui_after_id = None
+        self._update_ui()
     
     def _receiver_loop(self):
         """Thread que recebe dados UDP."""
         while True:
             try:
-                sock = socket.socket(socket.AF_INET, socket.SOCK_DGRAM)
-                sock.setsockopt(socket.SOL_SOCKET, socket.SO_REUSEADDR, 1)
-                sock.bind((HOST, self.porta))
-                sock.settimeout(1.0)
+                multicast = HAS_NETWORK_MODULE and self.connection_mode == "multicast"
+                group = CONFIG.get("multicast_group", "239.255.0.1")
+                if HAS_NETWORK_MODULE:
+                    family = socket_family(self.sender_ip, group if multicast else "", ipv6=CONFIG.get("ipv6", False))
+                    ports, warnings = listen_ports(self.porta, CONFIG.get("ports"))
+                    for warning in warnings:
+                        print(f"[Config] Aviso: {warning}")
+                else:
+                    family, ports = socket.AF_INET, [self.porta]
                 
-                mode_str = f"Manual ({self.sender_ip})" if self.sender_ip else "Auto (broadcast)"
-                print(f"[Receiver] Ouvindo em {HOST}:{self.porta} - Modo: {mode_str}")
+                if multicast:
+                    mode_str = f"Multicast ({group})"
+                else:
+                    mode_str = f"Manual ({self.sender_ip})" if self.sender_ip else "Auto (broadcast)"
+                
+                # Um socket por porta (ports no config); todos alimentam o mesmo loop
+                sockets = {}
+                try:
+                    for port in ports:
+                        if HAS_NETWORK_MODULE:
+                            sock = open_udp_socket(family)
+                            listen = bind_address("", port, family)
+                        else:
+                            sock = socket.socket(socket.AF_INET, socket.SOCK_DGRAM)
+                            listen = (HOST, port)
+                        sockets[port] = sock
+                        sock.setsockopt(socket.SOL_SOCKET, socket.SO_REUSEADDR, 1)
+                        sock.bind(listen)
+                        sock.settimeout(1.0)
+                        if multicast:
+                            join_multicast_group(sock, group)
+                        listen_str = format_address(*listen) if HAS_NETWORK_MODULE else f"{HOST}:{port}"
+                        print(f"[Receiver] Ouvindo em {listen_str} - Modo: {mode_str}")
+                except OSError:
+                    for sock in sockets.values():
+                        sock.close()
+                    raise
+                self.control_sock = sockets[ports[0]]
+                self.listen_ports = ports
+                
+                if HAS_NETWORK_MODULE:
+                    listener = PortListener(sockets)
+                    receive = listener.recvfrom
+                else:
+                    listener, sock = None, sockets[ports[0]]
+                    
+                    def receive():
+                        data, addr = sock.recvfrom(RECV_BUFFER_SIZE if HAS_PROTOCOL_MODULE else 16384)
+                        return data, addr, ports[0]
                 
                 self.restart_receiver = False
                 
                 while not self.restart_receiver:
                     try:
-                        data, addr = sock.recvfrom(16384)
+                        data, addr, port = receive()
+                        reply_addr = addr  # Endereço cru (IPv6 completo) para os pedidos de controle
+                        if HAS_NETWORK_MODULE:
+                            # IPv6 vem como (host, porta, flowinfo, scope); IPv4 em dual-stack como ::ffff:
+                            addr = (normalize_host(addr[0]), addr[1])
+                        source_str = format_address(*addr) if HAS_NETWORK_MODULE else f"{addr[0]}:{addr[1]}"
                         
                         # Debug: mostrar de onde veio o pacote
-                        print(f"[Receiver] Pacote recebido de {addr[0]}:{addr[1]} ({len(data)} bytes)")
+                        print(f"[Receiver] Pacote recebido de {source_str} na porta {port} ({len(data)} bytes)")
+                        wire_size = len(data)
                         
-                        # Se modo manual, filtra por IP
-                        if self.sender_ip and addr[0] != self.sender_ip:
+                        # Se modo manual, filtra por IP (multi_sender aceita todos)
+                        if self.sender_ip and not self.multi_sender and addr[0] != self.sender_ip:
                             print(f"[Receiver] Ignorando pacote de {addr[0]} (esperado: {self.sender_ip})")
                             continue
                         
+                        # Autenticação/criptografia antes do magic byte (descarta se falhar).
+                        # Vale também no modo manual: o filtro de IP não impede spoofing.
+                        if HAS_PROTOCOL_MODULE and data:
+                            try:
+                                data = unwrap_frame(check_datagram(data), self.encryption_key, self.auth_secret)
+                            except ProtocolError as e:
+                                print(f"[Receiver] Frame descartado de {addr[0]}: {e}")
+                                self._record_decode_error(e)
+                                continue
+                            
+                            # Sender encerrando: desconecta na hora em vez de esperar o timeout
+                            try:
+                                goodbye = decode_goodbye(data)
+                            except ProtocolError as e:
+                                print(f"[Receiver] Frame descartado de {addr[0]}: {e}")
+                                self._record_decode_error(e)
+                                continue
+                            if goodbye is not None:
+                                self._sender_offline(addr[0], goodbye.get("hostname", ""))
+                                continue
+                        
                         # Magic byte: 0x01 = gzip, 0x00 = raw JSON
+                        # Com o módulo de protocolo, gzip e listas têm limite de tamanho
+                        if HAS_PROTOCOL_MODULE:
+                            try:
+                                if is_delta_frame(data):
+                                    data = self.delta_decoder.decode(data, addr[0])
+                                else:
+                                    data = extract_body(data, legacy=self.accept_legacy)
+                                payload = parse_body(data)
+                            except VersionMismatchError as e:
+                                # Não é corrupção: a tela explica em vez de só "Waiting for data"
+                                if self.version_mismatch != (e.sender_version, e.receiver_version):
+                                    print(f"[Receiver] {e} ({addr[0]}); atualize o sender ou o receiver")
+                                with self.data_lock:
+                                    self.mismatch_packets += 1
+                                    self.version_mismatch = (e.sender_version, e.receiver_version)
+                                    self.version_mismatch_time = time.time()
+                                self._record_decode_error(e)
+                                continue
+                            except ProtocolError as e:
+                                print(f"[Receiver] Frame descartado de {addr[0]}: {e}")
+                                self._record_decode_error(e)
+                                continue
+                            with self.data_lock:
+                                self.decode_stats.record_ok()
                         # Retrocompatível: se não começar com 0x00 ou 0x01, tenta gzip
-                        if len(data) > 0:
+                        elif len(data) > 0:
                             magic = data[0]
                             if magic == 0x01:  # GZIP
                                 data = gzip.decompress(data[1:])
@@ -463,48 +1338,173 @@ class TelemetryDashboard:
                                 except:
                                     pass
                         
-                        payload = json.loads(data.decode())
+                        if not HAS_PROTOCOL_MODULE:
+                            payload = json.loads(data.decode())
                         
                         # Debug: confirmar que o payload foi parseado
                         cpu_usage = payload.get("cpu", {}).get("usage", 0)
                         print(f"[Receiver] Payload OK - CPU: {cpu_usage}%")
                         
+                        if JSON_STREAM:
+                            self._write_json_stream(payload, addr[0])
+                        if self.exporter:
+                            self.exporter.update(addr[0], payload)
+                        if self.records and self.records.update(payload.get("hostname") or addr[0], payload):
+                            self.records_dirty = True
+                        
                         with self.data_lock:
+                            if self.traffic:
+                                self.traffic.record(wire_size)
+                            self.last_json_size = len(data)
+                            
+                            # Multi-sender: guarda todos, mas só o host selecionado vai para a tela
+                            if self.sources is not None:
+                                self.sources.update(addr[0], payload)
+                                if not self.selected_source:
+                                    self.selected_source = addr[0]
+                                if addr[0] != self.selected_source:
+                                    continue
+                            
                             self.current_data = payload
+                            self.last_source = source_str
+                            self.control_addr = reply_addr
+                            self.control_sock = sockets[port]  # Resposta sai pela porta onde o Sender é ouvido
+                            self.last_port = port
                             self.last_data_time = time.time()
+                            if self.ui_idle:
+                                # UI no ritmo ocioso: redesenha já em vez de esperar o próximo ciclo
+                                self.ui_idle = False
+                                self.data_wakeup.set()
+                            if self.packet_interval:
+                                self.packet_interval.record(self.last_data_time)
                             
-                            # Atualiza históricos
-                            self.history["cpu_usage"].append(payload.get("cpu", {}).get("usage", 0))
-                            self.history["cpu_temp"].append(payload.get("cpu", {}).get("temp", 0))
-                            self.history["gpu_load"].append(payload.get("gpu", {}).get("load", 0))
-                            self.history["gpu_temp"].append(payload.get("gpu", {}).get("temp", 0))
-                            self.history["ram"].append(payload.get("ram", {}).get("percent", 0))
-                            self.history["net_down"].append(payload.get("network", {}).get("down_kbps", 0))
-                            self.history["net_up"].append(payload.get("network", {}).get("up_kbps", 0))
-                            self.history["ping"].append(payload.get("network", {}).get("ping_ms", 0))
+                            # Pacotes perdidos viram lacunas no gráfico
+                            timestamp = payload.get("timestamp")
+                            self._push_gaps(timestamp)
                             
+                            # Atualiza históricos
+                            self._push_samples({
+                                "cpu_usage": payload.get("cpu", {}).get("usage", 0),
+                                "cpu_temp": payload.get("cpu", {}).get("temp", 0),
+                                "gpu_load": payload.get("gpu", {}).get("load", 0),
+                                "gpu_temp": payload.get("gpu", {}).get("temp", 0),
+                                "ram": payload.get("ram", {}).get("percent", 0),
+                                "net_down": payload.get("network", {}).get("down_kbps", 0),
+                                "net_up": payload.get("network", {}).get("up_kbps", 0),
+                                # ping_ms = -1 indica host inalcançável; o gráfico mostra 0
+                                "ping": max(payload.get("network", {}).get("ping_ms", 0), 0),
+                            }, timestamp if timestamp is not None else self.last_data_time)
+
                     except socket.timeout:
                         continue
                     except Exception as e:
                         print(f"[Receiver] Erro: {e}")
                 
-                sock.close()
+                if listener:
+                    listener.close()
+                else:
+                    sock.close()
                 print("[Receiver] Reiniciando com novas configurações...")
-                
+
             except Exception as e:
                 print(f"[Receiver] Erro ao criar socket: {e}")
                 time.sleep(2)
     
-    def _update_value(self, panel, key, label, value, unit="", warn_threshold=None, crit_threshold=None):
-        """Atualiza ou cria um valor em um painel."""
+    def _record_decode_error(self, error: Exception) -> None:
+        """Conta um frame recusado para o overlay de debug."""
+        with self.data_lock:
+            self.decode_stats.record_error(error, time.time())
+    
+    def _sender_offline(self, host: str, hostname: str) -> None:
+        """Marca o Sender como desconectado após o frame de despedida."""
+        print(f"[Receiver] Sender {hostname or host} encerrou")
+        if self.delta_decoder is not None:
+            self.delta_decoder.forget(host)
+        with self.data_lock:
+            if self.sources is not None:
+                self.sources.mark_offline(host)
+                if not goodbye_resets_connection(host, self.selected_source):
+                    return
+            self.last_data_time = 0  # A UI passa para "Disconnected" no próximo ciclo
+            self.last_payload_ts = None
+            self._flush_aggregator()
+    
+    def _push_gaps(self, timestamp) -> None:
+        """Insere lacunas (NaN) nos gráficos para os intervalos sem pacote (chamado com data_lock)."""
+        previous, self.last_payload_ts = self.last_payload_ts, timestamp
+        if not HAS_SERIES_MODULE or not CONFIG.get("history_gaps", True):
+            return
+        interval = self._sample_interval()
+        gaps = gap_samples(previous, timestamp, interval, self.history["cpu_usage"].maxlen)
+        if gaps:
+            # O balde aberto é anterior à lacuna: entra no gráfico antes dela
+            self._flush_aggregator()
+            for series in self.history.values():
+                series.extend([GAP] * gaps)
+    
+    def _sample_interval(self):
+        """Segundos entre amostras dos gráficos (intervalo dos pacotes ou o balde de agregação)."""
+        packet = self.packet_interval.interval if self.packet_interval else None
+        if not HAS_SERIES_MODULE:
+            return packet
+        return sample_interval(packet, self.aggregate_secs)
+    
+    def _push_samples(self, values: dict, timestamp: float) -> None:
+        """
+        Adiciona um pacote aos gráficos, direto ou pela média do balde (chamado com data_lock).
+        
+        timestamp: instante do payload (o mesmo relógio das lacunas; horário
+        local só em senders antigos sem timestamp).
+        """
+        if self.aggregator:
+            values = self.aggregator.add(timestamp, values)
+            if values is None:  # Balde ainda aberto
+                return
+        for key, value in values.items():
+            self._push_history(key, value)
+    
+    def _flush_aggregator(self) -> None:
+        """Fecha o balde aberto e leva a média aos gráficos (chamado com data_lock)."""
+        if not self.aggregator:
+            return
+        means = self.aggregator.flush()
+        if means:
+            for key, value in means.items():
+                self._push_history(key, value)
+    
+    def _push_history(self, key: str, value: float) -> None:
+        """Adiciona valor ao histórico dos gráficos (suavizado se configurado)."""
+        value = history_sample(value)  # Sensor ausente: lacuna no gráfico
+        if isinstance(value, float) and math.isnan(value):  # Lacuna: não entra na suavização
+            self.history[key].append(value)
+            return
+        if self.smoother:
+            value = self.smoother.update(key, value)
+        self.history[key].append(value)
+    
+    def _remove_value(self, panel, key):
+        """Remove a linha de um valor do painel (se existir)."""
+        entry = panel["labels"].pop(key, None)
+        if entry:
+            entry["row"].destroy()
+    
+    def _update_value(self, panel, key, label, value, unit="", warn_threshold=None, crit_threshold=None, level=None):
+        """
+        Atualiza ou cria um valor em um painel.
+        
+        O nível de alerta vem dos thresholds (maior = pior) ou, para métricas
+        com outra regra (ex: velocidade do link), já calculado em `level`;
+        nível "" marca a métrica como monitorada mas normal.
+        """
         if key not in panel["labels"]:
             row = tk.Frame(panel["values"], bg=self.colors["panel"])
             row.pack(fill=tk.X, pady=1)
+            name_font, value_font = self._panel_fonts(panel)
             
             lbl_name = tk.Label(
                 row,
                 text=f"{label}:",
-                font=self.font_small,
+                font=name_font,
                 fg=self.colors["dim"],
                 bg=self.colors["panel"],
                 anchor="w",
@@ -515,7 +1515,7 @@ class TelemetryDashboard:
             lbl_value = tk.Label(
                 row,
                 text="-",
-                font=self.font_value,
+                font=value_font,
                 fg=self.colors["text"],
                 bg=self.colors["panel"],
                 anchor="e",
@@ -523,34 +1523,323 @@ class TelemetryDashboard:
             )
             lbl_value.pack(side=tk.RIGHT)
             
-            panel["labels"][key] = {"name": lbl_name, "value": lbl_value, "row": row}
+            # Clique silencia/reativa os alertas da métrica
+            metric = f"{panel['name']}.{key}"
+            for widget in (lbl_name, lbl_value):
+                widget.bind("<Button-1>", lambda e, m=metric: self._toggle_mute(m))
+            
+            panel["labels"][key] = {"name": lbl_name, "value": lbl_value, "row": row, "label": label}
         
         lbl = panel["labels"][key]["value"]
-        
-        # Formata valor
-        if isinstance(value, float):
+        metric = f"{panel['name']}.{key}"
+        shown, expired = self._held_value(metric, value)
+        
+        # Formata valor (None = sensor ausente -> "—")
+        if HAS_FORMATTING_MODULE:
+            text = format_value(
+                shown, unit, self.temp_unit,
+                precision=self.display_precision, compact=CONFIG.get("compact_values", False),
+                number_locale=self.number_locale
+            )
+        elif shown is None:
+            text = "—"
+        elif isinstance(shown, float):
             if unit == "V":
-                text = f"{value:.3f}{unit}"
+                text = f"{shown:.3f}{unit}"
             elif unit in ["°C", "%", "W"]:
-                text = f"{value:.1f}{unit}"
+                text = f"{shown:.1f}{unit}"
             else:
-                text = f"{value:.1f}{unit}"
+                text = f"{shown:.1f}{unit}"
         else:
-            text = f"{value}{unit}"
+            text = f"{shown}{unit}"
         
         lbl.config(text=text)
         
-        # Cor baseada em thresholds
-        if crit_threshold and isinstance(value, (int, float)) and value >= crit_threshold:
+        monitored = level is not None or bool(warn_threshold or crit_threshold)
+        if level is None and monitored:
+            if HAS_ALERTS_MODULE:
+                level = alert_level(value, warn_threshold, crit_threshold)
+            elif isinstance(value, (int, float)):
+                # Sem o módulo de alertas: só a cor, direto dos thresholds
+                if crit_threshold and value >= crit_threshold:
+                    level = "critical"
+                elif warn_threshold and value >= warn_threshold:
+                    level = "warning"
+        level = level or None
+        
+        # Mute expira ao voltar ao normal
+        if self.mutes and monitored:
+            self.mutes.update(metric, level)
+        muted = bool(self.mutes) and self.mutes.is_muted(metric)
+        panel["labels"][key]["name"].config(text=f"🔇{label}:" if muted else f"{label}:")
+        
+        # Cor pelo nível (continua colorido mesmo silenciado)
+        if level is None:
+            lbl.config(fg=self.colors["text"])
+        elif getattr(level, "value", level) == "critical":
             lbl.config(fg=self.colors["critical"])
-            self._notify_critical(key, label, value, unit)
-        elif warn_threshold and isinstance(value, (int, float)) and value >= warn_threshold:
+            if not muted:
+                self._notify_critical(key, label, value, unit)
+        else:
             lbl.config(fg=self.colors["warning"])
+        
+        # Som ao entrar em Warning/Critical (cooldown global no SoundManager)
+        if self.alert_tracker and monitored:
+            transition = self.alert_tracker.update(metric, level)
+            if transition and self.alert_log is not None:
+                self.alert_log.record(metric, format_value(value, unit, self.temp_unit, number_locale=self.number_locale) if HAS_FORMATTING_MODULE else value, transition)
+                self.alert_log_dirty = True
+            if transition and not muted:
+                self._play_alert_sound(transition, metric)
+        
+        # Passou de max_hold_frames sem pacote: último valor real, esmaecido
+        if expired:
+            lbl.config(fg=self.colors["dim"])
+        
+        # Cor do nível, restaurada quando a busca deixa de esmaecer a linha
+        panel["labels"][key]["value_fg"] = lbl.cget("fg")
+        if self.search_query:
+            self._filter_row(panel, key)
+    
+    def _metric_thresholds(self, alertas, metric):
+        """(warning, critical) de potência/voltagem/VRAM; (None, None) sem o módulo de alertas."""
+        if not HAS_ALERTS_MODULE:
+            return None, None
+        return metric_thresholds(alertas, metric)
+    
+    def _held_value(self, metric: str, value):
+        """
+        Valor exibido com max_hold_frames (extrapolado entre pacotes)
+        
+        Returns:
+            (valor, obsoleto): obsoleto = sem pacote há mais de max_hold_frames
+        """
+        if not self.max_hold_frames or not HAS_SMOOTHING_MODULE or not isinstance(value, (int, float)):
+            return value, False
+        interval = self.packet_interval.interval if self.packet_interval else None
+        if not interval:
+            return value, False
+        hold = self.holds.get(metric)
+        if hold is None:
+            hold = self.holds[metric] = HoldState(self.max_hold_frames)
+        hold.update(value, self.last_data_time)
+        held = hold.value(time.time(), interval)
+        return (value, True) if held is None else (held, False)
+    
+    def _toggle_alert_log(self, event=None):
+        """Mostra/oculta o log das últimas transições de alerta."""
+        if self.alert_log is None:
+            return
+        self.alert_log_visible = not self.alert_log_visible
+        if self.alert_log_visible and not self.focus_panel:
+            self.alert_log_row.pack(fill=tk.X, pady=3)
+            self._render_alert_log()
         else:
-            lbl.config(fg=self.colors["text"])
+            self.alert_log_row.pack_forget()
+    
+    def _toggle_records(self, event=None):
+        """Mostra/oculta os recordes de todos os tempos do Sender exibido."""
+        if self.records is None:
+            return
+        self.records_visible = not self.records_visible
+        if self.records_visible and not self.focus_panel:
+            self.records_row.pack(fill=tk.X, pady=3)
+            self._render_records()
+        else:
+            self.records_row.pack_forget()
+    
+    def _records_host(self) -> str:
+        """Chave dos recordes do Sender exibido (hostname, ou IP para Senders antigos)."""
+        with self.data_lock:
+            data = self.current_data or {}
+            return data.get("hostname") or self.last_source.rsplit(":", 1)[0].strip("[]")
+    
+    def _render_records(self) -> None:
+        """Redesenha os recordes: máxima e, para temperaturas/ping, mínima."""
+        self.records_dirty = False
+        self.records_list.delete(0, tk.END)
+        
+        records = self.records.host_records(self._records_host())
+        if not records:
+            self.records_list.insert(tk.END, "Nenhum recorde ainda")
+            self.records_list.itemconfig(0, fg=self.colors["dim"])
+            return
+        
+        def fmt(value, unit):
+            return format_value(value, unit, self.temp_unit, number_locale=self.number_locale) if HAS_FORMATTING_MODULE else f"{value}{unit}"
+        
+        for key, label, unit, _ in RECORD_METRICS:
+            record = records.get(key)
+            if not record:
+                continue
+            when = datetime.fromtimestamp(record["max_at"]).strftime("%Y-%m-%d %H:%M")
+            line = f"{label:<10} max {fmt(record['max'], unit):>12}  {when}"
+            if "min" in record:
+                when = datetime.fromtimestamp(record["min_at"]).strftime("%Y-%m-%d %H:%M")
+                line += f"   min {fmt(record['min'], unit):>12}  {when}"
+            self.records_list.insert(tk.END, line)
+    
+    def _toggle_theme_editor(self, event=None):
+        """Mostra/oculta o editor de cores do tema atual."""
+        if not HAS_THEME_MODULE:
+            return
+        self.theme_editor_visible = not self.theme_editor_visible
+        if self.theme_editor_visible and not self.focus_panel:
+            if not self.theme_editor_swatches:
+                self._build_theme_editor()
+            self._refresh_theme_editor()
+            self.theme_editor_row.pack(fill=tk.X, pady=3)
+        else:
+            self.theme_editor_row.pack_forget()
+    
+    def _build_theme_editor(self) -> None:
+        """Cria um seletor por cor do tema, o nome do tema e o botão Save."""
+        values = self.theme_editor_panel["values"]
+        grid = tk.Frame(values)
+        grid.pack(fill=tk.X)
+        self.theme_editor_widgets.append(grid)
+        
+        for i, field in enumerate(COLOR_FIELDS):
+            row, col = divmod(i, 5)
+            label = tk.Label(grid, text=field, font=self.font_small, anchor="e", width=9)
+            label.grid(row=row, column=col * 2, sticky="e", padx=(6, 2), pady=2)
+            swatch = tk.Button(grid, width=4, relief="flat", cursor="hand2",
+                               command=lambda f=field: self._pick_theme_color(f))
+            swatch.grid(row=row, column=col * 2 + 1, sticky="w", pady=2)
+            self.theme_editor_widgets.append(label)
+            self.theme_editor_swatches[field] = swatch
+        
+        actions = tk.Frame(values)
+        actions.pack(fill=tk.X, pady=(5, 0))
+        name_label = tk.Label(actions, text="Name:", font=self.font_small)
+        name_label.pack(side=tk.LEFT, padx=(6, 0))
+        self.theme_editor_name = tk.Entry(actions, font=self.font_small, relief="flat", width=16)
+        self.theme_editor_name.pack(side=tk.LEFT, padx=5)
+        save_button = tk.Button(actions, text="💾 Save", font=self.font_small, relief="flat",
+                                cursor="hand2", command=self._save_edited_theme)
+        save_button.pack(side=tk.LEFT)
+        self.theme_editor_status = tk.Label(actions, text="", font=self.font_small)
+        self.theme_editor_status.pack(side=tk.LEFT, padx=10)
+        self.theme_editor_widgets.extend([actions, name_label, self.theme_editor_name, save_button, self.theme_editor_status])
+    
+    def _refresh_theme_editor(self) -> None:
+        """Sincroniza as amostras de cor e o fundo do editor com o tema atual."""
+        for widget in self.theme_editor_widgets:
+            widget.configure(bg=self.colors["panel"])
+            if not isinstance(widget, tk.Frame):
+                widget.configure(fg=self.colors["dim"])
+        self.theme_editor_name.configure(fg=self.colors["text"], insertbackground=self.colors["text"])
+        for field, swatch in self.theme_editor_swatches.items():
+            color = self.colors.get(field, "#000000")
+            swatch.configure(bg=color, activebackground=color)
+        if not self.theme_editor_name.get():
+            self.theme_editor_name.insert(0, f"{self.theme_name}_custom")
+    
+    def _pick_theme_color(self, field: str) -> None:
+        """Abre o seletor de cor do campo e aplica a escolha na hora."""
+        rgb, _ = colorchooser.askcolor(self.colors.get(field), parent=self.root, title=f"Theme: {field}")
+        if rgb is None:
+            return
+        color = rgb_to_hex(rgb)
+        # Cópia: não altera o dicionário compartilhado do tema embutido
+        self.colors = dict(self.colors)
+        self.colors[field] = color
+        # Títulos dos painéis guardam a cor do setor na criação
+        sectors = {
+            "cpu": (self.cpu_panel, self.processes_panel), "gpu": (self.gpu_panel,),
+            "ram": (self.ram_panel,), "mobo": (self.mobo_panel,), "storage": (self.storage_panel,),
+            "network": (self.network_panel,), "warning": (self.battery_panel,),
+            "critical": (self.alert_log_panel,), "title": (self.records_panel, self.theme_editor_panel)
+        }
+        for panel in sectors.get(field, ()):
+            panel["color"] = color
+        self._apply_theme()
+    
+    def _save_edited_theme(self) -> None:
+        """Grava as cores atuais como tema customizado e passa a usá-lo."""
+        name = self.theme_editor_name.get().strip()
+        try:
+            save_custom_theme(THEMES_PATH, name, self.colors)
+        except (ValueError, OSError) as e:
+            self.theme_editor_status.config(text=f"❌ {e}", fg=self.colors["critical"])
+            return
+        self.theme_name = name
+        CONFIG["tema"] = name  # Salvo no ui_state.json ao sair
+        self.theme_editor_status.config(text=f"✅ Saved to {os.path.basename(THEMES_PATH)}", fg=self.colors["gpu"])
+    
+    def _render_alert_log(self) -> None:
+        """Redesenha o log de alertas (mais recentes primeiro)."""
+        self.alert_log_dirty = False
+        self.alert_log_list.delete(0, tk.END)
+        
+        entries = self.alert_log.entries()
+        if not entries:
+            self.alert_log_list.insert(tk.END, "Nenhum alerta")
+            self.alert_log_list.itemconfig(0, fg=self.colors["dim"])
+            return
+        
+        for i, entry in enumerate(entries):
+            when = datetime.fromtimestamp(entry.timestamp).strftime("%H:%M:%S")
+            self.alert_log_list.insert(tk.END, f"{when}  {entry.level.value.upper():<8} {entry.metric:<22} {entry.value}")
+            color = self.colors["critical"] if entry.level == AlertLevel.CRITICAL else self.colors["warning"]
+            self.alert_log_list.itemconfig(i, fg=color)
+    
+    def _toggle_mute(self, metric: str) -> None:
+        """Silencia uma métrica em alerta (ou reativa, se já silenciada)."""
+        if not self.mutes:
+            return
+        if self.mutes.is_muted(metric):
+            self.mutes.unmute(metric)
+            print(f"[Alert] {metric}: notificações reativadas")
+        elif self.alert_tracker and self.alert_tracker.levels.get(metric):
+            self.mutes.mute(metric)
+            print(f"[Alert] {metric}: silenciado por {self.mutes.duration_seconds / 60:.0f} min")
+    
+    def _init_sound_manager(self):
+        """Cria o SoundManager com a seção "sons" do config."""
+        if not HAS_SOUND_MODULE:
+            return None
+        sons_config = CONFIG.get("sons", {})
+        return init_sounds(SoundConfig(
+            enabled=sons_config.get("enabled", True),
+            cooldown_seconds=sons_config.get("cooldown_seconds", 10),
+            warning_sound=sons_config.get("warning_sound", "warning"),
+            critical_sound=sons_config.get("critical_sound", "beep_urgent"),
+            connection_lost_sound=sons_config.get("connection_lost_sound", "beep_low"),
+            per_metric=sons_config.get("per_metric", {})
+        ))
+    
+    def _play_alert_sound(self, level, metric="") -> None:
+        """Toca o som configurado para a métrica/nível (em thread, não bloqueia a UI)."""
+        if not self.sound_manager:
+            return
+        try:
+            self.sound_manager.play_alert(metric, level == AlertLevel.CRITICAL)
+        except Exception as e:
+            print(f"[Sound] Erro: {e}")
+    
+    def _notify_connection_lost(self, hostname) -> None:
+        """Som e notificação únicos quando o Sender conectado para de enviar."""
+        name = hostname or "sender"
+        print(f"[Watchdog] Conexão perdida com {name}")
+        if self.sound_manager:
+            try:
+                self.sound_manager.play_connection_lost()
+            except Exception as e:
+                print(f"[Sound] Erro: {e}")
+        if self.toaster:
+            try:
+                self.toaster.show_toast(
+                    "📡 Telemetry - Connection lost",
+                    f"No data from {name} for {CONNECTION_TIMEOUT:.0f}s",
+                    duration=5,
+                    threaded=True
+                )
+            except:
+                pass
     
     def _notify_critical(self, key: str, label: str, value: float, unit: str) -> None:
-        """Sends Windows notification and plays sound for critical values."""
+        """Sends Windows notification for critical values (sound is played on the transition)."""
         now = time.time()
         last_notify = self.notified_critical.get(key, 0)
         
@@ -562,14 +1851,6 @@ class TelemetryDashboard:
         if now - last_notify > cooldown:
             self.notified_critical[key] = now
             
-            # Play alert sound only if enabled in config
-            if HAS_SOUND_MODULE and sons_config.get("enabled", True):
-                try:
-                    sound_manager = get_sound_manager()
-                    sound_manager.play(AlertSound.BEEP_URGENT)
-                except:
-                    pass
-            
             # Show Windows notification
             if self.toaster:
                 try:
@@ -585,12 +1866,22 @@ class TelemetryDashboard:
     def _update_ui(self):
         """Updates the interface with the latest data."""
         try:
+            if self.sources is not None:
+                self._update_sources()
+            self._resize_history()
+            if self.records:
+                self.records.flush()  # Só grava com recorde novo e a cada records_save_secs
+            
             with self.data_lock:
                 data = self.current_data.copy() if self.current_data else None
                 last_time = self.last_data_time
+                mismatch = self.version_mismatch if self.version_mismatch_time > last_time else None
             
             now = time.time()
-            time_diff = now - last_time if last_time else float('inf')
+            if HAS_SOURCES_MODULE:
+                time_diff = elapsed_since(last_time, now)
+            else:
+                time_diff = now - last_time if last_time else float('inf')
             
             # Update window title with timestamp
             self.root.title(f"Telemetry Center - {time.strftime('%H:%M:%S')}")
@@ -599,18 +1890,39 @@ class TelemetryDashboard:
             if data:
                 print(f"[UI] Data available, time_diff={time_diff:.1f}s, timeout={CONNECTION_TIMEOUT}s")
             
-            # Check connection timeout
-            if data and (now - last_time) < CONNECTION_TIMEOUT:
+            # Check connection timeout (metade do timeout sem dados = esmaecido)
+            if HAS_SOURCES_MODULE:
+                state = connection_state(time_diff, CONNECTION_TIMEOUT)
+            else:
+                state = "fresh" if time_diff < CONNECTION_TIMEOUT else "disconnected"
+            connected = bool(data) and state != "disconnected"
+            if self.connection_watchdog and self.connection_watchdog.update(connected, now):
+                self._notify_connection_lost(self.last_hostname)
+            if connected:
                 if not self.is_connected:
                     self.is_connected = True
                 
+                # Senders antigos não mandam hostname/uptime: banner mostra o endereço
+                hostname = data.get("hostname") or self.last_source
+                self.last_hostname = hostname
+                uptime = format_uptime(data.get("uptime_secs")) if HAS_FORMATTING_MODULE else ""
+                stale = state == "stale"
                 self.status_label.config(
-                    text=f"● Connected | Updated: {time.strftime('%H:%M:%S')}" + 
+                    text=tr("status.stale" if stale else "status.connected") + 
+                         (tr("status.host", host=hostname) if hostname else "") + 
+                         (tr("status.uptime", uptime=uptime) if uptime else "") + 
+                         (tr("status.no_data_for", secs=f"{time_diff:.0f}") if stale else tr("status.updated", time=time.strftime('%H:%M:%S'))) + 
+                         (tr("status.senders", count=len(self.source_tabs)) if self.sources is not None else "") + 
+                         (tr("status.port", port=self.last_port) if len(self.listen_ports) > 1 and self.last_port else "") + 
                          (f" | 📝 LOG" if self.logging_enabled else ""),
-                    fg=self.colors["gpu"]
+                    fg=self.colors["warning"] if stale else self.colors["gpu"]
                 )
                 
                 self._update_panels(data)
+                self._update_diagnostics(data.get("sensor_status"))
+                self._update_system_strip(data.get("system"))
+                if stale:
+                    self._dim_panels()
                 
                 # Log CSV
                 if self.logging_enabled:
@@ -619,22 +1931,37 @@ class TelemetryDashboard:
                 # Graphs
                 if self.show_graphs:
                     self._draw_graphs()
+                
+                if self.show_debug:
+                    self._update_debug_overlay()
             else:
                 if self.is_connected:
                     self.is_connected = False
+                    with self.data_lock:
+                        self._flush_aggregator()  # Último balde não espera o Sender voltar
                 
-                mode_text = f" (IP: {self.sender_ip})" if self.sender_ip else " (broadcast)"
-                self.status_label.config(
-                    text=f"○ Disconnected - Waiting for data...{mode_text} | [I] Config",
-                    fg=self.colors["critical"]
-                )
+                if self.connection_mode == "multicast":
+                    mode_text = f" (multicast {CONFIG.get('multicast_group', '239.255.0.1')})"
+                else:
+                    mode_text = f" (IP: {self.sender_ip})" if self.sender_ip else " (broadcast)"
+                if mismatch:
+                    status = tr("status.version_mismatch", sender=mismatch[0], receiver=mismatch[1])
+                else:
+                    status = tr("status.disconnected", mode=mode_text)
+                self.status_label.config(text=f"{status} | [I] Config", fg=self.colors["critical"])
         
         except Exception as e:
             print(f"[UI] Update error: {e}")
         
         # Schedule next update (always, even on error)
         try:
-            self.root.after(500, self._update_ui)
+            if HAS_REDRAW_MODULE:
+                last_activity = max(self.last_data_time, self.last_input_time)
+                interval = render_interval(self.max_fps, self.idle_fps, self.idle_after, last_activity, time.time())
+                self.ui_idle = interval > render_interval(self.max_fps, 0, 0, 0, 0)
+            else:
+                interval = 0.5
+            self.ui_after_id = self.root.after(int(interval * 1000), self._update_ui)
         except Exception as e:
             print(f"[UI] Error scheduling update: {e}")
     
@@ -645,36 +1972,74 @@ class TelemetryDashboard:
         
         # CPU
         cpu = data.get("cpu", {})
-        self._update_value(self.cpu_panel, "usage", "Uso", cpu.get("usage", 0), "%", 
+        self._update_value(self.cpu_panel, "usage", tr("row.usage"), cpu.get("usage", 0), "%", 
                           alertas.get("cpu_uso_warning", 70), alertas.get("cpu_uso_critical", 90))
-        self._update_value(self.cpu_panel, "temp", "Temp", cpu.get("temp", 0), "°C", 
+        self._update_value(self.cpu_panel, "temp", tr("row.temp"), cpu.get("temp", 0), "°C", 
                           alertas.get("cpu_temp_warning", 70), alertas.get("cpu_temp_critical", 85))
-        self._update_value(self.cpu_panel, "voltage", "Voltagem", cpu.get("voltage", 0), "V")
-        self._update_value(self.cpu_panel, "power", "Consumo", cpu.get("power", 0), "W")
-        self._update_value(self.cpu_panel, "clock", "Clock", cpu.get("clock", 0), " MHz")
-        
-        # GPU
+        self._update_value(self.cpu_panel, "voltage", tr("row.voltage"), cpu.get("voltage", 0), "V",
+                          *self._metric_thresholds(alertas, "cpu_voltage"))
+        self._update_value(self.cpu_panel, "power", tr("row.power"), cpu.get("power", 0), "W",
+                          *self._metric_thresholds(alertas, "cpu_power"))
+        self._update_value(self.cpu_panel, "clock", tr("row.clock"), cpu.get("clock", 0), " MHz")
+        # Origem do clock (Sender antigo não manda): o nominal do Windows não acompanha o boost
+        clock_source = cpu.get("clock_source")
+        known = clock_source in ("lhm", "os", "os_base")
+        if known or "clock_source" in self.cpu_panel["labels"]:
+            self._update_value(self.cpu_panel, "clock_source", tr("row.clock_source"),
+                               tr(f"clock_source.{clock_source}") if known else None)
+        self._set_badge(self.cpu_panel, "THROTTLING" if cpu.get("throttling") else "")
+        
+        # GPU (notebook só com a integrada da Intel: sinaliza no título)
         gpu = data.get("gpu", {})
-        self._update_value(self.gpu_panel, "load", "Uso", gpu.get("load", 0), "%", 
+        gpu_title = "── GPU (iGPU) ──" if gpu.get("source") == "intel" else "── GPU ──"
+        if self.gpu_panel["title"].cget("text") != gpu_title:
+            self.gpu_panel["title"].configure(text=gpu_title)
+        self._set_badge(self.gpu_panel, "THROTTLING" if gpu.get("throttling") else "")
+        self._update_value(self.gpu_panel, "load", tr("row.usage"), gpu.get("load", 0), "%", 
                           alertas.get("gpu_uso_warning", 80), alertas.get("gpu_uso_critical", 95))
-        self._update_value(self.gpu_panel, "temp", "Temp", gpu.get("temp", 0), "°C", 
+        self._update_value(self.gpu_panel, "temp", tr("row.temp"), gpu.get("temp", 0), "°C", 
                           alertas.get("gpu_temp_warning", 75), alertas.get("gpu_temp_critical", 90))
-        self._update_value(self.gpu_panel, "voltage", "Voltagem", gpu.get("voltage", 0), "V")
+        self._update_value(self.gpu_panel, "voltage", tr("row.voltage"), gpu.get("voltage", 0), "V")
+        self._update_value(self.gpu_panel, "power", tr("row.power"), gpu.get("power_w", 0), "W",
+                          *self._metric_thresholds(alertas, "gpu_power"))
         self._update_value(self.gpu_panel, "clock_core", "Core", gpu.get("clock_core", 0), " MHz")
         self._update_value(self.gpu_panel, "clock_mem", "Mem Clk", gpu.get("clock_mem", 0), " MHz")
         self._update_value(self.gpu_panel, "mem_used", "VRAM", gpu.get("mem_used_mb", 0), " MB")
-        self._update_value(self.gpu_panel, "fan", "Fan", gpu.get("fan", 0), " RPM")
+        # Percentual só quando o sender conhece o total (senão fica só o MB usado)
+        vram = vram_percent(gpu.get("mem_used_mb"), gpu.get("mem_total_mb")) if HAS_PAYLOAD_MODULE else None
+        if vram is not None:
+            self._update_value(self.gpu_panel, "mem_percent", "VRAM %", vram, "%",
+                              *self._metric_thresholds(alertas, "vram"))
+        else:
+            self._remove_value(self.gpu_panel, "mem_percent")  # Total sumiu (ex: troca de GPU)
+        # Sensores que só expõem duty cycle reportam fan em % (padrão: RPM)
+        fan_unit = "%" if gpu.get("fan_unit") == "%" else " RPM"
+        self._update_value(self.gpu_panel, "fan", "Fan", gpu.get("fan", 0), fan_unit)
+        # NVENC/NVDEC: só com uso (GPUs sem NVML ou sem suporte mandam 0)
+        for key, label in (("enc_util", "Encoder"), ("dec_util", "Decoder")):
+            if gpu.get(key):
+                self._update_value(self.gpu_panel, key, label, gpu[key], "%")
+            else:
+                self._remove_value(self.gpu_panel, key)
+        # Barramento de memória e PCIe: também só NVML (GPUs antigas mandam 0)
+        for key, label, unit in (("mem_util", "Mem Bus", "%"), ("pcie_tx_kbps", "PCIe TX", " KB/s"),
+                                 ("pcie_rx_kbps", "PCIe RX", " KB/s")):
+            if gpu.get(key):
+                self._update_value(self.gpu_panel, key, label, gpu[key], unit)
+            else:
+                self._remove_value(self.gpu_panel, key)
         
         # RAM
         ram = data.get("ram", {})
-        self._update_value(self.ram_panel, "percent", "Uso", ram.get("percent", 0), "%", 
+        self._update_value(self.ram_panel, "percent", tr("row.usage"), ram.get("percent", 0), "%", 
                           alertas.get("ram_warning", 70), alertas.get("ram_critical", 90))
-        self._update_value(self.ram_panel, "used", "Usado", ram.get("used_gb", 0), " GB")
-        self._update_value(self.ram_panel, "total", "Total", ram.get("total_gb", 0), " GB")
+        self._update_value(self.ram_panel, "used", tr("row.used"), ram.get("used_gb", 0), " GB")
+        self._update_value(self.ram_panel, "total", tr("row.total"), ram.get("total_gb", 0), " GB")
         
         # MOBO
         mobo = data.get("mobo", {})
-        self._update_value(self.mobo_panel, "temp", "Temp", mobo.get("temp", 0), "°C", 50, 70)
+        self._update_value(self.mobo_panel, "temp", tr("row.temp"), mobo.get("temp", 0), "°C",
+                          alertas.get("mobo_temp_warning", 50), alertas.get("mobo_temp_critical", 70))
         
         # Fans da MOBO
         fans = data.get("fans", [])
@@ -684,6 +2049,9 @@ class TelemetryDashboard:
                 name = fan.get("name", f"Fan {i}")[:10]
                 rpm = fan.get("rpm", 0)
                 self._update_value(self.mobo_panel, f"fan{i}", name, rpm, " RPM")
+            else:
+                # Fan sumiu do payload: não deixa a última leitura na tela
+                self._remove_value(self.mobo_panel, f"fan{i}")
         
         # STORAGE (usa labels pré-criados)
         storage = data.get("storage", [])
@@ -691,52 +2059,91 @@ class TelemetryDashboard:
             if i < len(storage):
                 disk = storage[i]
                 name = disk.get("name", f"Disk {i}")[:15]
-                self._update_value(self.storage_panel, f"disk{i}_name", f"Disco {i+1}", name, "")
-                self._update_value(self.storage_panel, f"disk{i}_temp", "  Temp", disk.get("temp", 0), "°C", 
-                                  alertas.get("storage_temp_warning", 45), alertas.get("storage_temp_critical", 55))
-                self._update_value(self.storage_panel, f"disk{i}_health", "  Saúde", disk.get("health", 100), "%")
-                self._update_value(self.storage_panel, f"disk{i}_used", "  Usado", disk.get("used_space", 0), "%", 
-                                  alertas.get("storage_uso_warning", 80), alertas.get("storage_uso_critical", 95))
+                # Overrides por disco (disk_overrides) antes dos thresholds globais
+                if HAS_ALERTS_MODULE:
+                    limits = disk_thresholds(disk.get("name", ""), alertas, CONFIG.get("disk_overrides", {}),
+                                             disk.get("drive_id", ""))
+                else:
+                    limits = {
+                        "temp_warning": alertas.get("storage_temp_warning", 45),
+                        "temp_critical": alertas.get("storage_temp_critical", 55),
+                        "uso_warning": alertas.get("storage_uso_warning", 80),
+                        "uso_critical": alertas.get("storage_uso_critical", 95)
+                    }
+                self._update_value(self.storage_panel, f"disk{i}_name", tr("row.disk", n=i + 1), name, "")
+                self._update_value(self.storage_panel, f"disk{i}_temp", f"  {tr('row.temp')}", disk.get("temp", 0), "°C", 
+                                  limits["temp_warning"], limits["temp_critical"])
+                self._update_value(self.storage_panel, f"disk{i}_health", tr("row.health"), disk.get("health", 100), "%")
+                self._update_value(self.storage_panel, f"disk{i}_used", tr("row.used_space"), disk.get("used_space", 0), "%", 
+                                  limits["uso_warning"], limits["uso_critical"])
+                # Livre/total só com a capacidade (senders antigos mandam só o percentual)
+                if disk.get("total_gb"):
+                    free_text = f"{disk.get('free_gb', 0):.0f} / {disk['total_gb']:.0f} GB"
+                else:
+                    free_text = "-"
+                self._update_value(self.storage_panel, f"disk{i}_free", tr("row.free"), free_text, "")
+                # Taxas chegam em bytes/s
+                self._update_value(self.storage_panel, f"disk{i}_read", tr("row.read"), disk.get("read_rate", 0) / 1024, " KB/s")
+                self._update_value(self.storage_panel, f"disk{i}_write", tr("row.write"), disk.get("write_rate", 0) / 1024, " KB/s")
             else:
                 # Limpa dados de disco não existente
-                self._update_value(self.storage_panel, f"disk{i}_name", f"Disco {i+1}", "-", "")
-                self._update_value(self.storage_panel, f"disk{i}_temp", "  Temp", 0, "°C")
-                self._update_value(self.storage_panel, f"disk{i}_health", "  Saúde", 0, "%")
-                self._update_value(self.storage_panel, f"disk{i}_used", "  Usado", 0, "%")
+                self._clear_disk_labels(i)
         
         # NETWORK
         net = data.get("network", {})
         self._update_value(self.network_panel, "down", "Download", net.get("down_kbps", 0), " KB/s")
         self._update_value(self.network_panel, "up", "Upload", net.get("up_kbps", 0), " KB/s")
-        self._update_value(self.network_panel, "ping", "Ping", net.get("ping_ms", 0), " ms", 
-                          alertas.get("ping_warning", 50), alertas.get("ping_critical", 100))
+        ping = net.get("ping_ms", 0)
+        ping_label = f"Ping {net['ping_method'].upper()}" if net.get("ping_method") else "Ping"
+        if ping < 0:  # Sender não alcança o host de ping
+            self._update_value(self.network_panel, "ping", ping_label, tr("value.unreachable"), "")
+            self.network_panel["labels"]["ping"]["value"].config(fg=self.colors["critical"])
+        else:
+            self._update_value(self.network_panel, "ping", ping_label, ping, " ms", 
+                              alertas.get("ping_warning", 50), alertas.get("ping_critical", 100))
+        self.network_panel["labels"]["ping"]["name"].config(text=f"{ping_label}:")
         
         # Link Speed com verificação de saúde baseada na velocidade esperada
         link_speed = net.get("link_speed_mbps", 0)
         adapter = net.get("adapter_name", "N/A")
         expected_speed = CONFIG.get("expected_link_speed_mbps", 1000)
         
-        # Determinar saúde do link baseado na velocidade ESPERADA (configurável)
-        if link_speed >= expected_speed:
-            link_status = "OK"
-            link_color = self.colors['gpu']  # Verde
-        elif link_speed >= expected_speed * 0.1:
-            link_status = f"Esperado: {expected_speed}"
-            link_color = self.colors['warning']  # Amarelo
-        elif link_speed > 0:
-            link_status = f"Esperado: {expected_speed}"
-            link_color = self.colors['critical']  # Vermelho
+        # Saúde do link pela velocidade ESPERADA (configurável): menor é pior
+        if alertas.get("link_speed_alert", True) and HAS_ALERTS_MODULE:
+            link_level = link_speed_level(link_speed, expected_speed) or ""
         else:
-            link_status = "N/A"
-            link_color = self.colors['dim']  # Cinza
-        
-        self._update_value(self.network_panel, "link", "Link", link_speed, " Mbps", expected_speed * 0.5, expected_speed * 0.1)
-        self._update_value(self.network_panel, "adapter", "Adaptador", adapter[:15] if adapter else "N/A", "")
+            link_level = None
+        self._update_value(self.network_panel, "link", "Link", link_speed, " Mbps", level=link_level)
+        self._update_value(self.network_panel, "adapter", tr("row.adapter"), adapter[:15] if adapter else "N/A", "")
+        self._update_interfaces(net.get("interfaces", []))
+        
+        # TOP PROCESSES
+        self._update_processes(data.get("processes", []))
+        if self.alert_log_visible and self.alert_log_dirty:
+            self._render_alert_log()
+        if self.records_visible and self.records_dirty:
+            self._render_records()
+        
+        # BATTERY (oculto em desktops)
+        battery = data.get("battery", {})
+        present = battery.get("present", False)
+        self._set_battery_visible(present)
+        if present:
+            status = tr("value.charging" if battery.get("charging") else "value.on_battery")
+            self._update_value(self.battery_panel, "percent", tr("row.charge"), battery.get("percent", 0), "%")
+            self._update_value(self.battery_panel, "status", tr("row.status"), status, "")
+            self._update_value(self.battery_panel, "remaining", tr("row.remaining"), battery.get("time_remaining_min", 0), " min")
+            self._update_value(self.battery_panel, "power", tr("row.battery_power"), battery.get("power_w", 0), "W")
     
     def _log_to_csv(self, data):
         """Salva dados em arquivo CSV."""
         if not self.log_file:
             return
+        # Sem mudança além de csv_change_epsilon (só oscilação dos sensores): não grava
+        if self.csv_epsilon > 0 and HAS_PAYLOAD_MODULE:
+            if self.last_csv_data is not None and not significant_diff(data, self.last_csv_data, self.csv_epsilon):
+                return
+            self.last_csv_data = data
         
         try:
             timestamp = datetime.now().strftime("%Y-%m-%d %H:%M:%S")
@@ -745,7 +2152,9 @@ class TelemetryDashboard:
             ram = data.get("ram", {})
             net = data.get("network", {})
             
-            line = f"{timestamp},{cpu.get('usage', 0)},{cpu.get('temp', 0)},{gpu.get('load', 0)},{gpu.get('temp', 0)},{ram.get('percent', 0)},{net.get('ping_ms', 0)}\n"
+            values = [cpu.get('usage', 0), cpu.get('temp', 0), gpu.get('load', 0), gpu.get('temp', 0), ram.get('percent', 0), net.get('ping_ms', 0)]
+            # Sensor ausente (None) vira campo vazio no CSV
+            line = timestamp + "," + ",".join("" if v is None else str(v) for v in values) + "\n"
             self.log_file.write(line)
             self.log_file.flush()
         except Exception as e:
@@ -764,37 +2173,324 @@ class TelemetryDashboard:
         graph_w = w - 2 * padding
         graph_h = h - 2 * padding
         
-        self._draw_line_graph(list(self.history["cpu_usage"]), padding, padding, graph_w // 2, graph_h // 2, self.colors["cpu"], "CPU %", 100)
-        self._draw_line_graph(list(self.history["gpu_load"]), padding + graph_w // 2, padding, graph_w // 2, graph_h // 2, self.colors["gpu"], "GPU %", 100)
-        self._draw_line_graph(list(self.history["cpu_temp"]), padding, padding + graph_h // 2, graph_w // 2, graph_h // 2, "#ff8800", "CPU Temp", 100)
-        self._draw_line_graph(list(self.history["ping"]), padding + graph_w // 2, padding + graph_h // 2, graph_w // 2, graph_h // 2, self.colors["network"], "Ping ms", max(max(self.history["ping"]) * 1.2, 50))
+        if self.graph_mode == "histogram" and HAS_HISTOGRAM_MODULE:
+            self._draw_histogram(list(self.history["cpu_temp"]), padding, padding, graph_w // 2, graph_h, self.colors["cpu"], "CPU Temp °C")
+            self._draw_histogram(list(self.history["gpu_temp"]), padding + graph_w // 2, padding, graph_w // 2, graph_h, self.colors["gpu"], "GPU Temp °C")
+            return
+        
+        if self.graph_mode == "scatter" and HAS_SERIES_MODULE:
+            self._draw_scatter(padding, padding, graph_w, graph_h)
+            return
+        
+        if self.graph_mode == "bars" and HAS_LAYOUT_MODULE:
+            self._draw_core_bars(padding, padding, graph_w * 3 // 5, graph_h)
+            self._draw_vram_bar(padding + graph_w * 3 // 5 + 10, padding, graph_w * 2 // 5 - 10, graph_h)
+            return
+        
+        autoscale = CONFIG.get("graph_autoscale", False)
+        ping_values = series_values(self.history["ping"]) if HAS_SERIES_MODULE else list(self.history["ping"])
+        # (título, [(série, cor, legenda)], topo da escala fixa, autoscale)
+        plots = [
+            ("CPU %", [(self.history["cpu_usage"], self.colors["cpu"], "")], 100, False),
+            ("GPU %", [(self.history["gpu_load"], self.colors["gpu"], "")], 100, False),
+            ("CPU Temp", [(self.history["cpu_temp"], "#ff8800", "")], 100, autoscale),
+            # Download e upload dividem o eixo, sempre ajustado aos dados
+            ("Net KB/s", [(self.history["net_down"], self.colors["network"], "Down"),
+                          (self.history["net_up"], self.colors["ram"], "Up")], None, True),
+            ("Ping ms", [(self.history["ping"], self.colors["network"], "")], max(max(ping_values, default=0) * 1.2, 50), autoscale),
+        ]
+        
+        if HAS_LAYOUT_MODULE:
+            cells = plot_grid(len(plots), graph_w, graph_h)
+        else:
+            cells = [((i % 2) * (graph_w // 2), (i // 2) * (graph_h // 3), graph_w // 2, graph_h // 3) for i in range(len(plots))]
+        for (label, lines, max_val, scale), (cx, cy, cw, ch) in zip(plots, cells):
+            self._draw_line_graph(lines, padding + cx, padding + cy, cw - 4, ch - 4, label, max_val, scale)
     
-    def _draw_line_graph(self, data, x, y, w, h, color, label, max_val):
-        """Desenha um gráfico de linha."""
-        if not data or w < 10 or h < 10:
+    def _draw_line_graph(self, lines, x, y, w, h, label, max_val, autoscale=False):
+        """
+        Desenha um gráfico com uma ou mais linhas num eixo compartilhado.
+        
+        lines: [(série, cor, legenda)]; com mais de uma linha, as legendas
+        aparecem ao lado do título. autoscale: eixo Y segue a faixa dos dados.
+        """
+        if not lines or w < 10 or h < 10:
             return
         
+        color = lines[0][1]
         self.graph_canvas.create_text(x + 5, y + 5, text=label, fill=color, anchor="nw", font=self.font_small)
         self.graph_canvas.create_rectangle(x, y, x + w, y + h, outline=self.colors["border"])
         
-        if len(data) < 2:
+        # Legenda
+        if len(lines) > 1:
+            legend_x = x + 5 + self.font_small.measure(label) + 10
+            for _, line_color, name in lines:
+                text = f"▬ {name}"
+                self.graph_canvas.create_text(legend_x, y + 5, text=text, fill=line_color, anchor="nw", font=self.font_help)
+                legend_x += self.font_help.measure(text) + 8
+        
+        if not HAS_SERIES_MODULE:
+            return
+        
+        # Eixo compartilhado: limites calculados sobre todas as séries do gráfico
+        samples = [value for data, _, _ in lines for value in data]
+        min_val, max_val = axis_bounds(samples, max_val, autoscale)
+        if autoscale:
+            # Sem o zero no eixo, a escala precisa aparecer
+            self.graph_canvas.create_text(x + w - 3, y + 3, text=f"{max_val:.0f}", fill=self.colors["dim"], anchor="ne", font=self.font_help)
+            self.graph_canvas.create_text(x + w - 3, y + h - 3, text=f"{min_val:.0f}", fill=self.colors["dim"], anchor="se", font=self.font_help)
+        
+        # Lacunas (pacotes perdidos) quebram a linha em trechos; 10px livres para o título
+        for data, line_color, _ in lines:
+            for points in plot_points(data, x, y + 10, w, h - 10, min_val, max_val):
+                self.graph_canvas.create_line(points, fill=line_color, width=2, smooth=True)
+    
+    def _draw_scatter(self, x, y, w, h):
+        """Desenha a dispersão entre as duas séries escolhidas (X = eixo horizontal)."""
+        if w < 10 or h < 30:
+            return
+        
+        x_name, y_name = self.scatter_x.get(), self.scatter_y.get()
+        x_key, x_max = SCATTER_SERIES[x_name]
+        y_key, y_max = SCATTER_SERIES[y_name]
+        points = paired_points(self.history[x_key], self.history[y_key])
+        
+        label = f"{y_name} × {x_name} ({len(points)} amostras)"
+        self.graph_canvas.create_text(x + 5, y + 5, text=label, fill=self.colors["title"], anchor="nw", font=self.font_small)
+        self.graph_canvas.create_rectangle(x, y, x + w, y + h, outline=self.colors["border"])
+        if not points:
+            return
+        
+        # Eixos de percentual ficam em 0-100; os demais seguem os dados
+        x_bounds = axis_bounds([px for px, _ in points], x_max, autoscale=x_max is None)
+        y_bounds = axis_bounds([py for _, py in points], y_max, autoscale=y_max is None)
+        # 20px livres no topo para o título e 12px na base para a escala
+        coords = scatter_coords(points, x + 4, y + 20, w - 8, h - 32, x_bounds, y_bounds)
+        
+        # Amostras antigas esmaecidas; a mais recente em destaque
+        old_color = blend_color(self.colors["network"], self.colors["panel"], 0.5) if HAS_THEME_MODULE else self.colors["dim"]
+        for cx, cy in coords[:-1]:
+            self.graph_canvas.create_oval(cx - 2, cy - 2, cx + 2, cy + 2, fill=old_color, outline="")
+        cx, cy = coords[-1]
+        self.graph_canvas.create_oval(cx - 4, cy - 4, cx + 4, cy + 4, fill=self.colors["warning"], outline="")
+        
+        # Escalas nas pontas dos eixos
+        self.graph_canvas.create_text(x + 2, y + h - 2, text=f"{x_bounds[0]:.0f}", fill=self.colors["dim"], anchor="sw", font=self.font_help)
+        self.graph_canvas.create_text(x + w - 2, y + h - 2, text=f"{x_bounds[1]:.0f}", fill=self.colors["dim"], anchor="se", font=self.font_help)
+        self.graph_canvas.create_text(x + w - 3, y + 3, text=f"{y_bounds[1]:.0f}", fill=self.colors["dim"], anchor="ne", font=self.font_help)
+    
+    def _bar_color(self, level, color):
+        """Cor da barra: a do setor, ou a do nível de alerta."""
+        return self.colors[level] if level else color
+    
+    def _draw_core_bars(self, x, y, w, h, max_temp=100):
+        """Barras verticais da temperatura de cada núcleo (só o pacote se o Sender não manda por núcleo)."""
+        if w < 10 or h < 30:
             return
+        with self.data_lock:
+            cpu = dict((self.current_data or {}).get("cpu") or {})
+        core_temps = cpu.get("core_temps")
+        if isinstance(core_temps, list) and core_temps:
+            bars = [(f"C{i}", temp) for i, temp in enumerate(core_temps)]
+        else:
+            bars = [("CPU", cpu.get("temp"))]
         
-        points = []
-        step_x = w / (len(data) - 1)
-        for i, val in enumerate(data):
-            px = x + i * step_x
-            py = y + h - (val / max_val) * (h - 10) if max_val > 0 else y + h
-            points.extend([px, py])
+        alertas = CONFIG.get("alertas", {})
+        warn, crit = alertas.get("cpu_temp_warning", 70), alertas.get("cpu_temp_critical", 85)
+        self.graph_canvas.create_text(x + 5, y + 5, text="CPU Temp °C", fill=self.colors["cpu"], anchor="nw", font=self.font_small)
+        self.graph_canvas.create_rectangle(x, y, x + w, y + h, outline=self.colors["border"])
         
-        if len(points) >= 4:
-            self.graph_canvas.create_line(points, fill=color, width=2, smooth=True)
+        # 20px livres no topo para o título e 12px na base para os rótulos
+        top, bottom = y + 20, y + h - 12
+        slot = (w - 8) / len(bars)
+        for i, (name, temp) in enumerate(bars):
+            fraction, level = bar_fill(temp, max_temp, warn, crit)
+            x0 = x + 4 + i * slot + 2
+            x1 = x0 + max(2, slot - 4)
+            # Contorno sempre (barra vazia = sem dado); preenchimento de baixo para cima
+            self.graph_canvas.create_rectangle(x0, top, x1, bottom, outline=self.colors["border"])
+            if fraction:
+                fill_top = bottom - fraction * (bottom - top)
+                self.graph_canvas.create_rectangle(x0, fill_top, x1, bottom, fill=self._bar_color(level, self.colors["cpu"]), outline="")
+            self.graph_canvas.create_text((x0 + x1) / 2, y + h - 2, text=name, fill=self.colors["dim"], anchor="s", font=self.font_help)
+    
+    def _draw_vram_bar(self, x, y, w, h):
+        """Barra horizontal do uso de VRAM (vazia se o Sender não conhece o total)."""
+        if w < 10 or h < 30:
+            return
+        with self.data_lock:
+            gpu = dict((self.current_data or {}).get("gpu") or {})
+        used, total = gpu.get("mem_used_mb"), gpu.get("mem_total_mb")
+        vram = vram_percent(used, total) if HAS_PAYLOAD_MODULE else None
+        
+        fraction, level = bar_fill(vram, 100, *self._metric_thresholds(CONFIG.get("alertas", {}), "vram"))
+        self.graph_canvas.create_text(x + 5, y + 5, text="VRAM", fill=self.colors["gpu"], anchor="nw", font=self.font_small)
+        self.graph_canvas.create_rectangle(x, y, x + w, y + h, outline=self.colors["border"])
+        
+        bar_h = min(30, h - 40)
+        x0, x1 = x + 6, x + w - 6
+        y0 = y + (h - bar_h) / 2
+        self.graph_canvas.create_rectangle(x0, y0, x1, y0 + bar_h, outline=self.colors["border"])
+        if fraction:
+            self.graph_canvas.create_rectangle(x0, y0, x0 + fraction * (x1 - x0), y0 + bar_h,
+                                               fill=self._bar_color(level, self.colors["gpu"]), outline="")
+        text = f"{used:.0f} / {total:.0f} MB ({vram:.0f}%)" if vram is not None else "-"
+        self.graph_canvas.create_text(x + w / 2, y0 + bar_h + 4, text=text, fill=self.colors["dim"], anchor="n", font=self.font_help)
+    
+    def _draw_histogram(self, data, x, y, w, h, color, label, min_val=20, max_val=100, bin_width=5):
+        """Desenha histograma das amostras em faixas de temperatura."""
+        if w < 10 or h < 10:
+            return
+        
+        # Lacunas (posições ainda sem dados) e leituras zeradas ficam de fora
+        samples = [v for v in data if v > 0]
+        counts = histogram(samples, min_val, max_val, bin_width)
+        
+        self.graph_canvas.create_text(x + 5, y + 5, text=tr("graph.samples", label=label, count=len(samples)), fill=color, anchor="nw", font=self.font_small)
+        self.graph_canvas.create_rectangle(x, y, x + w, y + h, outline=self.colors["border"])
+        
+        peak = max(counts) if counts else 0
+        if peak == 0:
+            return
+        
+        bar_w = w / len(counts)
+        for i, count in enumerate(counts):
+            if count == 0:
+                continue
+            bar_h = (count / peak) * (h - 30)
+            x0 = x + i * bar_w + 1
+            self.graph_canvas.create_rectangle(x0, y + h - 12 - bar_h, x0 + bar_w - 2, y + h - 12, fill=color, outline="")
+        
+        # Escala do eixo X nas pontas
+        self.graph_canvas.create_text(x + 2, y + h - 2, text=f"{min_val}", fill=self.colors["dim"], anchor="sw", font=self.font_help)
+        self.graph_canvas.create_text(x + w - 2, y + h - 2, text=f"{max_val}", fill=self.colors["dim"], anchor="se", font=self.font_help)
     
     def _toggle_fullscreen(self, event=None):
         """Alterna modo fullscreen."""
         self.is_fullscreen = not self.is_fullscreen
         self.root.attributes("-fullscreen", self.is_fullscreen)
     
+    def _grid_panels(self):
+        """Painéis da grade pelo nome (mesma ordem das teclas 1-6)."""
+        return {
+            "CPU": self.cpu_panel, "GPU": self.gpu_panel, "RAM": self.ram_panel,
+            "MOBO": self.mobo_panel, "STORAGE": self.storage_panel, "NETWORK": self.network_panel
+        }
+    
+    def _on_focus_key(self, event):
+        """Teclas 1-6 ampliam um painel; 0 (ou a mesma tecla) volta para a grade."""
+        if not HAS_LAYOUT_MODULE:
+            return
+        focus = focus_for_key(event.keysym, self.focus_panel)
+        if focus is not None and focus not in self.panel_order:
+            return  # Painel fora do layout configurado
+        if focus != self.focus_panel:
+            self.focus_panel = focus
+            self._apply_focus()
+    
+    def _on_escape(self, event=None):
+        """ESC fecha a busca ou sai do painel em foco; na grade, encerra."""
+        if self.search_visible:
+            self._close_search()
+        elif self.focus_panel:
+            self.focus_panel = None
+            self._apply_focus()
+        else:
+            self._quit_app()
+    
+    def _open_search(self, event=None):
+        """Mostra a barra de busca de métricas (tecla /)."""
+        if not self.search_visible:
+            self.search_frame.pack(before=self.panels_frame, pady=(3, 0))
+            self.search_visible = True
+        self.search_entry.focus_set()
+    
+    def _close_search(self, event=None):
+        """Fecha a busca e tira o esmaecimento (ESC)."""
+        self.search_entry.delete(0, tk.END)
+        self.search_query = ""
+        self._apply_search()
+        self.search_frame.pack_forget()
+        self.search_visible = False
+        self.root.focus_set()
+        return "break"
+    
+    def _on_search_changed(self, event=None):
+        query = self.search_entry.get()
+        if query != self.search_query:
+            self.search_query = query
+            self._apply_search()
+    
+    def _apply_search(self):
+        """Reaplica o filtro em todas as linhas dos painéis da grade e da bateria."""
+        for panel in list(self._grid_panels().values()) + [self.battery_panel]:
+            for key in panel["labels"]:
+                self._filter_row(panel, key)
+    
+    def _filter_row(self, panel, key):
+        """Esmaece a linha que não casa com a busca; destaca o nome das que casam."""
+        row = panel["labels"][key]
+        value_fg = row.get("value_fg", self.colors["text"])
+        if not self.search_query:
+            row["name"].config(fg=self.colors["dim"])
+            row["value"].config(fg=value_fg)
+            return
+        if metric_matches(self.search_query, panel["name"], panel["title"].cget("text"), row["label"], key):
+            row["name"].config(fg=self.colors["text"])
+            row["value"].config(fg=value_fg)
+        else:
+            faded = blend_color(self.colors["dim"], self.colors["panel"], 0.7) if HAS_THEME_MODULE else self.colors["panel"]
+            row["name"].config(fg=faded)
+            row["value"].config(fg=faded)
+    
+    def _panel_fonts(self, panel):
+        """Fontes (nome, valor) das linhas do painel: ampliadas se estiver em foco."""
+        if self.focus_panel and panel["name"] == self.focus_panel:
+            return self.font_focus_name, self.font_focus_value
+        return self.font_small, self.font_value
+    
+    def _apply_focus(self):
+        """Mostra a grade completa ou só o painel em foco, ampliado."""
+        panels = self._grid_panels()
+        visible = visible_panels(self.focus_panel, self.panel_order)
+        focused = self.focus_panel is not None
+        
+        for row, _ in self.panel_rows:
+            row.pack_forget()
+        for row in (self.processes_row, self.alert_log_row, self.records_row, self.theme_editor_row):
+            row.pack_forget()
+        self.battery_panel["frame"].pack_forget()
+        
+        for row, names in self.panel_rows:
+            for name in names:
+                panels[name]["frame"].pack_forget()
+            shown = [name for name in names if name in visible]
+            if not shown:
+                continue
+            # Em foco, a linha ocupa toda a altura disponível
+            row.pack(fill=tk.BOTH if focused else tk.X, expand=focused, pady=3)
+            for name in shown:
+                panels[name]["frame"].pack(side=tk.LEFT, fill=tk.BOTH, expand=True, padx=3)
+        
+        if not focused:
+            if self.battery_visible:
+                self.battery_panel["frame"].pack(side=tk.LEFT, fill=tk.BOTH, expand=True, padx=3)
+            if self.processes_visible:
+                self.processes_row.pack(fill=tk.X, pady=3)
+            if self.alert_log_visible:
+                self.alert_log_row.pack(fill=tk.X, pady=3)
+            if self.records_visible:
+                self.records_row.pack(fill=tk.X, pady=3)
+            if self.theme_editor_visible:
+                self.theme_editor_row.pack(fill=tk.X, pady=3)
+        
+        # Fontes das linhas já criadas (novas linhas usam _panel_fonts)
+        for panel in panels.values():
+            name_font, value_font = self._panel_fonts(panel)
+            for label_dict in panel["labels"].values():
+                label_dict["name"].configure(font=name_font)
+                label_dict["value"].configure(font=value_font)
+    
     def _toggle_graphs(self, event=None):
         """Alterna exibição de gráficos."""
         self.show_graphs = not self.show_graphs
@@ -802,13 +2498,159 @@ class TelemetryDashboard:
             self.graph_canvas.pack(fill=tk.X, pady=5, before=self.help_label)
         else:
             self.graph_canvas.pack_forget()
+        self._update_scatter_bar()
+    
+    def _toggle_histogram(self, event=None):
+        """Alterna entre gráficos de linha e histograma de temperaturas."""
+        self.graph_mode = "lines" if self.graph_mode == "histogram" else "histogram"
+        if not self.show_graphs:
+            self._toggle_graphs()
+        self._update_scatter_bar()
+    
+    def _toggle_bars(self, event=None):
+        """Alterna entre gráficos de linha e barras (temperatura por núcleo, VRAM)."""
+        self.graph_mode = "lines" if self.graph_mode == "bars" else "bars"
+        if not self.show_graphs:
+            self._toggle_graphs()
+        self._update_scatter_bar()
+    
+    def _toggle_scatter(self, event=None):
+        """Alterna entre gráficos de linha e dispersão de duas séries (ex: GPU % × CPU Temp)."""
+        self.graph_mode = "lines" if self.graph_mode == "scatter" else "scatter"
+        if not self.show_graphs:
+            self._toggle_graphs()
+        self._update_scatter_bar()
+    
+    def _update_scatter_bar(self):
+        """Mostra os seletores de eixo só com a dispersão visível."""
+        if self.show_graphs and self.graph_mode == "scatter":
+            self.scatter_bar.pack(fill=tk.X, before=self.graph_canvas)
+        else:
+            self.scatter_bar.pack_forget()
+    
+    def _resize_history(self, force: bool = False) -> None:
+        """Ajusta a capacidade dos gráficos para cobrir history_seconds."""
+        interval = self._sample_interval()
+        if interval is None:
+            return
+        
+        capacity = history_capacity(CONFIG.get("history_seconds", 30), interval)
+        with self.data_lock:
+            current = self.history["cpu_usage"].maxlen
+            # Tolera pequenas variações do intervalo (evita redimensionar a cada pacote)
+            if capacity == current or (not force and abs(capacity - current) <= current // 10):
+                return
+            for key in self.history:
+                self.history[key] = resize_series(self.history[key], capacity)
+        print(f"[Graphs] Janela: {capacity} amostras ({interval:.2f}s entre amostras)")
+    
+    def _write_json_stream(self, payload: dict, source: str) -> None:
+        """Escreve o payload como uma linha NDJSON no stdout original."""
+        global JSON_STREAM
+        try:
+            STREAM_OUT.write(to_ndjson(stream_record(payload, source)) + "\n")
+            STREAM_OUT.flush()
+        except (OSError, ValueError) as e:
+            # Pipe fechado (ex: consumidor encerrou): desliga o stream e segue com a GUI
+            print(f"[JSON Stream] Desativado: {e}")
+            JSON_STREAM = False
+    
+    def _update_sources(self) -> None:
+        """Expira Senders parados e atualiza as abas de hosts."""
+        with self.data_lock:
+            self.sources.evict()
+            active = self.sources.active()
+        
+        if self.selected_source not in active and active:
+            self._select_source(active[0])
+        
+        if list(self.source_tabs) != active:
+            for tab in self.source_tabs.values():
+                tab.destroy()
+            self.source_tabs = {}
+            for addr in active:
+                tab = tk.Label(self.sources_bar, text=f" {addr} ", font=self.font_small, cursor="hand2")
+                tab.pack(side=tk.LEFT, padx=2)
+                tab.bind("<Button-1>", lambda e, a=addr: self._select_source(a))
+                self.source_tabs[addr] = tab
+        
+        for addr, tab in self.source_tabs.items():
+            selected = addr == self.selected_source
+            tab.configure(
+                bg=self.colors["border"] if selected else self.colors["bg"],
+                fg=self.colors["title"] if selected else self.colors["dim"]
+            )
+    
+    def _select_source(self, addr: str) -> None:
+        """Passa a exibir outro Sender (o histórico dos gráficos recomeça)."""
+        with self.data_lock:
+            if addr == self.selected_source:
+                return
+            self.selected_source = addr
+            entry = self.sources.get(addr)
+            if entry:
+                self.current_data, self.last_data_time = entry
+            self.records_dirty = True  # Recordes do outro Sender
+            for series in self.history.values():
+                series.extend([GAP] * series.maxlen)
+            self.last_payload_ts = None
+            if self.aggregator:
+                self.aggregator.flush()  # Balde do Sender anterior é descartado
+            if self.smoother:
+                self.smoother.reset()
+            self.holds.clear()
+    
+    def _toggle_debug(self, event=None):
+        """Mostra/oculta o overlay de estatísticas de pacotes."""
+        self.show_debug = not self.show_debug
+        if self.show_debug:
+            self.debug_label.pack(after=self.status_label)
+            self._update_debug_overlay()
+        else:
+            self.debug_label.pack_forget()
+    
+    def _update_debug_overlay(self):
+        """Atualiza tamanho de pacote, pacotes/s e banda estimada."""
+        if not self.traffic:
+            self.debug_label.config(text="Debug indisponível (core.rates não encontrado)")
+            return
+        
+        with self.data_lock:
+            last_size = self.traffic.last_size
+            json_size = self.last_json_size
+            avg_size = self.traffic.avg_size
+            pps = self.traffic.packets_per_sec
+            kbps = self.traffic.kbit_per_sec
+            total = self.traffic.total_packets
+            mismatched = self.mismatch_packets
+            decode = self.decode_stats.summary() if self.decode_stats else ""
+            last_error = self.decode_stats.last_error_message if self.decode_stats else ""
+        
+        reduction = (1 - last_size / json_size) * 100 if json_size else 0
+        text = (f"Pacote: {last_size} B (JSON {json_size} B, -{reduction:.0f}%) | "
+                f"Média: {avg_size:.0f} B | {pps:.1f} pkt/s | {kbps:.1f} kbit/s | Total: {total} | "
+                f"Versão incompatível: {mismatched}")
+        if decode:
+            text += f"\nDecodificação - {decode}"
+            if last_error:
+                text += f": {last_error}"
+        self.debug_label.config(text=text)
     
     def _toggle_theme(self, event=None):
         """Alterna entre tema escuro e claro."""
         self.dark_theme = not self.dark_theme
-        self.colors = self.themes["dark" if self.dark_theme else "light"]
+        self.theme_name = "dark" if self.dark_theme else "light"
+        self.colors = self.themes[self.theme_name]
+        # Gravado no config.json na hora (e no ui_state.json ao sair)
+        CONFIG["tema"] = self.theme_name
+        salvar_config(CONFIG)
         self._apply_theme()
     
+    def _toggle_temp_unit(self, event=None):
+        """Alterna a exibição de temperaturas entre °C e °F (alertas seguem em °C)."""
+        self.temp_unit = "C" if self.temp_unit == "F" else "F"
+        print(f"[UI] Temperaturas em °{self.temp_unit}")
+    
     def _apply_theme(self):
         """Aplica o tema atual a todos os widgets."""
         self.root.configure(bg=self.colors["bg"])
@@ -816,11 +2658,30 @@ class TelemetryDashboard:
         self.panels_frame.configure(bg=self.colors["bg"])
         self.title_label.configure(bg=self.colors["bg"], fg=self.colors["title"])
         self.status_label.configure(bg=self.colors["bg"])
+        self.sources_bar.configure(bg=self.colors["bg"])
+        self.debug_label.configure(bg=self.colors["bg"], fg=self.colors["dim"])
+        self.diag_label.configure(bg=self.colors["bg"])
         self.help_label.configure(bg=self.colors["bg"], fg=self.colors["dim"])
         self.graph_canvas.configure(bg=self.colors["panel"], highlightbackground=self.colors["border"])
+        self.scatter_bar.configure(bg=self.colors["bg"])
+        for child in self.scatter_bar.winfo_children():
+            if child in self.scatter_menus:
+                child.configure(bg=self.colors["panel"], fg=self.colors["text"], activebackground=self.colors["border"])
+            else:
+                child.configure(bg=self.colors["bg"], fg=self.colors["dim"])
         
         # Atualiza painéis
-        for panel in [self.cpu_panel, self.gpu_panel, self.ram_panel, self.mobo_panel, self.storage_panel, self.network_panel]:
+        self.alert_log_list.configure(bg=self.colors["panel"], fg=self.colors["text"])
+        self.alert_log_row.configure(bg=self.colors["bg"])
+        if self.alert_log_visible:
+            self._render_alert_log()  # Cores das linhas seguem o tema
+        self.records_list.configure(bg=self.colors["panel"], fg=self.colors["text"])
+        self.records_row.configure(bg=self.colors["bg"])
+        self.theme_editor_row.configure(bg=self.colors["bg"])
+        if self.theme_editor_swatches:
+            self._refresh_theme_editor()
+        
+        for panel in [self.cpu_panel, self.gpu_panel, self.ram_panel, self.mobo_panel, self.storage_panel, self.network_panel, self.battery_panel, self.processes_panel, self.alert_log_panel, self.records_panel, self.theme_editor_panel]:
             panel["frame"].configure(bg=self.colors["panel"])
             panel["title"].configure(bg=self.colors["panel"], fg=panel["color"])
             panel["values"].configure(bg=self.colors["panel"])
@@ -963,7 +2824,10 @@ class TelemetryDashboard:
                              fg=self.colors["text"], bg=self.colors["bg"])
         mode_label.pack(anchor="w", pady=(0, 5))
         
-        self.settings_mode_var = tk.StringVar(value="manual" if self.sender_ip else "auto")
+        if self.connection_mode == "multicast":
+            self.settings_mode_var = tk.StringVar(value="multicast")
+        else:
+            self.settings_mode_var = tk.StringVar(value="manual" if self.sender_ip else "auto")
         
         auto_radio = tk.Radiobutton(frame, text="🔍 Automatic (UDP Broadcast - Auto-discovery)",
                                     variable=self.settings_mode_var, value="auto",
@@ -977,6 +2841,12 @@ class TelemetryDashboard:
                                       selectcolor=self.colors["panel"])
         manual_radio.pack(anchor="w", padx=10)
         
+        multicast_radio = tk.Radiobutton(frame, text="📡 Multicast (Join group - crosses managed switches)",
+                                         variable=self.settings_mode_var, value="multicast",
+                                         font=self.font_small, fg=self.colors["text"], bg=self.colors["bg"],
+                                         selectcolor=self.colors["panel"])
+        multicast_radio.pack(anchor="w", padx=10)
+        
         # Sender IP
         ip_label = tk.Label(frame, text="Sender IP (PC):", font=self.font_small,
                            fg=self.colors["text"], bg=self.colors["bg"])
@@ -988,6 +2858,17 @@ class TelemetryDashboard:
         self.settings_ip_entry.pack(anchor="w", pady=2, ipady=5)
         self.settings_ip_entry.insert(0, self.sender_ip or "192.168.1.100")
         
+        # Multicast group
+        group_label = tk.Label(frame, text="Multicast group:", font=self.font_small,
+                              fg=self.colors["text"], bg=self.colors["bg"])
+        group_label.pack(anchor="w", pady=(15, 5))
+        
+        self.settings_group_entry = tk.Entry(frame, font=self.font_value, bg=self.colors["panel"],
+                                            fg=self.colors["text"], insertbackground=self.colors["text"],
+                                            relief="flat", width=25)
+        self.settings_group_entry.pack(anchor="w", pady=2, ipady=5)
+        self.settings_group_entry.insert(0, CONFIG.get("multicast_group", "239.255.0.1"))
+        
         # Port
         port_label = tk.Label(frame, text="UDP Port:", font=self.font_small,
                              fg=self.colors["text"], bg=self.colors["bg"])
@@ -1031,7 +2912,7 @@ class TelemetryDashboard:
                               fg=self.colors["text"], bg=self.colors["bg"])
         theme_label.pack(anchor="w", pady=(0, 5))
         
-        theme_names = ["dark", "light", "high_contrast", "cyberpunk"]
+        theme_names = get_theme_names() if HAS_THEME_MODULE else ["dark", "light", "high_contrast", "cyberpunk"]
         self.settings_theme_var = tk.StringVar(value=CONFIG.get("tema", "dark"))
         
         theme_frame = tk.Frame(frame, bg=self.colors["bg"])
@@ -1110,6 +2991,10 @@ class TelemetryDashboard:
             ("cpu_temp_critical", "Temp Critical (°C)", alertas_config.get("cpu_temp_critical", 85)),
             ("cpu_uso_warning", "Usage Warning (%)", alertas_config.get("cpu_uso_warning", 70)),
             ("cpu_uso_critical", "Usage Critical (%)", alertas_config.get("cpu_uso_critical", 90)),
+            ("cpu_power_warning", "Power Warning (W)", alertas_config.get("cpu_power_warning", 200)),
+            ("cpu_power_critical", "Power Critical (W)", alertas_config.get("cpu_power_critical", 250)),
+            ("cpu_voltage_warning", "Vcore Warning (V)", alertas_config.get("cpu_voltage_warning", 1.45)),
+            ("cpu_voltage_critical", "Vcore Critical (V)", alertas_config.get("cpu_voltage_critical", 1.55)),
         ])
         
         # GPU
@@ -1118,6 +3003,10 @@ class TelemetryDashboard:
             ("gpu_temp_critical", "Temp Critical (°C)", alertas_config.get("gpu_temp_critical", 90)),
             ("gpu_uso_warning", "Usage Warning (%)", alertas_config.get("gpu_uso_warning", 80)),
             ("gpu_uso_critical", "Usage Critical (%)", alertas_config.get("gpu_uso_critical", 95)),
+            ("gpu_power_warning", "Power Warning (W)", alertas_config.get("gpu_power_warning", 350)),
+            ("gpu_power_critical", "Power Critical (W)", alertas_config.get("gpu_power_critical", 450)),
+            ("vram_warning", "VRAM Warning (%)", alertas_config.get("vram_warning", 90)),
+            ("vram_critical", "VRAM Critical (%)", alertas_config.get("vram_critical", 98)),
         ])
         
         # RAM
@@ -1134,6 +3023,12 @@ class TelemetryDashboard:
             ("storage_uso_critical", "Usage Critical (%)", alertas_config.get("storage_uso_critical", 95)),
         ])
         
+        # Motherboard
+        self._create_threshold_group(scroll_frame, "🔌 Motherboard", [
+            ("mobo_temp_warning", "Temp Warning (°C)", alertas_config.get("mobo_temp_warning", 50)),
+            ("mobo_temp_critical", "Temp Critical (°C)", alertas_config.get("mobo_temp_critical", 70)),
+        ])
+        
         # Network
         self._create_threshold_group(scroll_frame, "🌐 Network", [
             ("ping_warning", "Ping Warning (ms)", alertas_config.get("ping_warning", 50)),
@@ -1306,6 +3201,23 @@ class TelemetryDashboard:
                              bg=self.colors["bg"])
         log_status.pack(anchor="w", padx=10, pady=5)
         
+        # Graphs
+        graph_frame = tk.LabelFrame(frame, text="📈 Graphs", font=self.font_small,
+                                   fg=self.colors["title"], bg=self.colors["bg"], bd=1)
+        graph_frame.pack(fill=tk.X, pady=10)
+        
+        window_frame = tk.Frame(graph_frame, bg=self.colors["bg"])
+        window_frame.pack(fill=tk.X, padx=10, pady=5)
+        
+        tk.Label(window_frame, text="Graph window (seconds):", font=self.font_small,
+                fg=self.colors["dim"], bg=self.colors["bg"]).pack(side=tk.LEFT)
+        
+        self.settings_history_seconds = tk.Entry(window_frame, font=self.font_small,
+                                                bg=self.colors["panel"], fg=self.colors["text"],
+                                                width=8, relief="flat")
+        self.settings_history_seconds.pack(side=tk.LEFT, padx=5)
+        self.settings_history_seconds.insert(0, str(CONFIG.get("history_seconds", 30)))
+        
         # Shortcut tip
         tip_label = tk.Label(frame, 
                             text="💡 Use [L] key to toggle logging manually",
@@ -1314,10 +3226,12 @@ class TelemetryDashboard:
     
     def _save_all_settings(self, window):
         """Saves all settings."""
+        global CONFIG
         try:
             # === CONNECTION ===
             mode = self.settings_mode_var.get()
             ip = self.settings_ip_entry.get().strip()
+            group = self.settings_group_entry.get().strip()
             port_str = self.settings_port_entry.get().strip()
             speed = self.settings_speed_var.get()
             
@@ -1330,8 +3244,18 @@ class TelemetryDashboard:
                 self.settings_status.config(text="❌ Invalid port!", fg=self.colors["critical"])
                 return
             
+            # Validate group if multicast mode
+            if mode == "multicast" and not (HAS_NETWORK_MODULE and is_multicast_address(group)):
+                self.settings_status.config(text="❌ Invalid multicast group!", fg=self.colors["critical"])
+                return
+            
             # Validate IP if manual mode
-            if mode == "manual":
+            if mode == "manual" and ":" in ip:
+                if not (HAS_NETWORK_MODULE and is_ipv6_address(ip)):
+                    self.settings_status.config(text="❌ Invalid IP!", fg=self.colors["critical"])
+                    return
+                ip = ip.strip("[]")
+            elif mode == "manual":
                 parts = ip.split(".")
                 if len(parts) != 4:
                     self.settings_status.config(text="❌ Invalid IP!", fg=self.colors["critical"])
@@ -1346,12 +3270,21 @@ class TelemetryDashboard:
                     return
             
             # === ALERTS ===
-            alertas = {}
+            # Parte dos campos não tem entrada na aba (ex: link_speed_alert)
+            alertas = dict(CONFIG.get("alertas", {}))
             for key, entry in self.settings_alerts.items():
                 try:
-                    alertas[key] = int(entry.get())
+                    # Voltagens são fracionárias; o resto continua inteiro no JSON
+                    value = float(entry.get())
+                    alertas[key] = int(value) if value.is_integer() else value
                 except:
                     alertas[key] = 0
+            # Temperaturas em °C mesmo com a exibição em °F; crítico acima do aviso
+            if HAS_VALIDATORS_MODULE:
+                errors = validate_alert_thresholds(alertas)
+                if errors:
+                    self.settings_status.config(text=f"❌ {errors[0]}", fg=self.colors["critical"])
+                    return
             
             # === SOUNDS ===
             try:
@@ -1370,6 +3303,10 @@ class TelemetryDashboard:
                 retention = int(self.settings_retention.get())
             except:
                 retention = 7
+            try:
+                history_seconds = max(5, int(self.settings_history_seconds.get()))
+            except:
+                history_seconds = 30
             
             # === CUSTOM COLORS ===
             cores = {}
@@ -1381,6 +3318,7 @@ class TelemetryDashboard:
                 "porta": port,
                 "sender_ip": ip if mode == "manual" else "",
                 "modo": mode,
+                "multicast_group": group if mode == "multicast" else CONFIG.get("multicast_group", "239.255.0.1"),
                 "expected_link_speed_mbps": int(speed),
                 "tema": self.settings_theme_var.get(),
                 "cores_customizadas": cores,
@@ -1388,8 +3326,11 @@ class TelemetryDashboard:
                 "sons": {
                     "enabled": self.settings_sounds_enabled.get(),
                     "cooldown_seconds": sound_cooldown,
-                    "warning_sound": "warning",
-                    "critical_sound": "beep_urgent"
+                    "warning_sound": CONFIG.get("sons", {}).get("warning_sound", "warning"),
+                    "critical_sound": CONFIG.get("sons", {}).get("critical_sound", "beep_urgent"),
+                    "connection_lost_sound": CONFIG.get("sons", {}).get("connection_lost_sound", "beep_low"),
+                    "mute_minutes": CONFIG.get("sons", {}).get("mute_minutes", 15),
+                    "per_metric": CONFIG.get("sons", {}).get("per_metric", {})
                 },
                 "webhooks": {
                     "enabled": self.settings_webhooks_enabled.get(),
@@ -1404,11 +3345,11 @@ class TelemetryDashboard:
                     "csv_enabled": self.logging_enabled,
                     "auto_start_log": self.settings_auto_log.get(),
                     "retention_days": retention
-                }
+                },
+                "history_seconds": history_seconds
             }
             
             # Update global CONFIG
-            global CONFIG
             CONFIG.update(new_config)
             
             # Save to file
@@ -1421,6 +3362,12 @@ class TelemetryDashboard:
                 # Apply theme
                 self._apply_new_theme(new_config["tema"], new_config["cores_customizadas"])
                 
+                # Graph window
+                self._resize_history(force=True)
+                
+                # Sounds
+                self.sound_manager = self._init_sound_manager()
+                
                 # Signal receiver restart if port/IP changed
                 self.restart_receiver = True
                 
@@ -1449,6 +3396,7 @@ class TelemetryDashboard:
                 new_colors[key] = color
         
         self.colors = new_colors
+        self.theme_name = theme_name
         self.dark_theme = theme_name in ["dark", "cyberpunk", "high_contrast"]
         self._apply_theme()
     
@@ -1463,6 +3411,7 @@ class TelemetryDashboard:
                 filepath = os.path.join(self.log_dir, filename)
                 self.log_file = open(filepath, 'w', encoding='utf-8')
                 self.log_file.write("timestamp,cpu_usage,cpu_temp,gpu_load,gpu_temp,ram_percent,ping_ms\n")
+                self.last_csv_data = None  # Arquivo novo começa pelo pacote atual
                 print(f"[Log] Iniciado: {filepath}")
             except Exception as e:
                 print(f"[Log] Erro ao criar arquivo: {e}")
@@ -1473,8 +3422,87 @@ class TelemetryDashboard:
                 self.log_file = None
                 print("[Log] Encerrado")
     
+    def _export_graphs(self, event=None):
+        """Exporta o histórico dos gráficos em CSV e a área dos gráficos em PNG, ao lado do config."""
+        if not HAS_EXPORT_MODULE:
+            print("[Export] Módulo de exportação indisponível")
+            return
+        folder = os.path.dirname(CONFIG_PATH)
+        interval = self._sample_interval()
+        try:
+            with self.data_lock:
+                text = history_csv(self.history, interval)
+            path = os.path.join(folder, export_filename("graphs", "csv"))
+            with open(path, 'w', encoding='utf-8', newline='') as f:
+                f.write(text)
+            print(f"[Export] Histórico salvo em {path}")
+        except Exception as e:
+            print(f"[Export] Erro ao gravar CSV: {e}")
+        
+        # PNG: melhor esforço (precisa do Pillow e dos gráficos visíveis na tela)
+        if not self.show_graphs:
+            return
+        try:
+            from PIL import ImageGrab
+            canvas = self.graph_canvas
+            canvas.update_idletasks()
+            x, y = canvas.winfo_rootx(), canvas.winfo_rooty()
+            image = ImageGrab.grab(bbox=(x, y, x + canvas.winfo_width(), y + canvas.winfo_height()))
+            path = os.path.join(folder, export_filename("graphs", "png"))
+            image.save(path)
+            print(f"[Export] Gráficos salvos em {path}")
+        except Exception as e:
+            print(f"[Export] PNG indisponível: {e}")
+    
+    def _send_control(self, request) -> bool:
+        """Envia um pedido de controle ao Sender exibido (False se ainda não há Sender)."""
+        if not HAS_CONTROL_MODULE or not HAS_PROTOCOL_MODULE:
+            print("[Control] Módulo de controle indisponível")
+            return False
+        sock, addr = self.control_sock, self.control_addr
+        if sock is None or addr is None:
+            print("[Control] Nenhum Sender conectado")
+            return False
+        try:
+            sock.sendto(encode_control(request, self.encryption_key, self.auth_secret), addr)
+            return True
+        except (OSError, ProtocolError) as e:
+            print(f"[Control] Erro ao enviar pedido: {e}")
+            return False
+    
+    def _request_interval(self, factor: float):
+        """Pede ao Sender um intervalo de envio menor (+) ou maior (-) que o atual."""
+        current = self.packet_interval.interval if self.packet_interval else None
+        if not current:
+            print("[Control] Intervalo atual desconhecido (aguardando pacotes)")
+            return
+        # Arredonda para não acumular 0.1249999 entre pedidos
+        interval = round(min(max(current * factor, 0.1), 3600), 2)
+        if self._send_control(config_request(interval=interval)):
+            print(f"[Control] Pedido de intervalo {interval}s enviado a {self.last_source}")
+    
+    def _request_processes(self, event=None):
+        """Pede ao Sender para ligar/desligar a lista de processos."""
+        with self.data_lock:
+            enabled = not self.current_data.get("processes")
+        if self._send_control(config_request(sections={"processes": enabled})):
+            print(f"[Control] Pedido de processos {'ligados' if enabled else 'desligados'} enviado a {self.last_source}")
+    
+    def _save_ui_state(self):
+        """Grava geometria da janela e tema atual para a próxima execução."""
+        if not self.ui_state:
+            return
+        # Em fullscreen a geometria é a da tela: mantém a da janela normal
+        if not self.is_fullscreen:
+            self.ui_state.geometry = self.root.geometry()
+        self.ui_state.theme = self.theme_name
+        save_ui_state(self.ui_state)
+    
     def _quit_app(self, event=None):
         """Encerra a aplicação."""
+        self._save_ui_state()
+        if self.records:
+            self.records.flush(force=True)
         if self.log_file:
             self.log_file.close()
         self.root.quit()
@@ -1488,6 +3516,12 @@ class TelemetryDashboard:
 
 def main():
     """Função principal do Receiver"""
+    if JSON_STREAM:
+        sys.stdout = sys.stderr
+    # --log-format json (ou TELEMETRIA_LOG_FORMAT=json): uma linha JSON por mensagem de log
+    if HAS_LOGGING_MODULE:
+        sys.stdout = configure_output(log_format_from(sys.argv, os.environ), sys.stdout)
+    
     print("=" * 50)
     print("   CENTRAL DE TELEMETRIA - RECEIVER")
     print("=" * 50)
@@ -1498,6 +3532,8 @@ def main():
     else:
         print("Modo: Automático (broadcast UDP)")
     print("Atalhos: [F]ullscreen [G]ráficos [T]ema [L]og [S]ettings [Q]uit")
+    if JSON_STREAM:
+        print("JSON stream: NDJSON no stdout (logs no stderr)")
     print("=" * 50)
     print()
     
@@ -23,8 +23,10 @@ PyInstaller.__main__.run([
     '--add-data=sender_pc.py;.',
     '--add-data=receiver_notebook.py;.',
     '--add-data=hardware_monitor.py;.',
+    '--add-data=collector.py;.',
     '--add-data=config.json;.',
     '--add-data=libs;libs',
+    '--add-data=assets;assets',
     
     # Hidden imports para dependências
     '--hidden-import=psutil',
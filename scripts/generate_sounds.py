@@ -0,0 +1,52 @@
+"""
+Gera os WAVs de alerta empacotados em assets/sounds
+Tons sintetizados (sem dependências externas); rode de novo para regenerar
+"""
+import math
+import os
+import struct
+import wave
+
+SAMPLE_RATE = 22050
+OUTPUT_DIR = os.path.join(os.path.dirname(os.path.dirname(os.path.abspath(__file__))), "assets", "sounds")
+
+
+def tone(frequency: float, duration: float, volume: float = 0.5) -> list[int]:
+    """Senoide com fade de 5 ms nas pontas (evita estalos)"""
+    count = int(SAMPLE_RATE * duration)
+    fade = int(SAMPLE_RATE * 0.005)
+    samples = []
+    for i in range(count):
+        envelope = min(1.0, i / fade, (count - i) / fade)
+        samples.append(int(32767 * volume * envelope * math.sin(2 * math.pi * frequency * i / SAMPLE_RATE)))
+    return samples
+
+
+def silence(duration: float) -> list[int]:
+    return [0] * int(SAMPLE_RATE * duration)
+
+
+SOUNDS = {
+    # Dois tons descendentes
+    "warning": tone(880, 0.15) + silence(0.05) + tone(660, 0.2),
+    # Três beeps rápidos (mesmo padrão do winsound)
+    "beep_urgent": (tone(1000, 0.15) + silence(0.1)) * 2 + tone(1000, 0.15),
+    # Tom grave longo
+    "critical": tone(440, 0.2) + silence(0.05) + tone(330, 0.35),
+}
+
+
+def main():
+    os.makedirs(OUTPUT_DIR, exist_ok=True)
+    for name, samples in SOUNDS.items():
+        path = os.path.join(OUTPUT_DIR, f"{name}.wav")
+        with wave.open(path, "wb") as wav:
+            wav.setnchannels(1)
+            wav.setsampwidth(2)
+            wav.setframerate(SAMPLE_RATE)
+            wav.writeframes(struct.pack(f"<{len(samples)}h", *samples))
+        print(f"✓ {path}")
+
+
+if __name__ == "__main__":
+    main()
@@ -5,6 +5,23 @@ Coleta métricas de hardware e envia via UDP para o Receiver.
 Roda na bandeja do sistema (System Tray) após inicialização.
 
 Requer privilégios de administrador para acessar sensores.
+
+Opções:
+    --no-admin: Não pede elevação (debug; sensores limitados)
+    --dump-sensors: Lista todos os sensores do LibreHardwareMonitor
+                    (identificador, tipo, nome, hardware, valor) e sai
+    --selftest: Faz algumas coletas, imprime o payload completo (campos zerados
+                como "indisponível") e as fontes detectadas, e sai sem enviar nada
+    --port, --dest-ip, --interval, --mode, --bind-ip, --multicast-group,
+    --ping-host, --log-dir, --log-level <valor>: Sobrescrevem o config.json
+    --set <campo>=<valor>: Sobrescreve qualquer campo (ex: --set top_processes=5)
+    --log-format json: Uma linha JSON por mensagem de log (ts, level, target,
+                       message), para agregadores; também TELEMETRIA_LOG_FORMAT=json
+
+Variáveis de ambiente: TELEMETRIA_SENDER_<OPÇÃO> para as mesmas opções nomeadas
+(ex: TELEMETRIA_SENDER_PORT=6000, TELEMETRIA_SENDER_DEST_IP=192.168.1.20)
+
+Precedência do config: padrão < config.json < variáveis de ambiente < linha de comando
 """
 from __future__ import annotations
 
@@ -14,9 +31,11 @@ import time
 import json
 import sys
 import os
-import gzip
 import ctypes
+import select
+import signal
 import threading
+from datetime import timedelta
 from typing import Optional, Any
 
 # ========== AUTO-ELEVAÇÃO PARA ADMINISTRADOR ==========
@@ -41,6 +60,11 @@ def run_as_admin():
 
 # Argumento --no-admin para desativar elevação (para debug)
 SKIP_ADMIN = "--no-admin" in sys.argv
+# --dump-sensors: uma leitura bruta dos sensores, sem loop de envio
+DUMP_SENSORS = "--dump-sensors" in sys.argv
+# --selftest: confere os sensores sem receiver (coleta, imprime o payload e sai)
+SELFTEST = "--selftest" in sys.argv
+SELFTEST_CYCLES = 3
 
 if not SKIP_ADMIN and not is_admin():
     print("=" * 50)
@@ -52,11 +76,31 @@ if not SKIP_ADMIN and not is_admin():
 
 
 # ========== IMPORTS PÓS-ELEVAÇÃO ==========
-try:
+# Coleta (LHM, NVML, psutil): ver collector.py; aqui ficam envio, bandeja e controle
+from collector import TelemetryCollector, HAS_HWMON
+if HAS_HWMON:
     import hardware_monitor
-    HAS_HWMON = True
-except ImportError:
-    HAS_HWMON = False
+
+from core.network import parse_ping_host, DEFAULT_PING_HOST
+from core.network import socket_family, bind_address, peer_address, open_udp_socket, format_address
+from core.network import set_multicast_ttl, set_multicast_interface, DEFAULT_MULTICAST_GROUP
+from core.network import apply_socket_options, socket_ttl, send_buffer_size, interface_address
+from core.payload import format_payload, sensor_status_hints, SENSOR_STATUS_FIELDS
+from core.validators import validate_sender_network
+from core.compact import encode_compact
+from core.delta import DeltaEncoder
+from core.protocol import wrap_frame, fit_payload, serialize_payload, compress_body, FrameTooLargeError, ProtocolError, encode_goodbye, MagicByte, FLAGS_MASK, HAS_CRYPTO, MAX_UDP_PAYLOAD
+from core.transport import send_with_retry, SendHealth, SendSummary
+from core.scheduling import SlowRefresh, FixedTicker
+from core.control import decode_control, apply_config_request
+from core.sensors import parse_disabled_sources
+from core.logging_config import log_format_from, configure_output, rotating_file_handler
+from core.overrides import parse_cli_overrides, env_overrides, apply_overrides, SENDER_CLI_OPTIONS, SENDER_ENV_PREFIX
+from core.config import load_versioned_config, CONFIG_VERSION
+
+# --log-format json (ou TELEMETRIA_LOG_FORMAT=json): uma linha JSON por mensagem de log
+LOG_FORMAT = log_format_from(sys.argv, os.environ)
+sys.stdout = configure_output(LOG_FORMAT, sys.stdout)
 
 # System Tray (pystray)
 try:
@@ -74,22 +118,44 @@ def carregar_config():
     config_path = os.path.join(os.path.dirname(os.path.abspath(__file__)), "config.json")
     
     config_padrao = {
+        "config_version": CONFIG_VERSION,  # Formato do arquivo (migrado automaticamente)
         "modo": "broadcast",
         "dest_ip": "255.255.255.255",
         "porta": 5005,
         "intervalo": 0.5,
-        "bind_ip": ""  # IP local para enviar (vazio = auto)
+        "bind_ip": "",  # IP local para enviar (vazio = auto)
+        "bind_interface": "",  # Interface para enviar pelo nome, ex: "Ethernet" (tem precedência sobre bind_ip)
+        "ipv6": False,  # Força IPv6 (automático se dest_ip/bind_ip for IPv6)
+        "multicast_group": DEFAULT_MULTICAST_GROUP,  # Grupo usado no modo "multicast"
+        "multicast_ttl": 1,  # Saltos de roteador do multicast (1 = só a sub-rede local)
+        "ttl": 0,  # TTL do unicast/broadcast (0 = padrão do sistema)
+        "socket_send_buf_bytes": 0,  # SO_SNDBUF (0 = padrão do sistema)
+        "top_processes": 0,  # Processos mais pesados no payload (0 = desativado)
+        "gpu_index": 0,  # GPU NVIDIA lida pelo NVML (ordem do nvidia-smi)
+        "network_interface": "",  # Interface monitorada (vazio = soma das físicas)
+        "ping_host": DEFAULT_PING_HOST,  # Alvo do ping ("host" ou "host:porta")
+        "ping_icmp": False,  # Ping ICMP real (precisa de Admin); cai para TCP se negado
+        "encryption_key": "",  # Chave compartilhada com o receiver (vazio = sem criptografia)
+        "auth_secret": "",  # Segredo da tag HMAC (vazio = sem tag)
+        "compact_encoding": False,  # Frame binário quantizado (~100 bytes, com perda) em vez de JSON
+        "delta_keyframe_every": 0,  # Frames delta: payload inteiro a cada N frames, só o que mudou entre eles (0 = desligado)
+        "control_requests": False,  # Aceita pedidos do receiver (intervalo, seções opcionais)
+        "slow_refresh_secs": 10.0,  # Discos (SMART): atualização lenta
+        "link_check_interval_secs": 60.0,  # Velocidade do link/nome do adaptador (consulta ao PowerShell)
+        "log_dir": "",  # Pasta do log em arquivo, rotacionado por dia (vazio = só console)
+        "log_level": "info",  # Nível mínimo: debug, info, warn, error ou off
+        "log_backup_days": 7,  # Dias de log antigo mantidos na pasta
+        "log_summary_secs": 0,  # Resumo dos envios a cada N segundos (0 = uma linha por envio)
+        "disabled_sources": []  # Fontes desligadas mesmo se disponíveis: lhm, nvml, hwmon, cpu_freq, storage, wmi
     }
     
     if os.path.exists(config_path):
         try:
-            with open(config_path, 'r', encoding='utf-8') as f:
-                config = json.load(f)
-                for key in config_padrao:
-                    if key not in config:
-                        config[key] = config_padrao[key]
-                print(f"[Config] Carregado de {config_path}")
-                return config
+            config, migrado = load_versioned_config(config_path, config_padrao)
+            print(f"[Config] Carregado de {config_path}")
+            if migrado:
+                print(f"[Config] config.json atualizado para a versão {CONFIG_VERSION}")
+            return config
         except Exception as e:
             print(f"[Config] Erro: {e}")
     else:
@@ -98,10 +164,34 @@ def carregar_config():
                 json.dump({
                     **config_padrao,
                     "comentarios": {
-                        "modo": "Opções: 'broadcast' ou 'unicast'",
+                        "config_version": "Versão do formato do arquivo; não edite (arquivos antigos são atualizados ao iniciar)",
+                        "modo": "Opções: 'broadcast', 'unicast' ou 'multicast'",
                         "dest_ip": "IP do notebook (ignorado em broadcast)",
+                        "ipv6": "true = socket IPv6; automático quando dest_ip ou bind_ip é IPv6",
+                        "bind_interface": "Nome da interface para enviar (ex: 'Ethernet', 'wlan0'); o IP acompanha o DHCP. Tem precedência sobre bind_ip",
+                        "multicast_group": "Grupo multicast do modo 'multicast' (mesmo no receiver)",
+                        "multicast_ttl": "TTL do multicast; aumente para atravessar roteadores",
+                        "ttl": "TTL do unicast/broadcast (1-255); 0 mantém o padrão do sistema",
+                        "socket_send_buf_bytes": "Buffer de envio do socket em bytes (4096 a 16 MiB); 0 mantém o padrão do sistema",
                         "porta": "Porta UDP",
-                        "intervalo": "Segundos entre envios"
+                        "intervalo": "Segundos entre envios",
+                        "top_processes": "Quantos processos (por uso de CPU) enviar; 0 desativa",
+                        "gpu_index": "Índice da GPU NVIDIA monitorada pelo NVML (0 = primeira)",
+                        "network_interface": "Interface de rede monitorada (vazio = todas as físicas)",
+                        "ping_host": "Alvo do ping no formato 'host' ou 'host:porta'",
+                        "ping_icmp": "true = ping ICMP (requer Administrador), false = handshake TCP",
+                        "encryption_key": "Chave compartilhada para cifrar os pacotes (vazio = em claro)",
+                        "auth_secret": "Segredo para assinar os pacotes com HMAC (vazio = sem assinatura)",
+                        "compact_encoding": "Encoding binário quantizado (com perda: 0.1 em temperaturas/uso, só os campos principais) para links muito lentos; o receiver entende sem configurar",
+                        "delta_keyframe_every": "Envia o payload inteiro a cada N frames e, entre eles, só os campos que mudaram (sem perda). 0 = desligado; ignorado com compact_encoding",
+                        "control_requests": "true = o receiver pode mudar o intervalo e ligar/desligar os processos em tempo real",
+                        "slow_refresh_secs": "Segundos entre leituras lentas (SMART dos discos)",
+                        "link_check_interval_secs": "Segundos entre consultas da velocidade do link e do nome do adaptador (lentas, mudam raramente)",
+                        "log_dir": "Pasta para gravar o log (um arquivo por dia); vazio = só console",
+                        "log_level": "Nível mínimo do log: debug, info, warn, error ou off",
+                        "log_backup_days": "Quantos dias de log antigo manter",
+                        "log_summary_secs": "Resumo dos envios a cada N segundos em vez de uma linha por envio (erros saem na hora); 0 = uma linha por envio",
+                        "disabled_sources": "Fontes de sensores a nunca consultar, mesmo disponíveis: lhm, nvml, hwmon, cpu_freq, storage (discos/SMART; 'smart' também vale), wmi (consultas ao PowerShell)"
                     }
                 }, f, indent=4, ensure_ascii=False)
             print(f"[Config] Criado config.json padrão")
@@ -111,71 +201,152 @@ def carregar_config():
     return config_padrao
 
 CONFIG = carregar_config()
-DEST_IP = CONFIG["dest_ip"]
+# TELEMETRIA_SENDER_PORT=6000...: valor inválido só gera aviso
+CONFIG, _avisos_config = apply_overrides(
+    CONFIG, env_overrides(os.environ, SENDER_ENV_PREFIX, SENDER_CLI_OPTIONS), "variável de ambiente"
+)
+for _aviso in _avisos_config:
+    print(f"[Config] Aviso: {_aviso} (ignorado)")
+# --port 6000, --set campo=valor...: valem só nesta execução (config.json não muda)
+CONFIG, _erros_config = apply_overrides(CONFIG, parse_cli_overrides(sys.argv, SENDER_CLI_OPTIONS))
+for _erro in _erros_config:
+    print(f"[Config] ERRO: {_erro}")
+if _erros_config:
+    sys.exit(1)
+MODO = CONFIG["modo"]
+# Em multicast o destino é o grupo (dest_ip fica para broadcast/unicast)
+DEST_IP = CONFIG.get("multicast_group", DEFAULT_MULTICAST_GROUP) if MODO == "multicast" else CONFIG["dest_ip"]
 PORTA = CONFIG["porta"]
 INTERVALO = CONFIG["intervalo"]
-MODO = CONFIG["modo"]
-BIND_IP = CONFIG.get("bind_ip", "")  # IP local para bind
+MULTICAST_TTL = CONFIG.get("multicast_ttl", 1)
+SOCKET_TTL = socket_ttl(CONFIG.get("ttl", 0))
+SOCKET_SEND_BUF = send_buffer_size(CONFIG.get("socket_send_buf_bytes", 0))
+BIND_INTERFACE = CONFIG.get("bind_interface", "").strip()  # IP resolvido no início e a cada BIND_RECHECK_SECS
+BIND_IP = "" if BIND_INTERFACE else CONFIG.get("bind_ip", "")  # IP local para bind
+SOCKET_FAMILY = socket_family(DEST_IP, BIND_IP, ipv6=CONFIG.get("ipv6", False))
+DEST_ADDR = peer_address(DEST_IP, PORTA, SOCKET_FAMILY)
+TOP_PROCESSES = CONFIG.get("top_processes", 0)
+GPU_INDEX = CONFIG.get("gpu_index", 0)
+NETWORK_INTERFACE = CONFIG.get("network_interface", "")
+PING_HOST = CONFIG.get("ping_host", DEFAULT_PING_HOST)
+PING_ICMP = CONFIG.get("ping_icmp", False)
+ENCRYPTION_KEY = CONFIG.get("encryption_key", "")
+AUTH_SECRET = CONFIG.get("auth_secret", "")
+COMPACT_ENCODING = bool(CONFIG.get("compact_encoding", False))
+DELTA_KEYFRAME_EVERY = CONFIG.get("delta_keyframe_every", 0)
+if DELTA_KEYFRAME_EVERY and COMPACT_ENCODING:
+    print("[Config] Aviso: delta_keyframe_every não vale com compact_encoding (ignorado)")
+    DELTA_KEYFRAME_EVERY = 0
+CONTROL_REQUESTS = CONFIG.get("control_requests", False)
+SLOW_REFRESH_SECS = CONFIG.get("slow_refresh_secs", 10.0)
+LINK_CHECK_INTERVAL_SECS = CONFIG.get("link_check_interval_secs", 60.0)
+BIND_RECHECK_SECS = 10.0  # Consulta barata (psutil): pega a renovação do DHCP logo
+LOG_DIR = CONFIG.get("log_dir", "")
+LOG_LEVEL = CONFIG.get("log_level", "info")
+LOG_BACKUP_DAYS = CONFIG.get("log_backup_days", 7)
+LOG_SUMMARY_SECS = CONFIG.get("log_summary_secs", 0)
+DISABLED_SOURCES, _avisos_fontes = parse_disabled_sources(CONFIG.get("disabled_sources", []))
+for _aviso in _avisos_fontes:
+    print(f"[Config] Aviso: {_aviso} (ignorado)")
+
+# Log em arquivo e nível mínimo só são conhecidos depois do config; o writer
+# fica como sys.stdout até o fim do programa (o arquivo é fechado no atexit)
+if LOG_DIR:
+    _log_dir = os.path.join(os.path.dirname(os.path.abspath(__file__)), LOG_DIR)
+    try:
+        _log_file = rotating_file_handler(_log_dir, "sender", LOG_BACKUP_DAYS)
+    except OSError as e:
+        print(f"[Log] Erro ao abrir a pasta de log {_log_dir}: {e}")
+        _log_file = None
+else:
+    _log_file = None
+sys.stdout = configure_output(LOG_FORMAT, sys.__stdout__, LOG_LEVEL, _log_file)
+if _log_file is not None:
+    print(f"[Log] Gravando em {_log_dir} (nível {LOG_LEVEL})")
 # ==========================================
 
 
 class TelemetrySender:
     """Sender de telemetria com suporte a System Tray."""
     
-    def __init__(self):
+    def __init__(self, open_socket: bool = True):
         self.running = True
         self.paused = False
-        self.monitor = None
+        self.stop_event = threading.Event()  # Interrompe o sleep do loop no encerramento
+        self.start_time = time.time()
+        self.packets_sent = 0
+        self.send_health = SendHealth(rebuild_after=5)
+        # Intervalo curto encheria o log com uma linha por envio: resumo periódico
+        self.send_summary = SendSummary(LOG_SUMMARY_SECS) if LOG_SUMMARY_SECS > 0 else None
+        self.delta = DeltaEncoder(DELTA_KEYFRAME_EVERY) if DELTA_KEYFRAME_EVERY > 0 else None
         self.sock = None
         self.icon = None
-        self.last_net = None
-        self.last_t = None
         
-        # Cache para link de rede (evita chamar PowerShell a cada ciclo)
-        self.cached_link_info: dict = {"link_speed_mbps": 0, "adapter_name": ""}
-        self.last_link_check: float = 0
-        self.LINK_CHECK_INTERVAL: float = 10.0  # Verificar apenas a cada 10 segundos
+        # Intervalo: começa no config, o receiver pode mudar (control_requests)
+        self.interval = INTERVALO
         
-        # Inicializa socket
-        self._init_socket()
+        # Sensores e montagem do payload (seções opcionais em self.collector.sections)
+        self.collector = TelemetryCollector(
+            top_processes=TOP_PROCESSES,
+            gpu_index=GPU_INDEX,
+            network_interface=NETWORK_INTERFACE,
+            ping_host=PING_HOST,
+            ping_icmp=PING_ICMP,
+            slow_refresh_secs=SLOW_REFRESH_SECS,
+            link_check_interval_secs=LINK_CHECK_INTERVAL_SECS,
+            disabled_sources=DISABLED_SOURCES,
+            admin=bool(is_admin())  # Sem Admin o LHM lê menos sensores (sensor_status)
+        )
+        
+        # IP de bind: fixo (bind_ip) ou o atual de bind_interface, reconsultado periodicamente
+        self.bind_ip = BIND_IP
+        self.bind_ip = self._resolver_bind_ip()
+        if BIND_INTERFACE:
+            print(f"[Socket] Interface {BIND_INTERFACE}: {self.bind_ip or 'sem endereço, usando a interface padrão'}")
+        self.bind_check = SlowRefresh(BIND_RECHECK_SECS)
+        self.bind_check.due()
         
-        # Inicializa hardware monitor
-        self._init_hardware_monitor()
+        # Inicializa socket (o --selftest só coleta)
+        if open_socket:
+            self._init_socket()
         
-        # Inicializa rede
-        self.last_net = psutil.net_io_counters()
-        self.last_t = time.time()
+        # LibreHardwareMonitor, NVML e a thread do ping
+        self.collector.start()
     
     def _init_socket(self):
         """Configura socket UDP."""
-        self.sock = socket.socket(socket.AF_INET, socket.SOCK_DGRAM)
+        self.sock = open_udp_socket(SOCKET_FAMILY)
         
-        if MODO == "broadcast" or DEST_IP == "255.255.255.255":
+        if MODO == "multicast":
+            set_multicast_ttl(self.sock, MULTICAST_TTL, SOCKET_FAMILY)
+            if self.bind_ip and SOCKET_FAMILY == socket.AF_INET:
+                set_multicast_interface(self.sock, self.bind_ip)
+            print(f"[Socket] Modo MULTICAST - grupo {format_address(DEST_IP, PORTA)} (TTL {MULTICAST_TTL})")
+        elif SOCKET_FAMILY == socket.AF_INET6:
+            print(f"[Socket] IPv6 - UNICAST {format_address(DEST_IP, PORTA)}")
+        elif MODO == "broadcast" or DEST_IP == "255.255.255.255":
             self.sock.setsockopt(socket.SOL_SOCKET, socket.SO_BROADCAST, 1)
             print("[Socket] Modo BROADCAST ativado")
         else:
             print(f"[Socket] Modo UNICAST - {DEST_IP}")
         
         # Bind a uma interface específica se configurado
-        if BIND_IP:
+        if self.bind_ip:
             try:
-                self.sock.bind((BIND_IP, 0))
-                print(f"[Socket] Bind na interface: {BIND_IP}")
+                self.sock.bind(bind_address(self.bind_ip, 0, SOCKET_FAMILY))
+                print(f"[Socket] Bind na interface: {self.bind_ip}")
             except Exception as e:
-                print(f"[Socket] Erro ao bind em {BIND_IP}: {e}")
+                print(f"[Socket] Erro ao bind em {self.bind_ip}: {e}")
         else:
             print("[Socket] Usando interface padrão")
-    
-    def _init_hardware_monitor(self):
-        """Inicializa LibreHardwareMonitor."""
-        if HAS_HWMON:
-            print("[HW] Inicializando LibreHardwareMonitor...")
-            self.monitor = hardware_monitor.HardwareMonitor()
-            if not self.monitor.enabled:
-                print("[HW] AVISO: DLL não carregou. Dados limitados.")
-                self.monitor = None
-        else:
-            print("[HW] hardware_monitor.py não encontrado.")
+        
+        # TTL e buffer de envio só quando configurados (senão, padrão do sistema)
+        if SOCKET_TTL or SOCKET_SEND_BUF:
+            try:
+                applied = apply_socket_options(self.sock, SOCKET_FAMILY, SOCKET_TTL, SOCKET_SEND_BUF)
+                print(f"[Socket] TTL {applied['ttl']}, buffer de envio {applied['send_buffer']} bytes")
+            except OSError as e:
+                print(f"[Socket] Erro ao aplicar TTL/buffer de envio: {e}")
     
     def _create_tray_icon(self):
         """Cria ícone para System Tray."""
@@ -210,177 +381,216 @@ class TelemetrySender:
     def _restart_monitor(self, icon=None, item=None):
         """Reinicia o hardware monitor."""
         print("[HW] Reiniciando monitor...")
-        if self.monitor:
-            try:
-                self.monitor.close()
-            except:
-                pass
-        self._init_hardware_monitor()
+        self.collector.restart_monitor()
     
     def _quit(self, icon=None, item=None):
         """Encerra o sender."""
         print("\n[Sender] Encerrando...")
         self.running = False
+        self.stop_event.set()
         if self.icon:
             self.icon.stop()
     
-    def _calcular_rede(self):
-        """Calcula velocidade de rede."""
-        now = time.time()
-        net_io = psutil.net_io_counters()
-        delta = now - self.last_t
-        if delta <= 0:
-            delta = 1
-        
-        sent = net_io.bytes_sent - self.last_net.bytes_sent
-        recv = net_io.bytes_recv - self.last_net.bytes_recv
+    def _install_signal_handlers(self):
+        """Encerra de forma limpa em Ctrl+C, SIGTERM e fechamento do console."""
+        def handler(signum, frame):
+            self._quit()
         
-        self.last_net = net_io
-        self.last_t = now
+        signal.signal(signal.SIGINT, handler)
+        signal.signal(signal.SIGTERM, handler)
+        if hasattr(signal, "SIGBREAK"):  # Ctrl+Break no Windows
+            signal.signal(signal.SIGBREAK, handler)
         
-        return (sent/1024)/delta, (recv/1024)/delta
-    
-    def _medir_ping(self, host="8.8.8.8"):
-        """Mede latência para host externo."""
-        try:
-            t1 = time.time()
-            s = socket.socket(socket.AF_INET, socket.SOCK_STREAM)
-            s.settimeout(1)
-            s.connect((host, 53))
-            s.close()
-            return round((time.time() - t1) * 1000, 1)
-        except:
-            return 0
-    
-    def _build_payload(self, hw_data):
-        """Monta payload de telemetria (unificado)."""
-        cpu_percent = psutil.cpu_percent(interval=None)
-        mem = psutil.virtual_memory()
-        up, down = self._calcular_rede()
-        ping = self._medir_ping()
-        
-        # Valores padrão
-        payload = {
-            "cpu": {
-                "usage": cpu_percent,
-                "temp": 0,
-                "voltage": 0,
-                "power": 0,
-                "clock": 0
-            },
-            "gpu": {
-                "load": 0,
-                "temp": 0,
-                "voltage": 0,
-                "clock_core": 0,
-                "clock_mem": 0,
-                "fan": 0,
-                "mem_used_mb": 0
-            },
-            "mobo": {
-                "temp": 0
-            },
-            "ram": {
-                "percent": mem.percent,
-                "used_gb": round(mem.used / (1024**3), 2),
-                "total_gb": round(mem.total / (1024**3), 2)
-            },
-            "storage": [],
-            "fans": [],
-            "network": {
-                "down_kbps": round(down, 1),
-                "up_kbps": round(up, 1),
-                "ping_ms": ping,
-                "link_speed_mbps": 0,
-                "adapter_name": ""
-            }
-        }
-        
-        # Sobrescreve com dados do hardware monitor se disponíveis
-        if hw_data:
-            payload["cpu"]["temp"] = round(hw_data["cpu"]["temp"], 1)
-            payload["cpu"]["voltage"] = round(hw_data["cpu"]["voltage"], 3)
-            payload["cpu"]["power"] = round(hw_data["cpu"]["power"], 1)
-            payload["cpu"]["clock"] = round(hw_data["cpu"]["clock"], 0)
-            
-            payload["gpu"]["load"] = round(hw_data["gpu"]["load"], 1)
-            payload["gpu"]["temp"] = round(hw_data["gpu"]["temp"], 1)
-            payload["gpu"]["voltage"] = round(hw_data["gpu"]["voltage"], 3)
-            payload["gpu"]["clock_core"] = round(hw_data["gpu"]["clock_core"], 0)
-            payload["gpu"]["clock_mem"] = round(hw_data["gpu"]["clock_mem"], 0)
-            payload["gpu"]["fan"] = round(hw_data["gpu"]["fan"], 0)
-            payload["gpu"]["mem_used_mb"] = round(hw_data["gpu"]["mem_used"], 0)
-            
-            payload["mobo"]["temp"] = round(hw_data["mobo"]["temp"], 1)
-            payload["storage"] = hw_data["storage"]
-            payload["fans"] = hw_data["fans"]
-        
-        # Obter informações do adaptador de rede (velocidade do link) COM CACHE
-        # A velocidade do link não muda frequentemente, só quando desconecta o cabo
-        if self.monitor and self.monitor.enabled:
+        # Windows: fechar a janela do console não gera sinal Python
+        if sys.platform == 'win32':
             try:
-                current_time = time.time()
-                # Só chama o PowerShell se passou o tempo do intervalo
-                if current_time - self.last_link_check > self.LINK_CHECK_INTERVAL:
-                    self.cached_link_info = self.monitor.get_network_link_info()
-                    self.last_link_check = current_time
+                HandlerRoutine = ctypes.WINFUNCTYPE(ctypes.c_bool, ctypes.c_uint)
                 
-                # Usa os dados cacheados
-                payload["network"]["link_speed_mbps"] = self.cached_link_info.get("link_speed_mbps", 0)
-                payload["network"]["adapter_name"] = self.cached_link_info.get("adapter_name", "")
-            except Exception:
-                pass
-        
-        return payload
+                def console_handler(event):
+                    # CTRL_CLOSE_EVENT, CTRL_LOGOFF_EVENT, CTRL_SHUTDOWN_EVENT
+                    if event in (2, 5, 6):
+                        self._quit()
+                        return True
+                    return False
+                
+                # Mantém referência para o callback não ser coletado
+                self._console_handler = HandlerRoutine(console_handler)
+                ctypes.windll.kernel32.SetConsoleCtrlHandler(self._console_handler, True)
+            except Exception as e:
+                print(f"[Sender] Não foi possível registrar handler do console: {e}")
     
     def _sender_loop(self):
         """Loop principal de coleta e envio."""
         print(f"\n{'='*50}")
         print("   SENTINELA DE TELEMETRIA - ATIVO")
         print(f"{'='*50}")
-        print(f"Destino: {'BROADCAST' if MODO == 'broadcast' else DEST_IP}:{PORTA}")
+        print(f"Destino: {format_address('BROADCAST' if MODO == 'broadcast' else DEST_IP, PORTA)}")
         print(f"Intervalo: {INTERVALO}s")
         print(f"{'='*50}\n")
         
         # Primeira leitura de CPU (prepara o contador)
         psutil.cpu_percent(interval=None)
         
+        # Prazos fixos: um ciclo lento não atrasa os seguintes
+        ticker = FixedTicker(self.interval)
         while self.running:
+            if BIND_INTERFACE and self.bind_check.due():
+                self._verificar_bind()
             if not self.paused:
-                try:
-                    # Coleta dados
-                    hw_data = None
-                    if self.monitor and self.monitor.enabled:
-                        hw_data = self.monitor.fetch_data()
-                    
-                    # Monta payload
-                    payload = self._build_payload(hw_data)
-                    
-                    # Serializa e compacta
-                    data = json.dumps(payload).encode()
-                    compressed = gzip.compress(data)
-                    
-                    # Magic byte: 0x01 = gzip, 0x00 = raw JSON
-                    # Envia com prefixo indicando tipo de encoding
-                    if len(compressed) < len(data):
-                        sent = self.sock.sendto(b'\x01' + compressed, (DEST_IP, PORTA))
-                        print(f"[Send] {sent} bytes para {DEST_IP}:{PORTA} (gzip)")
-                    else:
-                        sent = self.sock.sendto(b'\x00' + data, (DEST_IP, PORTA))
-                        print(f"[Send] {sent} bytes para {DEST_IP}:{PORTA} (raw)")
-                    
-                except Exception as e:
-                    print(f"[Erro] {e}")
+                self._enviar_pacote()
+            if CONTROL_REQUESTS:
+                self._poll_control()
+                ticker.interval = self.interval
             
-            time.sleep(INTERVALO)
+            # Dorme até o próximo prazo; acorda na hora se pedirem para encerrar
+            remaining, skipped = ticker.advance()
+            if skipped:
+                print(f"[Sender] Ciclo passou do intervalo de {self.interval}s: {skipped} envio(s) pulado(s)")
+            if remaining > 0 and self.stop_event.wait(remaining):
+                break
         
         # Cleanup
-        if self.monitor:
-            self.monitor.close()
+        uptime = timedelta(seconds=int(time.time() - self.start_time))
+        print(f"[Sender] Encerrado: {self.packets_sent} pacotes enviados em {uptime}")
+        self._enviar_despedida()
+        self.collector.close()
+        self.sock.close()
+    
+    def _resolver_bind_ip(self) -> str:
+        """bind_ip do config, ou o IP atual de bind_interface ("" sem endereço)."""
+        if not BIND_INTERFACE:
+            return BIND_IP
+        try:
+            return interface_address(psutil.net_if_addrs(), BIND_INTERFACE, SOCKET_FAMILY)
+        except OSError as e:
+            print(f"[Socket] Erro ao listar interfaces: {e}")
+            return self.bind_ip  # Mantém o último IP conhecido
+    
+    def _verificar_bind(self):
+        """Recria o socket se o IP de bind_interface mudou (DHCP renovado, cabo reconectado)."""
+        ip = self._resolver_bind_ip()
+        if ip == self.bind_ip:
+            return
+        print(f"[Socket] IP de {BIND_INTERFACE} mudou: {self.bind_ip or '-'} -> {ip or '-'}; recriando o socket")
+        self.bind_ip = ip
         self.sock.close()
+        self._init_socket()
+    
+    def _enviar_despedida(self):
+        """Avisa o receiver que o sender parou (senão ele só percebe pelo timeout)."""
+        try:
+            self.sock.sendto(encode_goodbye(self.collector.hostname, ENCRYPTION_KEY, AUTH_SECRET), DEST_ADDR)
+            print("[Sender] Despedida enviada ao receiver")
+        except (OSError, ProtocolError) as e:
+            print(f"[Sender] Não foi possível enviar a despedida: {e}")
+    
+    def _enviar_pacote(self):
+        """Coleta, serializa e envia um pacote de telemetria."""
+        try:
+            payload = self.collector.collect()
+            
+            # Serializa; se não couber num datagrama, descarta as seções opcionais
+            try:
+                frame, dropped = fit_payload(payload, self._codificar)
+            except FrameTooLargeError as e:
+                print(f"[Send] Pacote descartado: {e}")
+                return
+            if dropped:
+                print(f"[Send] Pacote acima de {MAX_UDP_PAYLOAD} bytes: sem {', '.join(dropped)}")
+            
+            encoding = {MagicByte.GZIP: "gzip", MagicByte.COMPACT: "compact", MagicByte.DELTA: "delta"}.get(frame[0] & ~FLAGS_MASK & 0xFF, "raw")
+            if ENCRYPTION_KEY:
+                encoding += "+chacha20"
+            if AUTH_SECRET:
+                encoding += "+hmac"
+            
+            try:
+                sent = send_with_retry(self.sock.sendto, frame, DEST_ADDR)
+            except OSError as e:
+                self._registrar_falha_envio(e)
+                return
+            if self.delta:
+                self.delta.commit()
+            
+            recovered = self.send_health.success()
+            if recovered:
+                print(f"[Send] Envio normalizado após {recovered} falha(s) seguida(s)")
+            if self.send_summary:
+                summary = self.send_summary.add(sent, payload["cpu"]["usage"], payload["gpu"]["load"])
+                if summary:
+                    print(f"[Send] {summary} para {format_address(DEST_IP, PORTA)} ({encoding})")
+            else:
+                print(f"[Send] {sent} bytes para {format_address(DEST_IP, PORTA)} ({encoding})")
+            
+            self.packets_sent += 1
+
+        except Exception as e:
+            print(f"[Erro] {e}")
+    
+    def _poll_control(self):
+        """Aplica os pedidos de controle que chegaram no socket (sem bloquear o envio)."""
+        while self.sock:
+            try:
+                readable, _, _ = select.select([self.sock], [], [], 0)
+                if not readable:
+                    return
+                frame, addr = self.sock.recvfrom(1024)
+            except OSError:
+                return  # Ex: ICMP port unreachable do último envio (Windows)
+            try:
+                request = decode_control(frame, ENCRYPTION_KEY, AUTH_SECRET)
+            except ProtocolError as e:
+                print(f"[Control] Pedido descartado de {addr[0]}: {e}")
+                continue
+            if request is None:
+                continue
+            self.interval, self.collector.sections, changes, errors = apply_config_request(
+                request, self.interval, self.collector.sections
+            )
+            for error in errors:
+                print(f"[Control] Recusado de {addr[0]}: {error}")
+            if changes:
+                print(f"[Control] Pedido de {addr[0]}: {', '.join(changes)}")
+    
+    def _codificar(self, payload):
+        """Serializa, compacta (se compensar) e aplica criptografia/assinatura."""
+        if COMPACT_ENCODING:
+            # Binário quantizado: só os campos principais, sem discos/processos
+            return wrap_frame(bytes([MagicByte.COMPACT]) + encode_compact(payload), ENCRYPTION_KEY, AUTH_SECRET)
+        if self.delta:
+            # Keyframe ou só os campos que mudaram; o estado avança no commit (após o envio)
+            return wrap_frame(self.delta.encode(payload), ENCRYPTION_KEY, AUTH_SECRET)
+        
+        data = serialize_payload(payload)
+        compressed = compress_body(data)
+        
+        # Magic byte: 0x01 = gzip, 0x00 = raw JSON
+        # Envia com prefixo indicando tipo de encoding
+        if len(compressed) < len(data):
+            frame = b'\x01' + compressed
+        else:
+            frame = b'\x00' + data
+        
+        # Criptografia e assinatura opcionais (flags no magic byte)
+        return wrap_frame(frame, ENCRYPTION_KEY, AUTH_SECRET)
+    
+    def _registrar_falha_envio(self, error):
+        """Conta a falha e recria o socket se elas se acumularem (ex: Wi-Fi trocou de AP)."""
+        if self.send_health.consecutive_failures == 0:
+            print(f"[Send] Falha ao enviar: {error}")
+        
+        if self.send_health.failure():
+            print(f"[Send] {self.send_health.consecutive_failures} falhas seguidas - recriando socket")
+            try:
+                self.sock.close()
+            except OSError:
+                pass
+            self._init_socket()
     
     def run(self):
         """Inicia o sender."""
+        self._install_signal_handlers()
+        
         # Inicia thread de envio
         sender_thread = threading.Thread(target=self._sender_loop, daemon=True)
         sender_thread.start()
@@ -409,13 +619,98 @@ class TelemetrySender:
             print("[Console] Ctrl+C para sair...")
             try:
                 while self.running:
-                    time.sleep(1)
+                    self.stop_event.wait(1)
             except KeyboardInterrupt:
                 self._quit()
+        
+        # Aguarda o loop terminar o ciclo atual e liberar o socket
+        sender_thread.join(timeout=INTERVALO + 5)
+
+
+def dump_sensors():
+    """Imprime todos os sensores brutos do LHM (para relatar sensores mal mapeados)."""
+    if not HAS_HWMON:
+        print("[HW] hardware_monitor.py não encontrado.")
+        sys.exit(1)
+    
+    monitor = hardware_monitor.HardwareMonitor()
+    if not monitor.enabled:
+        print("[HW] DLL não carregou: nada para listar.")
+        sys.exit(1)
+    
+    sensors = []
+    monitor.fetch_data(raw_sensors=sensors)
+    monitor.close()
+    table = hardware_monitor.format_sensor_table(sensors)
+    print(table)
+    print(f"\n{len(sensors)} sensores")
+    
+    # A janela elevada fecha ao terminar: deixa uma cópia para anexar ao relatório
+    dump_path = os.path.join(os.path.dirname(os.path.abspath(__file__)), "sensors_dump.txt")
+    try:
+        with open(dump_path, 'w', encoding='utf-8') as f:
+            f.write(table + "\n")
+        print(f"Salvo em {dump_path}")
+    except OSError as e:
+        print(f"[HW] Não foi possível salvar {dump_path}: {e}")
+
+
+def selftest(cycles: int = SELFTEST_CYCLES):
+    """Coleta algumas vezes sem abrir socket e imprime o último payload e as fontes."""
+    sender = TelemetrySender(open_socket=False)
+    
+    # Uso de CPU e taxas de rede/disco precisam de um intervalo entre leituras
+    psutil.cpu_percent(interval=None)
+    payload = {}
+    for cycle in range(1, cycles + 1):
+        time.sleep(INTERVALO)
+        payload = sender.collector.collect()
+        print(f"[Selftest] Coleta {cycle}/{cycles}")
+    
+    nvml = sender.collector.nvml_gpu is not None
+    sender.collector.close()
+    
+    print(f"\n{'='*50}\n   PAYLOAD\n{'='*50}")
+    print(format_payload(payload))
+    
+    status = payload.get("sensor_status", {})
+    print(f"\n{'='*50}\n   FONTES\n{'='*50}")
+    for field in SENSOR_STATUS_FIELDS:
+        print(f"  {'✓' if status.get(field) else '✗'} {field}")
+    print(f"  {'✓' if nvml else '✗'} nvml")
+    for hint in sensor_status_hints(status):
+        print(f"[Selftest] Aviso: {hint}")
+    print("\n[Selftest] Nada foi enviado pela rede.")
 
 
 def main():
     """Função principal do Sender"""
+    if DUMP_SENSORS:
+        dump_sensors()
+        return
+    if SELFTEST:
+        selftest()
+        return
+    
+    # Valida rede antes de abrir o socket (erro legível em vez de traceback)
+    valid, message = validate_sender_network(MODO, DEST_IP, BIND_IP)
+    if not valid:
+        print(f"[Config] ERRO: {message}")
+        print("[Config] Corrija o config.json e inicie novamente.")
+        sys.exit(1)
+    if message:
+        print(f"[Config] AVISO: {message}")
+    
+    try:
+        parse_ping_host(PING_HOST)
+    except ValueError as e:
+        print(f"[Config] ERRO: {e}")
+        sys.exit(1)
+    
+    if ENCRYPTION_KEY and not HAS_CRYPTO:
+        print("[Config] ERRO: encryption_key exige o pacote 'cryptography' (pip install cryptography)")
+        sys.exit(1)
+    
     sender = TelemetrySender()
     sender.run()
 
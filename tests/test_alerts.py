@@ -0,0 +1,158 @@
+"""
+Testes dos thresholds de alerta por disco e do log de alertas (core/alerts.py).
+Não envia webhooks nem abre a GUI.
+"""
+import sys
+import os
+
+# Adicionar diretório pai ao path para imports
+sys.path.insert(0, os.path.dirname(os.path.dirname(os.path.abspath(__file__))))
+
+from core.alerts import AlertLevel, AlertLog, AlertTracker, disk_thresholds, find_disk_override, storage_names_match
+from core.alerts import alert_level, link_speed_level, metric_thresholds, METRIC_THRESHOLD_DEFAULTS
+
+ALERTAS = {
+    "storage_temp_warning": 45,
+    "storage_temp_critical": 55,
+    "storage_uso_warning": 80,
+    "storage_uso_critical": 95,
+}
+
+
+def test_storage_names_match_is_fuzzy():
+    """Ignora caixa, espaços e pontuação; casa por prefixo/substring."""
+    assert storage_names_match("Samsung SSD 970", "Samsung SSD 970 EVO Plus 1TB")
+    assert storage_names_match("samsung-ssd-970", "SAMSUNG SSD 970")
+    assert not storage_names_match("Samsung SSD 970", "WDC WD20EZRZ")
+    assert not storage_names_match("", "WDC WD20EZRZ")
+
+
+def test_disk_override_takes_precedence():
+    """O override do disco vence; chaves não informadas vêm do global."""
+    overrides = {"Samsung SSD 970": {"temp_warning": 60, "temp_critical": 70}}
+    limits = disk_thresholds("Samsung SSD 970 EVO Plus 1TB", ALERTAS, overrides)
+    assert limits == {"temp_warning": 60, "temp_critical": 70, "uso_warning": 80, "uso_critical": 95}
+
+
+def test_most_specific_override_wins():
+    """Com dois padrões casando, o mais longo (mais específico) vence."""
+    overrides = {
+        "Samsung": {"temp_warning": 50},
+        "Samsung SSD 980 PRO": {"temp_warning": 65},
+    }
+    assert disk_thresholds("Samsung SSD 980 PRO 2TB", ALERTAS, overrides)["temp_warning"] == 65
+    assert disk_thresholds("Samsung SSD 860 EVO", ALERTAS, overrides)["temp_warning"] == 50
+
+
+def test_disk_without_override_uses_global():
+    """Disco sem override (ou sem config) usa os thresholds globais/padrão."""
+    overrides = {"Samsung SSD 970": {"temp_warning": 60}}
+    limits = disk_thresholds("WDC WD20EZRZ", ALERTAS, overrides)
+    assert limits == {"temp_warning": 45, "temp_critical": 55, "uso_warning": 80, "uso_critical": 95}
+    assert disk_thresholds("WDC WD20EZRZ", {}, None)["temp_critical"] == 55
+
+
+def test_same_model_disks_resolved_by_drive_id():
+    """Dois discos do mesmo modelo: o serial decide qual override cada um recebe."""
+    overrides = {
+        "WD-WCC6Y0LN1234": {"temp_warning": 38},
+        "WD-WCC6Y0LN5678": {"temp_warning": 42},
+        "WDC WD10EZEX": {"temp_warning": 50},
+    }
+    name = "WDC WD10EZEX-00BN5A0"
+    assert disk_thresholds(name, ALERTAS, overrides, "WD-WCC6Y0LN1234")["temp_warning"] == 38
+    assert disk_thresholds(name, ALERTAS, overrides, "WD-WCC6Y0LN5678")["temp_warning"] == 42
+    # Serial sem override (ou sender antigo, sem drive_id): cai no nome
+    assert disk_thresholds(name, ALERTAS, overrides, "WD-OTHER")["temp_warning"] == 50
+    assert disk_thresholds(name, ALERTAS, overrides)["temp_warning"] == 50
+
+
+def test_drive_id_is_exact_not_fuzzy():
+    """drive_id compara por inteiro: "PhysicalDrive1" não casa com "PhysicalDrive10"."""
+    overrides = {"PhysicalDrive1": {"temp_warning": 60}}
+    assert find_disk_override("WD Blue 1TB", overrides, "PhysicalDrive1") == {"temp_warning": 60}
+    assert find_disk_override("WD Blue 500GB", overrides, "PhysicalDrive10") is None
+
+
+def test_similar_models_do_not_cross_match():
+    """Modelos com prefixo comum e capacidades diferentes ficam separados."""
+    overrides = {"WD Blue 500GB": {"temp_warning": 40}, "WD Blue 1TB": {"temp_warning": 48}}
+    assert disk_thresholds("WD Blue 500GB", ALERTAS, overrides)["temp_warning"] == 40
+    assert disk_thresholds("WD Blue 1TB", ALERTAS, overrides)["temp_warning"] == 48
+
+
+def test_alert_log_evicts_oldest():
+    """Com o log cheio, a transição mais antiga sai; a mais recente vem primeiro."""
+    log = AlertLog(capacity=3)
+    for i in range(5):
+        log.record(f"CPU.m{i}", 90 + i, AlertLevel.WARNING, now=1000.0 + i)
+    assert len(log) == 3
+    assert [e.metric for e in log.entries()] == ["CPU.m4", "CPU.m3", "CPU.m2"]
+    assert log.entries()[0].timestamp == 1004.0
+
+
+def test_alert_log_driven_by_tracker_transitions():
+    """Só as transições do AlertTracker entram no log, não cada amostra em alerta."""
+    tracker, log = AlertTracker(), AlertLog()
+    for value, level in ((80, AlertLevel.WARNING), (82, AlertLevel.WARNING),
+                         (95, AlertLevel.CRITICAL), (60, None), (81, AlertLevel.WARNING)):
+        transition = tracker.update("CPU.temp", level)
+        if transition:
+            log.record("CPU.temp", value, transition)
+    assert [(e.value, e.level) for e in log.entries()] == [
+        (81, AlertLevel.WARNING), (95, AlertLevel.CRITICAL), (80, AlertLevel.WARNING)
+    ]
+
+
+def test_mobo_temp_alert_levels():
+    """Temperatura da placa-mãe pelos thresholds; sensor ausente (0/None) não alerta."""
+    assert alert_level(55.0, 50, 70) == AlertLevel.WARNING
+    assert alert_level(72.5, 50, 70) == AlertLevel.CRITICAL
+    assert alert_level(38.0, 50, 70) is None
+    assert alert_level(0, 50, 70) is None
+    assert alert_level(None, 50, 70) is None
+
+
+def test_link_speed_below_expected():
+    """Link abaixo do esperado é aviso; abaixo de 10% dele, crítico."""
+    assert link_speed_level(1000, 1000) is None
+    assert link_speed_level(2500, 1000) is None
+    assert link_speed_level(100, 1000) == AlertLevel.WARNING
+    assert link_speed_level(999, 1000) == AlertLevel.WARNING
+    assert link_speed_level(10, 1000) == AlertLevel.CRITICAL
+    assert link_speed_level(54.0, 1000) == AlertLevel.CRITICAL
+
+
+def test_link_speed_unknown_is_not_an_alert():
+    """Sem link (0), Wi-Fi sem leitura ou esperado não configurado: sem alerta."""
+    assert link_speed_level(0, 1000) is None
+    assert link_speed_level(-1, 1000) is None
+    assert link_speed_level(None, 1000) is None
+    assert link_speed_level(100, 0) is None
+    assert link_speed_level(100, None) is None
+
+
+def test_metric_thresholds_defaults_and_overrides():
+    """Sem chave na config vale o padrão; a config sobrescreve e 0 desliga."""
+    assert metric_thresholds({}, "cpu_voltage") == METRIC_THRESHOLD_DEFAULTS["cpu_voltage"]
+    alertas = {"gpu_power_warning": 300, "gpu_power_critical": 0}
+    assert metric_thresholds(alertas, "gpu_power") == (300, 0)
+    assert alert_level(500, *metric_thresholds(alertas, "gpu_power")) == AlertLevel.WARNING
+
+
+def test_metric_threshold_bands():
+    """Voltagem fracionária e VRAM % nas bordas das faixas."""
+    warn, crit = metric_thresholds({}, "cpu_voltage")
+    assert alert_level(1.2, warn, crit) is None
+    assert alert_level(1.45, warn, crit) == AlertLevel.WARNING
+    assert alert_level(1.55, warn, crit) == AlertLevel.CRITICAL
+    warn, crit = metric_thresholds({}, "vram")
+    assert alert_level(89.9, warn, crit) is None
+    assert alert_level(98.0, warn, crit) == AlertLevel.CRITICAL
+
+
+if __name__ == "__main__":
+    for name, func in list(globals().items()):
+        if name.startswith("test_"):
+            func()
+            print(f"✓ {name}")
@@ -0,0 +1,161 @@
+"""
+Testes do coletor (collector.py) pela API pública, como outra interface usaria.
+Não depende de hardware nem de rede: psutil substituído, LHM e NVML desligados.
+"""
+import sys
+import os
+from types import SimpleNamespace
+
+# Adicionar diretório pai ao path para imports
+sys.path.insert(0, os.path.dirname(os.path.dirname(os.path.abspath(__file__))))
+
+from collector import TelemetryCollector
+from core.protocol import PROTOCOL_VERSION, serialize_payload, parse_body
+from core.sensors import MockSource
+from hardware_monitor import merge_storage_entries
+
+
+class FakeProcess:
+    def __init__(self, name, pid, cpu):
+        self.info = {"name": name, "pid": pid, "cpu_percent": cpu, "memory_info": SimpleNamespace(rss=256 * 1024**2)}
+
+
+class FakePsutil:
+    """O que o coletor lê do psutil, com valores fixos"""
+    
+    class NoSuchProcess(Exception):
+        pass
+    
+    class AccessDenied(Exception):
+        pass
+    
+    def __init__(self):
+        self.cpu_calls = 0
+    
+    def boot_time(self):
+        return 0.0
+    
+    def cpu_percent(self, interval=None):
+        self.cpu_calls += 1
+        return 23.5
+    
+    def virtual_memory(self):
+        return SimpleNamespace(percent=50.0, used=8 * 1024**3, total=16 * 1024**3)
+    
+    def net_io_counters(self, pernic=False):
+        return {}
+    
+    def disk_io_counters(self, perdisk=False):
+        return {}
+    
+    def sensors_battery(self):
+        return None
+    
+    def sensors_fans(self):
+        return {}
+    
+    def cpu_freq(self):
+        return SimpleNamespace(current=3600.0)
+    
+    def getloadavg(self):
+        return (0.5, 0.25, 0.125)
+    
+    def pids(self):
+        return [1, 2, 3]
+    
+    def process_iter(self, attrs):
+        return [FakeProcess("idle", 1, 0.0), FakeProcess("game.exe", 2, 40.0), FakeProcess("browser", 3, 12.0)]
+
+
+class FakeMonitor:
+    """Só o que o coletor usa do HardwareMonitor para a capacidade: índice físico -> letras"""
+    
+    def __init__(self, letters):
+        self.letters = letters
+    
+    def get_drive_letters(self, drive_index):
+        return self.letters.get(drive_index, [])
+
+
+def _collector(**kwargs):
+    return TelemetryCollector(
+        disabled_sources={"lhm", "nvml"},
+        psutil_module=FakePsutil(),
+        platform="linux",
+        **kwargs
+    )
+
+
+def test_collect_builds_full_payload():
+    """Coletor montado pela API pública gera um payload completo e válido."""
+    collector = _collector()
+    collector.init_sources()
+    assert collector.monitor is None and collector.nvml_gpu is None
+    
+    payload = collector.collect()
+    assert payload["protocol_version"] == PROTOCOL_VERSION
+    assert payload["hostname"] == collector.hostname
+    assert payload["cpu"]["usage"] == 23.5
+    assert payload["cpu"]["clock"] == 3600
+    assert payload["cpu"]["clock_source"] == "os"
+    assert payload["ram"] == {"percent": 50.0, "used_gb": 8.0, "total_gb": 16.0}
+    assert payload["system"] == {"load_avg": [0.5, 0.25, 0.12], "process_count": 3}
+    assert payload["cpu"]["temp"] is None  # Sem LHM: null, não zero
+    assert payload["processes"] == []
+    
+    # Mesmo caminho do Receiver
+    assert parse_body(serialize_payload(payload)) == payload
+    collector.close()
+
+
+def test_extra_sources_and_state_between_collects():
+    """Fontes extras entram depois das padrão; o estado segue entre coletas."""
+    mock = MockSource("mock", {"gpu": {"temp": 48.0, "load": 7.0}})
+    collector = _collector(extra_sources=[mock])
+    assert [source.name for source in collector.sensor_sources] == ["hwmon", "cpu_freq", "mock"]
+    
+    for _ in range(3):
+        payload = collector.collect()
+    assert mock.calls == 3
+    assert payload["gpu"]["temp"] == 48.0 and payload["gpu"]["load"] == 7.0
+    assert collector.psutil.cpu_calls == 3
+    assert collector.cpu_peak_clock == 3600
+
+
+def test_processes_section_follows_sections():
+    """top_processes liga a seção; o Sender a muda por sections (pedido do receiver)."""
+    collector = _collector(top_processes=2)
+    names = [proc["name"] for proc in collector.collect()["processes"]]
+    assert names == ["game.exe", "browser"]
+    
+    collector.sections["processes"] = False
+    assert collector.collect()["processes"] == []
+
+
+def test_mount_points_collapse_into_their_physical_disk():
+    """Duas letras no mesmo disco físico viram uma entrada com a capacidade somada."""
+    collector = _collector()
+    collector.monitor = FakeMonitor({0: ["C", "D"], 1: ["E"]})
+    sizes = {"C:\\": (500, 100), "D:\\": (1500, 900), "E:\\": (1000, 250)}  # (total, livre) em GB
+    collector.psutil.disk_usage = lambda path: SimpleNamespace(
+        total=sizes[path][0] * 1024**3, free=sizes[path][1] * 1024**3
+    )
+    # Mesmo disco em dois nós do LHM (NVMe e o driver genérico) + outro disco
+    storage = merge_storage_entries([
+        {"name": "Samsung SSD 980", "drive_index": 0, "drive_id": "S4EW", "temp": 48.0, "used_space": 0},
+        {"name": "Samsung SSD 980 (ATA)", "drive_index": 0, "drive_id": "S4EW", "temp": 0, "used_space": 0},
+        {"name": "WD Blue", "drive_index": 1, "drive_id": "WD-01", "temp": 35.0, "used_space": 0},
+    ])
+    collector._calcular_capacidade(storage)
+    
+    assert [disk["name"] for disk in storage] == ["Samsung SSD 980", "WD Blue"]
+    assert (storage[0]["total_gb"], storage[0]["free_gb"], storage[0]["used_gb"]) == (2000.0, 1000.0, 1000.0)
+    assert storage[0]["used_space"] == 50.0  # Sem o sensor do LHM: sai da capacidade somada
+    assert (storage[1]["total_gb"], storage[1]["used_gb"]) == (1000.0, 750.0)
+
+
+if __name__ == "__main__":
+    for name, func in list(globals().items()):
+        if name.startswith("test_"):
+            func()
+            print(f"✓ {name}")
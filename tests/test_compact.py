@@ -0,0 +1,107 @@
+"""
+Testes do encoding compacto (core/compact.py).
+Não depende de hardware nem de rede.
+"""
+import sys
+import os
+import random
+
+# Adicionar diretório pai ao path para imports
+sys.path.insert(0, os.path.dirname(os.path.dirname(os.path.abspath(__file__))))
+
+from core.compact import COMPACT_FIELDS, encode_compact, decode_compact, max_error
+from core.protocol import encode_payload, decode_payload, extract_body, MagicByte, DeserializeError, PROTOCOL_VERSION
+
+PAYLOAD = {
+    "protocol_version": PROTOCOL_VERSION,
+    "timestamp": 1767225600.123,
+    "hostname": "PC-Sala",
+    "uptime_secs": 86400,
+    "cpu": {"usage": 37.4, "temp": 61.25, "voltage": 1.248, "power": 88.7, "clock": 4650, "throttling": False},
+    "gpu": {"load": 97.0, "temp": 72.5, "voltage": 0.975, "clock_core": 2610, "clock_mem": 10501,
+            "power_w": 312.4, "throttling": True, "fan": 64, "fan_unit": "%", "mem_used_mb": 14210, "mem_total_mb": 24564},
+    "mobo": {"temp": 44.0},
+    "ram": {"percent": 58.3, "used_gb": 18.62, "total_gb": 31.91},
+    "network": {"down_kbps": 52340.7, "up_kbps": 812.3, "ping_ms": 14.2, "ping_method": "tcp",
+                "interfaces": [["Ethernet", 52340.7, 812.3]]},
+    "storage": [{"name": "Samsung SSD 980 PRO 1TB", "temp": 38, "used_percent": 71.2}],
+    "fans": [{"name": "CPU Fan", "rpm": 1180}],
+}
+
+
+def _get(payload, path):
+    section, _, field = path.rpartition(".")
+    return (payload[section] if section else payload)[field]
+
+
+def test_roundtrip_within_error_bound():
+    """Cada campo decodificado fica dentro do erro documentado (max_error), em valores aleatórios na faixa dos tipos."""
+    rng = random.Random(1108)
+    for _ in range(200):
+        payload = {"hostname": "PC", "timestamp": rng.uniform(0, 2e9), "cpu": {}, "gpu": {}, "mobo": {}, "ram": {}, "network": {}}
+        for path, code, scale in COMPACT_FIELDS:
+            if path in ("timestamp", "protocol_version"):
+                continue
+            section, _, field = path.rpartition(".")
+            target = payload[section] if section else payload
+            target[field] = rng.random() < 0.5 if code == "?" else round(rng.uniform(0, 60), 3)
+        decoded = decode_compact(encode_compact(payload))
+        for path, code, _ in COMPACT_FIELDS:
+            if path == "protocol_version":
+                continue
+            assert abs(_get(decoded, path) - _get(payload, path)) <= max_error(path) + 1e-9, path
+    assert max_error("cpu.temp") == 0.05
+    assert max_error("timestamp") == 0.0
+
+
+def test_special_values():
+    """Ping -1 (inalcançável) sobrevive; acima da faixa satura; ausente/NaN vira 0; hostname e unidade do fan voltam."""
+    payload = {"hostname": "Café", "cpu": {"temp": float("nan"), "clock": 999999}, "network": {"ping_ms": -1}, "gpu": {"fan_unit": "%"}}
+    decoded = decode_compact(encode_compact(payload))
+    assert decoded["network"]["ping_ms"] == -1
+    assert decoded["cpu"]["clock"] == 0xFFFF
+    assert decoded["cpu"]["temp"] == 0
+    assert decoded["gpu"]["load"] == 0
+    assert decoded["gpu"]["fan_unit"] == "%"
+    assert decoded["hostname"] == "Café"
+
+
+def test_compact_frame_smaller_and_decodes():
+    """Frame compacto tem menos da metade do gzip e passa pelo decode normal (com assinatura)."""
+    compact = encode_payload(PAYLOAD, compact=True)
+    gzip_frame = encode_payload(PAYLOAD)
+    assert compact[0] == MagicByte.COMPACT
+    assert len(compact) * 2 <= len(gzip_frame), (len(compact), len(gzip_frame))
+    
+    decoded = decode_payload(encode_payload(PAYLOAD, compact=True, auth_secret="segredo"), auth_secret="segredo")
+    assert decoded["hostname"] == "PC-Sala"
+    assert decoded["protocol_version"] == PROTOCOL_VERSION
+    assert decoded["timestamp"] == PAYLOAD["timestamp"]
+    assert decoded["gpu"]["throttling"] is True
+    assert decoded["ram"]["used_gb"] == 18.62
+    assert "storage" not in decoded and "processes" not in decoded
+
+
+def test_truncated_body_rejected():
+    """Corpo compacto truncado ou com hostname de tamanho errado é recusado."""
+    body = encode_compact(PAYLOAD)
+    for bad in (body[:10], body[:-1], body + b"x"):
+        try:
+            decode_compact(bad)
+            assert False, "corpo inválido aceito"
+        except ValueError:
+            pass
+    assert decode_payload(bytes([MagicByte.COMPACT]) + body[:10]) is None
+    
+    try:
+        extract_body(bytes([MagicByte.COMPACT]) + body[:-1])
+        assert False, "corpo inválido aceito"
+    except DeserializeError:
+        pass
+
+
+if __name__ == "__main__":
+    for name, func in list(globals().items()):
+        if name.startswith("test_"):
+            func()
+            print(f"✓ {name}")
@@ -0,0 +1,32 @@
+"""
+Compila os scripts principais sem executá-los (receiver, sender, web...).
+Pega erros que só aparecem ao compilar, como um `global` declarado depois do uso.
+"""
+import os
+
+ROOT = os.path.dirname(os.path.dirname(os.path.abspath(__file__)))
+
+# Scripts que não têm teste próprio importando-os (GUI, elevação para Admin, servidor)
+SCRIPTS = (
+    "receiver_notebook.py",
+    "sender_pc.py",
+    "collector.py",
+    "hardware_monitor.py",
+    "telemetria.py",
+    os.path.join("web", "server.py"),
+)
+
+
+def test_main_scripts_compile():
+    """Todos os scripts principais compilam (SyntaxError aqui impede o programa de abrir)."""
+    for script in SCRIPTS:
+        path = os.path.join(ROOT, script)
+        with open(path, encoding="utf-8") as f:
+            compile(f.read(), path, "exec")
+
+
+if __name__ == "__main__":
+    for name, func in list(globals().items()):
+        if name.startswith("test_"):
+            func()
+            print(f"✓ {name}")
@@ -0,0 +1,93 @@
+"""
+Testes do estado da janela do Receiver e da migração dos configs (core/config.py).
+Usa um diretório temporário; não abre a GUI.
+"""
+import sys
+import os
+import json
+import tempfile
+from pathlib import Path
+
+# Adicionar diretório pai ao path para imports
+sys.path.insert(0, os.path.dirname(os.path.dirname(os.path.abspath(__file__))))
+
+from core.config import UiState, load_ui_state, save_ui_state, is_valid_geometry
+from core.config import CONFIG_VERSION, migrate_config, load_versioned_config
+
+
+def test_ui_state_roundtrip():
+    """Geometria e tema salvos são os mesmos carregados na próxima execução."""
+    with tempfile.TemporaryDirectory() as tmp:
+        path = Path(tmp) / "ui_state.json"
+        state = UiState(geometry="1280x720+-1920+40", theme="cyberpunk")
+        assert save_ui_state(state, path)
+        assert load_ui_state(path) == state
+
+
+def test_ui_state_missing_or_corrupt():
+    """Sem arquivo (primeira execução) ou com lixo, volta ao estado vazio."""
+    with tempfile.TemporaryDirectory() as tmp:
+        path = Path(tmp) / "ui_state.json"
+        assert load_ui_state(path) == UiState()
+        
+        path.write_text("{not json", encoding="utf-8")
+        assert load_ui_state(path) == UiState()
+        
+        path.write_text('{"geometry": "huge", "theme": "light"}', encoding="utf-8")
+        assert load_ui_state(path) == UiState(geometry="", theme="light")
+
+
+def test_geometry_validation():
+    """Aceita o formato do Tk, com ou sem posição."""
+    assert is_valid_geometry("1366x700")
+    assert is_valid_geometry("1366x700+10+20")
+    assert is_valid_geometry("800x600+-1920+0")
+    assert not is_valid_geometry("")
+    assert not is_valid_geometry("1366 x 700")
+
+
+DEFAULTS = {
+    "config_version": CONFIG_VERSION,
+    "porta": 5005,
+    "tema": "dark",
+    "alertas": {"cpu_temp_warning": 70, "cpu_temp_critical": 85},
+    "historico": {"csv_enabled": False, "retention_days": 7},
+}
+
+
+def test_v0_config_migrated_and_rewritten():
+    """Config sem config_version (v0) sobe para a versão atual, com seções novas e valores do usuário."""
+    with tempfile.TemporaryDirectory() as tmp:
+        path = Path(tmp) / "receiver_config.json"
+        path.write_text(json.dumps({"porta": 6000, "alertas": {"cpu_temp_critical": 90}}), encoding="utf-8")
+        
+        config, migrated = load_versioned_config(str(path), DEFAULTS)
+        assert migrated
+        assert config["config_version"] == CONFIG_VERSION
+        assert config["porta"] == 6000
+        assert config["tema"] == "dark"
+        assert config["alertas"] == {"cpu_temp_warning": 70, "cpu_temp_critical": 90}
+        assert config["historico"] == {"csv_enabled": False, "retention_days": 7}
+        
+        # Arquivo regravado: a próxima leitura já está na versão atual e não migra de novo
+        assert json.loads(path.read_text(encoding="utf-8")) == config
+        assert load_versioned_config(str(path), DEFAULTS) == (config, False)
+
+
+def test_migration_keeps_unknown_and_newer():
+    """Chaves desconhecidas ficam; versão mais nova que a do programa não é rebaixada."""
+    newer = {"config_version": CONFIG_VERSION + 1, "extra": [1, 2], **{k: v for k, v in DEFAULTS.items() if k != "config_version"}}
+    config, changed = migrate_config(newer, DEFAULTS)
+    assert not changed
+    assert config["config_version"] == CONFIG_VERSION + 1
+    assert config["extra"] == [1, 2]
+    
+    config, changed = migrate_config({"config_version": "x"}, DEFAULTS)
+    assert changed and config["config_version"] == CONFIG_VERSION
+
+
+if __name__ == "__main__":
+    for name, func in list(globals().items()):
+        if name.startswith("test_"):
+            func()
+            print(f"✓ {name}")
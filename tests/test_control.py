@@ -0,0 +1,85 @@
+"""
+Testes do canal de controle Receiver -> Sender (core/control.py).
+Só codifica/decodifica os frames e aplica os pedidos sobre um estado; não abre sockets.
+"""
+import sys
+import os
+
+# Adicionar diretório pai ao path para imports
+sys.path.insert(0, os.path.dirname(os.path.dirname(os.path.abspath(__file__))))
+
+from core.control import config_request, encode_control, decode_control, apply_config_request, MAX_CONTROL_SIZE
+from core.protocol import encode_payload, serialize_payload, MagicByte, AuthFailedError, DeserializeError
+
+
+def test_control_frame_roundtrip():
+    """Pedido codificado volta igual; o frame começa com o magic byte de controle."""
+    request = config_request(interval=0.25, sections={"processes": True})
+    frame = encode_control(request)
+    assert frame[0] == MagicByte.CONTROL
+    assert decode_control(frame) == {"type": "config_request", "interval": 0.25, "sections": {"processes": True}}
+    assert config_request() == {"type": "config_request"}
+
+
+def test_payload_frame_is_not_control():
+    """Payload comum no mesmo socket (ex: outro Sender em broadcast) é ignorado, não é erro."""
+    assert decode_control(encode_payload({"cpu": {"usage": 1}})) is None
+    assert decode_control(b"") is None
+
+
+def test_signed_control_frame():
+    """Com auth_secret, pedido sem tag ou com segredo errado é recusado."""
+    frame = encode_control(config_request(interval=2), auth_secret="segredo")
+    assert decode_control(frame, auth_secret="segredo")["interval"] == 2
+    for bad in (encode_control(config_request(interval=2)), encode_control(config_request(interval=2), auth_secret="outro")):
+        try:
+            decode_control(bad, auth_secret="segredo")
+            assert False, "pedido sem autenticação válida foi aceito"
+        except AuthFailedError:
+            pass
+
+
+def test_malformed_control_frames():
+    """Tipo desconhecido, JSON inválido e frame grande demais levantam DeserializeError."""
+    bad_frames = [
+        bytes([MagicByte.CONTROL]) + serialize_payload({"type": "reboot"}),
+        bytes([MagicByte.CONTROL]) + b"{nope",
+        bytes([MagicByte.CONTROL]) + serialize_payload({"type": "config_request", "pad": "x" * MAX_CONTROL_SIZE}),
+    ]
+    for frame in bad_frames:
+        try:
+            decode_control(frame)
+            assert False, f"frame inválido aceito: {frame[:20]!r}"
+        except DeserializeError:
+            pass
+
+
+def test_apply_bounded_interval():
+    """Intervalo dentro dos limites é aplicado; fora deles (ou não numérico) fica o atual."""
+    sections = {"processes": False}
+    interval, _, changes, errors = apply_config_request(config_request(interval=0.2), 1.0, sections)
+    assert interval == 0.2 and changes == ["intervalo 1.0s -> 0.2s"] and errors == []
+    for bad in (0.01, 7200, "rápido", True):
+        interval, _, changes, errors = apply_config_request({"type": "config_request", "interval": bad}, 1.0, sections)
+        assert interval == 1.0 and changes == [] and len(errors) == 1, bad
+
+
+def test_apply_sections():
+    """Seção conhecida muda; desconhecida ou não booleana é recusada sem afetar o resto."""
+    request = config_request(interval=1.0, sections={"processes": True, "per_core": True})
+    interval, sections, changes, errors = apply_config_request(request, 1.0, {"processes": False})
+    assert sections == {"processes": True}
+    assert changes == ["processes ligado"]
+    assert errors == ["Seção desconhecida: per_core"]
+    # Estado recebido não é alterado
+    original = {"processes": True}
+    _, sections, changes, errors = apply_config_request(config_request(sections={"processes": "sim"}), 1.0, original)
+    assert sections == original and changes == [] and len(errors) == 1
+    assert original == {"processes": True}
+
+
+if __name__ == "__main__":
+    for name, func in list(globals().items()):
+        if name.startswith("test_"):
+            func()
+            print(f"✓ {name}")
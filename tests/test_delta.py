@@ -0,0 +1,214 @@
+"""
+Testes dos frames delta (core/delta.py).
+Não depende de hardware nem de rede.
+"""
+import sys
+import os
+import copy
+
+# Adicionar diretório pai ao path para imports
+sys.path.insert(0, os.path.dirname(os.path.dirname(os.path.abspath(__file__))))
+
+from core.delta import DeltaEncoder, DeltaDecoder, MissingKeyframeError, DELTA_KEY, DELTA_CHANGES
+from core.delta import flatten_payload, unflatten_payload, pack_bitmask, unpack_bitmask, is_delta_frame
+from core.protocol import MagicByte, PROTOCOL_VERSION, DeserializeError, extract_body, parse_body, serialize_payload, wrap_frame, unwrap_frame
+
+BASE = {
+    "protocol_version": PROTOCOL_VERSION,
+    "timestamp": 1000.0,
+    "hostname": "DESKTOP",
+    "cpu": {"usage": 12.5, "temp": 55.0, "clock": 4200.0, "clock_source": "lhm"},
+    "gpu": {"load": 3.0, "temp": 41.0, "fan_unit": "RPM"},
+    "ram": {"percent": 40.0},
+    "storage": [{"name": "Samsung SSD 980 PRO 1TB", "temp": 38}],
+    "fans": [],
+    "system": {},
+}
+
+
+def _sequence(count):
+    """Payloads de um Sender rodando: timestamp sempre muda, o resto às vezes"""
+    payloads = []
+    for i in range(count):
+        payload = copy.deepcopy(BASE)
+        payload["timestamp"] = 1000.0 + i
+        payload["cpu"]["usage"] = 12.5 + (i % 3)
+        if i % 4 == 0:
+            payload["storage"][0]["temp"] = 38 + i
+        if i == 5:
+            payload["gpu"]["fan_unit"] = "%"
+        payloads.append(payload)
+    return payloads
+
+
+def _roundtrip(encoder, decoder, payload, sender="10.0.0.2"):
+    frame = encoder.encode(payload)
+    encoder.commit()
+    assert is_delta_frame(frame)
+    return parse_body(decoder.decode(frame, sender))
+
+
+def test_keyframe_and_deltas_rebuild_same_payloads_as_full_frames():
+    """Keyframe + sequência de deltas reconstrói os mesmos payloads que frames inteiros."""
+    encoder, decoder = DeltaEncoder(keyframe_every=4), DeltaDecoder()
+    for payload in _sequence(12):
+        full = parse_body(extract_body(bytes([MagicByte.RAW]) + serialize_payload(payload)))
+        assert _roundtrip(encoder, decoder, payload) == full
+
+
+def test_keyframe_every_n_frames():
+    """Um keyframe a cada N frames; entre eles, deltas do mesmo keyframe."""
+    encoder = DeltaEncoder(keyframe_every=3)
+    kinds = []
+    for payload in _sequence(7):
+        frame = encoder.encode(payload)
+        encoder.commit()
+        kinds.append((frame[1], frame[2]))
+    assert kinds == [
+        (DELTA_KEY, 0), (DELTA_CHANGES, 0), (DELTA_CHANGES, 0),
+        (DELTA_KEY, 1), (DELTA_CHANGES, 1), (DELTA_CHANGES, 1),
+        (DELTA_KEY, 2),
+    ]
+
+
+def test_delta_only_carries_changed_fields():
+    """Delta leva só os campos diferentes do keyframe (bem menor que o payload)."""
+    encoder = DeltaEncoder(keyframe_every=10)
+    payload = copy.deepcopy(BASE)
+    key = encoder.encode(payload)
+    encoder.commit()
+    payload["timestamp"] += 1
+    delta = encoder.encode(payload)
+    assert delta[1] == DELTA_CHANGES
+    assert delta.endswith(b"[1001.0]")
+    assert len(delta) < len(key)
+
+
+def test_new_field_forces_keyframe():
+    """Campo novo (ou removido) muda a ordem dos campos: vai um keyframe."""
+    encoder = DeltaEncoder(keyframe_every=10)
+    payload = copy.deepcopy(BASE)
+    encoder.encode(payload)
+    encoder.commit()
+    payload["battery"] = {"percent": 80}
+    frame = encoder.encode(payload)
+    assert frame[1] == DELTA_KEY and frame[2] == 1
+
+
+def test_encode_without_commit_keeps_state():
+    """encode() não avança o estado: só o frame enviado (commit) conta."""
+    encoder = DeltaEncoder(keyframe_every=10)
+    first = encoder.encode(BASE)
+    assert encoder.encode(BASE) == first
+    assert encoder.key_leaves is None
+    encoder.commit()
+    assert encoder.encode(BASE)[1] == DELTA_CHANGES
+
+
+def test_lost_delta_does_not_affect_next():
+    """Delta é relativo ao keyframe: perder um não estraga os seguintes."""
+    encoder, decoder = DeltaEncoder(keyframe_every=10), DeltaDecoder()
+    payloads = _sequence(5)
+    _roundtrip(encoder, decoder, payloads[0])
+    encoder.encode(payloads[1])
+    encoder.commit()  # Enviado, mas perdido na rede
+    for payload in payloads[2:]:
+        assert _roundtrip(encoder, decoder, payload) == payload
+
+
+def test_delta_without_keyframe_is_rejected():
+    """Receiver que ligou no meio (ou perdeu o keyframe) descarta até o próximo."""
+    encoder, decoder = DeltaEncoder(keyframe_every=3), DeltaDecoder()
+    payloads = _sequence(4)
+    for payload in payloads[:2]:
+        encoder.encode(payload)
+        encoder.commit()
+    frame = encoder.encode(payloads[2])
+    encoder.commit()
+    try:
+        decoder.decode(frame, "10.0.0.2")
+        assert False, "delta sem keyframe aceito"
+    except MissingKeyframeError:
+        pass
+    assert _roundtrip(encoder, decoder, payloads[3]) == payloads[3]
+
+
+def test_keyframes_are_kept_per_sender():
+    """Keyframe de um Sender não serve para os deltas de outro."""
+    encoder, decoder = DeltaEncoder(keyframe_every=10), DeltaDecoder()
+    _roundtrip(encoder, decoder, BASE, sender="10.0.0.2")
+    frame = encoder.encode(BASE)
+    try:
+        decoder.decode(frame, "10.0.0.3")
+        assert False, "delta de outro Sender aceito"
+    except MissingKeyframeError:
+        pass
+    decoder.forget("10.0.0.2")
+    try:
+        decoder.decode(frame, "10.0.0.2")
+        assert False, "delta após forget aceito"
+    except MissingKeyframeError:
+        pass
+
+
+def test_corrupt_delta_rejected():
+    """Delta com contagem, bitmask ou valores inconsistentes vira DeserializeError."""
+    encoder, decoder = DeltaEncoder(keyframe_every=10), DeltaDecoder()
+    _roundtrip(encoder, decoder, BASE)
+    payload = copy.deepcopy(BASE)
+    payload["timestamp"] += 1
+    frame = encoder.encode(payload)
+    for bad in (frame[:2], frame[:6], frame[:-1], frame + b"x", frame[:3] + b"\x01\x00" + frame[5:]):
+        try:
+            decoder.decode(bad, "10.0.0.2")
+            assert False, f"delta inválido aceito: {bad!r}"
+        except DeserializeError:
+            pass
+
+
+def test_extract_body_refuses_delta_frames():
+    """Sem DeltaDecoder o frame delta é recusado com mensagem clara."""
+    frame = DeltaEncoder(keyframe_every=1).encode(BASE)
+    try:
+        extract_body(frame)
+        assert False, "frame delta aceito sem DeltaDecoder"
+    except DeserializeError as e:
+        assert "DeltaDecoder" in str(e)
+
+
+def test_delta_frames_survive_encryption():
+    """Flags de criptografia/tag continuam por fora do frame delta."""
+    encoder, decoder = DeltaEncoder(keyframe_every=5), DeltaDecoder()
+    for payload in _sequence(6):
+        frame = wrap_frame(encoder.encode(payload), auth_secret="segredo")
+        encoder.commit()
+        data = unwrap_frame(frame, auth_secret="segredo")
+        assert parse_body(decoder.decode(data)) == payload
+
+
+def test_flatten_is_stable_and_reversible():
+    """Ordem dos campos não depende da ordem das chaves; unflatten desfaz."""
+    shuffled = {key: BASE[key] for key in reversed(list(BASE))}
+    assert [path for path, _ in flatten_payload(shuffled)] == [path for path, _ in flatten_payload(BASE)]
+    assert unflatten_payload(flatten_payload(BASE)) == BASE
+
+
+def test_bitmask_roundtrip():
+    """Bitmask: bit i no byte i // 8; tamanho errado é recusado."""
+    flags = [True, False, False, True, False, False, False, False, True]
+    mask = pack_bitmask(flags)
+    assert mask == b"\x09\x01"
+    assert unpack_bitmask(mask, len(flags)) == flags
+    assert pack_bitmask([]) == b""
+    try:
+        unpack_bitmask(mask, 20)
+        assert False, "bitmask curto aceito"
+    except ValueError:
+        pass
+
+
+if __name__ == "__main__":
+    for name, func in list(globals().items()):
+        if name.startswith("test_"):
+            func()
+            print(f"✓ {name}")
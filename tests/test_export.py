@@ -0,0 +1,107 @@
+"""
+Testes da exportação da telemetria recebida (core/export.py).
+Não depende de rede nem da GUI (o formatter Prometheus é testado sem o servidor HTTP).
+"""
+import sys
+import os
+import json
+
+# Adicionar diretório pai ao path para imports
+sys.path.insert(0, os.path.dirname(os.path.dirname(os.path.abspath(__file__))))
+
+from core.export import stream_record, to_ndjson, prometheus_text, history_csv, export_filename
+from core.export import PrometheusExporter
+
+SAMPLE = {
+    "cpu": {"usage": 12.5, "temp": 55.0},
+    "gpu": {"load": 3.0, "temp": 41.0, "fan": 40, "fan_unit": "%"},
+    "network": {"down_kbps": 120.4, "up_kbps": 8.1, "ping_ms": -1}
+}
+
+
+def test_ndjson_shape():
+    """Uma linha por payload, com timestamp, origem e o payload intacto."""
+    line = to_ndjson(stream_record(SAMPLE, "192.168.1.10", timestamp=1700000000.12345))
+    assert "\n" not in line
+
+    record = json.loads(line)
+    assert list(record) == ["timestamp", "source", "payload"]
+    assert record["timestamp"] == 1700000000.123
+    assert record["source"] == "192.168.1.10"
+    assert record["payload"] == SAMPLE
+
+
+def test_ndjson_keeps_unicode():
+    """Nomes de disco/processo com acentos saem legíveis."""
+    line = to_ndjson(stream_record({"storage": [{"name": "Disco Café"}]}, "pc", timestamp=0))
+    assert "Disco Café" in line
+
+
+def test_prometheus_text_per_source():
+    """Uma série por Sender, rotulada por source; staleness em telemetry_up."""
+    other = {"cpu": {"usage": 80, "temp": 70.5}, "storage": [{"name": 'SSD "NVMe"', "temp": 38, "health": 99}]}
+    entries = [("192.168.1.10", (SAMPLE, 100.0)), ("192.168.1.11", (other, 90.0))]
+    text = prometheus_text(entries, now=101.0, timeout=5)
+    lines = text.splitlines()
+
+    assert 'telemetry_up{source="192.168.1.10"} 1' in lines
+    assert 'telemetry_up{source="192.168.1.11"} 0' in lines
+    assert 'telemetry_last_seen_seconds{source="192.168.1.11"} 11.0' in lines
+    assert 'telemetry_cpu_temp{source="192.168.1.10"} 55.0' in lines
+    assert 'telemetry_cpu_temp{source="192.168.1.11"} 70.5' in lines
+    assert 'telemetry_gpu_load{source="192.168.1.10"} 3.0' in lines
+    assert 'telemetry_network_ping_ms{source="192.168.1.10"} -1' in lines
+    assert 'telemetry_storage_temp{source="192.168.1.11",disk="SSD \\"NVMe\\""} 38' in lines
+
+    # Texto não numérico (fan_unit) fica de fora; cada métrica tem HELP/TYPE uma vez
+    assert not any(line.startswith("telemetry_gpu_fan_unit") for line in lines)
+    assert lines.count("# TYPE telemetry_cpu_temp gauge") == 1
+    assert text.endswith("\n")
+
+
+def test_prometheus_exporter_drops_senders_after_retention():
+    """Sender parado aparece como down até a retenção; depois sai do /metrics."""
+    exporter = PrometheusExporter(port=0, timeout=5, retention=60)
+    exporter.update("192.168.1.10", SAMPLE, now=100.0)
+    exporter.update("192.168.1.11", SAMPLE, now=150.0)
+
+    lines = exporter.render(now=120.0).splitlines()
+    assert 'telemetry_up{source="192.168.1.10"} 0' in lines
+
+    text = exporter.render(now=165.0)
+    assert "192.168.1.10" not in text
+    assert 'telemetry_up{source="192.168.1.11"} 0' in text.splitlines()
+
+
+def test_prometheus_text_empty():
+    """Sem Senders, só os cabeçalhos das métricas de estado."""
+    text = prometheus_text([], now=0, timeout=5)
+    assert "{" not in text
+
+
+def test_history_csv_rows():
+    """Uma linha por amostra, colunas na ordem das séries; lacunas ficam vazias."""
+    history = {"cpu_usage": [10, 20.5, float("nan")], "ping": [3.0, -1, 4]}
+    lines = history_csv(history).splitlines()
+    assert lines == ["sample,cpu_usage,ping", "0,10,3", "1,20.5,-1", "2,,4"]
+
+
+def test_history_csv_aligns_newest_and_seconds_ago():
+    """Séries de tamanhos diferentes se alinham no fim; com intervalo a coluna é seconds_ago."""
+    text = history_csv({"a": [1, 2, 3], "b": [9]}, interval=0.5)
+    assert text.splitlines() == ["seconds_ago,a,b", "1,1,", "0.5,2,", "0,3,9"]
+    assert history_csv({}) == "sample\n"
+
+
+def test_export_filename_timestamp():
+    """Nome com prefixo, data/hora local e extensão."""
+    name = export_filename("graphs", "csv", now=0)
+    assert name.startswith("graphs_") and name.endswith(".csv")
+    assert len(name) == len("graphs_YYYYmmdd_HHMMSS.csv")
+
+
+if __name__ == "__main__":
+    for name, func in list(globals().items()):
+        if name.startswith("test_"):
+            func()
+            print(f"✓ {name}")
@@ -0,0 +1,154 @@
+"""
+Testes da formatação dos painéis (core/formatting.py).
+Não depende da GUI.
+"""
+import sys
+import os
+
+# Adicionar diretório pai ao path para imports
+sys.path.insert(0, os.path.dirname(os.path.dirname(os.path.abspath(__file__))))
+
+from core.formatting import format_value, format_uptime, is_absent, celsius_to_fahrenheit, unit_category, ABSENT
+from core.formatting import number_separators, NUMBER_LOCALES
+from core.alerts import AlertLevel, alert_level
+from core.payload import mark_absent
+from core.protocol import encode_payload, decode_payload
+
+
+def test_absent_renders_placeholder():
+    """None e NaN viram o placeholder, nunca "0.0V"."""
+    assert format_value(None, "V") == ABSENT
+    assert format_value(float("nan"), "°C") == ABSENT
+    assert is_absent(None) and is_absent(float("nan"))
+    assert not is_absent(0) and not is_absent(0.0)
+
+
+def test_real_zero_is_distinguishable():
+    """Uso 0% em idle é leitura real e continua sendo exibida."""
+    assert format_value(0.0, "%") == "0.0%"
+    assert format_value(0, " RPM") == "0 RPM"
+    assert format_value(1.2345, "V") == "1.234V"
+    assert format_value(55.25, "°C") == "55.2°C"
+
+
+def test_mark_absent_only_touches_impossible_zeros():
+    """Temperatura/voltagem zeradas viram null; carga e fan zerados ficam."""
+    payload = {
+        "cpu": {"usage": 0.0, "temp": 0, "voltage": 0, "power": 35.2, "clock": 0},
+        "gpu": {"load": 0, "temp": 41.0, "voltage": 0, "clock_core": 0, "clock_mem": 0, "fan": 0},
+        "mobo": {"temp": 0},
+        "storage": [{"name": "SSD", "temp": 0, "used_space": 0}],
+    }
+    mark_absent(payload)
+    assert payload["cpu"] == {"usage": 0.0, "temp": None, "voltage": None, "power": 35.2, "clock": None}
+    assert payload["gpu"]["load"] == 0 and payload["gpu"]["fan"] == 0
+    assert payload["gpu"]["temp"] == 41.0 and payload["gpu"]["voltage"] is None
+    assert payload["mobo"]["temp"] is None
+    assert payload["storage"][0] == {"name": "SSD", "temp": None, "used_space": 0}
+    
+    # null sobrevive ao protocolo e chega como None
+    decoded = decode_payload(encode_payload(payload))
+    assert decoded["cpu"]["voltage"] is None
+    assert format_value(decoded["cpu"]["voltage"], "V") == ABSENT
+
+
+def test_fahrenheit_conversion():
+    """°C vira °F na exibição; outras unidades não mudam."""
+    assert celsius_to_fahrenheit(0) == 32
+    assert celsius_to_fahrenheit(100) == 212
+    assert format_value(45.0, "°C", "F") == "113.0°F"
+    assert format_value(45, "°C", "f") == "113.0°F"
+    assert format_value(45.0, "°C", "C") == "45.0°C"
+    assert format_value(45.0, "%", "F") == "45.0%"
+    assert format_value(None, "°C", "F") == ABSENT
+
+
+def test_display_unit_does_not_affect_thresholds():
+    """O nível do alerta compara em Celsius, qualquer que seja a unidade exibida."""
+    value = 80.0  # °C (176°F)
+    assert format_value(value, "°C", "F") == "176.0°F"
+    assert alert_level(value, 70, 85) == AlertLevel.WARNING
+    assert alert_level(60.0, 70, 85) is None  # 140°F não passa do warning de 70°C
+
+
+def test_format_uptime():
+    """Uptime legível; ausente (sender antigo) não aparece no banner."""
+    assert format_uptime(7 * 60 + 30) == "7m"
+    assert format_uptime(5 * 3600 + 12 * 60) == "5h 12m"
+    assert format_uptime(3 * 86400 + 4 * 3600 + 59) == "3d 04h"
+    assert format_uptime(None) == ""
+    assert format_uptime(0) == ""
+
+
+def test_default_precision_by_unit():
+    """Sem tabela, mantém 3 casas para voltagem e 1 para o resto."""
+    assert unit_category("°F") == "temp"
+    assert unit_category(" KB/s") == "rate"
+    assert unit_category(" MHz") == "other"
+    assert format_value(1.2345, "V", precision={}) == "1.234V"
+    assert format_value(12.34, " MHz") == "12.3 MHz"
+
+
+def test_configured_precision():
+    """Temperaturas inteiras, uso com 2 casas; categorias omitidas usam o padrão."""
+    precision = {"temp": 0, "percent": 2}
+    assert format_value(55.6, "°C", precision=precision) == "56°C"
+    assert format_value(55.6, "°C", "F", precision=precision) == "132°F"
+    assert format_value(42.125, "%", precision=precision) == "42.12%"
+    assert format_value(1.2345, "V", precision=precision) == "1.234V"
+    assert format_value(3, " RPM", precision={"other": 2}) == "3 RPM"  # Inteiros não ganham casas
+
+
+def test_rate_crossover_kb_to_mb():
+    """Abaixo de 1024 KB/s fica em KB/s; a partir daí vira MB/s."""
+    assert format_value(512.0, " KB/s") == "512.0 KB/s"
+    assert format_value(1023.9, " KB/s") == "1023.9 KB/s"
+    assert format_value(1024.0, " KB/s") == "1.0 MB/s"
+    assert format_value(5529.6, " KB/s") == "5.4 MB/s"
+    assert format_value(0, " KB/s") == "0.0 KB/s"
+    assert format_value(5529.6, " KB/s", precision={"rate": 2}) == "5.40 MB/s"
+
+
+def test_compact_rates():
+    """Modo compacto abrevia a unidade das taxas; outras unidades não mudam."""
+    assert format_value(5529.6, " KB/s", compact=True) == "5.4M"
+    assert format_value(512.0, " KB/s", compact=True) == "512.0K"
+    assert format_value(5529.6, " KB/s", precision={"rate": 0}, compact=True) == "5M"
+    assert format_value(55.0, "°C", compact=True) == "55.0°C"
+    assert format_value(None, " KB/s", compact=True) == ABSENT
+
+
+def test_number_locale_default_keeps_dot():
+    """Sem number_locale (ou desconhecido) tudo fica como antes: ponto e sem milhar."""
+    assert format_value(1234.5, " MHz") == "1234.5 MHz"
+    assert format_value(1.2345, "V") == "1.234V"
+    assert format_value(1800, " RPM") == "1800 RPM"
+    assert format_value(5529.6, " KB/s", number_locale="xx") == "5.4 MB/s"
+    assert number_separators("XX") == NUMBER_LOCALES[""]
+
+
+def test_number_locale_comma_decimals():
+    """pt/de: vírgula decimal e ponto no milhar, inclusive em MB/s e voltagem."""
+    assert format_value(1.2345, "V", number_locale="pt") == "1,234V"
+    assert format_value(55.25, "°C", number_locale="de") == "55,2°C"
+    assert format_value(1023.9, " KB/s", number_locale="pt") == "1.023,9 KB/s"
+    assert format_value(5529.6, " KB/s", number_locale="pt") == "5,4 MB/s"
+    assert format_value(5529.6, " KB/s", compact=True, number_locale="pt") == "5,4M"
+    assert format_value(4200, " MHz", number_locale="PT") == "4.200 MHz"
+    assert format_value(None, "V", number_locale="pt") == ABSENT
+
+
+def test_number_locale_english_and_french_grouping():
+    """en agrupa com vírgula; fr usa espaço fino no milhar e vírgula decimal."""
+    assert format_value(1.2345, "V", number_locale="en") == "1.234V"
+    assert format_value(1023.9, " KB/s", number_locale="en") == "1,023.9 KB/s"
+    assert format_value(1048576.0, " KB/s", number_locale="en") == "1,024.0 MB/s"
+    assert format_value(12345.67, " MHz", precision={"other": 2}, number_locale="fr") == "12\u202f345,67 MHz"
+    assert format_value(True, "", number_locale="en") == "True"  # Não é número
+
+
+if __name__ == "__main__":
+    for name, func in list(globals().items()):
+        if name.startswith("test_"):
+            func()
+            print(f"✓ {name}")
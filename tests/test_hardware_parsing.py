@@ -0,0 +1,416 @@
+"""
+Testes das regras de interpretação de sensores do hardware_monitor.
+Não requer a DLL do LibreHardwareMonitor nem privilégios de admin.
+"""
+import io
+import sys
+import os
+from contextlib import redirect_stdout
+
+# Adicionar diretório pai ao path para imports
+sys.path.insert(0, os.path.dirname(os.path.dirname(os.path.abspath(__file__))))
+
+from hardware_monitor import (
+    resolve_gpu_fan, format_sensor_table, parse_drive_serials, parse_drive_letters, make_drive_id,
+    merge_storage_entries, gpu_source, parse_gpu_sensors, select_gpu, parse_storage_temps,
+    parse_bus_type, parse_physical_disks, apply_physical_disks, HardwareMonitor, UNKNOWN_BUS,
+    RELIABILITY_REFRESH_SECS, DRIVE_QUERY_REFRESH_SECS
+)
+from core.payload import fans_from_sensors, fans_from_psutil
+from core.scheduling import SlowRefresh
+
+
+class FakeClock:
+    def __init__(self):
+        self.now = 1000.0
+    
+    def __call__(self):
+        return self.now
+
+
+def test_gpu_fan_percent_only():
+    """Placa que só expõe o duty cycle (Control) reporta em %."""
+    assert resolve_gpu_fan(None, 45.0) == (45.0, "%")
+
+
+def test_gpu_fan_rpm_preferred():
+    """Com sensor de RPM disponível, mantém RPM (compatível com payload antigo)."""
+    assert resolve_gpu_fan(1450.0, 45.0) == (1450.0, "RPM")
+    assert resolve_gpu_fan(None, 0) == (0, "RPM")
+
+
+def test_gpu_fan_stopped_and_slow():
+    """0 RPM é fan parado (zero-RPM) e abaixo de 100 RPM ainda é leitura; só negativo/absurdo some."""
+    assert resolve_gpu_fan(0, 30.0) == (0, "RPM")
+    assert resolve_gpu_fan(60.0, 0) == (60.0, "RPM")
+    assert resolve_gpu_fan(-1.0, 30.0) == (30.0, "%")
+    assert resolve_gpu_fan(65535.0, 0) == (0, "RPM")
+    gpu = parse_gpu_sensors("nvidia", [("Fan", "GPU Fan 1", 0.0), ("Control", "GPU Fan 1", 30.0)])
+    assert (gpu["fan"], gpu["fan_unit"]) == (0.0, "RPM")
+
+
+def test_sensor_table_formatting():
+    """Tabela do --dump-sensors: cabeçalho, colunas alinhadas e valor ausente como "-"."""
+    sensors = [
+        {"identifier": "/amdcpu/0/temperature/2", "sensor_type": "Temperature",
+         "name": "Core (Tctl/Tdie)", "parent": "AMD Ryzen 7 5800X", "value": 61.25},
+        {"identifier": "/lpc/nct6798d/fan/1", "sensor_type": "Fan",
+         "name": "Fan #2", "parent": "Nuvoton NCT6798D", "value": None},
+    ]
+    lines = format_sensor_table(sensors).split("\n")
+    assert lines[0].split() == ["Identifier", "Type", "Name", "Parent", "Value"]
+    assert set(lines[1].replace(" ", "")) == {"-"}
+    assert len(lines) == 4
+    assert lines[2].startswith("/amdcpu/0/temperature/2  Temperature")
+    assert lines[2].endswith("61.250")
+    assert lines[3].endswith("-")
+    # Colunas alinhadas: todas as linhas com o mesmo comprimento
+    assert len({len(line) for line in lines}) == 1
+    
+    assert len(format_sensor_table([]).split("\n")) == 2
+
+
+def test_drive_serials_and_ids():
+    """Seriais do WMI por índice; sem serial, o índice físico é a chave."""
+    rows = [
+        {"Index": 0, "SerialNumber": "  S4EWNX0N123456   "},
+        {"Index": 1, "SerialNumber": None},
+        {"Index": None, "SerialNumber": "ignored"},
+    ]
+    serials = parse_drive_serials(rows)
+    assert serials == {0: "S4EWNX0N123456"}
+    assert parse_drive_serials({"Index": 2, "SerialNumber": "ABC"}) == {2: "ABC"}
+    
+    assert make_drive_id("S4EWNX0N123456", 0) == "S4EWNX0N123456"
+    assert make_drive_id("", 1) == "PhysicalDrive1"
+    assert make_drive_id("", -1) == ""
+
+
+def test_gpu_source_classification():
+    """GpuIntel e identificadores /intelgpu/ contam como GPU."""
+    assert gpu_source("GpuNvidia", "/gpu-nvidia/0") == "nvidia"
+    assert gpu_source("GpuAmd") == "amd"
+    assert gpu_source("GpuIntel", "/gpu-intel-integrated/0") == "intel"
+    assert gpu_source("Unknown", "/intelgpu/0") == "intel"
+    assert gpu_source("Cpu", "/intelcpu/0") == ""
+
+
+def test_intel_igpu_parser():
+    """iGPU Intel: carga pelo D3D 3D, memória compartilhada, temperatura do pacote."""
+    readings = [
+        ("Power", "GPU Power", 4.2),
+        ("Load", "D3D 3D", 37.5),
+        ("Load", "D3D Video Decode", 80.0),
+        ("SmallData", "D3D Shared Memory Used", 512.0),
+        ("Temperature", "GPU Package", 58.0),
+    ]
+    gpu = parse_gpu_sensors("intel", readings)
+    assert gpu["source"] == "intel"
+    assert gpu["load"] == 37.5
+    assert gpu["mem_used"] == 512.0
+    assert gpu["temp"] == 58.0
+    assert gpu["power"] == 4.2
+    assert (gpu["fan"], gpu["fan_unit"]) == (0, "RPM")
+    
+    # Em GPU dedicada, D3D não conta como carga e temperatura exige "Core"
+    nvidia = parse_gpu_sensors("nvidia", readings + [("Load", "GPU Core", 12.0)])
+    assert nvidia["load"] == 12.0
+    assert nvidia["temp"] == 0
+
+
+def test_discrete_gpu_preferred_over_igpu():
+    """Com iGPU e dedicada, o painel mostra a dedicada (em qualquer ordem)."""
+    igpu = parse_gpu_sensors("intel", [("Load", "D3D 3D", 5.0)])
+    dgpu = parse_gpu_sensors("nvidia", [("Load", "GPU Core", 90.0)])
+    assert select_gpu([igpu, dgpu]) is dgpu
+    assert select_gpu([igpu]) is igpu
+    assert select_gpu([]) is None
+
+
+def test_nvme_multiple_temperatures():
+    """Composite é a principal; a máxima e os demais sensores vão à parte."""
+    readings = [
+        ("Temperature #1", 52.0),
+        ("Composite Temperature", 47.0),
+        ("Temperature #2", 61.0),
+    ]
+    primary, hottest, extras = parse_storage_temps(readings)
+    assert primary == 47.0
+    assert hottest == 61.0
+    assert extras == [52.0, 61.0]
+
+
+def test_single_storage_temperature_unchanged():
+    """Disco com um sensor (SATA/HDD) mantém o comportamento antigo."""
+    assert parse_storage_temps([("Temperature", 38.0)]) == (38.0, 38.0, [])
+    assert parse_storage_temps([("Temperature", 0)]) == (0, 0, [])
+    assert parse_storage_temps([]) == (0, 0, [])
+
+
+def test_fans_only_from_fan_sensors():
+    """Só sensores "Fan" viram fans: temperatura/Control acima de 100 não são RPM."""
+    readings = [
+        ("Temperature", "CPU Core", 105.0),
+        ("Control", "Fan Control #1", 100.0),
+        ("Fan", "Fan #1", 1200.0),
+        ("Fan", "Fan #2", 0),
+        ("Fan", "Fan #3", 65535.0),
+        ("Fan", "CPU Fan", 850.4),
+    ]
+    assert fans_from_sensors(readings) == [
+        {"name": "Fan #1", "rpm": 1200.0},
+        {"name": "CPU Fan", "rpm": 850.0},
+    ]
+    assert fans_from_sensors([("Temperature", "Hot Spot", 3000.0)]) == []
+
+
+def test_fans_from_hwmon():
+    """psutil.sensors_fans (Linux): rótulo vazio usa o chip; fan parado é ignorado."""
+    from collections import namedtuple
+    sfan = namedtuple("sfan", ["label", "current"])
+    fans = {
+        "nct6798": [sfan("CPU Fan", 1100), sfan("", 900), sfan("", 0)],
+        "amdgpu": [sfan("", 1500)],
+    }
+    assert fans_from_psutil(fans) == [
+        {"name": "CPU Fan", "rpm": 1100.0},
+        {"name": "nct6798 2", "rpm": 900.0},
+        {"name": "amdgpu 1", "rpm": 1500.0},
+    ]
+    assert fans_from_psutil(None) == []
+    assert fans_from_psutil({}) == []
+
+
+def test_drive_letters_by_disk():
+    """Letras das partições por disco; sem letra (EFI/recuperação) fica de fora."""
+    rows = [
+        {"DiskNumber": 0, "DriveLetter": None},
+        {"DiskNumber": 0, "DriveLetter": "C"},
+        {"DiskNumber": 0, "DriveLetter": 68},  # 'D' como código de caractere
+        {"DiskNumber": 1, "DriveLetter": "e:"},
+        {"DiskNumber": None, "DriveLetter": "F"},
+    ]
+    assert parse_drive_letters(rows) == {0: ["C", "D"], 1: ["E"]}
+    assert parse_drive_letters({"DiskNumber": 2, "DriveLetter": "G"}) == {2: ["G"]}
+    assert parse_drive_letters(None) == {}
+
+
+def test_same_disk_in_two_nodes_is_merged():
+    """Duas entradas do mesmo disco viram uma, com os sensores das duas."""
+    nvme = {"name": "Samsung SSD 980", "drive_id": "S4EWNX0N123456", "temp": 48.0, "temp_max": 52.0,
+            "extra_temps": [52.0], "health": 100, "used_space": 0, "read_rate": 0}
+    generic = {"name": "Samsung SSD 980 (ATA)", "drive_id": "S4EWNX0N123456", "temp": 0, "temp_max": 0,
+               "extra_temps": [], "health": 97, "used_space": 76.0, "read_rate": 1024.0}
+    other = {"name": "WD Blue", "drive_id": "PhysicalDrive1", "temp": 35.0, "health": 100, "used_space": 40.0}
+    merged = merge_storage_entries([nvme, generic, other])
+    assert [disk["name"] for disk in merged] == ["Samsung SSD 980", "WD Blue"]
+    assert merged[0]["temp"] == 48.0 and merged[0]["temp_max"] == 52.0
+    assert merged[0]["health"] == 97 and merged[0]["used_space"] == 76.0
+    assert merged[0]["read_rate"] == 1024.0 and merged[0]["extra_temps"] == [52.0]
+    assert nvme["health"] == 100  # Entradas originais não são alteradas
+
+
+def test_disks_without_id_are_kept_apart():
+    """Sem drive_id não há como saber se é o mesmo disco: ficam separados."""
+    disks = [{"name": "USB", "drive_id": "", "temp": 30.0}, {"name": "USB", "drive_id": "", "temp": 31.0}]
+    assert len(merge_storage_entries(disks)) == 2
+
+
+def test_bus_type_decoding():
+    """BusType numérico (PowerShell 5) ou por nome (versões novas)."""
+    assert parse_bus_type(7) == "usb"
+    assert parse_bus_type(11) == "sata"
+    assert parse_bus_type(17) == "nvme"
+    assert parse_bus_type("7") == "usb"
+    assert parse_bus_type("NVMe") == "nvme"
+    assert parse_bus_type(" USB ") == "usb"
+    for value in (0, 99, None, "", "Thunderbolt", True, [7]):
+        assert parse_bus_type(value) == UNKNOWN_BUS
+
+
+def test_physical_disks_parsing():
+    """Temperatura ausente ou implausível vira 0; linhas sem DeviceId são ignoradas."""
+    rows = [
+        {"DeviceId": "0", "BusType": 17, "Temperature": 41},
+        {"DeviceId": "2", "BusType": 7, "Temperature": None},
+        {"DeviceId": "3", "BusType": 7, "Temperature": 255},
+        {"DeviceId": None, "BusType": 11, "Temperature": 30},
+    ]
+    assert parse_physical_disks(rows) == {
+        0: {"bus_type": "nvme", "temp": 41.0},
+        2: {"bus_type": "usb", "temp": 0},
+        3: {"bus_type": "usb", "temp": 0},
+    }
+    # Um disco só: o PowerShell devolve um dict
+    assert parse_physical_disks({"DeviceId": "1", "BusType": 11, "Temperature": 35}) == {1: {"bus_type": "sata", "temp": 35.0}}
+    assert parse_physical_disks(None) == {}
+
+
+def test_usb_temperature_fallback():
+    """Só o disco sem sensor no LHM recebe a temperatura do contador; todos recebem o barramento."""
+    disks = [
+        {"drive_index": 0, "temp": 45.0, "temp_max": 47.0, "bus_type": UNKNOWN_BUS},
+        {"drive_index": 2, "temp": 0, "temp_max": 0, "bus_type": UNKNOWN_BUS},
+        {"drive_index": -1, "temp": 0, "temp_max": 0, "bus_type": UNKNOWN_BUS},
+    ]
+    physical = {0: {"bus_type": "nvme", "temp": 40.0}, 2: {"bus_type": "usb", "temp": 33.0}}
+    assert apply_physical_disks(disks, physical) == [2]
+    assert (disks[0]["temp"], disks[0]["bus_type"]) == (45.0, "nvme")
+    assert (disks[1]["temp"], disks[1]["temp_max"], disks[1]["bus_type"]) == (33.0, 33.0, "usb")
+    assert disks[2]["bus_type"] == UNKNOWN_BUS
+
+
+def test_physical_disks_query_skips_unsupported_drives():
+    """Disco USB sem temperatura em lugar nenhum não faz o PowerShell rodar a cada ciclo."""
+    with redirect_stdout(io.StringIO()):
+        monitor = HardwareMonitor()  # Sem pythonnet/DLL: só os caches
+    calls = []
+    
+    def fake_query():
+        calls.append(1)
+        return {0: {"bus_type": "sata", "temp": 38.0}, 2: {"bus_type": "usb", "temp": 0}}
+    
+    monitor._query_physical_disks = fake_query
+    disks = [{"drive_index": 0, "temp": 36.0}, {"drive_index": 2, "temp": 0}]
+    monitor._get_physical_disks(disks)
+    assert len(calls) == 1
+    for _ in range(3):
+        assert monitor._get_physical_disks(disks)[2]["bus_type"] == "usb"
+    assert len(calls) == 1
+    
+    # Disco novo: consulta de novo
+    monitor._get_physical_disks(disks + [{"drive_index": 5, "temp": 30.0}])
+    assert len(calls) == 2
+
+
+def test_reliability_temp_refresh_is_throttled():
+    """Disco USB com temperatura só pelo contador: consulta a cada RELIABILITY_REFRESH_SECS, não a cada ciclo."""
+    with redirect_stdout(io.StringIO()):
+        monitor = HardwareMonitor()
+    clock = FakeClock()
+    monitor._reliability_refresh = SlowRefresh(RELIABILITY_REFRESH_SECS, clock=clock)
+    calls = []
+    
+    def fake_query():
+        calls.append(clock.now)
+        return {2: {"bus_type": "usb", "temp": 33.0 + len(calls)}}
+    
+    monitor._query_physical_disks = fake_query
+    disks = [{"drive_index": 2, "temp": 0}]  # LHM nunca lê a temperatura do USB
+    assert monitor._get_physical_disks(disks)[2]["temp"] == 34.0
+    for _ in range(5):
+        clock.now += 10  # Um slow_refresh_secs por volta
+        assert monitor._get_physical_disks(disks)[2]["temp"] == 34.0
+    assert len(calls) == 1
+    clock.now += RELIABILITY_REFRESH_SECS
+    assert monitor._get_physical_disks(disks)[2]["temp"] == 35.0
+    assert len(calls) == 2
+    assert 2 not in monitor._no_fallback_temp
+
+
+def test_failed_reliability_query_does_not_mark_unsupported():
+    """PowerShell que falhou (None) não marca disco como sem temperatura; tenta de novo no próximo intervalo."""
+    with redirect_stdout(io.StringIO()):
+        monitor = HardwareMonitor()
+    clock = FakeClock()
+    monitor._reliability_refresh = SlowRefresh(RELIABILITY_REFRESH_SECS, clock=clock)
+    results = [None, {2: {"bus_type": "usb", "temp": 31.0}}]
+    monitor._query_physical_disks = lambda: results.pop(0)
+    disks = [{"drive_index": 2, "temp": 0}]
+    
+    assert monitor._get_physical_disks(disks) == {}
+    assert monitor._no_fallback_temp == set()
+    assert monitor._get_physical_disks(disks) == {}  # Antes do intervalo: sem nova consulta
+    assert len(results) == 1
+    clock.now += RELIABILITY_REFRESH_SECS
+    assert monitor._get_physical_disks(disks)[2]["temp"] == 31.0
+
+
+def test_letterless_disks_query_letters_once():
+    """Dois discos sem letra não fazem o Get-Partition rodar por disco a cada ciclo."""
+    with redirect_stdout(io.StringIO()):
+        monitor = HardwareMonitor()
+    clock = FakeClock()
+    monitor._drive_letters_refresh = SlowRefresh(DRIVE_QUERY_REFRESH_SECS, clock=clock)
+    calls = []
+    
+    def fake_query():
+        calls.append(clock.now)
+        return {0: ["C"]}  # Discos 1 e 2 sem partição com letra
+    
+    monitor._query_drive_letters = fake_query
+    for _ in range(3):
+        assert monitor.get_drive_letters(0) == ["C"]
+        assert monitor.get_drive_letters(1) == []
+        assert monitor.get_drive_letters(2) == []
+        clock.now += 10
+    assert len(calls) == 1
+    assert monitor.get_drive_letters(0) == ["C"]  # Não apagado pelos discos sem letra
+    
+    # Disco novo só consulta de novo depois do intervalo
+    assert monitor.get_drive_letters(3) == []
+    assert len(calls) == 1
+    clock.now += DRIVE_QUERY_REFRESH_SECS
+    monitor.get_drive_letters(3)
+    assert len(calls) == 2
+
+
+def test_failed_letters_query_waits_for_interval():
+    """Get-Partition que falhou não é repetido a cada disco nem marca os discos como sem letra."""
+    with redirect_stdout(io.StringIO()):
+        monitor = HardwareMonitor()
+    clock = FakeClock()
+    monitor._drive_letters_refresh = SlowRefresh(DRIVE_QUERY_REFRESH_SECS, clock=clock)
+    results = [None, {0: ["C"], 1: ["E"]}]
+    monitor._query_drive_letters = lambda: results.pop(0)
+    
+    assert monitor.get_drive_letters(0) == []
+    assert monitor.get_drive_letters(1) == []
+    assert len(results) == 1
+    clock.now += DRIVE_QUERY_REFRESH_SECS
+    assert monitor.get_drive_letters(1) == ["E"]
+    assert monitor.get_drive_letters(0) == ["C"]
+
+
+def test_disk_without_serial_keeps_other_serials():
+    """Disco USB/virtual sem serial não refaz a consulta a cada leitura nem apaga os seriais dos outros."""
+    with redirect_stdout(io.StringIO()):
+        monitor = HardwareMonitor()
+    clock = FakeClock()
+    monitor._drive_serials_refresh = SlowRefresh(DRIVE_QUERY_REFRESH_SECS, clock=clock)
+    results = [None, {0: "S0", 1: "S1"}, {0: "S0", 1: "S1"}]
+    monitor._query_drive_serials = lambda: results.pop(0)
+    
+    assert monitor._get_drive_serial(0) == ""  # Consulta falhou
+    assert monitor._get_drive_serial(0) == ""  # Antes do intervalo: sem nova consulta
+    assert len(results) == 2
+    clock.now += DRIVE_QUERY_REFRESH_SECS
+    for _ in range(3):
+        assert monitor._get_drive_serial(2) == ""  # Disco sem serial
+        assert monitor._get_drive_serial(0) == "S0"
+        assert monitor._get_drive_serial(1) == "S1"
+        clock.now += DRIVE_QUERY_REFRESH_SECS
+    assert len(results) == 1
+
+
+def test_disabled_wmi_is_never_queried():
+    """Com "wmi" em disabled_sources nenhuma consulta ao PowerShell roda."""
+    with redirect_stdout(io.StringIO()):
+        monitor = HardwareMonitor(disabled={"wmi", "storage"})
+    assert not monitor.wmi_enabled and not monitor.storage_enabled
+    
+    def fail():
+        raise AssertionError("consulta ao PowerShell com wmi desligado")
+    
+    monitor._query_drive_serials = monitor._query_drive_letters = monitor._query_physical_disks = fail
+    assert monitor._get_drive_serial(0) == ""
+    assert monitor.get_drive_letters(0) == []
+    assert monitor._get_physical_disks([{"drive_index": 0, "temp": 0}]) == {}
+    assert monitor.get_network_link_info()["link_speed_mbps"] == 0
+
+
+if __name__ == "__main__":
+    for name, func in list(globals().items()):
+        if name.startswith("test_"):
+            func()
+            print(f"✓ {name}")
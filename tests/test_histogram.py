@@ -0,0 +1,39 @@
+"""
+Testes do histograma de temperaturas (core/histogram.py).
+"""
+import sys
+import os
+
+# Adicionar diretório pai ao path para imports
+sys.path.insert(0, os.path.dirname(os.path.dirname(os.path.abspath(__file__))))
+
+from core.histogram import bin_index, histogram
+
+
+def test_empty_history():
+    """Sem amostras: todas as faixas zeradas."""
+    assert histogram([], 20, 100, 5) == [0] * 16
+
+
+def test_bin_boundaries():
+    """Valor na fronteira entra na faixa de cima; fora do range é preso nas pontas."""
+    assert bin_index(20.0, 20, 5, 16) == 0
+    assert bin_index(24.99, 20, 5, 16) == 0
+    assert bin_index(25.0, 20, 5, 16) == 1
+    assert bin_index(100.0, 20, 5, 16) == 15  # máximo vai para a última faixa
+    assert bin_index(130.0, 20, 5, 16) == 15
+    assert bin_index(5.0, 20, 5, 16) == 0
+
+
+def test_bimodal_counts():
+    """Idle e carga aparecem como dois picos."""
+    samples = [40, 41, 42, 44, 80, 81, 83]
+    counts = histogram(samples, 20, 100, 10)
+    assert counts == [0, 0, 4, 0, 0, 0, 3, 0]
+
+
+if __name__ == "__main__":
+    for name, func in list(globals().items()):
+        if name.startswith("test_"):
+            func()
+            print(f"✓ {name}")
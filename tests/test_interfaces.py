@@ -0,0 +1,313 @@
+"""
+Testes dos utilitários de rede do Sender (core/network.py).
+Não depende de hardware nem de rede: usa snapshots e medições sintéticas.
+"""
+import sys
+import os
+import socket
+from collections import namedtuple
+
+# Adicionar diretório pai ao path para imports
+sys.path.insert(0, os.path.dirname(os.path.dirname(os.path.abspath(__file__))))
+
+from core.network import (
+    is_physical_interface, select_interfaces, compute_interface_rates,
+    parse_ping_host, PingWorker, PingMethod, PING_UNREACHABLE, icmp_checksum,
+    socket_family, bind_address, peer_address, normalize_host, format_address,
+    open_udp_socket, join_multicast_group, set_multicast_ttl, set_multicast_interface,
+    is_multicast_address, DEFAULT_MULTICAST_GROUP, interface_address,
+    apply_socket_options, socket_ttl, send_buffer_size, MIN_SEND_BUFFER, MAX_SEND_BUFFER,
+    listen_ports, PortListener, is_datagram_too_long
+)
+from core.protocol import encode_payload, decode_payload, check_datagram, TruncatedFrameError
+
+NetIO = namedtuple("NetIO", "bytes_sent bytes_recv")
+
+
+def test_physical_interfaces_are_kept():
+    """Adaptadores reais entram na soma."""
+    for name in ("Ethernet", "Ethernet 2", "Wi-Fi", "Local Area Connection", "eth0", "enp3s0", "wlan0"):
+        assert is_physical_interface(name), name
+
+
+def test_virtual_interfaces_are_filtered():
+    """Loopback, VPN e adaptadores virtuais ficam de fora."""
+    for name in ("Loopback Pseudo-Interface 1", "vEthernet (WSL)", "VMware Network Adapter VMnet8",
+                 "VirtualBox Host-Only Network", "Tailscale", "OpenVPN TAP-Windows6",
+                 "Local Area Connection* 10", "lo", "docker0", "veth1a2b", "br-3f2c", "tun0", "wg0", ""):
+        assert not is_physical_interface(name), name
+
+
+def test_explicit_interface_overrides_filter():
+    """Com network_interface definido, só ela é monitorada (mesmo se virtual)."""
+    names = ["Ethernet", "Wi-Fi", "vEthernet (WSL)"]
+    assert select_interfaces(names) == ["Ethernet", "Wi-Fi"]
+    assert select_interfaces(names, "wi-fi") == ["Wi-Fi"]
+    assert select_interfaces(names, "vEthernet (WSL)") == ["vEthernet (WSL)"]
+    assert select_interfaces(names, "Inexistente") == []
+
+
+def test_interface_rates_skip_loopback():
+    """Tráfego do loopback não é somado; primeiro ciclo reporta zero."""
+    prev = {"Ethernet": NetIO(0, 0), "Loopback Pseudo-Interface 1": NetIO(0, 0)}
+    curr = {
+        "Ethernet": NetIO(2048, 4096),
+        "Loopback Pseudo-Interface 1": NetIO(10**9, 10**9),
+        "Wi-Fi": NetIO(500, 500),
+    }
+    rates = compute_interface_rates(prev, curr, 2.0)
+    assert rates == [["Ethernet", 2.0, 1.0], ["Wi-Fi", 0.0, 0.0]]
+
+
+def test_parse_ping_host():
+    """Aceita "host" e "host:porta"; rejeita valores inválidos."""
+    assert parse_ping_host("8.8.8.8:53") == ("8.8.8.8", 53)
+    assert parse_ping_host("1.1.1.1") == ("1.1.1.1", 53)
+    assert parse_ping_host(" router.local:443 ") == ("router.local", 443)
+    for invalid in ("", ":53", "host:abc", "host:0", "host:70000", "[2001:db8::1", "[]:53", "[::1]53"):
+        try:
+            parse_ping_host(invalid)
+            assert False, invalid
+        except ValueError:
+            pass
+
+
+def test_parse_ping_host_ipv6():
+    """IPv6 sozinho usa a porta padrão; com porta vai entre colchetes."""
+    assert parse_ping_host("2001:db8::1") == ("2001:db8::1", 53)
+    assert parse_ping_host("::1") == ("::1", 53)
+    assert parse_ping_host("[2001:db8::1]:443") == ("2001:db8::1", 443)
+    assert parse_ping_host("[fe80::1]") == ("fe80::1", 53)
+
+
+def test_ping_unreachable_sentinel():
+    """Host inalcançável vira -1 (não 0, que é uma latência válida)."""
+    def timeout(host, port):
+        raise OSError("timed out")
+
+    worker = PingWorker("10.255.255.1", 53, measure=timeout)
+    assert worker.measure_once() == PING_UNREACHABLE
+    assert worker.last_ms == PING_UNREACHABLE
+
+    worker = PingWorker("8.8.8.8", 53, measure=lambda host, port: 0.0)
+    assert worker.measure_once() == 0.0
+
+
+def test_icmp_falls_back_to_tcp_without_permission():
+    """Sem raw socket (PermissionError) passa a usar TCP e informa o método."""
+    calls = []
+
+    def icmp(host, port):
+        calls.append("icmp")
+        raise PermissionError(13, "Permission denied")
+
+    def tcp(host, port):
+        calls.append("tcp")
+        return 12.5
+
+    method = PingMethod(use_icmp=True, icmp=icmp, tcp=tcp)
+    worker = PingWorker("8.8.8.8", 53, measure=method)
+    assert worker.method == "icmp"
+    assert worker.measure_once() == 12.5
+    assert worker.method == "tcp"
+    # Não tenta ICMP de novo nos ciclos seguintes
+    worker.measure_once()
+    assert calls == ["icmp", "tcp", "tcp"]
+
+
+def test_icmp_timeout_is_unreachable_not_fallback():
+    """Timeout do ICMP é host inalcançável, não motivo para trocar de método."""
+    def icmp(host, port):
+        raise TimeoutError("timed out")
+
+    method = PingMethod(use_icmp=True, icmp=icmp, tcp=lambda host, port: 1.0)
+    worker = PingWorker("10.255.255.1", 53, measure=method)
+    assert worker.measure_once() == PING_UNREACHABLE
+    assert worker.method == "icmp"
+
+
+def test_icmp_checksum():
+    """Checksum de um echo request zerado confere com o RFC 1071."""
+    assert icmp_checksum(b"\x08\x00\x00\x00\x00\x01\x00\x01") == 0xF7FD
+
+
+def test_bind_address_ipv4_config():
+    """Sem IPv6 configurado, continua em 0.0.0.0 / no bind_ip informado."""
+    family = socket_family("255.255.255.255", "")
+    assert family == socket.AF_INET
+    assert bind_address("", 5005, family) == ("0.0.0.0", 5005)
+    assert bind_address("192.168.1.10", 0, family) == ("192.168.1.10", 0)
+
+
+def test_bind_address_ipv6_config():
+    """Endereço IPv6 em qualquer campo, ou ipv6=true, escolhe [::]."""
+    assert socket_family("fe80::1%eth0", "") == socket.AF_INET6
+    assert socket_family("192.168.1.50", "2001:db8::10") == socket.AF_INET6
+    family = socket_family("192.168.1.50", ipv6=True)
+    assert family == socket.AF_INET6
+    assert bind_address("", 5005, family) == ("::", 5005)
+    assert bind_address("[2001:db8::10]", 0, family) == ("2001:db8::10", 0)
+    # IPv4 por socket IPv6 (dual-stack) vai como endereço mapeado
+    assert peer_address("192.168.1.50", 5005, family) == ("::ffff:192.168.1.50", 5005)
+    assert peer_address("2001:db8::20", 5005, family) == ("2001:db8::20", 5005)
+
+
+def test_source_address_formatting():
+    """Banner mostra IPv6 entre colchetes e IPv4 mapeado como IPv4."""
+    assert format_address("192.168.1.10", 5005) == "192.168.1.10:5005"
+    assert format_address("fe80::1", 5005) == "[fe80::1]:5005"
+    assert format_address("::ffff:192.168.1.10", 5005) == "192.168.1.10:5005"
+    assert normalize_host("::ffff:10.0.0.2") == "10.0.0.2"
+    assert normalize_host("::1") == "::1"
+
+
+def test_multicast_group_detection():
+    """224.0.0.0/4 e ff00::/8 são grupos; broadcast e unicast não."""
+    assert is_multicast_address(DEFAULT_MULTICAST_GROUP)
+    assert is_multicast_address("ff15::1")
+    assert not is_multicast_address("255.255.255.255")
+    assert not is_multicast_address("192.168.1.50")
+    assert not is_multicast_address("")
+
+
+def test_multicast_loopback_roundtrip():
+    """Receiver entra no grupo pelo loopback e recebe o frame do sender."""
+    receiver = open_udp_socket(socket.AF_INET)
+    sender = open_udp_socket(socket.AF_INET)
+    try:
+        receiver.setsockopt(socket.SOL_SOCKET, socket.SO_REUSEADDR, 1)
+        receiver.bind(("", 0))
+        receiver.settimeout(2.0)
+        join_multicast_group(receiver, DEFAULT_MULTICAST_GROUP, "127.0.0.1")
+        
+        set_multicast_ttl(sender, 1)
+        set_multicast_interface(sender, "127.0.0.1")
+        payload = {"cpu": {"usage": 12.5}, "hostname": "PC"}
+        sender.sendto(encode_payload(payload), (DEFAULT_MULTICAST_GROUP, receiver.getsockname()[1]))
+        
+        data, _ = receiver.recvfrom(16384)
+        assert decode_payload(data) == payload
+    finally:
+        sender.close()
+        receiver.close()
+
+
+def test_socket_option_ranges():
+    """Ausente/zero mantém o padrão do sistema; fora da faixa é ajustado."""
+    for value in (None, 0, -5, "", "abc"):
+        assert socket_ttl(value) == 0
+        assert send_buffer_size(value) == 0
+    assert socket_ttl(64) == 64
+    assert socket_ttl("32") == 32
+    assert socket_ttl(1000) == 255
+    assert send_buffer_size(1) == MIN_SEND_BUFFER
+    assert send_buffer_size(262144) == 262144
+    assert send_buffer_size(10**9) == MAX_SEND_BUFFER
+
+
+def test_socket_options_applied():
+    """TTL e SO_SNDBUF chegam ao socket; zero não altera o padrão."""
+    sock = open_udp_socket(socket.AF_INET)
+    try:
+        default = apply_socket_options(sock, socket.AF_INET)
+        assert apply_socket_options(sock, socket.AF_INET, 0, 0) == default
+        applied = apply_socket_options(sock, socket.AF_INET, ttl=17, send_buffer=65536)
+        assert applied["ttl"] == 17
+        # O sistema pode arredondar (o Linux dobra), mas não fica abaixo do pedido
+        assert applied["send_buffer"] >= 65536
+    finally:
+        sock.close()
+
+
+def test_interface_address_by_name():
+    """Nome da interface -> IP atual da família pedida; link-local IPv6 só como último recurso."""
+    Addr = namedtuple("Addr", "family address netmask broadcast ptp")
+    interfaces = {
+        "Ethernet": [
+            Addr(socket.AF_INET6, "fe80::1c2a:3bff:fe4d:5e6f%12", None, None, None),
+            Addr(socket.AF_INET, "192.168.1.42", "255.255.255.0", None, None),
+            Addr(socket.AF_INET6, "2001:db8::42", None, None, None),
+        ],
+        "wlan0": [Addr(socket.AF_INET6, "fe80::2%wlan0", None, None, None)],
+        "Wi-Fi": [],
+    }
+    assert interface_address(interfaces, "Ethernet") == "192.168.1.42"
+    assert interface_address(interfaces, "ethernet ") == "192.168.1.42"
+    assert interface_address(interfaces, "Ethernet", socket.AF_INET6) == "2001:db8::42"
+    assert interface_address(interfaces, "wlan0", socket.AF_INET6) == "fe80::2"
+    # Sem IPv4 (cabo desconectado, DHCP pendente) ou interface inexistente
+    assert interface_address(interfaces, "wlan0") == ""
+    assert interface_address(interfaces, "Wi-Fi") == ""
+    assert interface_address(interfaces, "eth9") == ""
+
+
+def test_listen_ports_config():
+    """"ports" tem precedência; "porta" sozinha (ou como lista) continua valendo; inválidas viram aviso."""
+    assert listen_ports(5005) == ([5005], [])
+    assert listen_ports(5005, []) == ([5005], [])
+    assert listen_ports(5005, [6000, 6001, 6000]) == ([6000, 6001], [])
+    assert listen_ports([5005, 5006]) == ([5005, 5006], [])
+    ports, warnings = listen_ports(5005, [6000, 0, "x", True, 70000])
+    assert ports == [6000] and len(warnings) == 4
+    assert listen_ports("abc")[0] == [5005]
+
+
+def test_port_listener_tags_each_port():
+    """Frames em duas portas do loopback chegam pela mesma fila, cada um com a porta de chegada."""
+    sockets = {}
+    for _ in range(2):
+        sock = open_udp_socket(socket.AF_INET)
+        sock.bind(("127.0.0.1", 0))
+        sockets[sock.getsockname()[1]] = sock
+    port_a, port_b = sockets
+    listener = PortListener(sockets)
+    sender = open_udp_socket(socket.AF_INET)
+    try:
+        sender.sendto(encode_payload({"hostname": "A"}), ("127.0.0.1", port_a))
+        sender.sendto(encode_payload({"hostname": "B"}), ("127.0.0.1", port_b))
+        received = {}
+        for _ in range(2):
+            data, addr, port = listener.recvfrom(timeout=2.0)
+            received[decode_payload(data)["hostname"]] = port
+            assert addr[0] == "127.0.0.1"
+        assert received == {"A": port_a, "B": port_b}
+        try:
+            listener.recvfrom(timeout=0.1)
+            assert False, "fila deveria estar vazia"
+        except socket.timeout:
+            pass
+    finally:
+        sender.close()
+        listener.close()
+
+
+def test_oversized_datagram_detected():
+    """Datagrama maior que o buffer: truncado no Linux (check_datagram acusa) ou WSAEMSGSIZE no Windows."""
+    receiver = open_udp_socket(socket.AF_INET)
+    receiver.bind(("127.0.0.1", 0))
+    receiver.settimeout(2.0)
+    sender = open_udp_socket(socket.AF_INET)
+    try:
+        sender.sendto(b"\x01" + b"x" * 2000, receiver.getsockname())
+        try:
+            data, _ = receiver.recvfrom(1024)
+        except OSError as e:
+            assert is_datagram_too_long(e)
+        else:
+            try:
+                check_datagram(data, 1024)
+                assert False, "Datagrama truncado deveria falhar"
+            except TruncatedFrameError:
+                pass
+    finally:
+        sender.close()
+        receiver.close()
+    
+    assert not is_datagram_too_long(OSError(10054, "connection reset"))
+
+
+if __name__ == "__main__":
+    for name, func in list(globals().items()):
+        if name.startswith("test_"):
+            func()
+            print(f"✓ {name}")
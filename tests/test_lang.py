@@ -0,0 +1,59 @@
+"""
+Testes dos textos do dashboard (ui/lang.py).
+Não abre a GUI.
+"""
+import ast
+import sys
+import os
+import re
+
+# Adicionar diretório pai ao path para imports
+sys.path.insert(0, os.path.dirname(os.path.dirname(os.path.abspath(__file__))))
+
+from ui.lang import Language, STRINGS, DEFAULT_LANGUAGE, tr, set_language, parse_language
+
+
+def test_every_key_in_every_language():
+    """Toda chave existe em todos os idiomas, com os mesmos campos {…} para preencher."""
+    reference = STRINGS[DEFAULT_LANGUAGE]
+    for language in Language:
+        table = STRINGS[language]
+        assert set(table) == set(reference), (language, set(table) ^ set(reference))
+        for key, text in table.items():
+            assert text, (language, key)
+            assert set(re.findall(r"{(\w+)}", text)) == set(re.findall(r"{(\w+)}", reference[key])), (language, key)
+
+
+def test_language_selection():
+    """Código do config (com região ou maiúsculas) escolhe a tabela; desconhecido cai para português."""
+    try:
+        assert set_language("EN") == Language.EN
+        assert tr("row.disk", n=2) == "Disk 2"
+        assert parse_language("pt-BR") == Language.PT
+        assert parse_language("fr") is None and parse_language(None) is None
+        assert set_language("fr") == Language.PT
+        assert tr("row.disk", n=2) == "Disco 2"
+        assert tr("chave.inexistente") == "chave.inexistente"
+    finally:
+        set_language(DEFAULT_LANGUAGE)
+
+
+
+def test_receiver_fallback_matches_portuguese_table():
+    """A cópia do português no receiver (usada sem ui/lang) é igual à tabela de ui/lang.py."""
+    path = os.path.join(os.path.dirname(os.path.dirname(os.path.abspath(__file__))), "receiver_notebook.py")
+    with open(path, encoding="utf-8") as f:
+        tree = ast.parse(f.read())
+    fallback = next(
+        ast.literal_eval(node.value) for node in ast.walk(tree)
+        if isinstance(node, ast.Assign)
+        and any(isinstance(target, ast.Name) and target.id == "FALLBACK_STRINGS" for target in node.targets)
+    )
+    assert fallback == STRINGS[Language.PT]
+
+
+if __name__ == "__main__":
+    for name, func in list(globals().items()):
+        if name.startswith("test_"):
+            func()
+            print(f"✓ {name}")
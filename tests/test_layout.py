@@ -0,0 +1,109 @@
+"""
+Testes do modo foco do dashboard (ui/layout.py).
+Só o mapeamento tecla -> painel; não abre a GUI.
+"""
+import sys
+import os
+
+# Adicionar diretório pai ao path para imports
+sys.path.insert(0, os.path.dirname(os.path.dirname(os.path.abspath(__file__))))
+
+from ui.layout import GRID_PANELS, focus_for_key, panel_for_key, visible_panels, plot_grid, panel_order, panel_rows, bar_fill
+from ui.layout import metric_matches
+
+
+def test_number_keys_map_to_panels():
+    """1-6 seguem a ordem da grade; outras teclas não são de foco."""
+    assert [panel_for_key(str(n)) for n in range(1, 7)] == list(GRID_PANELS)
+    assert panel_for_key("7") is None
+    assert panel_for_key("0") is None
+    assert panel_for_key("g") is None
+
+
+def test_focus_transitions():
+    """Foca, troca de painel, repete a tecla ou 0/Esc para voltar à grade."""
+    assert focus_for_key("1", None) == "CPU"
+    assert focus_for_key("5", "CPU") == "STORAGE"
+    assert focus_for_key("5", "STORAGE") is None
+    assert focus_for_key("0", "GPU") is None
+    assert focus_for_key("Escape", "GPU") is None
+    assert focus_for_key("g", "GPU") == "GPU"  # Gráficos não mudam o foco
+
+
+def test_visible_panels():
+    """Grade mostra todos; foco mostra só o painel escolhido."""
+    assert visible_panels(None) == GRID_PANELS
+    assert visible_panels("NETWORK") == ("NETWORK",)
+    assert visible_panels("BOGUS") == GRID_PANELS
+
+
+def test_plot_grid_wraps():
+    """Cabem 5 gráficos numa linha larga; numa estreita, quebram em mais linhas."""
+    wide = plot_grid(5, 1300, 110)
+    assert len(wide) == 5 and {cell[1] for cell in wide} == {0}
+    assert wide[1] == (260, 0, 260, 110)
+    
+    narrow = plot_grid(5, 620, 120)  # 3 por linha
+    assert [cell[:2] for cell in narrow] == [(0, 0), (206, 0), (412, 0), (0, 60), (206, 60)]
+    assert plot_grid(2, 100, 100) == [(0, 0, 100, 50), (0, 50, 100, 50)]
+    assert plot_grid(0, 800, 100) == []
+
+
+def test_panel_order_from_config():
+    """Ordem do config é respeitada; caixa e repetidos não importam."""
+    order, unknown = panel_order(["storage", "CPU", "Ram", "cpu"])
+    assert order == ("STORAGE", "CPU", "RAM")
+    assert unknown == []
+    assert panel_rows(order) == [("STORAGE", "CPU", "RAM")]
+    assert panel_rows(GRID_PANELS) == [("CPU", "GPU", "RAM"), ("MOBO", "STORAGE", "NETWORK")]
+    assert panel_rows(("CPU", "GPU", "RAM", "MOBO")) == [("CPU", "GPU", "RAM"), ("MOBO",)]
+
+
+def test_panel_order_empty_and_unknown():
+    """Lista vazia usa a grade completa; nomes desconhecidos são devolvidos e pulados."""
+    assert panel_order([]) == (GRID_PANELS, [])
+    assert panel_order(None) == (GRID_PANELS, [])
+    order, unknown = panel_order(["CPU", "fans", "NETWORK"])
+    assert order == ("CPU", "NETWORK")
+    assert unknown == ["fans"]
+    # Só nomes inválidos: grade completa em vez de dashboard vazio
+    assert panel_order(["fans"]) == (GRID_PANELS, ["fans"])
+    assert panel_rows(()) == []
+
+
+def test_visible_panels_respects_layout():
+    """Foco num painel fora do layout mostra o layout configurado."""
+    layout = ("CPU", "RAM")
+    assert visible_panels(None, layout) == layout
+    assert visible_panels("RAM", layout) == ("RAM",)
+    assert visible_panels("GPU", layout) == layout
+
+
+def test_bar_fill():
+    """Fração proporcional (limitada a 1) e cor pelos limites; sem dado vira barra vazia."""
+    assert bar_fill(50, 100) == (0.5, None)
+    assert bar_fill(75, 100, 70, 85) == (0.75, "warning")
+    assert bar_fill(130, 100, 70, 85) == (1.0, "critical")
+    for empty in (None, 0, -1, float("nan"), "x", True):
+        assert bar_fill(empty, 100, 70, 85) == (0.0, None)
+    assert bar_fill(50, 0) == (0.0, None)
+
+
+def test_metric_matches():
+    """Busca sem diferenciar maiúsculas nem acentos; cada palavra pode casar com um texto diferente."""
+    assert metric_matches("temp", "GPU", "Temp")
+    assert metric_matches("TEMP", "CPU", "temp")
+    assert metric_matches("gpu temp", "GPU", "Temp")
+    assert metric_matches("  ", "RAM", "Uso")  # Busca vazia: tudo casa
+    assert metric_matches("", "RAM", "Uso")
+    assert metric_matches("saude", "STORAGE", "  Saúde")  # Sem acento na busca
+    assert metric_matches("SAÚ", "STORAGE", "  Saúde")
+    assert not metric_matches("gpu temp", "CPU", "Temp")
+    assert not metric_matches("clock", "RAM", "Usado")
+
+
+if __name__ == "__main__":
+    for name, func in list(globals().items()):
+        if name.startswith("test_"):
+            func()
+            print(f"✓ {name}")
@@ -0,0 +1,139 @@
+"""
+Testes do formato de log (core/logging_config.py).
+Usa um stream em memória e relógio fixo; não escreve no console.
+O log em arquivo é gravado em um diretório temporário.
+"""
+import sys
+import os
+import io
+import json
+import logging
+import tempfile
+from pathlib import Path
+
+# Adicionar diretório pai ao path para imports
+sys.path.insert(0, os.path.dirname(os.path.dirname(os.path.abspath(__file__))))
+
+from core.logging_config import (
+    log_format_from, configure_output, JsonLineWriter, JsonFormatter, LOG_FORMAT_ENV,
+    LogWriter, parse_log_level, rotating_file_handler
+)
+
+
+def test_log_format_selection():
+    """--log-format tem prioridade sobre a variável de ambiente; inválido vira texto."""
+    assert log_format_from([], {}) == "text"
+    assert log_format_from(["sender_pc.py", "--log-format", "json"], {}) == "json"
+    assert log_format_from(["--log-format=JSON"], {}) == "json"
+    assert log_format_from([], {LOG_FORMAT_ENV: "json"}) == "json"
+    assert log_format_from(["--log-format", "text"], {LOG_FORMAT_ENV: "json"}) == "text"
+    assert log_format_from(["--log-format", "xml"], {}) == "text"
+    assert log_format_from(["--log-format"], {}) == "text"
+
+
+def test_configure_output_picks_writer():
+    """json embrulha o stream; text (ou sem console) devolve o próprio stream."""
+    stream = io.StringIO()
+    assert isinstance(configure_output("json", stream), JsonLineWriter)
+    assert configure_output("text", stream) is stream
+    assert configure_output("json", None) is None
+
+
+def test_print_lines_become_json():
+    """print("[Tag] msg") vira um objeto com target/level; separadores somem."""
+    stream = io.StringIO()
+    out = JsonLineWriter(stream, clock=lambda: 0.0)
+    print("[Receiver] Erro: timeout", file=out)
+    print("=" * 50, file=out)
+    print("Intervalo: 0.5s", file=out)
+    print("[Config] Aviso: porta", "privilegiada", file=out)
+    
+    lines = [json.loads(line) for line in stream.getvalue().splitlines()]
+    assert lines == [
+        {"ts": "1970-01-01T00:00:00.000+00:00", "level": "ERROR", "target": "Receiver", "message": "Erro: timeout"},
+        {"ts": "1970-01-01T00:00:00.000+00:00", "level": "INFO", "target": "", "message": "Intervalo: 0.5s"},
+        {"ts": "1970-01-01T00:00:00.000+00:00", "level": "WARNING", "target": "Config", "message": "Aviso: porta privilegiada"},
+    ]
+
+
+def test_partial_writes_wait_for_newline():
+    """Linha só sai completa (print escreve texto e "\\n" separados)."""
+    stream = io.StringIO()
+    out = JsonLineWriter(stream, clock=lambda: 0.0)
+    out.write("[HW] Inicia")
+    assert stream.getvalue() == ""
+    out.write("lizado\n")
+    assert json.loads(stream.getvalue())["message"] == "Inicializado"
+
+
+def test_logging_json_formatter():
+    """Registros do logging saem com o nível e o nome do logger."""
+    record = logging.LogRecord("telemetry.alerts", logging.WARNING, __file__, 1, "CPU em %d°C", (91,), None)
+    record.created = 0.0
+    data = json.loads(JsonFormatter().format(record))
+    assert data == {"ts": "1970-01-01T00:00:00.000+00:00", "level": "WARNING",
+                    "target": "telemetry.alerts", "message": "CPU em 91°C"}
+
+
+def test_parse_log_level():
+    """Nomes do config (qualquer caixa) viram níveis; inválido volta para INFO."""
+    assert parse_log_level("debug") == logging.DEBUG
+    assert parse_log_level("trace") == logging.DEBUG
+    assert parse_log_level(" WARN ") == logging.WARNING
+    assert parse_log_level("warning") == logging.WARNING
+    assert parse_log_level("Error") == logging.ERROR
+    assert parse_log_level("off") > logging.CRITICAL
+    assert parse_log_level(logging.ERROR) == logging.ERROR
+    for value in ("", "verbose", None, True):
+        assert parse_log_level(value) == logging.INFO
+
+
+def test_level_filters_console():
+    """Abaixo do nível mínimo a linha some; texto passa sem alteração."""
+    stream = io.StringIO()
+    writer = configure_output("text", stream, "warn")
+    assert isinstance(writer, LogWriter)
+    writer.write("[Sender] Pacote enviado\n")
+    writer.write("[Rede] Aviso: link em 100 Mbps\n")
+    writer.write("[LHM] Erro ao ler sensores\n")
+    assert stream.getvalue() == "[Rede] Aviso: link em 100 Mbps\n[LHM] Erro ao ler sensores\n"
+    # Sem filtro nem arquivo não há por que embrulhar o stream
+    assert configure_output("text", stream, "info") is stream
+
+
+def test_file_log_keeps_console():
+    """Com log em arquivo o console continua recebendo as mesmas linhas."""
+    with tempfile.TemporaryDirectory() as tmp:
+        stream = io.StringIO()
+        handler = rotating_file_handler(str(Path(tmp) / "logs"), "sender", backup_days=3)
+        writer = LogWriter(stream, logging.INFO, handler, clock=lambda: 0.0)
+        writer.write("[Sender] Iniciado\n[Sender] Falha ao enviar\n")
+        writer.close_file()
+        
+        assert stream.getvalue() == "[Sender] Iniciado\n[Sender] Falha ao enviar\n"
+        path = Path(tmp) / "logs" / "sender.log"
+        lines = path.read_text(encoding="utf-8").splitlines()
+        assert len(lines) == 2
+        assert "INFO" in lines[0] and "Iniciado" in lines[0]
+        assert "ERROR" in lines[1] and "Falha ao enviar" in lines[1]
+
+
+def test_file_log_json():
+    """No formato JSON o arquivo também recebe uma linha JSON por mensagem."""
+    with tempfile.TemporaryDirectory() as tmp:
+        handler = rotating_file_handler(tmp, "sender")
+        writer = JsonLineWriter(None, logging.INFO, handler, clock=lambda: 0.0)
+        writer.write("[Config] Carregado\n")
+        writer.close_file()
+        record = json.loads((Path(tmp) / "sender.log").read_text(encoding="utf-8"))
+        assert record == {
+            "ts": "1970-01-01T00:00:00.000+00:00", "level": "INFO",
+            "target": "Config", "message": "Carregado"
+        }
+
+
+if __name__ == "__main__":
+    for name, func in list(globals().items()):
+        if name.startswith("test_"):
+            func()
+            print(f"✓ {name}")
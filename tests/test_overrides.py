@@ -0,0 +1,143 @@
+"""
+Testes da sobrescrita do config pela linha de comando e pelo ambiente (core/overrides.py).
+Usa argv, ambiente e configs em memória; não lê nem grava arquivos nem mexe em os.environ.
+"""
+import sys
+import os
+
+# Adicionar diretório pai ao path para imports
+sys.path.insert(0, os.path.dirname(os.path.dirname(os.path.abspath(__file__))))
+
+from core.overrides import (
+    parse_cli_overrides, apply_overrides, coerce_value, applied_values, without_overrides,
+    env_overrides, env_var_names,
+    SENDER_CLI_OPTIONS, RECEIVER_CLI_OPTIONS, SENDER_ENV_PREFIX, RECEIVER_ENV_PREFIX
+)
+
+BASE = {
+    "modo": "broadcast",
+    "dest_ip": "255.255.255.255",
+    "porta": 5005,
+    "intervalo": 0.5,
+    "ipv6": False,
+    "alertas": {"cpu_temp_warning": 70, "cpu_temp_critical": 85},
+}
+
+
+def test_parse_named_options():
+    """--opcao valor e --opcao=valor; flags desconhecidas ficam de fora."""
+    argv = ["sender_pc.py", "--no-admin", "--port", "6000", "--interval=1.0", "--log-format", "json"]
+    assert parse_cli_overrides(argv, SENDER_CLI_OPTIONS) == {"porta": "6000", "intervalo": "1.0"}
+    assert parse_cli_overrides(["receiver_notebook.py", "--theme", "light"], RECEIVER_CLI_OPTIONS) == {"tema": "light"}
+    # Opção sem valor no fim é ignorada
+    assert parse_cli_overrides(["sender_pc.py", "--port"], SENDER_CLI_OPTIONS) == {}
+
+
+def test_parse_set_any_field():
+    """--set caminho=valor alcança qualquer campo; a última ocorrência vence."""
+    argv = ["x", "--set", "alertas.cpu_temp_warning=80", "--set=ipv6=true", "--port", "1", "--port", "2"]
+    assert parse_cli_overrides(argv, SENDER_CLI_OPTIONS) == {
+        "alertas.cpu_temp_warning": "80", "ipv6": "true", "porta": "2"
+    }
+
+
+def test_cli_takes_precedence_over_file():
+    """Os valores da linha de comando substituem os do arquivo, no tipo do campo."""
+    overrides = parse_cli_overrides(
+        ["sender_pc.py", "--port", "6000", "--interval", "1", "--dest-ip", "192.168.1.20",
+         "--set", "alertas.cpu_temp_warning=80", "--set", "ipv6=yes"],
+        SENDER_CLI_OPTIONS
+    )
+    config, errors = apply_overrides(BASE, overrides)
+    assert errors == []
+    assert config["porta"] == 6000
+    assert config["intervalo"] == 1.0 and isinstance(config["intervalo"], float)
+    assert config["dest_ip"] == "192.168.1.20"
+    assert config["ipv6"] is True
+    assert config["alertas"] == {"cpu_temp_warning": 80, "cpu_temp_critical": 85}
+    # O config original não é alterado
+    assert BASE["porta"] == 5005 and BASE["alertas"]["cpu_temp_warning"] == 70
+
+
+def test_invalid_and_unknown_fields_reported():
+    """Valor incompatível ou campo inexistente vira erro e o campo fica como estava."""
+    config, errors = apply_overrides(BASE, {"porta": "abc", "prota": "6000", "alertas.nada": "1", "ipv6": "talvez"})
+    assert len(errors) == 4
+    assert any("'prota'" in e for e in errors)
+    assert config == BASE
+
+
+def test_coerce_value_types():
+    """O tipo do valor atual decide a conversão."""
+    assert coerce_value("0", True) is False
+    assert coerce_value(" 42 ", 1) == 42
+    assert coerce_value("2.5", 0.5) == 2.5
+    assert coerce_value('{"cpu": "#ff0000"}', {}) == {"cpu": "#ff0000"}
+    assert coerce_value("texto livre", "") == "texto livre"
+    for raw, current in (("1.5", 1), ("[1]", {}), ("x", 0.0)):
+        try:
+            coerce_value(raw, current)
+            assert False, f"aceitou {raw!r}"
+        except ValueError:
+            pass
+
+
+def test_saved_config_keeps_file_values():
+    """Gravar o config não persiste a sobrescrita, mas mantém mudanças feitas depois."""
+    overrides = {"porta": "6000", "tema": "light"}
+    base = {"porta": 5005, "tema": "dark"}
+    config, _ = apply_overrides(base, overrides)
+    overridden = applied_values(config, overrides)
+    assert overridden == {"porta": 6000, "tema": "light"}
+    
+    config["tema"] = "cyberpunk"  # Mudado no Settings depois
+    assert without_overrides(config, base, overridden) == {"porta": 5005, "tema": "cyberpunk"}
+
+
+def test_env_var_names():
+    """Nome da variável sai da opção nomeada, com o prefixo de cada programa."""
+    names = env_var_names(SENDER_ENV_PREFIX, SENDER_CLI_OPTIONS)
+    assert names["TELEMETRIA_SENDER_PORT"] == "porta"
+    assert names["TELEMETRIA_SENDER_DEST_IP"] == "dest_ip"
+    assert env_var_names(RECEIVER_ENV_PREFIX, RECEIVER_CLI_OPTIONS)["TELEMETRIA_RECEIVER_THEME"] == "tema"
+
+
+def test_env_overlay_only_touches_set_fields():
+    """Só as variáveis definidas mudam o config; ausentes e vazias mantêm o arquivo."""
+    environ = {
+        "TELEMETRIA_SENDER_PORT": "6000",
+        "TELEMETRIA_SENDER_INTERVAL": "",
+        "TELEMETRIA_RECEIVER_THEME": "light",  # De outro programa
+        "PATH": "/usr/bin",
+    }
+    overrides = env_overrides(environ, SENDER_ENV_PREFIX, SENDER_CLI_OPTIONS)
+    assert overrides == {"porta": "6000"}
+    config, warnings = apply_overrides(BASE, overrides, "variável de ambiente")
+    assert warnings == []
+    assert config == {**BASE, "porta": 6000}
+
+
+def test_env_invalid_value_is_ignored():
+    """Valor inválido no ambiente gera aviso e o valor do arquivo continua."""
+    environ = {"TELEMETRIA_SENDER_PORT": "seis mil", "TELEMETRIA_SENDER_MODE": "unicast"}
+    config, warnings = apply_overrides(BASE, env_overrides(environ, SENDER_ENV_PREFIX, SENDER_CLI_OPTIONS), "variável de ambiente")
+    assert len(warnings) == 1 and "porta" in warnings[0] and "variável de ambiente" in warnings[0]
+    assert config["porta"] == 5005
+    assert config["modo"] == "unicast"
+
+
+def test_cli_wins_over_env():
+    """Precedência: arquivo < ambiente < linha de comando."""
+    env = env_overrides({"TELEMETRIA_SENDER_PORT": "6000", "TELEMETRIA_SENDER_INTERVAL": "2"},
+                        SENDER_ENV_PREFIX, SENDER_CLI_OPTIONS)
+    config, _ = apply_overrides(BASE, env)
+    config, _ = apply_overrides(config, parse_cli_overrides(["x", "--port", "7000"], SENDER_CLI_OPTIONS))
+    assert config["porta"] == 7000
+    assert config["intervalo"] == 2.0
+
+
+if __name__ == "__main__":
+    for name, func in list(globals().items()):
+        if name.startswith("test_"):
+            func()
+            print(f"✓ {name}")
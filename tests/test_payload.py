@@ -0,0 +1,300 @@
+"""
+Testes da estrutura do payload (core/payload.py) e do roundtrip pelo protocolo.
+Não depende de hardware nem de rede.
+"""
+import sys
+import os
+from collections import namedtuple
+
+# Adicionar diretório pai ao path para imports
+sys.path.insert(0, os.path.dirname(os.path.dirname(os.path.abspath(__file__))))
+
+from core.payload import default_battery, battery_from_psutil, top_processes, vram_percent, MAX_TOP_PROCESSES
+from core.payload import default_sensor_status, sensor_status, sensor_status_hints, SENSOR_STATUS_FIELDS
+from core.payload import mark_absent, format_payload, UNAVAILABLE, significant_diff, disk_capacity
+from core.payload import default_system, system_from_psutil
+from core.protocol import encode_payload, decode_payload
+
+Battery = namedtuple("Battery", "percent secsleft power_plugged")
+DiskUsage = namedtuple("DiskUsage", "total used free percent")
+
+
+def test_battery_default_is_empty():
+    """Desktop sem bateria: seção vazia e zerada."""
+    battery = default_battery()
+    assert battery["present"] is False
+    assert battery["percent"] == 0
+    assert battery["charging"] is False
+    assert battery["time_remaining_min"] == 0
+    assert battery["power_w"] == 0
+    assert battery_from_psutil(None) == battery
+
+
+def test_battery_from_psutil():
+    """Converte o retorno do psutil (segundos -> minutos, plugged -> charging)."""
+    battery = battery_from_psutil(Battery(76.4, 5400, False), power_w=-12.34)
+    assert battery["present"] is True
+    assert battery["percent"] == 76.4
+    assert battery["charging"] is False
+    assert battery["time_remaining_min"] == 90
+    assert battery["power_w"] == 12.3
+
+    # Na tomada o psutil reporta tempo ilimitado (negativo)
+    plugged = battery_from_psutil(Battery(100, -2, True))
+    assert plugged["charging"] is True
+    assert plugged["time_remaining_min"] == 0
+
+
+def test_battery_roundtrip():
+    """A seção de bateria sobrevive ao encode/decode com e sem gzip."""
+    payload = {"cpu": {"usage": 10}, "battery": battery_from_psutil(Battery(50, 600, True), 8)}
+    for compress in (True, False):
+        assert decode_payload(encode_payload(payload, compress=compress)) == payload
+
+
+def test_top_processes_truncates_to_n():
+    """Ordena por CPU e mantém apenas os N primeiros."""
+    processes = [
+        {"name": f"proc{i}.exe", "pid": 100 + i, "cpu_percent": float(i), "mem_mb": 10.0 * i}
+        for i in range(50)
+    ]
+    top = top_processes(processes, 5)
+    assert len(top) == 5
+    assert [p["pid"] for p in top] == [149, 148, 147, 146, 145]
+
+    assert top_processes(processes, 0) == []
+    assert len(top_processes(processes, 1000)) == MAX_TOP_PROCESSES
+
+
+def test_top_processes_roundtrip():
+    """A lista de processos sobrevive ao encode/decode."""
+    processes = [{"name": "x" * 200, "pid": 1, "cpu_percent": None, "mem_mb": 512.4}]
+    payload = {"processes": top_processes(processes, 3)}
+    decoded = decode_payload(encode_payload(payload))
+    assert decoded == payload
+    assert len(decoded["processes"][0]["name"]) == 32
+
+
+def test_vram_percent():
+    """Percentual de VRAM; total desconhecido (0) não gera percentual."""
+    assert vram_percent(2048, 8192) == 25.0
+    assert vram_percent(3000, 0) is None
+    assert vram_percent(None, None) is None
+    assert vram_percent(9000, 8192) == 100.0
+
+
+def test_vram_roundtrip():
+    """mem_total_mb sobrevive ao encode/decode e alimenta o percentual."""
+    payload = {"gpu": {"mem_used_mb": 6144, "mem_total_mb": 12288}}
+    decoded = decode_payload(encode_payload(payload))
+    assert decoded == payload
+    assert vram_percent(decoded["gpu"]["mem_used_mb"], decoded["gpu"]["mem_total_mb"]) == 50.0
+
+
+def test_gpu_power_roundtrip():
+    """power_w sobrevive ao encode/decode e 0 (sem sensor) não vira null."""
+    payload = {"gpu": {"load": 97.0, "power_w": 287.4}}
+    assert decode_payload(encode_payload(payload)) == payload
+    absent = mark_absent({"gpu": {"temp": 0, "power_w": 0}})
+    assert absent["gpu"] == {"temp": None, "power_w": 0}
+
+
+def test_hostname_uptime_roundtrip():
+    """hostname/uptime_secs sobrevivem ao encode/decode; hostname vazio é compacto."""
+    payload = {"hostname": "GAMING-PC", "uptime_secs": 93784, "cpu": {"usage": 5}}
+    assert decode_payload(encode_payload(payload)) == payload
+    
+    # Sender antigo (sem os campos) e hostname vazio custam poucos bytes a mais
+    old = {"cpu": {"usage": 5}}
+    empty = {"hostname": "", "uptime_secs": 0, "cpu": {"usage": 5}}
+    assert decode_payload(encode_payload(empty)) == empty
+    extra = len(encode_payload(empty, compress=False)) - len(encode_payload(old, compress=False))
+    assert extra <= len('"hostname": "", "uptime_secs": 0, ')
+
+
+def test_sensor_status_defaults_all_false():
+    """Sem LHM e sem Admin, nenhuma fonte aparece como ativa."""
+    assert default_sensor_status() == {field: False for field in SENSOR_STATUS_FIELDS}
+    assert sensor_status(None, admin=False) == default_sensor_status()
+    assert sensor_status_hints(default_sensor_status())[0].startswith("LHM not detected")
+
+
+def test_sensor_status_from_monitor():
+    """LHM ativo sem Admin: GPU aparece, SMART (temperatura dos discos) não."""
+    hw_data = {"gpu": {"source": "nvidia"}, "storage": [{"name": "SSD", "temp": 0}]}
+    status = sensor_status(hw_data, admin=False)
+    assert status == {"lhm_available": True, "gpu_available": True, "smart_available": False, "admin": False}
+    assert sensor_status_hints(status) == ["Sender not admin: sensors limited", "No SMART data"]
+    
+    hw_data["storage"][0]["temp"] = 38.0
+    assert sensor_status_hints(sensor_status(hw_data, admin=True)) == []
+    assert sensor_status_hints(None) == []  # Sender antigo
+
+
+def test_sensor_status_roundtrip():
+    """A seção sensor_status sobrevive ao protocolo (raw e gzip)."""
+    status = sensor_status({"gpu": {"source": "intel"}, "storage": []}, admin=True)
+    for compress in (False, True):
+        decoded = decode_payload(encode_payload({"sensor_status": status}, compress=compress))
+        assert decoded["sensor_status"] == status
+
+
+def test_gpu_codec_utilization_roundtrip():
+    """Campos do NVML sobrevivem ao encode/decode e 0 (ocioso ou sem NVML) continua 0."""
+    payload = {"gpu": {"load": 64.0, "enc_util": 41.0, "dec_util": 0,
+                       "mem_util": 18.0, "pcie_tx_kbps": 1840.0, "pcie_rx_kbps": 96512.0}}
+    assert decode_payload(encode_payload(payload)) == payload
+    assert mark_absent({"gpu": {"enc_util": 0, "dec_util": 0}})["gpu"] == {"enc_util": 0, "dec_util": 0}
+    nvml_zeros = {"mem_util": 0, "pcie_tx_kbps": 0, "pcie_rx_kbps": 0}
+    assert mark_absent({"gpu": dict(nvml_zeros)})["gpu"] == nvml_zeros
+
+
+def test_format_payload_sample():
+    """Payload de exemplo: seções em blocos, listas indexadas, ausentes marcados."""
+    payload = mark_absent({
+        "hostname": "GAMING-PC",
+        "cpu": {"usage": 12.5, "temp": 0, "throttling": False},
+        "gpu": {"source": "", "fan_unit": "RPM"},
+        "storage": [{"name": "C:", "temp": 41.0}, {"name": "D:", "temp": 0}],
+        "fans": [],
+        "battery": {},
+    })
+    assert format_payload(payload).splitlines() == [
+        "hostname: GAMING-PC",
+        "cpu:",
+        "  usage: 12.5",
+        f"  temp: {UNAVAILABLE}",
+        "  throttling: não",
+        "gpu:",
+        f"  source: {UNAVAILABLE}",
+        "  fan_unit: RPM",
+        "storage[0]:",
+        "  name: C:",
+        "  temp: 41",
+        "storage[1]:",
+        "  name: D:",
+        f"  temp: {UNAVAILABLE}",
+        f"fans: {UNAVAILABLE}",
+        f"battery: {UNAVAILABLE}",
+    ]
+
+
+def test_format_payload_scalar_lists():
+    """Listas de números viram uma linha; booleanos verdadeiros não são ausência."""
+    text = format_payload({"cpu": {"cores": [10.0, 0, 55.5], "throttling": True}})
+    assert text == f"cpu:\n  cores: 10, {UNAVAILABLE}, 55.5\n  throttling: sim"
+
+
+def _diff_sample():
+    """Payload com seções, discos e fans para os testes de significant_diff"""
+    return {
+        "timestamp": 1700000000.0, "uptime_secs": 3600, "hostname": "PC",
+        "cpu": {"usage": 23.4, "temp": 61.0, "throttling": False},
+        "gpu": {"load": 97.0, "temp": None, "source": "nvidia"},
+        "storage": [{"name": "C:", "temp": 38.0, "used_percent": 71.2}],
+        "fans": [{"name": "CPU Fan", "rpm": 1200}, {"name": "Sys Fan", "rpm": 800}],
+    }
+
+
+def test_significant_diff_ignores_jitter():
+    """Variações abaixo do epsilon e o relógio do Sender não contam como mudança."""
+    previous = _diff_sample()
+    current = _diff_sample()
+    current["timestamp"] += 0.5
+    current["uptime_secs"] += 1
+    current["cpu"]["usage"] = 23.7
+    current["cpu"]["temp"] = 60.8
+    current["storage"][0]["used_percent"] = 71.25
+    current["fans"][1]["rpm"] = 800.4
+    assert not significant_diff(current, previous, epsilon=0.5)
+    assert not significant_diff(_diff_sample(), _diff_sample(), epsilon=0)
+
+
+def test_significant_diff_detects_changes():
+    """Mudança real em qualquer campo, inclusive dentro de storage/fans, é detectada."""
+    previous = _diff_sample()
+    changes = [
+        lambda p: p["cpu"].update(usage=40.0),
+        lambda p: p["cpu"].update(throttling=True),
+        lambda p: p["gpu"].update(temp=70.0),
+        lambda p: p["gpu"].update(source="intel"),
+        lambda p: p["storage"][0].update(temp=45.0),
+        lambda p: p["storage"].append({"name": "D:", "temp": 30.0, "used_percent": 10.0}),
+        lambda p: p["fans"][0].update(rpm=1500),
+        lambda p: p["fans"].pop(),
+        lambda p: p.update(battery={"percent": 80}),
+    ]
+    for change in changes:
+        current = _diff_sample()
+        change(current)
+        assert significant_diff(current, previous, epsilon=0.5)
+    # Sem pacote anterior, tudo é novo
+    assert significant_diff(previous, None)
+
+
+def test_disk_capacity_sums_partitions():
+    """Total e livre somam as partições; usado = total - livre."""
+    gib = 1024**3
+    usages = [DiskUsage(400 * gib, 300 * gib, 100 * gib, 75.0), DiskUsage(100 * gib, 80 * gib, 20 * gib, 80.0)]
+    capacity = disk_capacity(usages)
+    assert capacity == {"total_gb": 500.0, "free_gb": 120.0, "used_gb": 380.0}
+    assert capacity["used_gb"] == capacity["total_gb"] - capacity["free_gb"]
+    assert disk_capacity([]) == {"total_gb": 0.0, "free_gb": 0.0, "used_gb": 0.0}
+
+
+def test_storage_capacity_roundtrip():
+    """Capacidade do disco sobrevive ao encode/decode junto com o percentual."""
+    disk = {"name": "Samsung SSD 980", "used_space": 76.0, "total_gb": 465.8, "free_gb": 111.3, "used_gb": 354.5}
+    decoded = decode_payload(encode_payload({"storage": [disk]}))
+    assert decoded == {"storage": [disk]}
+    assert round(decoded["storage"][0]["total_gb"] - decoded["storage"][0]["free_gb"], 1) == decoded["storage"][0]["used_gb"]
+
+
+class FakePsutil:
+    """Só o que system_from_psutil usa"""
+    def __init__(self):
+        self.load_calls = 0
+    
+    def getloadavg(self):
+        self.load_calls += 1
+        return (1.234, 0.5, 0.25)
+    
+    def pids(self):
+        return list(range(312))
+
+
+def test_system_default_is_zeroed():
+    """Sem leitura a seção system vai zerada."""
+    assert default_system() == {"load_avg": [0.0, 0.0, 0.0], "process_count": 0}
+
+
+def test_system_windows_keeps_load_zero():
+    """No Windows o load average fica zerado sem chamar getloadavg."""
+    fake = FakePsutil()
+    system = system_from_psutil(fake, "win32")
+    assert system["load_avg"] == [0.0, 0.0, 0.0]
+    assert system["process_count"] == 312
+    assert fake.load_calls == 0
+
+
+def test_system_from_psutil_linux():
+    """Fora do Windows lê o load average (arredondado) e conta os processos."""
+    system = system_from_psutil(FakePsutil(), "linux")
+    assert system == {"load_avg": [1.23, 0.5, 0.25], "process_count": 312}
+    
+    # psutil sem getloadavg/pids: fica zerado, sem erro
+    assert system_from_psutil(object(), "linux") == default_system()
+
+
+def test_system_roundtrip():
+    """A seção system sobrevive ao encode/decode com e sem gzip."""
+    payload = {"cpu": {"usage": 10}, "system": system_from_psutil(FakePsutil(), "linux")}
+    for compress in (True, False):
+        assert decode_payload(encode_payload(payload, compress=compress)) == payload
+
+
+if __name__ == "__main__":
+    for name, func in list(globals().items()):
+        if name.startswith("test_"):
+            func()
+            print(f"✓ {name}")
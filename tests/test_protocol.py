@@ -0,0 +1,335 @@
+"""
+Testes do protocolo de comunicação (core/protocol.py).
+Não depende de hardware nem de rede.
+"""
+import sys
+import os
+import json
+import gzip
+
+# Adicionar diretório pai ao path para imports
+sys.path.insert(0, os.path.dirname(os.path.dirname(os.path.abspath(__file__))))
+
+from core.protocol import get_payload_stats, encode_payload, decode_payload, decode_any, fit_payload, AuthFailedError, FrameTooLargeError, FrameFlag, AUTH_TAG_SIZE
+from core.protocol import serialize_payload, MagicByte, DecodeLimitError, MAX_DECODED_SIZE, MAX_LIST_ITEMS, MAX_UDP_PAYLOAD
+from core.protocol import VersionMismatchError, PROTOCOL_VERSION, encode_goodbye, decode_goodbye, unwrap_frame, DeserializeError
+from core.protocol import DecodeStats, ProtocolError, extract_body, parse_body
+from core.protocol import check_datagram, TruncatedFrameError, RECV_BUFFER_SIZE, HEADER_SIZE
+from core.protocol import open_frame, NONCE_SIZE, CIPHER_TAG_SIZE
+
+SAMPLE = {
+    "cpu": {"usage": 12.5, "temp": 55.0},
+    "gpu": {"load": 3.0, "temp": 41.0},
+    "storage": [{"name": "Samsung SSD 980 PRO 1TB", "temp": 38}]
+}
+
+
+def test_payload_stats_reduction_is_real():
+    """A redução mostrada vem dos frames que iriam para a rede, não de uma estimativa."""
+    bigger = {**SAMPLE, "processes": [{"name": "x" * 40, "pid": i} for i in range(10)]}
+    for payload in (SAMPLE, bigger):
+        stats = get_payload_stats(payload)
+        raw = len(bytes([MagicByte.RAW]) + serialize_payload(payload))
+        compressed = len(encode_payload(payload, compress=True))
+        assert stats["raw_size"] == raw
+        assert stats["compressed_size"] == compressed
+        assert stats["compression_ratio"] == round((1 - compressed / raw) * 100, 1)
+    
+    # Payload repetitivo comprime bem; a redução acompanha
+    assert get_payload_stats(bigger)["compression_ratio"] > get_payload_stats(SAMPLE)["compression_ratio"]
+
+
+def test_encrypted_roundtrip():
+    """Frame cifrado volta ao payload original com a mesma chave."""
+    for compress in (True, False):
+        frame = encode_payload(SAMPLE, compress=compress, key="segredo")
+        assert frame[0] & FrameFlag.ENCRYPTED
+        assert b"Samsung" not in frame
+        assert decode_payload(frame, key="segredo") == SAMPLE
+
+
+def test_tampered_ciphertext_is_rejected():
+    """Um bit alterado (ou chave errada) gera AuthFailedError, não lixo."""
+    frame = bytearray(encode_payload(SAMPLE, key="segredo"))
+    frame[20] ^= 0x01
+    for data, key in ((bytes(frame), "segredo"), (encode_payload(SAMPLE, key="segredo"), "outra")):
+        try:
+            decode_payload(data, key=key)
+            assert False, "frame adulterado aceito"
+        except AuthFailedError:
+            pass
+
+
+def test_short_encrypted_frame_is_rejected():
+    """Datagrama curto com a flag ENCRYPTED vira AuthFailedError, não ValueError."""
+    short = bytes([MagicByte.GZIP | FrameFlag.ENCRYPTED]) + b"\x00" * 4
+    assert len(short) == 5
+    limit = bytes([MagicByte.GZIP | FrameFlag.ENCRYPTED]) + b"\x00" * (NONCE_SIZE + CIPHER_TAG_SIZE - 1)
+    for frame in (short, limit):
+        for decode in (lambda: open_frame(frame, "segredo"), lambda: unwrap_frame(frame, "segredo")):
+            try:
+                decode()
+                assert False, "frame curto aceito"
+            except AuthFailedError:
+                pass
+
+
+def test_plaintext_rejected_when_key_configured():
+    """Com chave configurada, frames em claro são recusados; sem chave, seguem funcionando."""
+    plain = encode_payload(SAMPLE)
+    assert decode_payload(plain) == SAMPLE
+    try:
+        decode_payload(plain, key="segredo")
+        assert False, "frame em claro aceito"
+    except AuthFailedError:
+        pass
+
+
+def test_valid_auth_tag_accepted():
+    """Frame assinado com o mesmo segredo é aceito (com e sem criptografia)."""
+    frame = encode_payload(SAMPLE, auth_secret="meu-pc")
+    assert frame[0] & FrameFlag.AUTH_TAG
+    assert len(frame) == len(encode_payload(SAMPLE)) + AUTH_TAG_SIZE
+    assert decode_payload(frame, auth_secret="meu-pc") == SAMPLE
+    # Receiver sem segredo apenas ignora a tag
+    assert decode_payload(frame) == SAMPLE
+
+    both = encode_payload(SAMPLE, key="segredo", auth_secret="meu-pc")
+    assert decode_payload(both, key="segredo", auth_secret="meu-pc") == SAMPLE
+
+
+def test_wrong_key_auth_tag_rejected():
+    """Tag de outro segredo, ou frame sem tag, é recusado antes de desserializar."""
+    for data in (encode_payload(SAMPLE, auth_secret="intruso"), encode_payload(SAMPLE)):
+        try:
+            decode_payload(data, auth_secret="meu-pc")
+            assert False, "frame não autenticado aceito"
+        except AuthFailedError:
+            pass
+
+
+def test_oversized_payload_drops_bulky_sections():
+    """Frame acima do limite perde processos/interfaces, na ordem, sem alterar o original."""
+    processes = [{"name": os.urandom(12).hex(), "pid": i, "cpu_percent": i, "mem_mb": i} for i in range(300)]
+    payload = {
+        "cpu": {"usage": 12.5},
+        "network": {"down_kbps": 1.0, "interfaces": [["eth0", 1.0, 2.0]]},
+        "processes": processes,
+    }
+    encode = lambda data: encode_payload(data, compress=False)
+    assert len(encode(payload)) > 4096
+    
+    frame, dropped = fit_payload(payload, encode, limit=4096)
+    assert dropped == ["processes"]
+    assert len(frame) <= 4096
+    decoded = decode_payload(frame)
+    assert "processes" not in decoded
+    assert decoded["network"]["interfaces"] == [["eth0", 1.0, 2.0]]
+    assert payload["processes"] is processes  # Original intacto
+    
+    # Cabe de primeira: nada é descartado
+    frame, dropped = fit_payload({"cpu": {"usage": 1}}, encode)
+    assert dropped == []
+
+
+def test_payload_too_large_even_without_bulky_sections():
+    """Sem seções opcionais para cortar, o erro é explícito."""
+    payload = {"storage": [{"name": os.urandom(16).hex()} for _ in range(400)]}
+    try:
+        fit_payload(payload, lambda data: encode_payload(data, compress=False), limit=1024)
+        assert False, "deveria rejeitar o frame"
+    except FrameTooLargeError:
+        pass
+
+
+def test_wire_format_is_pinned():
+    """Formato fixo: qualquer mudança no JSON/gzip altera o tamanho conhecido."""
+    payload = {
+        "hostname": "PC-Sala",
+        "uptime_secs": 3600,
+        "cpu": {"usage": 12.5, "temp": None},
+        "storage": [{"name": "SSD Çñ", "temp": 41.0}],
+        "fans": []
+    }
+    raw = encode_payload(payload, compress=False)
+    assert len(raw) == 137
+    assert raw[0] == MagicByte.RAW
+    assert raw[1:] == serialize_payload(payload)
+    assert b" " not in raw.replace(b"SSD ", b"")  # Sem espaços do json.dumps padrão
+    assert raw.isascii()
+    
+    # gzip sem horário no cabeçalho: mesmo payload, mesmos bytes
+    frame = encode_payload(payload)
+    assert frame[5:9] == b"\x00\x00\x00\x00"  # MTIME do cabeçalho gzip (após magic + 1f 8b 08 flags)
+    assert frame == encode_payload(payload)
+    assert decode_payload(frame) == payload
+
+
+def test_gzip_bomb_is_rejected():
+    """Frame pequeno que descomprime para megabytes é recusado, não alocado."""
+    body = b'{"pad":"' + b" " * (10 * 1024 * 1024) + b'"}'
+    frame = bytes([MagicByte.GZIP]) + gzip.compress(body, mtime=0)
+    assert len(frame) < MAX_UDP_PAYLOAD
+    try:
+        extract_body(frame)
+        assert False, "esperava DecodeLimitError"
+    except DecodeLimitError as e:
+        assert str(MAX_DECODED_SIZE) in str(e)
+    assert decode_payload(frame) is None  # Contrato de decode_payload: None
+
+
+def test_oversized_list_is_rejected():
+    """Lista de discos com milhares de itens (forjada) vira erro de desserialização."""
+    frame = encode_payload({"storage": [{}] * 10000})
+    assert len(frame) < MAX_UDP_PAYLOAD
+    try:
+        parse_body(extract_body(frame))
+        assert False, "esperava DecodeLimitError"
+    except DecodeLimitError as e:
+        assert "storage" in str(e)
+    assert decode_payload(frame) is None
+    # No limite ainda passa
+    limit = MAX_LIST_ITEMS["fans"]
+    assert len(decode_payload(encode_payload({"fans": [{}] * limit}))["fans"]) == limit
+
+
+def test_corrupt_frames_still_return_none():
+    """gzip/JSON corrompidos continuam devolvendo None (não são limite)."""
+    assert decode_payload(bytes([MagicByte.GZIP]) + b"not gzip at all") is None
+    assert decode_payload(bytes([MagicByte.RAW]) + b"{not json") is None
+    assert decode_payload(bytes([MagicByte.RAW]) + b"[1, 2]") is None
+
+
+def test_legacy_frames_only_with_decode_any():
+    """Frames sem magic byte (Sender antigo): decode_payload recusa, decode_any aceita."""
+    body = json.dumps(SAMPLE).encode()
+    for frame in (body, gzip.compress(body)):
+        assert decode_payload(frame) is None
+        assert decode_any(frame) == SAMPLE
+    # Frames atuais passam pelos dois caminhos
+    assert decode_any(encode_payload(SAMPLE)) == SAMPLE
+    assert decode_any(encode_payload(SAMPLE, compress=False)) == SAMPLE
+    # Byte inicial desconhecido continua inválido
+    assert decode_any(b"\x07" + body) is None
+
+
+def test_legacy_frame_rejected_when_auth_required():
+    """Frame legado não tem tag: com chave ou segredo configurado é recusado."""
+    legacy = gzip.compress(json.dumps(SAMPLE).encode())
+    for kwargs in ({"key": "segredo"}, {"auth_secret": "meu-pc"}):
+        try:
+            decode_any(legacy, **kwargs)
+            assert False, "frame legado aceito com autenticação configurada"
+        except AuthFailedError:
+            pass
+
+
+def test_version_mismatch_is_distinct_from_corruption():
+    """Versão desconhecida levanta VersionMismatchError (com as duas versões); frame corrompido só dá None."""
+    frame = encode_payload({"protocol_version": PROTOCOL_VERSION + 1, "cpu": {"usage": 5}})
+    try:
+        decode_payload(frame)
+        assert False, "versão incompatível foi aceita"
+    except VersionMismatchError as e:
+        assert (e.sender_version, e.receiver_version) == (PROTOCOL_VERSION + 1, PROTOCOL_VERSION)
+        assert f"v{PROTOCOL_VERSION + 1}" in str(e)
+    assert decode_payload(b"\x01" + b"lixo" * 10) is None
+
+
+def test_current_and_unversioned_payloads_accepted():
+    """Versão atual passa; payload sem o campo (sender antigo) conta como versão 1."""
+    payload = {"protocol_version": PROTOCOL_VERSION, "cpu": {"usage": 5}}
+    assert decode_payload(encode_payload(payload)) == payload
+    assert decode_payload(encode_payload({"cpu": {"usage": 5}})) == {"cpu": {"usage": 5}}
+    for bad in ("1", 0, True):
+        try:
+            decode_payload(encode_payload({"protocol_version": bad}))
+            assert False, f"versão {bad!r} aceita"
+        except VersionMismatchError:
+            pass
+
+
+def test_goodbye_frame_roundtrip():
+    """Despedida tem magic byte próprio, passa pela cifra/tag e não é confundida com payload."""
+    frame = encode_goodbye("PC-Sala")
+    assert frame[0] == MagicByte.GOODBYE
+    assert decode_goodbye(frame) == {"hostname": "PC-Sala"}
+    assert decode_goodbye(encode_payload({"hostname": "PC-Sala"})) is None
+    assert decode_payload(frame) is None  # Receiver antigo: só um frame desconhecido
+    
+    signed = encode_goodbye("PC-Sala", auth_secret="segredo")
+    assert decode_goodbye(unwrap_frame(signed, auth_secret="segredo")) == {"hostname": "PC-Sala"}
+    try:
+        unwrap_frame(signed, auth_secret="outro")
+        assert False, "despedida forjada aceita"
+    except AuthFailedError:
+        pass
+
+
+def test_goodbye_with_corrupt_body():
+    """Corpo inválido levanta DeserializeError em vez de desconectar o host."""
+    try:
+        decode_goodbye(bytes([MagicByte.GOODBYE]) + b"{quebrado")
+        assert False, "corpo inválido aceito"
+    except DeserializeError:
+        pass
+
+
+def test_decode_stats_mixed_frames():
+    """Frames válidos e inválidos misturados: contadores e o último erro batem com a sequência."""
+    frames = [
+        encode_payload(SAMPLE),
+        encode_payload(SAMPLE, auth_secret="outro"),
+        encode_payload(SAMPLE),
+        b"\x01" + b"lixo" * 10,
+        encode_payload(SAMPLE),
+    ]
+    stats = DecodeStats()
+    assert stats.summary() == "OK: 0 | Recusados: 0"
+    for i, frame in enumerate(frames):
+        try:
+            parse_body(extract_body(unwrap_frame(frame, auth_secret="segredo" if i == 1 else "")))
+            stats.record_ok()
+        except ProtocolError as e:
+            stats.record_error(e, now=100.0 + i)
+    
+    assert (stats.decoded, stats.rejected) == (3, 2)
+    assert stats.last_error == "DeserializeError"
+    assert stats.last_error_at == 103.0
+    assert stats.summary() == "OK: 3 | Recusados: 2 (último: DeserializeError)"
+
+
+def test_zero_byte_datagram():
+    """Datagrama vazio: passa pelo check_datagram e decodifica como None."""
+    assert check_datagram(b"") == b""
+    assert decode_payload(b"") is None
+    try:
+        extract_body(b"")
+        assert False, "Frame vazio deveria falhar"
+    except DeserializeError:
+        pass
+
+
+def test_truncated_datagram_detected():
+    """Datagrama que enche o buffer é recusado antes de virar erro de gzip/JSON."""
+    assert RECV_BUFFER_SIZE > MAX_UDP_PAYLOAD + HEADER_SIZE
+    frame = encode_payload(SAMPLE, compress=True)
+    assert check_datagram(frame) == frame
+    
+    # Leitura com buffer menor que o frame: o SO entrega os primeiros bytes
+    bufsize = len(frame) - 1
+    try:
+        check_datagram(frame[:bufsize], bufsize)
+        assert False, "Datagrama truncado deveria falhar"
+    except TruncatedFrameError as e:
+        assert isinstance(e, ProtocolError)
+    
+    # Sem a checagem, o mesmo frame cortado só daria um erro de gzip
+    assert decode_payload(frame[:bufsize]) is None
+
+
+if __name__ == "__main__":
+    for name, func in list(globals().items()):
+        if name.startswith("test_"):
+            func()
+            print(f"✓ {name}")
@@ -0,0 +1,61 @@
+"""
+Testes do cálculo de taxas de I/O por disco (core/rates.py).
+Não depende de hardware: usa snapshots sintéticos dos contadores.
+"""
+import sys
+import os
+from collections import namedtuple
+
+# Adicionar diretório pai ao path para imports
+sys.path.insert(0, os.path.dirname(os.path.dirname(os.path.abspath(__file__))))
+
+from core.rates import compute_rate, compute_disk_rates, TrafficMeter
+
+DiskIO = namedtuple("DiskIO", "read_bytes write_bytes read_time write_time")
+
+
+def test_first_cycle_reports_zero():
+    """Sem snapshot anterior, a taxa deve ser zero (e não o contador inteiro)."""
+    curr = {"PhysicalDrive0": DiskIO(50 * 1024**3, 20 * 1024**3, 900000, 400000)}
+    rates = compute_disk_rates(None, curr, 1.0)
+    assert rates["PhysicalDrive0"] == {"read_rate": 0.0, "write_rate": 0.0, "total_activity": 0.0}
+
+
+def test_delta_between_snapshots():
+    """Taxa = diferença dos contadores dividida pelo intervalo."""
+    prev = {"PhysicalDrive0": DiskIO(1000, 2000, 100, 100)}
+    curr = {"PhysicalDrive0": DiskIO(1000 + 4096, 2000 + 1024, 300, 200)}
+    rates = compute_disk_rates(prev, curr, 2.0)["PhysicalDrive0"]
+    assert rates["read_rate"] == 2048.0
+    assert rates["write_rate"] == 512.0
+    # 300 ms ocupados em 2 s -> 15%
+    assert rates["total_activity"] == 15.0
+
+
+def test_counter_reset_is_ignored():
+    """Contador que voltou (reset) não gera taxa negativa."""
+    assert compute_rate(5000, 100, 1.0) == 0.0
+    assert compute_rate(100, 200, 0) == 0.0
+
+
+def test_traffic_meter_window():
+    """Média, pacotes/s e banda consideram só a janela deslizante."""
+    meter = TrafficMeter(window_seconds=2)
+    meter.record(1000, now=0.0)
+    meter.record(500, now=1.0)
+    meter.record(500, now=2.0)
+    assert meter.packets_per_sec == 1.5
+    assert round(meter.avg_size, 1) == 666.7
+    assert meter.kbit_per_sec == 8.0  # 2000 B * 8 / 1000 / 2 s
+
+    meter.record(250, now=3.5)  # descarta as amostras de 0.0 e 1.0
+    assert meter.avg_size == 375
+    assert meter.last_size == 250
+    assert meter.total_packets == 4
+
+
+if __name__ == "__main__":
+    for name, func in list(globals().items()):
+        if name.startswith("test_"):
+            func()
+            print(f"✓ {name}")
@@ -0,0 +1,106 @@
+"""
+Testes dos recordes de todos os tempos (core/records.py).
+Atualização a partir de uma sequência de leituras e gravação espaçada em arquivo temporário.
+"""
+import sys
+import os
+import io
+import json
+import tempfile
+from contextlib import redirect_stdout
+
+# Adicionar diretório pai ao path para imports
+sys.path.insert(0, os.path.dirname(os.path.dirname(os.path.abspath(__file__))))
+
+from core.records import record_values, update_records, load_records, RecordsStore
+
+
+def test_records_from_sequence():
+    """Máxima guarda o maior valor e quando ocorreu; mínima só onde faz sentido."""
+    records = {}
+    sequence = [(100.0, 55.0), (101.0, 72.5), (102.0, 61.0), (103.0, 38.0), (104.0, 72.5)]
+    changed = [update_records(records, {"gpu.temp": temp, "gpu.power_w": temp * 3}, now) for now, temp in sequence]
+    assert records["gpu.temp"] == {"max": 72.5, "max_at": 101.0, "min": 38.0, "min_at": 103.0}
+    # Potência: só máxima
+    assert records["gpu.power_w"] == {"max": 217.5, "max_at": 101.0}
+    # Empate não é recorde novo
+    assert changed[4] == []
+    assert changed[2] == []
+    assert changed[3] == ["gpu.temp"]
+
+
+def test_record_values_skip_missing():
+    """Sensor ausente (0/None) e ping inalcançável (-1) não viram recorde de mínima."""
+    payload = {
+        "cpu": {"temp": 0, "power": 65.2},
+        "gpu": {"temp": None},
+        "network": {"ping_ms": -1, "down_kbps": 830.0},
+        "mobo": {"temp": True},
+    }
+    assert record_values(payload) == {"cpu.power": 65.2, "network.down_kbps": 830.0}
+    assert record_values({}) == {}
+
+
+def test_store_saves_throttled():
+    """Recorde novo só vai para o arquivo depois de save_secs (ou no flush forçado)."""
+    now = [1000.0]
+    with tempfile.TemporaryDirectory() as tmp:
+        path = os.path.join(tmp, "records.json")
+        store = RecordsStore(path, save_secs=60, clock=lambda: now[0])
+        assert store.update("gamer-pc", {"cpu": {"temp": 81.0}}) == ["cpu.temp"]
+        assert not store.flush()
+        assert not os.path.exists(path)
+        now[0] = 1061.0
+        assert store.flush()
+        assert load_records(path)["gamer-pc"]["cpu.temp"]["max"] == 81.0
+        # Sem recorde novo: não regrava nem com force
+        now[0] = 2000.0
+        store.update("gamer-pc", {"cpu": {"temp": 81.0}})
+        assert not store.flush(force=True)
+        # Recorde novo + saída do app
+        store.update("gamer-pc", {"cpu": {"temp": 88.0}})
+        assert store.flush(force=True)
+        # Próxima execução continua dos recordes salvos
+        reopened = RecordsStore(path, clock=lambda: now[0])
+        assert reopened.host_records("gamer-pc")["cpu.temp"]["max"] == 88.0
+        assert reopened.update("gamer-pc", {"cpu": {"temp": 85.0}}) == []  # Entre a mínima (81) e a máxima (88)
+
+
+def test_load_records_corrupted():
+    """Arquivo corrompido começa do zero em vez de derrubar o receiver."""
+    with tempfile.TemporaryDirectory() as tmp:
+        path = os.path.join(tmp, "records.json")
+        with open(path, "w", encoding="utf-8") as f:
+            f.write("{nope")
+        assert load_records(path) == {}
+        assert load_records(os.path.join(tmp, "missing.json")) == {}
+
+
+def test_load_records_drops_malformed_entries():
+    """Entradas com formato errado saem com aviso; as válidas do mesmo arquivo ficam."""
+    good = {"max": 81.0, "max_at": 100.0, "min": 38.0, "min_at": 90.0}
+    data = {
+        "gamer-pc": {"cpu.temp": good, "gpu.temp": 72.5, "ram.percent": {"max_at": 1.0}, "mobo.temp": {"max": "quente"}},
+        "notebook": ["não", "é", "dict"],
+    }
+    with tempfile.TemporaryDirectory() as tmp:
+        path = os.path.join(tmp, "records.json")
+        with open(path, "w", encoding="utf-8") as f:
+            json.dump(data, f)
+        out = io.StringIO()
+        with redirect_stdout(out):
+            records = load_records(path)
+        assert records == {"gamer-pc": {"cpu.temp": good}}
+        assert "gpu.temp" in out.getvalue() and "notebook" in out.getvalue()
+
+        with open(path, "w", encoding="utf-8") as f:
+            json.dump([1, 2], f)
+        with redirect_stdout(io.StringIO()):
+            assert load_records(path) == {}
+
+
+if __name__ == "__main__":
+    for name, func in list(globals().items()):
+        if name.startswith("test_"):
+            func()
+            print(f"✓ {name}")
@@ -0,0 +1,35 @@
+"""
+Testes do ritmo de redesenho do dashboard (ui/redraw.py).
+Não abre a GUI.
+"""
+import sys
+import os
+
+# Adicionar diretório pai ao path para imports
+sys.path.insert(0, os.path.dirname(os.path.dirname(os.path.abspath(__file__))))
+
+from ui.redraw import render_interval, MIN_FPS, MAX_FPS
+
+
+def test_render_interval_active_and_idle():
+    """Atividade recente redesenha a max_fps; parado há idle_after segundos, cai para idle_fps."""
+    assert render_interval(2, 0.5, 10, last_activity=100.0, now=105.0) == 0.5
+    assert render_interval(2, 0.5, 10, last_activity=100.0, now=110.0) == 2.0
+    # Receiver recém-aberto, sem nenhum pacote: já começa ocioso
+    assert render_interval(30, 0.5, 10, last_activity=0, now=1000.0) == 2.0
+    # idle_fps 0 desliga o modo ocioso
+    assert render_interval(2, 0, 10, last_activity=0, now=1000.0) == 0.5
+
+
+def test_render_interval_limits():
+    """max_fps fora dos limites é ajustado; ocioso mais rápido que o ativo não acelera."""
+    assert render_interval(1000, 0, 10, 0, 0) == 1.0 / MAX_FPS
+    assert render_interval(0, 0, 10, 0, 0) == 1.0 / MIN_FPS
+    assert render_interval(1, 5, 10, last_activity=0, now=100.0) == 1.0
+
+
+if __name__ == "__main__":
+    for name, func in list(globals().items()):
+        if name.startswith("test_"):
+            func()
+            print(f"✓ {name}")
@@ -0,0 +1,57 @@
+"""
+Testes das faixas plausíveis dos sensores (core/sanity.py).
+Os limites são os mesmos para todos os coletores (CPU, GPU, placa-mãe, discos).
+"""
+import sys
+import os
+
+# Adicionar diretório pai ao path para imports
+sys.path.insert(0, os.path.dirname(os.path.dirname(os.path.abspath(__file__))))
+
+from core.sanity import (
+    plausible_temp, plausible_rpm, spinning_rpm, plausible_voltage, plausible_percent,
+    MAX_TEMP, MIN_FAN_RPM, MAX_FAN_RPM, MAX_CORE_VOLTAGE
+)
+from hardware_monitor import parse_storage_temps, parse_gpu_sensors
+
+
+def test_temp_bounds():
+    """0 é sensor ausente; MAX_TEMP ou mais (255 de canal solto) é lixo."""
+    assert plausible_temp(0.5)
+    assert plausible_temp(MAX_TEMP - 0.1)
+    for bad in (0, -5.0, MAX_TEMP, 255.0):
+        assert not plausible_temp(bad), bad
+
+
+def test_rpm_bounds():
+    """0 é fan parado; negativo e 65535 de canal desconectado não são leitura."""
+    assert plausible_rpm(0) and plausible_rpm(MIN_FAN_RPM - 1) and plausible_rpm(MAX_FAN_RPM)
+    for bad in (-1, MAX_FAN_RPM + 1, 65535):
+        assert not plausible_rpm(bad), bad
+    # Lista de fans: parado/espúrio abaixo de MIN_FAN_RPM fica de fora
+    assert spinning_rpm(MIN_FAN_RPM) and spinning_rpm(MAX_FAN_RPM)
+    for bad in (0, MIN_FAN_RPM - 1, MAX_FAN_RPM + 1):
+        assert not spinning_rpm(bad), bad
+
+
+def test_voltage_and_percent_bounds():
+    """Voltagem de núcleo abaixo de 2V; percentual acima de 0 até 100."""
+    assert plausible_voltage(1.25)
+    assert not plausible_voltage(0) and not plausible_voltage(MAX_CORE_VOLTAGE) and not plausible_voltage(12.1)
+    assert plausible_percent(100.0) and plausible_percent(0.1)
+    assert not plausible_percent(0) and not plausible_percent(100.5)
+
+
+def test_collectors_share_the_bounds():
+    """Disco e GPU descartam os mesmos valores absurdos (antes só a placa-mãe tinha teto)."""
+    assert parse_storage_temps([("Temperature", 255.0), ("Temperature #1", 41.0)]) == (41.0, 41.0, [])
+    gpu = parse_gpu_sensors("nvidia", [("Temperature", "GPU Core", 255.0), ("Fan", "GPU Fan 1", 65535.0)])
+    assert gpu["temp"] == 0
+    assert (gpu["fan"], gpu["fan_unit"]) == (0, "RPM")
+
+
+if __name__ == "__main__":
+    for name, func in list(globals().items()):
+        if name.startswith("test_"):
+            func()
+            print(f"✓ {name}")
@@ -0,0 +1,182 @@
+"""
+Testes da atualização lenta e do ritmo de envio (core/scheduling.py) e do cache de discos do hardware_monitor.
+Usa relógio injetado e um Computer falso; não requer a DLL nem admin.
+"""
+import sys
+import os
+
+# Adicionar diretório pai ao path para imports
+sys.path.insert(0, os.path.dirname(os.path.dirname(os.path.abspath(__file__))))
+
+from core.scheduling import SlowRefresh, PeriodicCache, FixedTicker, next_deadline
+from hardware_monitor import HardwareMonitor, STORAGE_FAST_FIELDS
+
+
+class FakeClock:
+    def __init__(self):
+        self.now = 1000.0
+    
+    def __call__(self):
+        return self.now
+
+
+class FakeSensor:
+    def __init__(self, sensor_type, name, value):
+        self.SensorType = f"SensorType.{sensor_type}"
+        self.Name = name
+        self.Value = value
+
+
+class FakeHardware:
+    def __init__(self, hw_type, name, sensors, identifier="/hw/0"):
+        self.HardwareType = f"HardwareType.{hw_type}"
+        self.Name = name
+        self.Identifier = identifier
+        self.Sensors = sensors
+        self.SubHardware = []
+        self.updates = 0
+    
+    def Update(self):
+        self.updates += 1
+
+
+class FakeComputer:
+    def __init__(self, hardware):
+        self.Hardware = hardware
+
+
+def _fake_monitor():
+    cpu = FakeHardware("Cpu", "CPU", [FakeSensor("Load", "CPU Total", 20.0)])
+    disk = FakeHardware("Storage", "NVMe", [
+        FakeSensor("Temperature", "Composite Temperature", 41.0),
+        FakeSensor("Throughput", "Read Rate", 5000.0),
+    ], identifier="/nvme/1")
+    monitor = HardwareMonitor()
+    monitor.computer = FakeComputer([cpu, disk])
+    monitor.enabled = True
+    return monitor, cpu, disk
+
+
+def test_slow_refresh_interval():
+    """Libera na primeira chamada e depois só quando o intervalo vence."""
+    clock = FakeClock()
+    gate = SlowRefresh(10.0, clock=clock)
+    assert gate.due() is True
+    clock.now += 5
+    assert gate.due() is False
+    clock.now += 4.9
+    assert gate.due() is False
+    clock.now += 0.1
+    assert gate.due() is True
+    assert gate.due() is False
+    
+    gate.force()
+    assert gate.due() is True
+
+
+def test_slow_subsystems_refresh_only_after_interval():
+    """Discos só são atualizados quando o intervalo lento vence; CPU a cada ciclo."""
+    clock = FakeClock()
+    gate = SlowRefresh(10.0, clock=clock)
+    monitor, cpu, disk = _fake_monitor()
+    
+    for _ in range(4):  # Ciclos de 0.5s dentro do intervalo lento
+        data = monitor.fetch_data(refresh_storage=gate.due())
+        clock.now += 0.5
+    assert cpu.updates == 4
+    assert disk.updates == 1
+    
+    # Ciclos sem atualização reutilizam a leitura lenta, sem as taxas antigas
+    assert data["storage"][0]["temp"] == 41.0
+    assert data["storage"][0]["drive_index"] == 1
+    assert all(data["storage"][0][field] == 0 for field in STORAGE_FAST_FIELDS)
+    
+    clock.now += 10
+    data = monitor.fetch_data(refresh_storage=gate.due())
+    assert disk.updates == 2
+    assert data["storage"][0]["read_rate"] == 5000.0
+
+
+def test_periodic_cache_queries_only_after_interval():
+    """Consulta na primeira chamada e de novo só quando o intervalo vence."""
+    clock = FakeClock()
+    calls = []
+    
+    def fetch():
+        calls.append(clock.now)
+        return {"link_speed_mbps": 1000 if len(calls) == 1 else 100}
+    
+    link = PeriodicCache(fetch, 60.0, {"link_speed_mbps": 0}, clock=clock)
+    assert link.get() == {"link_speed_mbps": 1000}
+    clock.now += 59.9
+    assert link.get() == {"link_speed_mbps": 1000}  # Valor em cache
+    assert len(calls) == 1
+    clock.now += 0.1
+    assert link.get() == {"link_speed_mbps": 100}
+    assert len(calls) == 2
+    
+    # force() antecipa a próxima consulta (ex: monitor reiniciado)
+    link.force()
+    link.get()
+    assert len(calls) == 3
+
+
+def test_periodic_cache_keeps_value_on_failure():
+    """Consulta que falha mantém o último resultado até o próximo intervalo."""
+    clock = FakeClock()
+    results = [{"adapter_name": "Ethernet"}, RuntimeError("PowerShell travou")]
+    
+    def fetch():
+        result = results.pop(0)
+        if isinstance(result, Exception):
+            raise result
+        return result
+    
+    link = PeriodicCache(fetch, 10.0, {}, clock=clock)
+    assert link.get() == {"adapter_name": "Ethernet"}
+    clock.now += 10.0
+    assert link.get() == {"adapter_name": "Ethernet"}
+    assert not results
+
+
+def _run_ticker(interval, durations):
+    """Roda o FixedTicker com ciclos das durações dadas; devolve (inícios dos ciclos, pulados)."""
+    clock = FakeClock()
+    ticker = FixedTicker(interval, clock)
+    starts, skipped = [], []
+    for duration in durations:
+        starts.append(round(clock.now - 1000.0, 6))
+        clock.now += duration
+        wait, skip = ticker.advance()
+        skipped.append(skip)
+        clock.now += wait
+    return starts, skipped
+
+
+def test_fixed_ticker_no_drift():
+    """Ciclos de duração variável começam sempre no múltiplo do intervalo (intervalo fracionário)."""
+    starts, skipped = _run_ticker(0.25, [0.01, 0.2, 0.1, 0.24, 0.0])
+    assert starts == [0.0, 0.25, 0.5, 0.75, 1.0]
+    assert skipped == [0, 0, 0, 0, 0]
+
+
+def test_fixed_ticker_skips_missed_ticks():
+    """Ciclo que estoura pula os ticks perdidos e volta à grade, sem rajada."""
+    starts, skipped = _run_ticker(1.0, [0.5, 2.5, 0.1, 1.0])
+    assert starts == [0.0, 1.0, 4.0, 5.0]
+    assert skipped == [0, 2, 0, 0]
+
+
+def test_next_deadline_edges():
+    """Terminar exatamente no prazo não pula; intervalo 0 não divide por zero."""
+    assert next_deadline(10.0, 1.0, 11.0) == (11.0, 0)
+    assert next_deadline(10.0, 1.0, 10.2) == (11.0, 0)
+    assert next_deadline(10.0, 1.0, 13.5) == (14.0, 3)
+    assert next_deadline(10.0, 0.0, 12.0) == (10.0, 0)
+
+
+if __name__ == "__main__":
+    for name, func in list(globals().items()):
+        if name.startswith("test_"):
+            func()
+            print(f"✓ {name}")
@@ -0,0 +1,113 @@
+"""
+Testes do loop de envio do sender_pc.py.
+Não depende de hardware nem de rede: coletor e socket substituídos.
+"""
+import io
+import sys
+import os
+import threading
+import time
+import types
+from contextlib import redirect_stdout
+
+# Adicionar diretório pai ao path para imports
+sys.path.insert(0, os.path.dirname(os.path.dirname(os.path.abspath(__file__))))
+
+
+def import_sender():
+    """Importa sender_pc sem pedir Admin e sem deixar o sys.stdout trocado."""
+    try:
+        import psutil
+    except ImportError:
+        # Só o que o loop usa; o coletor de verdade não é criado
+        sys.modules["psutil"] = types.SimpleNamespace(cpu_percent=lambda interval=None: 0.0)
+    argv, stdout = sys.argv, sys.stdout
+    sys.argv = ["sender_pc.py", "--no-admin"]
+    try:
+        with redirect_stdout(io.StringIO()):
+            import sender_pc
+    finally:
+        sys.argv, sys.stdout = argv, stdout
+    return sender_pc
+
+
+class StubCollector:
+    """Payload fixo, sem sensores"""
+    
+    def __init__(self):
+        self.hostname = "test-pc"
+        self.sections = {}
+        self.collects = 0
+        self.closed = False
+    
+    def collect(self):
+        self.collects += 1
+        return {"cpu": {"usage": 10.0}, "gpu": {"load": 5.0}, "ram": {"percent": 40.0}}
+    
+    def close(self):
+        self.closed = True
+
+
+class StubSocket:
+    """Guarda os frames em vez de enviar"""
+    
+    def __init__(self):
+        self.frames = []
+        self.closed = False
+    
+    def sendto(self, frame, address):
+        self.frames.append(frame)
+        return len(frame)
+    
+    def close(self):
+        self.closed = True
+
+
+def make_sender(sender_pc, interval):
+    """TelemetrySender sem __init__ (que abriria o socket e os sensores)."""
+    sender = object.__new__(sender_pc.TelemetrySender)
+    sender.running = True
+    sender.paused = False
+    sender.stop_event = threading.Event()
+    sender.start_time = time.time()
+    sender.packets_sent = 0
+    sender.send_health = sender_pc.SendHealth(rebuild_after=5)
+    sender.send_summary = None
+    sender.delta = None
+    sender.interval = interval
+    sender.collector = StubCollector()
+    sender.sock = StubSocket()
+    sender.icon = None
+    return sender
+
+
+def test_sender_loop_exits_when_stopped():
+    """_quit() acorda o loop no meio da espera: a thread termina logo, com despedida e recursos fechados."""
+    sender_pc = import_sender()
+    saved = sender_pc.BIND_INTERFACE, sender_pc.CONTROL_REQUESTS
+    sender_pc.BIND_INTERFACE, sender_pc.CONTROL_REQUESTS = "", False
+    sender = make_sender(sender_pc, interval=60.0)  # Sem o stop_event, dormiria 1 minuto
+    try:
+        with redirect_stdout(io.StringIO()):
+            thread = threading.Thread(target=sender._sender_loop, daemon=True)
+            thread.start()
+            deadline = time.time() + 2.0
+            while sender.collector.collects == 0 and time.time() < deadline:
+                time.sleep(0.01)
+            sender._quit()
+            thread.join(timeout=2.0)
+    finally:
+        sender_pc.BIND_INTERFACE, sender_pc.CONTROL_REQUESTS = saved
+    
+    assert not thread.is_alive()
+    assert sender.collector.collects == 1
+    assert sender.packets_sent == 1
+    assert len(sender.sock.frames) == 2  # Pacote e despedida
+    assert sender.collector.closed and sender.sock.closed
+
+
+if __name__ == "__main__":
+    for name, func in list(globals().items()):
+        if name.startswith("test_"):
+            func()
+            print(f"✓ {name}")
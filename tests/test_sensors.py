@@ -0,0 +1,227 @@
+"""
+Testes das fontes de sensores do Sender (core/sensors.py).
+Sem hardware: o payload é montado com MockSource e leituras falsas do LHM/NVML,
+conferindo a ordem de prioridade e as regras de mescla.
+"""
+import copy
+import io
+import sys
+import os
+from collections import namedtuple
+from contextlib import redirect_stdout
+
+# Adicionar diretório pai ao path para imports
+sys.path.insert(0, os.path.dirname(os.path.dirname(os.path.abspath(__file__))))
+
+from core.sensors import SensorSource, LhmSource, NvmlSource, FanFallbackSource, MockSource, collect_sources
+from core.sensors import CpuClockFallbackSource, DEFAULT_CLOCK_SOURCE, parse_disabled_sources, enabled_sources
+
+
+def base_payload():
+    """Payload com os padrões do Sender (antes das fontes)"""
+    return {
+        "cpu": {"usage": 5.0, "temp": 0, "voltage": 0, "power": 0, "clock": 0,
+                "clock_source": DEFAULT_CLOCK_SOURCE, "throttling": False},
+        "gpu": {"load": 0, "temp": 0, "voltage": 0, "clock_core": 0, "clock_mem": 0, "power_w": 0,
+                "throttling": False, "enc_util": 0, "dec_util": 0, "mem_util": 0,
+                "pcie_tx_kbps": 0, "pcie_rx_kbps": 0, "fan": 0, "fan_unit": "RPM",
+                "source": "", "mem_used_mb": 0, "mem_total_mb": 0},
+        "mobo": {"temp": 0},
+        "storage": [],
+        "fans": [],
+    }
+
+
+def hw_data(source="nvidia", fans=None):
+    """Leitura do HardwareMonitor.fetch_data()"""
+    return {
+        "cpu": {"temp": 61.26, "voltage": 1.2345, "power": 45.67, "clock": 4200.4},
+        "gpu": {"temp": 55.0, "load": 30.04, "voltage": 0.9, "clock_core": 1800, "clock_mem": 7000,
+                "power": 120.33, "fan": 1500, "fan_unit": "RPM", "source": source,
+                "mem_used": 2048, "mem_total": 8192},
+        "mobo": {"temp": 40.0},
+        "storage": [{"name": "SSD", "temp": 38}],
+        "fans": fans if fans is not None else [{"name": "CPU Fan", "rpm": 900}],
+    }
+
+
+NVML_READING = {"throttle_reasons": 0x20, "enc_util": 12.4, "dec_util": 3.6,
+                "mem_util": 40.2, "pcie_tx_kbps": 1000.4, "pcie_rx_kbps": 2000.6}
+HWMON_FANS = [{"name": "fan1", "rpm": 1200}]
+
+
+def default_sources(nvml=NVML_READING, fans=HWMON_FANS):
+    return [LhmSource(), NvmlSource(lambda: nvml), FanFallbackSource(lambda: list(fans))]
+
+
+def test_lhm_source_copies_reading():
+    """LhmSource arredonda e copia CPU, GPU, placa-mãe, discos e fans."""
+    payload = base_payload()
+    LhmSource().contribute(payload, hw_data())
+    assert payload["cpu"]["temp"] == 61.3
+    assert payload["cpu"]["voltage"] == 1.234
+    assert payload["gpu"]["power_w"] == 120.3
+    assert payload["gpu"]["mem_total_mb"] == 8192
+    assert payload["mobo"]["temp"] == 40.0
+    assert payload["storage"] == [{"name": "SSD", "temp": 38}]
+    
+    # Sem LHM o payload fica como estava
+    empty = base_payload()
+    LhmSource().contribute(empty, None)
+    assert empty == base_payload()
+
+
+def test_nvml_only_for_nvidia_gpu():
+    """NVML completa a GPU NVIDIA (ou nenhuma), mas não a iGPU/AMD escolhida pelo LHM."""
+    payload = base_payload()
+    collect_sources(payload, default_sources(), hw_data("nvidia"))
+    assert payload["gpu"]["throttling"] is True
+    assert payload["gpu"]["enc_util"] == 12
+    assert payload["gpu"]["pcie_rx_kbps"] == 2001
+    
+    amd = base_payload()
+    collect_sources(amd, default_sources(), hw_data("amd"))
+    assert amd["gpu"]["source"] == "amd"
+    assert amd["gpu"]["enc_util"] == 0
+    assert amd["gpu"]["throttling"] is False
+    
+    # Sem LHM (source vazio) o NVML ainda vale
+    linux = base_payload()
+    collect_sources(linux, default_sources(), None)
+    assert linux["gpu"]["mem_util"] == 40
+
+
+def test_fan_fallback_only_when_empty():
+    """Fans do hwmon só entram se o LHM não trouxe nenhum."""
+    payload = base_payload()
+    collect_sources(payload, default_sources(), hw_data())
+    assert payload["fans"] == [{"name": "CPU Fan", "rpm": 900}]
+    
+    no_lhm_fans = base_payload()
+    collect_sources(no_lhm_fans, default_sources(), hw_data(fans=[]))
+    assert no_lhm_fans["fans"] == HWMON_FANS
+
+
+def test_mock_sources_priority():
+    """Fontes rodam em ordem: a seguinte sobrescreve, ou só completa com fill_only."""
+    first = MockSource("a", {"cpu": {"temp": 50.0, "power": 30.0}})
+    override = MockSource("b", {"cpu": {"temp": 70.0}})
+    filler = MockSource("c", {"cpu": {"temp": 99.0, "clock": 3000}, "fans": [{"name": "x", "rpm": 1}]}, fill_only=True)
+    payload = base_payload()
+    collect_sources(payload, [first, override, filler])
+    assert payload["cpu"]["temp"] == 70.0   # b venceu a; c não sobrescreve
+    assert payload["cpu"]["power"] == 30.0
+    assert payload["cpu"]["clock"] == 3000  # c completou o que estava zerado
+    assert payload["cpu"]["usage"] == 5.0   # Campo que nenhuma fonte conhece fica
+    assert payload["fans"] == [{"name": "x", "rpm": 1}]
+    
+    # A lista do payload não é a mesma da fonte (ciclos seguintes não se misturam)
+    payload["fans"].append({"name": "y", "rpm": 2})
+    assert filler.values["fans"] == [{"name": "x", "rpm": 1}]
+
+
+def test_mock_before_lhm_is_overwritten():
+    """Um mock antes do LHM perde para ele; depois, vence."""
+    mock = MockSource("mock", {"cpu": {"temp": 10.0}})
+    before = base_payload()
+    collect_sources(before, [mock, LhmSource()], hw_data())
+    assert before["cpu"]["temp"] == 61.3
+    
+    after = base_payload()
+    collect_sources(after, [LhmSource(), mock], hw_data())
+    assert after["cpu"]["temp"] == 10.0
+
+
+def test_failing_source_does_not_stop_others():
+    """Fonte que levanta exceção é pulada e reportada; as demais rodam."""
+    class Broken(SensorSource):
+        name = "broken"
+        
+        def contribute(self, payload, hw_data=None):
+            raise RuntimeError("sensor sumiu")
+    
+    later = MockSource("later", {"mobo": {"temp": 33.0}})
+    payload = base_payload()
+    with redirect_stdout(io.StringIO()) as out:
+        failed = collect_sources(payload, [Broken(), later])
+    assert failed == ["broken"]
+    assert "broken" in out.getvalue()
+    assert later.calls == 1
+    assert payload["mobo"]["temp"] == 33.0
+
+
+def test_sources_do_not_touch_hw_data():
+    """As fontes leem a leitura do LHM sem alterá-la."""
+    data = hw_data()
+    snapshot = copy.deepcopy(data)
+    collect_sources(base_payload(), default_sources(), data)
+    assert data == snapshot
+
+
+def test_clock_source_priority_chain():
+    """cpu.clock_source marca quem preencheu o clock: LHM, SO (Linux), nominal do Windows ou ninguém."""
+    Freq = namedtuple("Freq", "current min max")
+    
+    def chain(platform, freq):
+        return default_sources() + [CpuClockFallbackSource(lambda: freq, platform)]
+    
+    lhm = base_payload()
+    collect_sources(lhm, chain("win32", Freq(3600.0, 0, 3600.0)), hw_data())
+    assert (lhm["cpu"]["clock"], lhm["cpu"]["clock_source"]) == (4200, "lhm")
+    
+    # LHM sem leitura de clock: o psutil completa
+    no_clock = hw_data()
+    no_clock["cpu"]["clock"] = 0
+    windows = base_payload()
+    collect_sources(windows, chain("win32", Freq(3600.0, 0, 3600.0)), no_clock)
+    assert (windows["cpu"]["clock"], windows["cpu"]["clock_source"]) == (3600, "os_base")
+    
+    linux = base_payload()
+    collect_sources(linux, chain("linux", Freq(2894.6, 800.0, 4500.0)), None)
+    assert (linux["cpu"]["clock"], linux["cpu"]["clock_source"]) == (2895, "os")
+    
+    # Ninguém leu: fica zerado e "unknown"
+    for freq in (None, Freq(0.0, 0, 0)):
+        unknown = base_payload()
+        collect_sources(unknown, chain("linux", freq), None)
+        assert (unknown["cpu"]["clock"], unknown["cpu"]["clock_source"]) == (0, "unknown")
+
+
+def test_parse_disabled_sources():
+    """Nomes sem diferenciar maiúsculas; desconhecidos e valor que não é lista só geram aviso."""
+    assert parse_disabled_sources(["LHM", " nvml ", "wmi"]) == ({"lhm", "nvml", "wmi"}, [])
+    assert parse_disabled_sources([]) == (set(), [])
+    assert parse_disabled_sources(None) == (set(), [])
+    disabled, warnings = parse_disabled_sources(["acpi", "storage"])
+    assert disabled == {"storage"}
+    assert len(warnings) == 1 and "acpi" in warnings[0]
+    assert parse_disabled_sources(["SMART"]) == ({"storage"}, [])
+    disabled, warnings = parse_disabled_sources("lhm")
+    assert disabled == set() and len(warnings) == 1
+
+
+def test_disabled_source_is_never_queried():
+    """Fonte desligada sai da lista e a leitura dela nunca roda."""
+    calls = []
+    
+    def read_nvml():
+        calls.append("nvml")
+        return NVML_READING
+    
+    sources = [LhmSource(), NvmlSource(read_nvml), FanFallbackSource(lambda: calls.append("hwmon") or HWMON_FANS)]
+    disabled, _ = parse_disabled_sources(["nvml", "hwmon"])
+    active = enabled_sources(sources, disabled)
+    assert [source.name for source in active] == ["lhm"]
+    for _ in range(3):
+        payload = base_payload()
+        collect_sources(payload, active, hw_data(fans=[]))
+    assert calls == []
+    assert payload["gpu"]["enc_util"] == 0
+    assert payload["fans"] == []
+
+
+if __name__ == "__main__":
+    for name, func in list(globals().items()):
+        if name.startswith("test_"):
+            func()
+            print(f"✓ {name}")
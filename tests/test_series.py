@@ -0,0 +1,203 @@
+"""
+Testes das séries dos gráficos (core/series.py).
+Não depende da GUI: usa instantes de chegada sintéticos.
+"""
+import sys
+import os
+import math
+from collections import deque
+
+# Adicionar diretório pai ao path para imports
+sys.path.insert(0, os.path.dirname(os.path.dirname(os.path.abspath(__file__))))
+
+from core.series import history_capacity, resize_series, IntervalTracker, MIN_HISTORY_SIZE
+from core.series import GAP, gap_samples, series_values, split_segments, axis_bounds, plot_points
+from core.series import paired_points, scatter_coords, SampleAggregator, aggregate_samples, sample_interval
+from core.series import history_sample
+
+
+def test_capacity_tracks_interval():
+    """A mesma janela em segundos precisa de mais amostras com intervalo menor."""
+    assert history_capacity(60, 1.0) == 60
+    assert history_capacity(60, 0.5) == 120
+    assert history_capacity(60, 0.25) == 240
+    assert history_capacity(30, 0.7) == 43  # arredonda para cima
+    assert history_capacity(60, 0) == MIN_HISTORY_SIZE
+
+
+def test_interval_tracker_uses_real_arrivals():
+    """Mediana das chegadas, ignorando a lacuna de uma desconexão."""
+    tracker = IntervalTracker(max_gap=5)
+    assert tracker.interval is None
+    for t in (0.0, 0.5, 1.0, 1.5, 30.0, 30.5):
+        tracker.record(t)
+    assert tracker.interval == 0.5
+    assert history_capacity(60, tracker.interval) == 120
+
+
+def test_resize_preserves_recent_data():
+    """Encolher mantém as mais recentes; crescer completa com lacunas à esquerda."""
+    series = deque(range(1, 11), maxlen=10)
+
+    smaller = resize_series(series, 4)
+    assert list(smaller) == [7, 8, 9, 10]
+
+    bigger = resize_series(smaller, 6)
+    assert math.isnan(bigger[0]) and math.isnan(bigger[1])
+    assert series_values(bigger) == [7, 8, 9, 10]
+
+    # Passar da capacidade continua descartando a mais antiga
+    bigger.append(11)
+    assert series_values(bigger) == [7, 8, 9, 10, 11]
+    assert bigger.maxlen == 6
+    
+    # O preenchimento não puxa o auto-scale para 0
+    low, _ = axis_bounds(resize_series(deque([40.0, 42.0], maxlen=2), 10), autoscale=True)
+    assert low > 30
+
+
+def test_ten_second_gap_fills_nan():
+    """10s sem pacotes a 0.5s de intervalo: 19 amostras de lacuna antes do próximo."""
+    assert gap_samples(100.0, 110.0, 0.5, 3600) == 19
+    series = deque([1.0, 2.0], maxlen=60)
+    series.extend([GAP] * gap_samples(100.0, 110.0, 0.5, series.maxlen))
+    series.append(3.0)
+    assert sum(1 for v in series if math.isnan(v)) == 19
+    assert series_values(series) == [1.0, 2.0, 3.0]
+
+
+def test_no_gap_without_loss():
+    """Pacotes em dia, jitter, senders antigos sem timestamp: nenhuma lacuna."""
+    assert gap_samples(100.0, 100.5, 0.5, 60) == 0
+    assert gap_samples(100.0, 100.7, 0.5, 60) == 0
+    assert gap_samples(None, 100.0, 0.5, 60) == 0
+    assert gap_samples(100.0, None, 0.5, 60) == 0
+    assert gap_samples(100.0, 200.0, None, 60) == 0
+    assert gap_samples(100.0, 99.0, 0.5, 60) == 0
+    assert gap_samples(0.0, 10000.0, 0.5, 60) == 60
+
+
+def test_segments_break_at_gaps():
+    """A linha é desenhada em trechos separados pelas lacunas."""
+    segments = split_segments([1.0, 2.0, GAP, GAP, 5.0])
+    assert segments == [[(0, 1.0), (1, 2.0)], [(4, 5.0)]]
+    assert split_segments([GAP]) == []
+
+
+def test_axis_bounds_fixed_scale():
+    """Percentuais continuam em 0-100, qualquer que seja o dado."""
+    assert axis_bounds([12.0, 30.0], 100) == (0.0, 100)
+    assert axis_bounds([12.0, 30.0], 100, autoscale=False) == (0.0, 100)
+    low, high = axis_bounds([0, 0, 40.0])
+    assert low == 0.0 and math.isclose(high, 48.0)
+
+
+def test_axis_bounds_autoscale_follows_data():
+    """Ping estável em ~8ms ocupa a altura do gráfico, com margem."""
+    low, high = axis_bounds([7.0, 8.0, 9.0], 50, autoscale=True)
+    assert math.isclose(low, 6.8) and math.isclose(high, 9.2)
+    low, high = axis_bounds([-5.0, 5.0], autoscale=True)  # Negativos mantêm o eixo abaixo de 0
+    assert low < -5.0 and high > 5.0
+
+
+def test_axis_bounds_never_degenerate():
+    """Série zerada, constante, com uma amostra ou só lacunas ainda tem altura."""
+    for series in ([0, 0, 0], [8.0], [8.0, 8.0, 8.0], [], [GAP, GAP]):
+        low, high = axis_bounds(series, autoscale=True)
+        assert high > low
+        assert low >= 0.0
+    assert axis_bounds([], None) == (0.0, 1.0)
+    assert axis_bounds([GAP, 55.0, GAP], autoscale=True)[0] < 55.0 < axis_bounds([GAP, 55.0, GAP], autoscale=True)[1]
+
+
+def test_plot_points_empty_and_short_series():
+    """Série vazia ou com uma amostra não gera linha."""
+    assert plot_points(deque(), 0, 0, 100, 50, 0, 10) == []
+    assert plot_points(deque([5.0]), 0, 0, 100, 50, 0, 10) == []
+    assert plot_points([GAP, GAP], 0, 0, 100, 50, 0, 10) == []
+
+
+def test_plot_points_maps_to_rectangle():
+    """Primeira amostra na esquerda, última na direita; low na base, high no topo."""
+    lines = plot_points(deque([0.0, 5.0, 10.0]), 10, 20, 100, 50, 0, 10)
+    assert lines == [[10, 70, 60, 45.0, 110, 20.0]]
+    # Eixo degenerado não divide por zero
+    assert plot_points([3.0, 3.0], 0, 0, 10, 10, 3.0, 3.0) == [[0, 10, 10, 10]]
+    # Lacuna separa os trechos (trecho de um ponto some)
+    assert len(plot_points([1.0, 2.0, GAP, 3.0, GAP, 4.0, 5.0], 0, 0, 60, 10, 0, 5)) == 2
+
+
+def test_paired_points_aligns_recent_samples():
+    """Séries de tamanhos diferentes são pareadas pela amostra mais recente."""
+    xs = deque([10, 20, 30, 40], maxlen=4)
+    ys = [55.0, 60.0]
+    assert paired_points(xs, ys) == [(30.0, 55.0), (40.0, 60.0)]
+    assert paired_points([], [1, 2]) == []
+    assert paired_points(deque(), deque()) == []
+
+
+def test_paired_points_skips_gaps_and_padding():
+    """Lacunas (inclui o preenchimento do início) não viram pontos; leituras zeradas sim."""
+    xs = [GAP, GAP, 50, GAP, 0, 80, 0]
+    ys = [GAP, GAP, 60, 65, 42, None, 0]
+    assert paired_points(xs, ys) == [(50.0, 60.0), (0.0, 42.0), (0.0, 0.0)]
+
+
+def test_scatter_coords_maps_and_clamps():
+    """Mínimo no canto inferior esquerdo; fora da escala fica na borda."""
+    coords = scatter_coords([(0, 0), (100, 50), (150, -10)], 10, 20, 200, 100, (0, 100), (0, 100))
+    assert coords == [(10.0, 120.0), (210.0, 70.0), (210.0, 120.0)]
+    # Eixo degenerado não divide por zero
+    assert scatter_coords([(5, 5)], 0, 0, 10, 10, (5, 5), (0, 10)) == [(0.0, 5.0)]
+
+
+def test_aggregate_half_second_packets_into_seconds():
+    """Pacotes a 0.5s viram uma média por segundo; o último balde sai no flush."""
+    samples = [(100.0, {"cpu": 10}), (100.5, {"cpu": 20}), (101.0, {"cpu": 30}), (101.5, {"cpu": 50}), (102.2, {"cpu": 7})]
+    assert aggregate_samples(samples, 1.0) == [{"cpu": 15.0}, {"cpu": 40.0}, {"cpu": 7.0}]
+
+
+def test_aggregator_emits_when_bucket_closes():
+    """add() só devolve as médias ao abrir o balde seguinte; sensor ausente não entra na média."""
+    agg = SampleAggregator(2.0)
+    assert agg.add(10.0, {"a": 1.0, "b": None}) is None
+    assert agg.add(11.9, {"a": 3.0, "b": 4.0}) is None
+    assert agg.add(12.0, {"a": 9.0, "b": 9.0}) == {"a": 2.0, "b": 4.0}
+    assert agg.flush() == {"a": 9.0, "b": 9.0}
+    assert agg.flush() is None
+
+
+def test_aggregator_keeps_all_none_key_as_gap():
+    """Sensor ausente o balde inteiro sai como GAP, não some (as séries avançam juntas)."""
+    agg = SampleAggregator(1.0)
+    agg.add(10.0, {"a": 1.0, "b": None})
+    agg.add(10.5, {"a": 3.0, "b": None})
+    means = agg.add(11.0, {"a": 5.0, "b": 2.0})
+    assert set(means) == {"a", "b"}
+    assert means["a"] == 2.0 and math.isnan(means["b"])
+    assert agg.flush() == {"a": 5.0, "b": 2.0}
+
+
+def test_absent_sensor_is_a_gap_not_zero():
+    """Sensor ausente (None) entra no histórico como lacuna; 0 real continua 0."""
+    assert math.isnan(history_sample(None))
+    assert history_sample(0) == 0
+    assert history_sample(42.5) == 42.5
+    assert series_values([history_sample(v) for v in (10.0, None, 0.0)]) == [10.0, 0.0]
+
+
+def test_sample_interval_with_aggregation():
+    """Com agregação a janela do gráfico é contada em baldes (ou no pacote, se mais lento)."""
+    assert sample_interval(0.5, 0) == 0.5
+    assert sample_interval(0.5, 1.0) == 1.0
+    assert sample_interval(2.0, 1.0) == 2.0
+    assert sample_interval(None, 1.0) == 1.0
+    assert sample_interval(None, 0) is None
+    assert history_capacity(30, sample_interval(0.5, 1.0)) == 30
+
+
+if __name__ == "__main__":
+    for name, func in list(globals().items()):
+        if name.startswith("test_"):
+            func()
+            print(f"✓ {name}")
@@ -0,0 +1,84 @@
+"""
+Testes da média móvel exponencial (core/smoothing.py).
+"""
+import sys
+import os
+
+# Adicionar diretório pai ao path para imports
+sys.path.insert(0, os.path.dirname(os.path.dirname(os.path.abspath(__file__))))
+
+from core.smoothing import Smoother, HoldState
+
+
+def test_ema_converges_to_constant():
+    """Com entrada constante, a EMA converge para ela."""
+    smoother = Smoother(alpha=0.3)
+    smoother.update("temp", 0.0)
+    for _ in range(50):
+        value = smoother.update("temp", 80.0)
+    assert abs(value - 80.0) < 0.01
+
+
+def test_ema_step():
+    """Um passo da EMA: anterior + alpha * (novo - anterior)."""
+    smoother = Smoother(alpha=0.5)
+    assert smoother.update("ping", 10.0) == 10.0  # primeira amostra
+    assert smoother.update("ping", 30.0) == 20.0
+    assert smoother.update("ping", 30.0) == 25.0
+
+
+def test_alpha_zero_is_raw():
+    """alpha = 0 desliga a suavização."""
+    smoother = Smoother(alpha=0)
+    assert not smoother.enabled
+    assert smoother.update("ping", 10.0) == 10.0
+    assert smoother.update("ping", 99.0) == 99.0
+
+
+def test_hold_expires_after_max_frames():
+    """Após max_hold_frames intervalos sem pacote o valor fica obsoleto (None)."""
+    hold = HoldState(max_hold_frames=2)
+    hold.update(50.0, 10.0)
+    assert hold.value(10.2, 0.5) == 50.0  # Ainda dentro do intervalo
+    assert hold.value(10.6, 0.5) == 50.0  # 1 perdido, sem tendência: mantém
+    assert hold.value(11.1, 0.5) == 50.0  # 2 perdidos
+    assert hold.value(11.6, 0.5) is None  # 3 perdidos: obsoleto
+    # Pacote novo volta a valer
+    hold.update(52.0, 11.7)
+    assert hold.value(11.8, 0.5) == 52.0
+
+
+def test_hold_extrapolates_trend():
+    """Durante a perda o valor segue a reta das duas últimas amostras."""
+    hold = HoldState(max_hold_frames=2)
+    hold.update(40.0, 0.0)
+    hold.update(42.0, 1.0)
+    assert hold.value(2.0, 1.0) == 44.0
+    # Redesenho do mesmo pacote não altera a tendência
+    hold.update(42.0, 1.0)
+    assert hold.value(3.0, 1.0) == 46.0
+    assert hold.value(4.0, 1.0) is None
+
+
+def test_hold_does_not_go_negative():
+    """Métrica não negativa em queda para em zero."""
+    hold = HoldState(max_hold_frames=3)
+    hold.update(10.0, 0.0)
+    hold.update(2.0, 1.0)
+    assert hold.value(2.5, 1.0) == 0.0
+
+
+def test_hold_zero_frames_expires_immediately():
+    """max_hold_frames = 0: o primeiro intervalo perdido já é obsoleto."""
+    hold = HoldState(max_hold_frames=0)
+    assert hold.value(0.0, 1.0) is None  # Sem amostras
+    hold.update(5.0, 0.0)
+    assert hold.value(0.5, 1.0) == 5.0
+    assert hold.value(1.0, 1.0) is None
+
+
+if __name__ == "__main__":
+    for name, func in list(globals().items()):
+        if name.startswith("test_"):
+            func()
+            print(f"✓ {name}")
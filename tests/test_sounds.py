@@ -0,0 +1,113 @@
+"""
+Testes dos sons de alerta (core/sounds.py) e do que decide quando tocá-los (core/alerts.py).
+Não toca som: só o cooldown, a resolução de nomes, o AlertTracker e o MuteRegistry.
+"""
+import sys
+import os
+import tempfile
+
+# Adicionar diretório pai ao path para imports
+sys.path.insert(0, os.path.dirname(os.path.dirname(os.path.abspath(__file__))))
+
+from core.sounds import CooldownGate, resolve_sound_asset, SOUNDS_DIR, SoundConfig, resolve_alert_sound, AlertSound
+from core.alerts import AlertLevel, AlertTracker, MuteRegistry, alert_level
+
+
+def test_cooldown_gate_is_global():
+    """Um som a cada cooldown_seconds, não importa de qual métrica veio."""
+    gate = CooldownGate(10)
+    assert gate.ready(now=100.0) is True
+    assert gate.ready(now=105.0) is False
+    assert gate.ready(now=109.9) is False
+    assert gate.ready(now=110.0) is True
+
+    gate.reset()
+    assert gate.ready(now=111.0) is True
+
+
+def test_bundled_assets_resolve():
+    """Os nomes padrão do config apontam para os WAVs empacotados."""
+    for name in ("warning", "beep_urgent", "critical"):
+        path = resolve_sound_asset(name)
+        assert path == os.path.join(SOUNDS_DIR, f"{name}.wav")
+        assert os.path.isfile(path)
+
+
+def test_missing_or_unsafe_asset_falls_back():
+    """Sem WAV (ou nome fora do padrão) retorna None -> beep de fallback."""
+    with tempfile.TemporaryDirectory() as sounds_dir:
+        assert resolve_sound_asset("beep_urgent", sounds_dir) is None
+        open(os.path.join(sounds_dir, "meu_som.wav"), "wb").close()
+        assert resolve_sound_asset("meu_som", sounds_dir) == os.path.join(sounds_dir, "meu_som.wav")
+
+    for name in ("", "../config", "sons/warning", "warning.wav"):
+        assert resolve_sound_asset(name) is None
+
+
+def test_tracker_reports_only_transitions():
+    """Toca ao entrar em Warning/Critical; ficar no nível ou descer não toca de novo."""
+    tracker = AlertTracker()
+    assert tracker.update("CPU.temp", alert_level(60, 70, 85)) is None
+    assert tracker.update("CPU.temp", alert_level(72, 70, 85)) == AlertLevel.WARNING
+    assert tracker.update("CPU.temp", alert_level(75, 70, 85)) is None
+    assert tracker.update("CPU.temp", alert_level(90, 70, 85)) == AlertLevel.CRITICAL
+    assert tracker.update("CPU.temp", alert_level(80, 70, 85)) is None
+    assert tracker.update("CPU.temp", alert_level(50, 70, 85)) is None
+    assert tracker.update("CPU.temp", alert_level(86, 70, 85)) == AlertLevel.CRITICAL
+    # Métricas independentes
+    assert tracker.update("GPU.temp", alert_level(86, 70, 85)) == AlertLevel.CRITICAL
+
+
+def test_mute_expires_after_duration():
+    """Mute vale por duration_seconds e depois libera sozinho."""
+    mutes = MuteRegistry(duration_seconds=60)
+    mutes.mute("CPU.temp", now=0.0)
+    assert mutes.is_muted("CPU.temp", now=59.0)
+    assert not mutes.is_muted("GPU.temp", now=59.0)
+    assert not mutes.is_muted("CPU.temp", now=60.0)
+
+
+def test_mute_clears_when_back_to_normal():
+    """Voltar ao normal desfaz o mute; continuar em alerta mantém."""
+    mutes = MuteRegistry(duration_seconds=600)
+    mutes.mute("CPU.temp", now=0.0)
+    mutes.update("CPU.temp", alert_level(80, 70, 85))
+    assert mutes.is_muted("CPU.temp", now=1.0)
+    mutes.update("CPU.temp", alert_level(50, 70, 85))
+    assert not mutes.is_muted("CPU.temp", now=2.0)
+
+
+def test_alert_sound_without_overrides():
+    """Sem per_metric, cada nível usa o som genérico."""
+    config = SoundConfig(warning_sound="warning", critical_sound="beep_urgent")
+    assert resolve_alert_sound(config, "GPU.temp", critical=False) == "warning"
+    assert resolve_alert_sound(config, "GPU.temp", critical=True) == "beep_urgent"
+    assert resolve_alert_sound(SoundConfig(), "", critical=True) == AlertSound.BEEP_URGENT
+
+
+def test_alert_sound_per_metric_overrides():
+    """Métrica vence o painel; nível ausente no dict cai para o genérico."""
+    config = SoundConfig(
+        warning_sound="warning",
+        critical_sound="beep_urgent",
+        per_metric={
+            "gpu.TEMP": {"critical": "beep_high"},
+            "GPU": "question",
+            "RAM": {"warning": "beep_low", "critical": "critical"},
+            "CPU.temp": "",  # Vazio = sem override
+        }
+    )
+    assert resolve_alert_sound(config, "GPU.temp", critical=True) == "beep_high"
+    assert resolve_alert_sound(config, "GPU.temp", critical=False) == "question"  # Do painel
+    assert resolve_alert_sound(config, "GPU.load", critical=True) == "question"
+    assert resolve_alert_sound(config, "RAM.usage", critical=False) == "beep_low"
+    assert resolve_alert_sound(config, "RAM.usage", critical=True) == "critical"
+    assert resolve_alert_sound(config, "CPU.temp", critical=True) == "beep_urgent"
+    assert resolve_alert_sound(config, "STORAGE.disk0_temp", critical=False) == "warning"
+
+
+if __name__ == "__main__":
+    for name, func in list(globals().items()):
+        if name.startswith("test_"):
+            func()
+            print(f"✓ {name}")
@@ -0,0 +1,112 @@
+"""
+Testes do estado multi-sender do receiver (core/sources.py), incluindo a despedida do Sender.
+Não depende de rede: os instantes de recebimento são passados explicitamente.
+"""
+import sys
+import os
+
+# Adicionar diretório pai ao path para imports
+sys.path.insert(0, os.path.dirname(os.path.dirname(os.path.abspath(__file__))))
+
+from core.sources import SourceTable, ConnectionWatchdog, connection_state, FRESH, STALE, DISCONNECTED
+from core.sources import elapsed_since, goodbye_resets_connection
+from core.protocol import encode_goodbye, decode_goodbye, unwrap_frame
+
+
+def test_insert_and_update():
+    """Cada host guarda só o último payload."""
+    sources = SourceTable(timeout=5)
+    assert sources.update("192.168.1.20", {"cpu": {"usage": 10}}, now=0.0) is True
+    assert sources.update("192.168.1.10", {"cpu": {"usage": 50}}, now=0.5) is True
+    assert sources.update("192.168.1.20", {"cpu": {"usage": 30}}, now=1.0) is False
+
+    assert len(sources) == 2
+    assert sources.get("192.168.1.20") == ({"cpu": {"usage": 30}}, 1.0)
+    assert sources.get("10.0.0.1") is None
+    assert sources.active(now=1.0) == ["192.168.1.10", "192.168.1.20"]
+
+
+def test_timeout_is_per_host():
+    """Um host parado expira sem derrubar os outros."""
+    sources = SourceTable(timeout=5)
+    sources.update("pc-sala", {}, now=0.0)
+    sources.update("pc-escritorio", {}, now=0.0)
+    sources.update("pc-escritorio", {}, now=4.0)
+
+    assert sources.active(now=6.0) == ["pc-escritorio"]
+    assert sources.evict(now=6.0) == ["pc-sala"]
+    assert sources.get("pc-sala") is None
+    assert len(sources) == 1
+
+    # Nada mais expirou
+    assert sources.evict(now=6.0) == []
+    assert sources.evict(now=9.0) == ["pc-escritorio"]
+    assert sources.active(now=9.0) == []
+
+    # Retenção maior que o timeout: o host parado fica na tabela até passar dela
+    sources.update("pc-sala", {}, now=10.0)
+    assert sources.evict(now=20.0, older_than=30) == []
+    assert sources.evict(now=40.0, older_than=30) == ["pc-sala"]
+
+
+def test_connection_state_boundaries():
+    """Fresco até metade do timeout, esmaecido depois, desconectado no timeout."""
+    assert connection_state(0.0, 5.0) == FRESH
+    assert connection_state(2.5, 5.0) == FRESH
+    assert connection_state(2.51, 5.0) == STALE
+    assert connection_state(4.99, 5.0) == STALE
+    assert connection_state(5.0, 5.0) == DISCONNECTED
+    assert connection_state(float("inf"), 5.0) == DISCONNECTED  # Nunca recebeu
+    # Sender com intervalo longo: timeout maior adia os dois estados
+    assert connection_state(8.0, 30.0) == FRESH
+
+
+def test_goodbye_disconnects_immediately():
+    """Despedida recebida: o host sai da tabela e a conexão fica desconectada sem esperar o timeout."""
+    sources = SourceTable(timeout=5)
+    sources.update("192.168.1.10", {"cpu": {"usage": 1}}, now=100.0)
+    sources.update("192.168.1.11", {"cpu": {"usage": 2}}, now=100.0)
+    
+    frame = unwrap_frame(encode_goodbye("PC-Sala", auth_secret="s"), auth_secret="s")
+    goodbye = decode_goodbye(frame)
+    assert goodbye == {"hostname": "PC-Sala"}
+    assert sources.mark_offline("192.168.1.10")
+    assert sources.active(now=100.5) == ["192.168.1.11"]
+    assert not sources.mark_offline("192.168.1.10")
+    
+    # Um Sender só sempre zera a conexão; no multi-sender, só o host exibido
+    assert goodbye_resets_connection("192.168.1.10", None)
+    assert goodbye_resets_connection("192.168.1.10", "192.168.1.10")
+    assert not goodbye_resets_connection("192.168.1.10", "192.168.1.11")
+    
+    # last_data_time zerado: desconectado já no próximo ciclo da UI, sem esperar o timeout
+    assert connection_state(elapsed_since(99.0, 100.5), 5) == FRESH
+    assert connection_state(elapsed_since(0, 100.5), 5) == DISCONNECTED
+
+
+def test_watchdog_fires_once_on_drop():
+    """Só a borda conectado -> desconectado avisa; antes da primeira conexão, nunca."""
+    watchdog = ConnectionWatchdog(debounce_secs=60)
+    assert not watchdog.update(False, 0.0)  # Receiver abriu sem Sender
+    assert not watchdog.update(True, 1.0)
+    assert watchdog.update(False, 10.0)
+    assert not watchdog.update(False, 10.5)  # Continua caído: sem repetir
+
+
+def test_watchdog_debounces_flapping_link():
+    """Quedas dentro do debounce não avisam de novo; depois dele, sim."""
+    watchdog = ConnectionWatchdog(debounce_secs=60)
+    watchdog.update(True, 0.0)
+    assert watchdog.update(False, 5.0)
+    for t in (10.0, 20.0, 40.0):
+        watchdog.update(True, t)
+        assert not watchdog.update(False, t + 1)
+    watchdog.update(True, 70.0)
+    assert watchdog.update(False, 71.0)
+
+
+if __name__ == "__main__":
+    for name, func in list(globals().items()):
+        if name.startswith("test_"):
+            func()
+            print(f"✓ {name}")
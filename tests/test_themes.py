@@ -0,0 +1,97 @@
+"""
+Testes das cores e dos temas customizados (ui/themes.py).
+Não depende do Tk: os temas são gravados em um diretório temporário.
+"""
+import sys
+import os
+import json
+import tempfile
+from pathlib import Path
+
+# Adicionar diretório pai ao path para imports
+sys.path.insert(0, os.path.dirname(os.path.dirname(os.path.abspath(__file__))))
+
+from ui.themes import (
+    hex_to_rgb, rgb_to_hex, blend_color, COLOR_FIELDS, THEMES, DARK_THEME,
+    get_theme, load_custom_themes, save_custom_theme
+)
+
+
+def test_hex_rgb_roundtrip():
+    """#rrggbb -> (r, g, b) -> #rrggbb preserva a cor (saída sempre minúscula)."""
+    for color in ("#000000", "#ffffff", "#00d9ff", "#7744aa"):
+        assert rgb_to_hex(hex_to_rgb(color)) == color
+    assert rgb_to_hex(hex_to_rgb("#FF6600")) == "#ff6600"
+    assert hex_to_rgb("#ff8000") == (255, 128, 0)
+
+
+def test_rgb_to_hex_clamps_and_rounds():
+    """Canais fora de 0-255 (ou fracionários, do seletor de cor) são ajustados."""
+    assert rgb_to_hex((300, -5, 127.6)) == "#ff0080"
+
+
+def test_invalid_hex_rejected():
+    """Cor fora do formato gera ValueError; blend_color devolve a original."""
+    for color in ("", "red", "#fff", "#gggggg"):
+        try:
+            hex_to_rgb(color)
+            assert False, f"aceitou {color!r}"
+        except ValueError:
+            pass
+    assert blend_color("red", "#000000", 0.5) == "red"
+    assert blend_color("#ffffff", "#000000", 0.5) == "#808080"
+
+
+def test_save_and_load_custom_theme():
+    """Tema salvo pelo editor volta no próximo carregamento e fica registrado."""
+    with tempfile.TemporaryDirectory() as tmp:
+        path = str(Path(tmp) / "themes.json")
+        colors = DARK_THEME.to_dict()
+        colors["cpu"] = "#123456"
+        save_custom_theme(path, "meu_tema", colors)
+        save_custom_theme(path, "outro", {"bg": "#000000"})
+        
+        with open(path, encoding="utf-8") as f:
+            data = json.load(f)
+        assert set(data) == {"meu_tema", "outro"}
+        assert set(data["meu_tema"]) == set(COLOR_FIELDS)
+        
+        del THEMES["meu_tema"], THEMES["outro"]
+        loaded = load_custom_themes(path)
+        assert set(loaded) == {"meu_tema", "outro"}
+        assert get_theme("meu_tema").cpu == "#123456"
+        # Cores não informadas vêm do tema escuro
+        assert get_theme("outro").cpu == DARK_THEME.cpu
+        del THEMES["meu_tema"], THEMES["outro"]
+
+
+def test_builtin_theme_names_protected():
+    """Não dá para sobrescrever um tema embutido nem salvar sem nome."""
+    with tempfile.TemporaryDirectory() as tmp:
+        path = str(Path(tmp) / "themes.json")
+        for name in ("dark", "  ", ""):
+            try:
+                save_custom_theme(path, name, {})
+                assert False, f"aceitou o nome {name!r}"
+            except ValueError:
+                pass
+        assert not os.path.exists(path)
+
+
+def test_load_ignores_invalid_colors():
+    """Arquivo inexistente não é erro; cores inválidas voltam para o padrão."""
+    with tempfile.TemporaryDirectory() as tmp:
+        path = Path(tmp) / "themes.json"
+        assert load_custom_themes(str(path)) == {}
+        path.write_text(json.dumps({"quebrado": {"cpu": "verde", "gpu": "#ABCDEF"}}), encoding="utf-8")
+        theme = load_custom_themes(str(path))["quebrado"]
+        assert theme.cpu == DARK_THEME.cpu
+        assert theme.gpu == "#abcdef"
+        del THEMES["quebrado"]
+
+
+if __name__ == "__main__":
+    for name, func in list(globals().items()):
+        if name.startswith("test_"):
+            func()
+            print(f"✓ {name}")
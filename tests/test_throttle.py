@@ -0,0 +1,229 @@
+"""
+Testes da detecção de throttling (core/throttle.py) e das consultas ao NVML (core/nvml.py).
+Não requer NVML nem o LibreHardwareMonitor: máscaras e leituras são passadas direto,
+e o módulo pynvml é substituído por um objeto falso.
+"""
+import io
+import sys
+import os
+from contextlib import redirect_stdout
+
+# Adicionar diretório pai ao path para imports
+sys.path.insert(0, os.path.dirname(os.path.dirname(os.path.abspath(__file__))))
+
+from core.throttle import (
+    NvmlThrottleReason, gpu_throttle_reasons, gpu_throttling, cpu_throttling,
+    CPU_THROTTLE_TEMP
+)
+from core.nvml import (
+    query_gpu, handle_lost, ReinitBackoff, NvmlDevice,
+    NVML_ERROR_GPU_IS_LOST, NVML_ERROR_NOT_SUPPORTED, NVML_ERROR_UNINITIALIZED, NVML_ERROR_INVALID_ARGUMENT
+)
+
+
+class FakeNvmlError(Exception):
+    """Como o pynvml.NVMLError: o código do nvml.h fica em .value"""
+    
+    def __init__(self, value):
+        super().__init__(f"NVML error {value}")
+        self.value = value
+
+
+class FakeNvml:
+    """pynvml falso: cada função devolve o valor configurado ou levanta a exceção"""
+    
+    def __init__(self, reasons=0, encoder=(0, 167000), decoder=(0, 167000), memory=0, pcie=(0, 0)):
+        self.reasons, self.encoder, self.decoder = reasons, encoder, decoder
+        self.memory, self.pcie = memory, pcie
+        self.count = 1  # GPUs em nvmlDeviceGetCount
+        self.init_error = None  # Exceção de nvmlInit (driver fora do ar)
+        self.inits = 0
+    
+    def nvmlInit(self):
+        self.inits += 1
+        self._result(self.init_error)
+    
+    def nvmlShutdown(self):
+        pass
+    
+    def nvmlDeviceGetCount(self):
+        return self.count
+    
+    def nvmlDeviceGetHandleByIndex(self, index):
+        if index >= self.count:
+            raise FakeNvmlError(NVML_ERROR_INVALID_ARGUMENT)
+        return f"gpu{index}-{self.inits}"
+    
+    @staticmethod
+    def _result(value):
+        if isinstance(value, Exception):
+            raise value
+        return value
+    
+    def nvmlDeviceGetCurrentClocksThrottleReasons(self, handle):
+        return self._result(self.reasons)
+    
+    def nvmlDeviceGetEncoderUtilization(self, handle):
+        return self._result(self.encoder)
+    
+    def nvmlDeviceGetDecoderUtilization(self, handle):
+        return self._result(self.decoder)
+    
+    def nvmlDeviceGetUtilizationRates(self, handle):
+        memory = self._result(self.memory)
+        return type("Utilization", (), {"gpu": 0, "memory": memory})()
+    
+    def nvmlDeviceGetPcieThroughput(self, handle, counter):
+        if isinstance(self.pcie, Exception):
+            raise self.pcie
+        return self.pcie[counter]
+
+
+def test_nvml_mask_interpretation():
+    """Térmico/energia contam como throttling; ocioso e clocks fixados não."""
+    assert gpu_throttling(NvmlThrottleReason.HW_THERMAL_SLOWDOWN)
+    assert gpu_throttling(NvmlThrottleReason.SW_POWER_CAP | NvmlThrottleReason.GPU_IDLE)
+    assert gpu_throttling(0x20)  # SW thermal slowdown
+    assert not gpu_throttling(NvmlThrottleReason.GPU_IDLE)
+    assert not gpu_throttling(NvmlThrottleReason.APPLICATIONS_CLOCKS_SETTING | NvmlThrottleReason.SYNC_BOOST)
+    assert not gpu_throttling(NvmlThrottleReason.DISPLAY_CLOCK_SETTING)
+    assert not gpu_throttling(0)
+    assert not gpu_throttling(None)
+
+
+def test_nvml_reason_names():
+    """Nomes só dos motivos de throttling, em ordem de bit."""
+    mask = 0x01 | 0x04 | 0x40 | 0x100
+    assert gpu_throttle_reasons(mask) == ["SW_POWER_CAP", "HW_THERMAL_SLOWDOWN"]
+    assert gpu_throttle_reasons(None) == []
+
+
+def test_cpu_tjmax_distance_decides():
+    """Com o sensor de distância ao TjMax (Intel), só ele decide."""
+    assert cpu_throttling(99.0, 4000, 5000, tjmax_distance=1.0)
+    assert cpu_throttling(100.0, 5000, 5000, tjmax_distance=0.0)
+    assert not cpu_throttling(98.0, 3000, 5000, tjmax_distance=8.0)
+
+
+def test_cpu_hot_and_clock_drop():
+    """Sem o sensor: precisa estar quente E com o clock abaixo do pico."""
+    hot = CPU_THROTTLE_TEMP + 1
+    assert cpu_throttling(hot, 3800, 4600)
+    assert not cpu_throttling(hot, 4500, 4600)  # Quente, mas no clock máximo
+    assert not cpu_throttling(70.0, 2000, 4600)  # Clock baixo por ociosidade
+    # Sem leitura de temperatura ou de clock: nunca acusa
+    assert not cpu_throttling(None, 3000, 4600)
+    assert not cpu_throttling(hot, 0, 4600)
+    assert not cpu_throttling(hot, 3000, 0)
+
+
+def test_nvml_query_codec_utilization():
+    """Uso de NVENC/NVDEC vem do primeiro item da resposta do NVML."""
+    nvml = FakeNvml(reasons=0x40, encoder=[37, 167000], decoder=[12, 167000])
+    data = query_gpu(nvml, "gpu0")
+    assert (data["throttle_reasons"], data["enc_util"], data["dec_util"]) == (0x40, 37.0, 12.0)
+
+
+def test_nvml_query_memory_and_pcie():
+    """Uso do barramento de memória e tráfego PCIe TX/RX (KB/s) vêm do NVML."""
+    nvml = FakeNvml(memory=23, pcie=(1840, 96512))
+    data = query_gpu(nvml, "gpu0")
+    assert data["mem_util"] == 23.0
+    assert (data["pcie_tx_kbps"], data["pcie_rx_kbps"]) == (1840.0, 96512.0)
+
+
+def test_nvml_query_unsupported_defaults_to_zero():
+    """GPU sem encoder (ou driver antigo) dá 0; o throttling continua sendo lido."""
+    error = RuntimeError("NVML_ERROR_NOT_SUPPORTED")
+    nvml = FakeNvml(reasons=0x04, encoder=error, decoder=error, memory=error, pcie=error)
+    zeros = {"enc_util": 0.0, "dec_util": 0.0, "mem_util": 0.0, "pcie_tx_kbps": 0.0, "pcie_rx_kbps": 0.0}
+    assert query_gpu(nvml, "gpu0") == {"throttle_reasons": 0x04, **zeros}
+    # pynvml antigo sem as funções de encoder/decoder/PCIe
+    assert query_gpu(object(), "gpu0") == {"throttle_reasons": None, **zeros}
+
+
+def test_nvml_lost_handle_codes():
+    """GPU perdida/driver fora reinicializa; recurso não suportado não."""
+    assert handle_lost(FakeNvmlError(NVML_ERROR_GPU_IS_LOST))
+    assert handle_lost(FakeNvmlError(NVML_ERROR_UNINITIALIZED))
+    assert not handle_lost(FakeNvmlError(NVML_ERROR_NOT_SUPPORTED))
+    assert not handle_lost(FakeNvmlError(NVML_ERROR_INVALID_ARGUMENT))  # Erro de chamada, não da GPU
+    assert not handle_lost(RuntimeError("sem código"))
+    # query_gpu repassa o erro de handle perdido em vez de devolver zeros
+    nvml = FakeNvml(reasons=FakeNvmlError(NVML_ERROR_GPU_IS_LOST))
+    try:
+        query_gpu(nvml, "gpu0")
+        assert False, "handle perdido não levantou"
+    except FakeNvmlError:
+        pass
+
+
+def test_reinit_backoff_doubles_until_max():
+    """Espera inicial após a perda; cada falha dobra até o máximo."""
+    now = [100.0]
+    backoff = ReinitBackoff(initial=5, maximum=20, clock=lambda: now[0])
+    assert not backoff.due() and not backoff.waiting
+    backoff.lost()
+    assert backoff.waiting and not backoff.due()
+    now[0] = 105.0
+    assert backoff.due()
+    delays = []
+    for _ in range(4):
+        backoff.failed()
+        delays.append(backoff.delay)
+    assert delays == [10, 20, 20, 20]
+    assert backoff.attempts == 4
+    backoff.succeeded()
+    assert not backoff.waiting and backoff.delay == 5
+
+
+def test_nvml_device_recovers_after_driver_restart():
+    """Handle perdido -> None durante a espera -> reinicializa e volta a ler."""
+    now = [0.0]
+    nvml = FakeNvml(reasons=0x04)
+    device = NvmlDevice.open(nvml, 0, ReinitBackoff(initial=5, maximum=60, clock=lambda: now[0]))
+    assert device.query()["throttle_reasons"] == 0x04
+    
+    # Driver reiniciando: a consulta falha e o nvmlInit também
+    nvml.reasons = FakeNvmlError(NVML_ERROR_GPU_IS_LOST)
+    nvml.init_error = FakeNvmlError(NVML_ERROR_UNINITIALIZED)
+    assert device.query() is None
+    assert device.query() is None  # Ainda esperando: nem tenta
+    assert nvml.inits == 1
+    now[0] = 5.0
+    assert device.query() is None  # Tentou e falhou
+    assert nvml.inits == 2 and device.backoff.delay == 10
+    
+    # Driver de volta
+    nvml.reasons = 0x40
+    nvml.init_error = None
+    now[0] = 15.0
+    assert device.query()["throttle_reasons"] == 0x40
+    assert device.handle == "gpu0-3"
+
+
+def test_nvml_device_absent_without_driver():
+    """Sem driver na inicialização não há GPU NVIDIA: nada de tentar de novo."""
+    nvml = FakeNvml()
+    nvml.init_error = FakeNvmlError(NVML_ERROR_UNINITIALIZED)
+    assert NvmlDevice.open(nvml) is None
+
+
+def test_nvml_device_gpu_index():
+    """gpu_index escolhe a GPU; índice além do nvmlDeviceGetCount avisa e volta para a 0."""
+    nvml = FakeNvml()
+    nvml.count = 2
+    assert NvmlDevice.open(nvml, 1).handle == "gpu1-1"
+    
+    out = io.StringIO()
+    with redirect_stdout(out):
+        device = NvmlDevice.open(nvml, 3)
+    assert device.index == 0 and device.handle == "gpu0-2"
+    assert "gpu_index 3" in out.getvalue()
+
+
+if __name__ == "__main__":
+    for name, func in list(globals().items()):
+        if name.startswith("test_"):
+            func()
+            print(f"✓ {name}")
@@ -0,0 +1,109 @@
+"""
+Testes do envio com retry do Sender (core/transport.py).
+Não usa rede: o socket é substituído por um sink que falha N vezes.
+"""
+import sys
+import os
+import errno
+
+# Adicionar diretório pai ao path para imports
+sys.path.insert(0, os.path.dirname(os.path.dirname(os.path.abspath(__file__))))
+
+from core.transport import send_with_retry, SendHealth, SendSummary
+
+
+class FlakySink:
+    """Falha `failures` vezes com o errno dado e depois aceita."""
+
+    def __init__(self, failures, error=errno.ENETUNREACH):
+        self.failures = failures
+        self.error = error
+        self.calls = 0
+
+    def __call__(self, frame, address):
+        self.calls += 1
+        if self.calls <= self.failures:
+            raise OSError(self.error, os.strerror(self.error))
+        return len(frame)
+
+
+def test_transient_error_is_retried_with_backoff():
+    """ENETUNREACH passageiro: tenta de novo e o frame sai."""
+    sink, waits = FlakySink(failures=2), []
+    assert send_with_retry(sink, b"frame", ("10.0.0.2", 5005), retries=2, backoff=0.05, sleep=waits.append) == 5
+    assert sink.calls == 3
+    assert waits == [0.05, 0.1]
+
+
+def test_gives_up_after_retries():
+    """Falha persistente propaga o erro depois das tentativas."""
+    sink = FlakySink(failures=10)
+    try:
+        send_with_retry(sink, b"frame", ("10.0.0.2", 5005), retries=2, sleep=lambda s: None)
+        assert False, "deveria ter falhado"
+    except OSError as e:
+        assert e.errno == errno.ENETUNREACH
+    assert sink.calls == 3
+
+
+def test_non_transient_error_is_not_retried():
+    """Erro de configuração (ex: permissão) não adianta repetir."""
+    sink = FlakySink(failures=1, error=errno.EACCES)
+    try:
+        send_with_retry(sink, b"frame", ("10.0.0.2", 5005), sleep=lambda s: None)
+        assert False, "deveria ter falhado"
+    except OSError:
+        pass
+    assert sink.calls == 1
+
+
+def test_health_requests_rebuild_after_threshold():
+    """Recria o socket a cada N falhas seguidas; sucesso zera a contagem."""
+    health = SendHealth(rebuild_after=3)
+    assert [health.failure() for _ in range(6)] == [False, False, True, False, False, True]
+    assert health.success() == 6
+    assert health.success() is None
+    assert health.failure() is False
+    assert health.total_failures == 7
+
+
+class FakeClock:
+    def __init__(self):
+        self.now = 100.0
+    
+    def __call__(self):
+        return self.now
+
+
+def test_summary_once_per_period():
+    """Um resumo só quando o período fecha, com médias de tamanho e CPU/GPU."""
+    clock = FakeClock()
+    summary = SendSummary(60, clock=clock)
+    for i in range(120):
+        clock.now = 100.0 + i * 0.5  # 0.5s de intervalo: último envio em 159.5s
+        assert summary.add(180 if i % 2 else 184, cpu=45.0, gpu=80.0) is None
+    clock.now = 160.0
+    line = summary.add(182, cpu=45.0, gpu=80.0)
+    assert line == "121 frames em 60s, média 182 bytes, CPU 45% GPU 80%"
+    
+    # Recomeça do zero no próximo período
+    assert summary.frames == 0
+    clock.now = 170.0
+    assert summary.add(100) is None
+    assert summary.frames == 1
+
+
+def test_summary_absent_readings_count_as_zero():
+    """Sensores ausentes (None no payload) não quebram a média."""
+    clock = FakeClock()
+    summary = SendSummary(1, clock=clock)
+    assert summary.add(100, cpu=50.0, gpu=None) is None
+    clock.now += 1
+    assert summary.add(300, cpu=None, gpu=None) == "2 frames em 1s, média 200 bytes, CPU 25% GPU 0%"
+
+
+if __name__ == "__main__":
+    for name, func in list(globals().items()):
+        if name.startswith("test_"):
+            func()
+            print(f"✓ {name}")
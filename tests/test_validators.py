@@ -0,0 +1,120 @@
+"""
+Testes dos validadores de configuração (core/validators.py).
+Não depende de hardware nem de rede.
+"""
+import sys
+import os
+
+# Adicionar diretório pai ao path para imports
+sys.path.insert(0, os.path.dirname(os.path.dirname(os.path.abspath(__file__))))
+
+from core.validators import validate_sender_network, validate_alert_thresholds, check_threshold_pair
+
+
+def test_sender_network_valid():
+    """Configurações corretas passam sem aviso."""
+    assert validate_sender_network("broadcast", "255.255.255.255", "") == (True, None)
+    assert validate_sender_network("broadcast", "192.168.1.255", "192.168.1.10") == (True, None)
+    assert validate_sender_network("unicast", "192.168.1.50", "") == (True, None)
+
+
+def test_sender_network_invalid():
+    """IPs malformados ou modo desconhecido são erros."""
+    valid, message = validate_sender_network("broadcast", "255.255.255.255", "192.168.1")
+    assert not valid and "bind_ip" in message
+
+    valid, message = validate_sender_network("unicast", "notebook.local", "")
+    assert not valid and "dest_ip" in message
+
+    valid, message = validate_sender_network("unicast", "", "")
+    assert not valid and "dest_ip" in message
+
+    valid, message = validate_sender_network("anycast", "255.255.255.255", "")
+    assert not valid and "modo" in message
+
+
+def test_sender_network_broadcast_mismatch():
+    """Broadcast com destino unicast é válido, mas gera aviso."""
+    valid, message = validate_sender_network("broadcast", "192.168.1.50", "")
+    assert valid
+    assert message and "unicast" in message
+
+
+def test_sender_network_ipv6():
+    """IPv6 é aceito em unicast; broadcast e famílias misturadas são erros."""
+    assert validate_sender_network("unicast", "2001:db8::20", "") == (True, None)
+    assert validate_sender_network("unicast", "fe80::20%eth0", "fe80::10%eth0") == (True, None)
+    
+    valid, message = validate_sender_network("broadcast", "ff02::1", "")
+    assert not valid and "broadcast" in message
+    
+    valid, message = validate_sender_network("unicast", "2001:db8::20", "192.168.1.10")
+    assert not valid and "famílias" in message
+    
+    valid, message = validate_sender_network("unicast", "2001:db8:::20", "")
+    assert not valid and "dest_ip" in message
+
+
+def test_sender_network_multicast():
+    """Modo multicast exige um grupo multicast como destino."""
+    assert validate_sender_network("multicast", "239.255.0.1", "") == (True, None)
+    assert validate_sender_network("multicast", "ff15::1", "") == (True, None)
+    
+    valid, message = validate_sender_network("multicast", "192.168.1.50", "")
+    assert not valid and "multicast_group" in message
+
+
+def test_thresholds_defaults_are_plausible():
+    """Os thresholds do exemplo de config passam sem avisos."""
+    import json
+    path = os.path.join(os.path.dirname(os.path.dirname(os.path.abspath(__file__))), "receiver_config.example.json")
+    with open(path, encoding="utf-8") as f:
+        config = json.load(f)
+    assert validate_alert_thresholds(config["alertas"], config["disk_overrides"]) == []
+
+
+def test_threshold_critical_below_warning():
+    """Crítico abaixo do aviso: o aviso nunca apareceria."""
+    errors = validate_alert_thresholds({"cpu_temp_warning": 85, "cpu_temp_critical": 70})
+    assert errors == ["cpu_temp: crítico (70) abaixo do aviso (85); o aviso nunca apareceria"]
+    # 0 desativa uma das faixas: não é erro
+    assert validate_alert_thresholds({"cpu_power_warning": 200, "cpu_power_critical": 0}) == []
+
+
+def test_threshold_temperature_in_fahrenheit():
+    """Temperatura acima de 120°C (ex: 185 digitado em °F) é implausível."""
+    errors = validate_alert_thresholds({"gpu_temp_warning": 167, "gpu_temp_critical": 194})
+    assert len(errors) == 2 and all("°F?" in e for e in errors)
+    assert validate_alert_thresholds({"mobo_temp_critical": 120}) == []
+
+
+def test_threshold_percent_above_100():
+    """Uso, RAM e VRAM acima de 100% nunca disparam."""
+    for key in ("cpu_uso_critical", "ram_warning", "vram_critical", "storage_uso_warning"):
+        errors = validate_alert_thresholds({key: 101})
+        assert len(errors) == 1 and "100%" in errors[0], key
+    # Ping e potência não são percentuais
+    assert validate_alert_thresholds({"ping_warning": 150, "gpu_power_critical": 450}) == []
+
+
+def test_threshold_voltage_and_negative():
+    """Vcore em mV (1450) e valores negativos são recusados."""
+    assert "mV?" in validate_alert_thresholds({"cpu_voltage_warning": 1450})[0]
+    assert "negativo" in check_threshold_pair("cpu_temp", -5, 85)[0]
+    assert check_threshold_pair("cpu_voltage", 1.45, 1.55) == []
+
+
+def test_threshold_disk_overrides():
+    """Override parcial é comparado com o threshold global de disco."""
+    alertas = {"storage_temp_warning": 45, "storage_temp_critical": 55}
+    errors = validate_alert_thresholds(alertas, {"WD Blue": {"temp_warning": 60}})
+    assert errors == ["disk_overrides.WD Blue.temp: crítico (55) abaixo do aviso (60); o aviso nunca apareceria"]
+    assert validate_alert_thresholds(alertas, {"WD Blue": {"temp_warning": 60, "temp_critical": 70}}) == []
+    assert "100%" in validate_alert_thresholds(alertas, {"WD Blue": {"uso_critical": 110}})[0]
+
+
+if __name__ == "__main__":
+    for name, func in list(globals().items()):
+        if name.startswith("test_"):
+            func()
+            print(f"✓ {name}")
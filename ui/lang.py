@@ -0,0 +1,162 @@
+"""
+Textos do dashboard em português e inglês ("language" no receiver_config.json)
+Só a tela principal e os painéis; logs e a janela de configurações não mudam
+"""
+from enum import Enum
+from typing import Any, Dict, Optional
+
+
+class Language(str, Enum):
+    """Idiomas com tabela completa em STRINGS"""
+    PT = "pt"
+    EN = "en"
+
+
+DEFAULT_LANGUAGE = Language.PT
+
+STRINGS: Dict[Language, Dict[str, str]] = {
+    Language.PT: {
+        # Barra de status
+        "status.connected": "● Conectado",
+        "status.stale": "◐ Sem dados recentes",
+        "status.host": " a {host}",
+        "status.uptime": " (ligado há {uptime})",
+        "status.no_data_for": " | Sem dados há {secs}s",
+        "status.updated": " | Atualizado: {time}",
+        "status.senders": " | {count} senders",
+        "status.port": " | porta {port}",
+        "status.waiting": "○ Aguardando dados...",
+        "status.disconnected": "○ Desconectado - Aguardando dados...{mode}",
+        "status.version_mismatch": "⚠ Protocolo do sender v{sender} incompatível com o receiver v{receiver}",
+        "footer.hint": "[F] Tela cheia | [G] Gráficos | [H] Histograma | [B] Barras | [X] Dispersão | [D] Debug | [T] Tema | [U] °C/°F | [1-6] Foco | [A] Alertas | [M] Recordes | [E] Editor de temas | [L] Log | [P] Exportar | [/] Buscar | [+/-] Taxa | [S] ⚙️ Configurações | [Q] Sair",
+        # Títulos dos painéis
+        "panel.CPU": "CPU",
+        "panel.GPU": "GPU",
+        "panel.RAM": "RAM",
+        "panel.MOBO": "PLACA-MÃE",
+        "panel.STORAGE": "ARMAZENAMENTO",
+        "panel.NETWORK": "REDE",
+        "panel.BATTERY": "BATERIA",
+        "panel.PROCESSES": "PROCESSOS",
+        "panel.ALERT_LOG": "LOG DE ALERTAS",
+        "panel.RECORDS": "RECORDES",
+        "panel.THEME_EDITOR": "EDITOR DE TEMAS",
+        # Linhas dos painéis
+        "row.usage": "Uso",
+        "row.temp": "Temp",
+        "row.voltage": "Voltagem",
+        "row.power": "Consumo",
+        "row.clock": "Clock",
+        "row.clock_source": "  Fonte",
+        "row.used": "Usado",
+        "row.total": "Total",
+        "row.disk": "Disco {n}",
+        "row.health": "  Saúde",
+        "row.used_space": "  Usado",
+        "row.free": "  Livre",
+        "row.read": "  Leitura",
+        "row.write": "  Escrita",
+        "row.adapter": "Adaptador",
+        "row.charge": "Carga",
+        "row.status": "Status",
+        "row.remaining": "Restante",
+        "row.battery_power": "Potência",
+        # Valores em texto
+        "value.unreachable": "Inalcançável",
+        "value.charging": "Carregando",
+        "value.on_battery": "Na bateria",
+        "graph.samples": "{label} ({count} amostras)",
+        "clock_source.lhm": "LHM",
+        "clock_source.os": "SO",
+        "clock_source.os_base": "SO (base)",
+        # Faixa de sistema
+        "system.load": "Carga {load}",
+        "system.processes": "{count} processos",
+    },
+    Language.EN: {
+        "status.connected": "● Connected",
+        "status.stale": "◐ Stale",
+        "status.host": " to {host}",
+        "status.uptime": " (up {uptime})",
+        "status.no_data_for": " | No data for {secs}s",
+        "status.updated": " | Updated: {time}",
+        "status.senders": " | {count} senders",
+        "status.port": " | port {port}",
+        "status.waiting": "○ Waiting for data...",
+        "status.disconnected": "○ Disconnected - Waiting for data...{mode}",
+        "status.version_mismatch": "⚠ Sender protocol v{sender} incompatible with receiver v{receiver}",
+        "footer.hint": "[F] Fullscreen | [G] Graphs | [H] Histogram | [B] Bars | [X] Scatter | [D] Debug | [T] Theme | [U] °C/°F | [1-6] Focus | [A] Alerts | [M] Records | [E] Theme Editor | [L] Log | [P] Export | [/] Search | [+/-] Rate | [S] ⚙️ Settings | [Q] Quit",
+        "panel.CPU": "CPU",
+        "panel.GPU": "GPU",
+        "panel.RAM": "RAM",
+        "panel.MOBO": "MOBO",
+        "panel.STORAGE": "STORAGE",
+        "panel.NETWORK": "NETWORK",
+        "panel.BATTERY": "BATTERY",
+        "panel.PROCESSES": "TOP PROCESSES",
+        "panel.ALERT_LOG": "ALERT LOG",
+        "panel.RECORDS": "RECORDS",
+        "panel.THEME_EDITOR": "THEME EDITOR",
+        "row.usage": "Usage",
+        "row.temp": "Temp",
+        "row.voltage": "Voltage",
+        "row.power": "Power",
+        "row.clock": "Clock",
+        "row.clock_source": "  Source",
+        "row.used": "Used",
+        "row.total": "Total",
+        "row.disk": "Disk {n}",
+        "row.health": "  Health",
+        "row.used_space": "  Used",
+        "row.free": "  Free",
+        "row.read": "  Read",
+        "row.write": "  Write",
+        "row.adapter": "Adapter",
+        "row.charge": "Charge",
+        "row.status": "Status",
+        "row.remaining": "Remaining",
+        "row.battery_power": "Power",
+        "value.unreachable": "Unreachable",
+        "value.charging": "Charging",
+        "value.on_battery": "On battery",
+        "graph.samples": "{label} ({count} samples)",
+        "clock_source.lhm": "LHM",
+        "clock_source.os": "OS",
+        "clock_source.os_base": "OS (base)",
+        "system.load": "Load {load}",
+        "system.processes": "{count} processes",
+    },
+}
+
+_current = DEFAULT_LANGUAGE
+
+
+def parse_language(value: Any) -> Optional[Language]:
+    """Idioma do config ("pt", "EN", "pt-BR"...); None se não houver tabela para ele"""
+    if not isinstance(value, str):
+        return None
+    code = value.strip().lower().replace("_", "-").split("-")[0]
+    try:
+        return Language(code)
+    except ValueError:
+        return None
+
+
+def set_language(value: Any) -> Language:
+    """Seleciona o idioma de tr(); desconhecido cai para o português"""
+    global _current
+    _current = parse_language(value) or DEFAULT_LANGUAGE
+    return _current
+
+
+def tr(key: str, **values: Any) -> str:
+    """
+    Texto da chave no idioma atual, com os campos preenchidos
+    
+    Exemplo:
+        tr("row.disk", n=1)  # "Disco 1" / "Disk 1"
+    
+    Chave ausente no idioma usa a tabela em português; ausente nela também, a própria chave.
+    """
+    text = STRINGS[_current].get(key) or STRINGS[DEFAULT_LANGUAGE].get(key, key)
+    return text.format(**values) if values else text
@@ -0,0 +1,156 @@
+"""
+Layout do dashboard: grade com todos os painéis ou um painel em foco, e o
+filtro da busca de métricas
+Sem Tk aqui: o mapeamento tecla -> painel é testável isoladamente
+"""
+import unicodedata
+from typing import Any, Iterable, List, Optional, Tuple
+
+# Painéis da grade, na ordem das teclas 1-6
+GRID_PANELS: Tuple[str, ...] = ("CPU", "GPU", "RAM", "MOBO", "STORAGE", "NETWORK")
+
+# Painéis por linha da grade
+PANELS_PER_ROW = 3
+
+# Teclas que voltam para a grade
+GRID_KEYS = ("0", "Escape")
+
+
+def panel_for_key(key: str) -> Optional[str]:
+    """Painel associado à tecla ("1" -> "CPU"); None se a tecla não for de foco"""
+    if len(key) == 1 and key.isdigit() and 1 <= int(key) <= len(GRID_PANELS):
+        return GRID_PANELS[int(key) - 1]
+    return None
+
+
+def focus_for_key(key: str, current: Optional[str]) -> Optional[str]:
+    """
+    Próximo painel em foco após uma tecla
+    
+    Args:
+        key: keysym do Tk ("1".."6", "0", "Escape", ...)
+        current: Painel em foco agora (None = grade)
+    
+    Returns:
+        Nome do painel em foco ou None para a grade. A mesma tecla de novo
+        volta para a grade; teclas sem relação mantêm o estado.
+    """
+    if key in GRID_KEYS:
+        return None
+    panel = panel_for_key(key)
+    if panel is None:
+        return current
+    return None if panel == current else panel
+
+
+def visible_panels(focus: Optional[str], panels: Tuple[str, ...] = GRID_PANELS) -> Tuple[str, ...]:
+    """Painéis exibidos: todos do layout na grade, só o focado no modo foco"""
+    if focus is None or focus not in panels:
+        return panels
+    return (focus,)
+
+
+def panel_order(names: Optional[Iterable[Any]]) -> Tuple[Tuple[str, ...], List[str]]:
+    """
+    Painéis a exibir, na ordem do config ("panels")
+    
+    Nomes não diferenciam maiúsculas; repetidos contam uma vez.
+    
+    Args:
+        names: Lista do config (None ou vazia = grade completa)
+    
+    Returns:
+        (painéis na ordem, nomes desconhecidos ignorados). Se nenhum nome
+        for válido, volta para a grade completa em vez de um dashboard vazio.
+    """
+    order: List[str] = []
+    unknown: List[str] = []
+    for name in names or ():
+        panel = str(name).strip().upper()
+        if panel not in GRID_PANELS:
+            unknown.append(str(name))
+        elif panel not in order:
+            order.append(panel)
+    return (tuple(order) or GRID_PANELS), unknown
+
+
+def panel_rows(order: Tuple[str, ...], per_row: int = PANELS_PER_ROW) -> List[Tuple[str, ...]]:
+    """Quebra a ordem dos painéis em linhas de até per_row painéis"""
+    per_row = max(1, per_row)
+    return [tuple(order[i:i + per_row]) for i in range(0, len(order), per_row)]
+
+
+# Largura mínima de cada gráfico antes de quebrar para a próxima linha
+MIN_PLOT_WIDTH = 200
+
+
+def plot_grid(
+    count: int,
+    width: int,
+    height: int,
+    min_cell_width: int = MIN_PLOT_WIDTH
+) -> List[Tuple[int, int, int, int]]:
+    """
+    Posições dos gráficos: tantos por linha quanto couberem, quebrando o resto
+    
+    Args:
+        count: Número de gráficos
+        width, height: Área disponível
+        min_cell_width: Largura mínima de um gráfico
+    
+    Returns:
+        (x, y, largura, altura) de cada gráfico, na ordem, relativos à área
+    """
+    if count <= 0 or width <= 0 or height <= 0:
+        return []
+    columns = max(1, min(count, width // max(1, min_cell_width)))
+    rows = -(-count // columns)
+    cell_w, cell_h = width // columns, height // rows
+    return [((i % columns) * cell_w, (i // columns) * cell_h, cell_w, cell_h) for i in range(count)]
+
+
+def bar_fill(
+    value: Any,
+    max_value: float,
+    warn_threshold: Optional[float] = None,
+    crit_threshold: Optional[float] = None
+) -> Tuple[float, Optional[str]]:
+    """
+    Geometria de uma barra da visão em barras (temperaturas por núcleo, VRAM)
+    
+    Args:
+        value: Leitura atual (None, NaN, não numérico ou <= 0 = sem dado)
+        max_value: Valor que enche a barra
+        warn_threshold, crit_threshold: Limites de cor (None = sem limite)
+    
+    Returns:
+        (fração preenchida 0.0-1.0, "critical"/"warning"/None); sem dado = (0.0, None), barra vazia
+    """
+    if (not isinstance(value, (int, float)) or isinstance(value, bool)
+            or value != value or value <= 0 or max_value <= 0):
+        return 0.0, None
+    fraction = min(1.0, value / max_value)
+    if crit_threshold and value >= crit_threshold:
+        return fraction, "critical"
+    if warn_threshold and value >= warn_threshold:
+        return fraction, "warning"
+    return fraction, None
+
+
+def metric_matches(query: str, *texts: str) -> bool:
+    """
+    Filtro da busca (tecla /): cada palavra da busca aparece em algum dos textos
+    
+    Sem diferenciar maiúsculas nem acentos ("saude" acha "Saúde"); busca
+    vazia casa com tudo.
+    
+    Exemplo:
+        metric_matches("gpu temp", "GPU", "Temp")  # True: painel + linha
+    """
+    haystack = _fold(" ".join(texts))
+    return all(term in haystack for term in _fold(query).split())
+
+
+def _fold(text: str) -> str:
+    decomposed = unicodedata.normalize("NFKD", text.casefold())
+    return "".join(ch for ch in decomposed if not unicodedata.combining(ch))
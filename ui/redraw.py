@@ -0,0 +1,38 @@
+"""
+Ritmo de redesenho do dashboard: max_fps com dados chegando ou o usuário
+mexendo, idle_fps (modo ocioso) parado
+Sem Tk aqui: o loop de atualização só pergunta quanto esperar
+"""
+
+# Limites do max_fps do Receiver (Tk não passa de ~60 redesenhos/s)
+MIN_FPS = 0.1
+MAX_FPS = 60.0
+
+
+def render_interval(
+    max_fps: float,
+    idle_fps: float,
+    idle_after: float,
+    last_activity: float,
+    now: float
+) -> float:
+    """
+    Segundos até o próximo redesenho do dashboard
+    
+    Com dados chegando ou o usuário mexendo (nos últimos idle_after segundos),
+    redesenha a max_fps; parado há mais tempo, cai para idle_fps.
+    
+    Args:
+        max_fps: Redesenhos por segundo com atividade (limitado a MIN_FPS..MAX_FPS)
+        idle_fps: Redesenhos por segundo parado (0 = sem modo ocioso)
+        idle_after: Segundos sem atividade até o modo ocioso
+        last_activity: Instante do último pacote ou entrada do usuário (0 = nunca)
+        now: Instante atual
+    
+    Returns:
+        Intervalo em segundos; o ocioso nunca é mais rápido que o ativo
+    """
+    active = 1.0 / min(MAX_FPS, max(MIN_FPS, max_fps or 0))
+    if idle_fps <= 0 or now - last_activity < idle_after:
+        return active
+    return max(active, 1.0 / max(MIN_FPS, idle_fps))
@@ -2,8 +2,10 @@
 Definição de temas para a interface do Sistema de Telemetria
 Usa dataclass para type safety e fácil extensão
 """
-from dataclasses import dataclass, asdict
-from typing import Dict, Any
+import json
+import os
+from dataclasses import dataclass, asdict, fields
+from typing import Dict, Any, Tuple
 
 
 @dataclass(frozen=True)
@@ -122,13 +124,14 @@ CYBERPUNK_THEME = Theme(
     critical="#ff0033",
 )
 
-# Registro de temas disponíveis
+# Registro de temas disponíveis (os customizados entram via load_custom_themes)
 THEMES: Dict[str, Theme] = {
     "dark": DARK_THEME,
     "light": LIGHT_THEME,
     "high_contrast": HIGH_CONTRAST_THEME,
     "cyberpunk": CYBERPUNK_THEME,
 }
+BUILTIN_THEMES = tuple(THEMES)
 
 
 def get_theme(name: str = "dark") -> Theme:
@@ -149,6 +152,100 @@ def get_theme_names() -> list[str]:
     return list(THEMES.keys())
 
 
+# Campos de cor do Theme (tudo menos o nome), na ordem da dataclass
+COLOR_FIELDS: Tuple[str, ...] = tuple(f.name for f in fields(Theme) if f.name != "name")
+
+
+def hex_to_rgb(color: str) -> Tuple[int, int, int]:
+    """
+    Converte "#rrggbb" em (r, g, b)
+    
+    Raises:
+        ValueError: Cor fora do formato "#rrggbb"
+    """
+    if len(color) != 7 or not color.startswith("#"):
+        raise ValueError(f"Cor inválida (use #rrggbb): {color}")
+    return tuple(int(color[i:i + 2], 16) for i in (1, 3, 5))
+
+
+def rgb_to_hex(rgb: Tuple[float, float, float]) -> str:
+    """Inverso de hex_to_rgb: (r, g, b) em "#rrggbb" (canais limitados a 0-255)"""
+    return "#" + "".join(f"{max(0, min(255, round(c))):02x}" for c in rgb)
+
+
+def load_custom_themes(path: str) -> Dict[str, Theme]:
+    """
+    Carrega os temas salvos pelo editor e os registra em THEMES
+    
+    O arquivo é {"nome": {"cpu": "#00ff88", ...}}; cores ausentes ou
+    inválidas vêm do tema escuro. Arquivo inexistente não é erro.
+    
+    Args:
+        path: Caminho do arquivo de temas (JSON)
+    
+    Returns:
+        Temas carregados, por nome
+    """
+    if not os.path.exists(path):
+        return {}
+    try:
+        with open(path, 'r', encoding='utf-8') as f:
+            data = json.load(f)
+    except (OSError, json.JSONDecodeError) as e:
+        print(f"[Themes] Erro ao carregar {path}: {e}")
+        return {}
+    
+    loaded = {}
+    for name, colors in data.items() if isinstance(data, dict) else ():
+        if not isinstance(colors, dict):
+            continue
+        valid = {}
+        for key in COLOR_FIELDS:
+            try:
+                hex_to_rgb(colors.get(key, ""))
+                valid[key] = colors[key].lower()
+            except (ValueError, AttributeError):
+                pass
+        loaded[name] = THEMES[name] = create_custom_theme(name, "dark", **valid)
+    return loaded
+
+
+def save_custom_theme(path: str, name: str, colors: Dict[str, str]) -> Theme:
+    """
+    Grava um tema no arquivo de temas (mantendo os outros) e o registra
+    
+    Args:
+        path: Caminho do arquivo de temas (JSON)
+        name: Nome do tema (substitui um tema salvo com o mesmo nome)
+        colors: Cores do tema; só os campos de COLOR_FIELDS são gravados
+    
+    Returns:
+        O Theme gravado
+    
+    Raises:
+        ValueError: Nome vazio ou de um tema embutido
+        OSError: Falha ao gravar o arquivo
+    """
+    name = name.strip()
+    if not name or name in BUILTIN_THEMES:
+        raise ValueError(f"Nome de tema inválido: '{name}'")
+    
+    data: Dict[str, Any] = {}
+    if os.path.exists(path):
+        try:
+            with open(path, 'r', encoding='utf-8') as f:
+                data = json.load(f)
+        except (OSError, json.JSONDecodeError):
+            data = {}
+    
+    theme = create_custom_theme(name, "dark", **{k: colors[k] for k in COLOR_FIELDS if k in colors})
+    data[name] = theme.to_dict()
+    with open(path, 'w', encoding='utf-8') as f:
+        json.dump(data, f, indent=4)
+    THEMES[name] = theme
+    return theme
+
+
 def create_custom_theme(
     name: str,
     base_theme: str = "dark",
@@ -192,3 +289,24 @@ def get_legacy_colors(dark_mode: bool = True) -> Dict[str, str]:
     colors['label'] = colors['dim']  # 'label' era usado como alias
     
     return colors
+
+
+def blend_color(color: str, background: str, amount: float) -> str:
+    """
+    Mistura uma cor com o fundo (o Tk não tem transparência)
+    
+    Args:
+        color: Cor "#rrggbb"
+        background: Cor de fundo "#rrggbb"
+        amount: 0 = cor original, 1 = cor do fundo
+    
+    Returns:
+        Cor resultante "#rrggbb"; cores fora do formato voltam sem mudança
+    """
+    amount = max(0.0, min(1.0, amount))
+    try:
+        fg, bg = hex_to_rgb(color), hex_to_rgb(background)
+    except ValueError:
+        return color
+    return rgb_to_hex([c * (1 - amount) + b * amount for c, b in zip(fg, bg)])
+
@@ -24,6 +24,14 @@ except ImportError:
     HAS_FASTAPI = False
     print("[Web] FastAPI não instalado. pip install fastapi uvicorn")
 
+try:
+    from core.protocol import unwrap_frame, extract_body, parse_body, decode_goodbye, ProtocolError
+    from core.protocol import check_datagram, RECV_BUFFER_SIZE
+    from core.delta import DeltaDecoder, is_delta_frame
+    HAS_PROTOCOL_MODULE = True
+except ImportError:
+    HAS_PROTOCOL_MODULE = False
+
 
 @dataclass
 class WebConfig:
@@ -33,6 +41,9 @@ class WebConfig:
     udp_port: int = 5005  # Porta para receber telemetria
     title: str = "Telemetria Dashboard"
     refresh_interval_ms: int = 1000
+    encryption_key: str = ""  # Mesma chave do Sender (vazio = frames em claro)
+    auth_secret: str = ""     # Mesmo segredo HMAC do Sender (vazio = não exige tag)
+    accept_legacy_frames: bool = True  # Aceita frames sem magic byte (Senders antigos)
 
 
 class TelemetryWebServer:
@@ -48,6 +59,7 @@ class TelemetryWebServer:
         self.config = config or WebConfig()
         self.current_data: Dict[str, Any] = {}
         self.last_update: float = 0
+        self.delta_decoder = DeltaDecoder() if HAS_PROTOCOL_MODULE else None
         self.connected_clients: list = []
         self._running = False
         self._udp_thread: Optional[threading.Thread] = None
@@ -278,6 +290,18 @@ class TelemetryWebServer:
                 <span class="metric-label">Core Clock:</span>
                 <span id="gpu-clock" class="metric-value">-- MHz</span>
             </div>
+            <div class="metric">
+                <span class="metric-label">Consumo:</span>
+                <span id="gpu-power" class="metric-value">-- W</span>
+            </div>
+            <div class="metric" id="gpu-codec-row" style="display: none">
+                <span class="metric-label">Enc / Dec:</span>
+                <span id="gpu-codec" class="metric-value">-- %</span>
+            </div>
+            <div class="metric" id="gpu-pcie-row" style="display: none">
+                <span class="metric-label">PCIe TX / RX:</span>
+                <span id="gpu-pcie" class="metric-value">-- KB/s</span>
+            </div>
             <div class="metric">
                 <span class="metric-label">VRAM:</span>
                 <span id="gpu-mem" class="metric-value">-- MB</span>
@@ -332,6 +356,12 @@ class TelemetryWebServer:
         const API_URL = '/api/telemetry';
         const REFRESH_MS = 1000;
         
+        // null = sensor ausente no Sender: mostra "—" em vez de um zero enganoso
+        function fmt(value, digits, unit) {
+            if (value === null || value === undefined) return '—';
+            return value.toFixed(digits) + unit;
+        }
+        
         function getClass(value, warn, crit) {
             if (value >= crit) return 'critical';
             if (value >= warn) return 'warning';
@@ -347,25 +377,41 @@ class TelemetryWebServer:
             document.getElementById('cpu-usage').className = 'metric-value ' + getClass(cpuUsage, 70, 90);
             document.getElementById('cpu-bar').style.width = cpuUsage + '%';
             
-            const cpuTemp = data.cpu.temp || 0;
-            document.getElementById('cpu-temp').textContent = cpuTemp.toFixed(1) + '°C';
-            document.getElementById('cpu-temp').className = 'metric-value ' + getClass(cpuTemp, 70, 85);
+            const cpuTemp = data.cpu.temp;
+            document.getElementById('cpu-temp').textContent = fmt(cpuTemp, 1, '°C');
+            document.getElementById('cpu-temp').className = 'metric-value ' + getClass(cpuTemp || 0, 70, 85);
             
-            document.getElementById('cpu-clock').textContent = (data.cpu.clock || 0).toFixed(0) + ' MHz';
-            document.getElementById('cpu-power').textContent = (data.cpu.power || 0).toFixed(1) + ' W';
+            document.getElementById('cpu-clock').textContent = fmt(data.cpu.clock, 0, ' MHz');
+            document.getElementById('cpu-power').textContent = fmt(data.cpu.power, 1, ' W');
             
             // GPU
             const gpuLoad = data.gpu?.load || 0;
             document.getElementById('gpu-load').textContent = gpuLoad.toFixed(1) + '%';
             document.getElementById('gpu-bar').style.width = gpuLoad + '%';
             
-            const gpuTemp = data.gpu?.temp || 0;
-            document.getElementById('gpu-temp').textContent = gpuTemp.toFixed(1) + '°C';
-            document.getElementById('gpu-temp').className = 'metric-value ' + getClass(gpuTemp, 75, 90);
+            const gpuTemp = data.gpu?.temp;
+            document.getElementById('gpu-temp').textContent = fmt(gpuTemp, 1, '°C');
+            document.getElementById('gpu-temp').className = 'metric-value ' + getClass(gpuTemp || 0, 75, 90);
             
-            document.getElementById('gpu-clock').textContent = (data.gpu?.clock_core || 0).toFixed(0) + ' MHz';
-            document.getElementById('gpu-mem').textContent = (data.gpu?.mem_used_mb || 0).toFixed(0) + ' MB';
-            document.getElementById('gpu-fan').textContent = (data.gpu?.fan || 0).toFixed(0) + ' RPM';
+            document.getElementById('gpu-clock').textContent = fmt(data.gpu?.clock_core, 0, ' MHz');
+            document.getElementById('gpu-power').textContent = (data.gpu?.power_w || 0).toFixed(1) + ' W';
+            // NVENC/NVDEC: linha só aparece com o encoder ou o decoder em uso
+            const encUtil = data.gpu?.enc_util || 0;
+            const decUtil = data.gpu?.dec_util || 0;
+            document.getElementById('gpu-codec-row').style.display = encUtil || decUtil ? '' : 'none';
+            document.getElementById('gpu-codec').textContent = encUtil.toFixed(0) + '% / ' + decUtil.toFixed(0) + '%';
+            // PCIe TX/RX: só NVML; 0 em GPUs sem suporte
+            const pcieTx = data.gpu?.pcie_tx_kbps || 0;
+            const pcieRx = data.gpu?.pcie_rx_kbps || 0;
+            document.getElementById('gpu-pcie-row').style.display = pcieTx || pcieRx ? '' : 'none';
+            document.getElementById('gpu-pcie').textContent = pcieTx.toFixed(0) + ' / ' + pcieRx.toFixed(0) + ' KB/s';
+            const memUsed = data.gpu?.mem_used_mb || 0;
+            const memTotal = data.gpu?.mem_total_mb || 0;
+            document.getElementById('gpu-mem').textContent = memTotal > 0
+                ? memUsed.toFixed(0) + ' / ' + memTotal.toFixed(0) + ' MB (' + (memUsed / memTotal * 100).toFixed(0) + '%)'
+                : memUsed.toFixed(0) + ' MB';
+            const fanUnit = data.gpu?.fan_unit === '%' ? '%' : ' RPM';
+            document.getElementById('gpu-fan').textContent = (data.gpu?.fan || 0).toFixed(0) + fanUnit;
             
             // RAM
             const ramPercent = data.ram?.percent || 0;
@@ -380,12 +426,28 @@ class TelemetryWebServer:
             document.getElementById('net-up').textContent = (data.network?.up_kbps || 0).toFixed(1) + ' KB/s';
             
             const ping = data.network?.ping_ms || 0;
-            document.getElementById('net-ping').textContent = ping.toFixed(0) + ' ms';
-            document.getElementById('net-ping').className = 'metric-value ' + getClass(ping, 50, 100);
+            if (ping < 0) {
+                document.getElementById('net-ping').textContent = 'Unreachable';
+                document.getElementById('net-ping').className = 'metric-value critical';
+            } else {
+                document.getElementById('net-ping').textContent = ping.toFixed(0) + ' ms';
+                document.getElementById('net-ping').className = 'metric-value ' + getClass(ping, 50, 100);
+            }
             
             document.getElementById('net-link').textContent = (data.network?.link_speed_mbps || 0) + ' Mbps';
         }
         
+        function fmtUptime(secs) {
+            if (!secs || secs <= 0) return '';
+            const minutes = Math.floor(secs / 60);
+            const days = Math.floor(minutes / 1440);
+            const hours = Math.floor(minutes / 60) % 24;
+            const pad = n => String(n).padStart(2, '0');
+            if (days) return days + 'd ' + pad(hours) + 'h';
+            if (hours) return hours + 'h ' + pad(minutes % 60) + 'm';
+            return minutes + 'm';
+        }
+        
         async function fetchData() {
             try {
                 const response = await fetch(API_URL);
@@ -393,7 +455,9 @@ class TelemetryWebServer:
                 
                 const statusEl = document.getElementById('status');
                 if (result.connected) {
-                    statusEl.textContent = '● Conectado - ' + new Date().toLocaleTimeString();
+                    const host = result.data?.hostname ? ' a ' + result.data.hostname : '';
+                    const up = fmtUptime(result.data?.uptime_secs);
+                    statusEl.textContent = '● Conectado' + host + (up ? ' (uptime ' + up + ')' : '') + ' - ' + new Date().toLocaleTimeString();
                     statusEl.className = 'status connected';
                     updateUI(result.data);
                 } else {
@@ -411,7 +475,7 @@ class TelemetryWebServer:
     </script>
 </body>
 </html>'''
-    
+
     def _start_udp_receiver(self) -> None:
         """Inicia thread para receber dados UDP"""
         def receiver_loop():
@@ -424,9 +488,36 @@ class TelemetryWebServer:
             
             while self._running:
                 try:
-                    data, addr = sock.recvfrom(16384)
+                    data, addr = sock.recvfrom(RECV_BUFFER_SIZE if HAS_PROTOCOL_MODULE else 16384)
+                    
+                    # Autenticação/criptografia: resolve antes do magic byte
+                    if HAS_PROTOCOL_MODULE and data:
+                        try:
+                            data = unwrap_frame(check_datagram(data), self.config.encryption_key, self.config.auth_secret)
+                        except ProtocolError as e:
+                            print(f"[Web] Frame descartado de {addr[0]}: {e}")
+                            continue
+                    
+                    # Decodifica (magic byte), com limite de tamanho no gzip e nas listas
+                    if HAS_PROTOCOL_MODULE:
+                        try:
+                            # Sender encerrando: "connected" vira false na hora
+                            if decode_goodbye(data) is not None:
+                                print(f"[Web] Sender {addr[0]} encerrou")
+                                self.last_update = 0
+                                self.delta_decoder.forget(addr[0])
+                                continue
+                            if is_delta_frame(data):
+                                body = self.delta_decoder.decode(data, addr[0])
+                            else:
+                                body = extract_body(data, legacy=self.config.accept_legacy_frames)
+                            self.current_data = parse_body(body)
+                        except ProtocolError as e:
+                            print(f"[Web] Frame descartado de {addr[0]}: {e}")
+                            continue
+                        self.last_update = time.time()
+                        continue
                     
-                    # Decodifica (magic byte)
                     if len(data) > 0:
                         magic = data[0]
                         if magic == 0x01:  # GZIP
@@ -442,7 +533,7 @@ class TelemetryWebServer:
                     payload = json.loads(data.decode())
                     self.current_data = payload
                     self.last_update = time.time()
-                    
+
                 except socket.timeout:
                     continue
                 except Exception as e: